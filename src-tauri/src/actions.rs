@@ -1,12 +1,16 @@
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 use crate::apple_intelligence;
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
+use crate::audio_toolkit::post_process_transcript;
+use crate::llm_client::SamplingOptions;
 use crate::managers::audio::AudioRecordingManager;
 use crate::managers::history::HistoryManager;
-use crate::managers::transcription::TranscriptionManager;
+use crate::managers::transcription::{
+    emit_transcription_timeout, TranscriptionError, TranscriptionManager,
+};
 use crate::settings::{get_settings, AppSettings, APPLE_INTELLIGENCE_PROVIDER_ID};
 use crate::shortcut;
-use crate::tray::{change_tray_icon, TrayIconState};
+use crate::tray::{change_tray_icon, update_tray_menu, TrayIconState};
 use crate::utils::{
     self, show_processing_overlay, show_recording_overlay, show_transcribing_overlay,
 };
@@ -92,7 +96,7 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
         .iter()
         .find(|prompt| prompt.id == selected_prompt_id)
     {
-        Some(prompt) => prompt.prompt.clone(),
+        Some(prompt) => prompt.clone(),
         None => {
             debug!(
                 "Post-processing skipped because prompt '{}' was not found",
@@ -101,6 +105,8 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
             return None;
         }
     };
+    let sampling = SamplingOptions::for_prompt(&prompt);
+    let prompt = prompt.prompt;
 
     if prompt.trim().is_empty() {
         debug!("Post-processing skipped because the selected prompt is empty");
@@ -188,6 +194,7 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
             user_content,
             Some(system_prompt),
             Some(json_schema),
+            sampling,
         )
         .await
         {
@@ -237,8 +244,14 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
     let processed_prompt = prompt.replace("${output}", transcription);
     debug!("Processed prompt length: {} chars", processed_prompt.len());
 
-    match crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
-        .await
+    match crate::llm_client::send_chat_completion_with_options(
+        &provider,
+        api_key,
+        &model,
+        processed_prompt,
+        sampling,
+    )
+    .await
     {
         Ok(Some(content)) => {
             let content = strip_invisible_chars(&content);
@@ -370,6 +383,8 @@ impl ShortcutAction for TranscribeAction {
         if recording_started {
             // Dynamically register the cancel shortcut in a separate task to avoid deadlock
             shortcut::register_cancel_shortcut(app);
+            // Now that the start time is known, add the recording timer to the menu
+            update_tray_menu(app, &TrayIconState::Recording, None);
         }
 
         debug!(
@@ -412,6 +427,7 @@ impl ShortcutAction for TranscribeAction {
 
             let stop_recording_time = Instant::now();
             if let Some(samples) = rm.stop_recording(&binding_id) {
+                let archive = rm.take_archival_audio();
                 debug!(
                     "Recording stopped and samples retrieved in {:?}, sample count: {}",
                     stop_recording_time.elapsed(),
@@ -420,15 +436,21 @@ impl ShortcutAction for TranscribeAction {
 
                 let transcription_time = Instant::now();
                 let samples_clone = samples.clone(); // Clone for history saving
-                match tm.transcribe(samples) {
+                match tm.transcribe_with_timeout(samples, None).await {
                     Ok(transcription) => {
+                        let settings = get_settings(&ah);
+                        let transcription =
+                            post_process_transcript(&transcription, &settings.word_replacements);
+                        let detected_language = tm.detected_language(None, &transcription);
                         debug!(
                             "Transcription completed in {:?}: '{}'",
                             transcription_time.elapsed(),
                             transcription
                         );
                         if !transcription.is_empty() {
-                            let settings = get_settings(&ah);
+                            if let Some(c) = ah.try_state::<TranscriptionCoordinator>() {
+                                c.notify_transcription_complete(&transcription);
+                            }
                             let mut final_text = transcription.clone();
                             let mut post_processed_text: Option<String> = None;
                             let mut post_process_prompt: Option<String> = None;
@@ -479,6 +501,8 @@ impl ShortcutAction for TranscribeAction {
                                         transcription_for_history,
                                         post_processed_text,
                                         post_process_prompt,
+                                        detected_language,
+                                        archive,
                                     )
                                     .await
                                 {
@@ -513,6 +537,9 @@ impl ShortcutAction for TranscribeAction {
                     }
                     Err(err) => {
                         debug!("Global Shortcut Transcription error: {}", err);
+                        if let TranscriptionError::Timeout { .. } = err {
+                            emit_transcription_timeout(&ah, None);
+                        }
                         utils::hide_recording_overlay(&ah);
                         change_tray_icon(&ah, TrayIconState::Idle);
                     }
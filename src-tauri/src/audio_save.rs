@@ -1,7 +1,15 @@
 use anyhow::Result;
 use hound::{WavSpec, WavWriter};
 use log::debug;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Folder, inside a recordings folder, that archival copies are saved to.
+pub const ARCHIVE_DIR: &str = "archive";
+
+/// Where the archival copy of the recording `file_name` in `dir` lives.
+pub fn archival_copy_path(dir: &Path, file_name: &str) -> PathBuf {
+    dir.join(ARCHIVE_DIR).join(file_name)
+}
 
 /// Save audio samples (16kHz mono f32) as a WAV file.
 /// This is a cross-platform module — available on both desktop and mobile.
@@ -24,3 +32,185 @@ pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Res
     debug!("Saved WAV file: {:?}", file_path.as_ref());
     Ok(())
 }
+
+/// Decode any symphonia-supported audio or video file to mono f32 samples,
+/// returned with their sample rate.
+pub fn decode_audio_file(file_path: &str) -> Result<(Vec<f32>, u32), String> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let file =
+        std::fs::File::open(file_path).map_err(|e| format!("Failed to open media file: {}", e))?;
+
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = std::path::Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+    {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| {
+            format!(
+                "Unsupported media format: {}. Supported formats: MP4, MKV, WebM, MP3, M4A, OGG, WAV.",
+                e
+            )
+        })?;
+
+    let mut format = probed.format;
+
+    // Find the first audio track
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != symphonia::core::codecs::CODEC_TYPE_NULL)
+        .ok_or_else(|| "No audio track found in file".to_string())?
+        .clone();
+
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| "Unknown sample rate in audio track".to_string())?;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Failed to create audio decoder: {}", e))?;
+
+    let mut all_samples: Vec<f32> = Vec::new();
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(symphonia::core::errors::Error::IoError(ref e))
+                if e.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break; // EOF
+            }
+            Err(symphonia::core::errors::Error::ResetRequired) => {
+                break;
+            }
+            Err(_) => break,
+        };
+
+        if packet.track_id() != track.id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(_) => continue,
+        };
+
+        let spec = *decoded.spec();
+        let num_channels = spec.channels.count();
+        let mut sample_buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        sample_buf.copy_interleaved_ref(decoded);
+
+        let samples = sample_buf.samples();
+
+        // Mix to mono if multichannel
+        if num_channels > 1 {
+            for frame in samples.chunks(num_channels) {
+                let mono: f32 = frame.iter().sum::<f32>() / num_channels as f32;
+                all_samples.push(mono);
+            }
+        } else {
+            all_samples.extend_from_slice(samples);
+        }
+    }
+
+    if all_samples.is_empty() {
+        return Err("No audio data could be extracted from the file".to_string());
+    }
+
+    log::info!(
+        "Extracted {} audio samples at {}Hz from {}",
+        all_samples.len(),
+        sample_rate,
+        file_path
+    );
+
+    Ok((all_samples, sample_rate))
+}
+
+/// Resample by linear interpolation. Rough next to the sinc resampler used on
+/// desktop, but needs no extra dependencies and is fine for speech.
+pub fn linear_resample(samples: &[f32], from_hz: u32, to_hz: u32) -> Vec<f32> {
+    if from_hz == to_hz || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let ratio = from_hz as f64 / to_hz as f64;
+    let new_len = (samples.len() as f64 / ratio) as usize;
+    (0..new_len)
+        .map(|i| {
+            let src_idx = i as f64 * ratio;
+            let idx = src_idx as usize;
+            let frac = src_idx - idx as f64;
+            let a = samples.get(idx).copied().unwrap_or(0.0);
+            let b = samples.get(idx + 1).copied().unwrap_or(a);
+            a + (b - a) * frac as f32
+        })
+        .collect()
+}
+
+/// Decode any supported audio file (m4a, mp3, ogg, ...) and save it as a
+/// 16kHz mono WAV at `dest`, the format every recording is stored in.
+pub async fn convert_to_wav(src: &Path, dest: &Path) -> Result<()> {
+    let src = src
+        .to_str()
+        .ok_or_else(|| anyhow::anyhow!("Invalid file path"))?;
+    let (samples, sample_rate) = decode_audio_file(src).map_err(|e| anyhow::anyhow!(e))?;
+    save_wav_file(dest, &linear_resample(&samples, sample_rate, 16000)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_resample_changes_length_by_ratio() {
+        let samples = vec![0.5; 48000];
+        let resampled = linear_resample(&samples, 48000, 16000);
+        assert_eq!(resampled.len(), 16000);
+        assert!(resampled.iter().all(|s| (s - 0.5).abs() < 1e-6));
+        assert_eq!(linear_resample(&samples, 16000, 16000).len(), 48000);
+    }
+
+    #[test]
+    fn test_decode_audio_file_reads_wav() {
+        let path = std::env::temp_dir().join(format!("decode-test-{}.wav", std::process::id()));
+        let spec = WavSpec {
+            channels: 2,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&path, spec).unwrap();
+        for _ in 0..800 {
+            writer.write_sample(i16::MAX / 2).unwrap();
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let result = decode_audio_file(path.to_str().unwrap());
+        let _ = std::fs::remove_file(&path);
+        let (samples, sample_rate) = result.unwrap();
+        assert_eq!(sample_rate, 8000);
+        assert_eq!(samples.len(), 800);
+        // Channels are mixed down to mono
+        assert!((samples[0] - 0.25).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,231 @@
+//! System-audio ("loopback") capture, so a recording can include the other
+//! side of a call.
+//!
+//! - Windows: WASAPI render endpoints, opened as input streams in loopback mode.
+//! - Linux: PulseAudio/PipeWire monitor sources, captured with `parec`.
+//! - macOS: no native tap; virtual loopback drivers (BlackHole, Loopback,
+//!   Soundflower) or an aggregate device that includes one show up as inputs.
+
+#[cfg(target_os = "linux")]
+use std::io::Read;
+use std::sync::mpsc;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use cpal::traits::DeviceTrait;
+
+/// A system-audio source that can be recorded like a microphone.
+#[derive(Clone)]
+pub enum LoopbackDevice {
+    /// Opened through cpal like any input device.
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    Cpal(cpal::Device),
+    /// A PulseAudio/PipeWire monitor source, by source name.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    PulseMonitor(String),
+}
+
+pub struct LoopbackDeviceInfo {
+    pub name: String,
+    pub is_default: bool,
+    pub device: LoopbackDevice,
+}
+
+/// List the system-audio sources available on this platform. Errs with a
+/// capability message where loopback capture isn't implemented.
+#[cfg(target_os = "windows")]
+pub fn list_loopback_devices() -> Result<Vec<LoopbackDeviceInfo>, String> {
+    let devices = super::list_output_devices()
+        .map_err(|e| format!("Failed to list output devices: {}", e))?;
+    Ok(devices
+        .into_iter()
+        .map(|d| LoopbackDeviceInfo {
+            name: d.name,
+            is_default: d.is_default,
+            device: LoopbackDevice::Cpal(d.device),
+        })
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+pub fn list_loopback_devices() -> Result<Vec<LoopbackDeviceInfo>, String> {
+    use std::process::Command;
+
+    let output = Command::new("pactl")
+        .args(["list", "short", "sources"])
+        .output()
+        .map_err(|_| {
+            "System audio capture needs PulseAudio or PipeWire (pactl was not found)".to_string()
+        })?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to list monitor sources: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let default_monitor = Command::new("pactl")
+        .arg("get-default-sink")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| format!("{}.monitor", String::from_utf8_lossy(&o.stdout).trim()));
+
+    // Lines look like "52\talsa_output.pci-0000_00_1f.3.analog-stereo.monitor\tmodule-alsa-card.c\t..."
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .filter(|name| name.ends_with(".monitor"))
+        .map(|name| LoopbackDeviceInfo {
+            name: name.to_string(),
+            is_default: default_monitor.as_deref() == Some(name),
+            device: LoopbackDevice::PulseMonitor(name.to_string()),
+        })
+        .collect())
+}
+
+#[cfg(target_os = "macos")]
+pub fn list_loopback_devices() -> Result<Vec<LoopbackDeviceInfo>, String> {
+    const LOOPBACK_DRIVERS: [&str; 4] = ["blackhole", "loopback", "soundflower", "aggregate"];
+
+    let devices =
+        super::list_input_devices().map_err(|e| format!("Failed to list audio devices: {}", e))?;
+    let found: Vec<LoopbackDeviceInfo> = devices
+        .into_iter()
+        .filter(|d| {
+            let name = d.name.to_lowercase();
+            LOOPBACK_DRIVERS.iter().any(|driver| name.contains(driver))
+        })
+        .map(|d| LoopbackDeviceInfo {
+            name: d.name,
+            is_default: false,
+            device: LoopbackDevice::Cpal(d.device),
+        })
+        .collect();
+
+    if found.is_empty() {
+        return Err(
+            "No system audio device found. Install a loopback driver such as BlackHole \
+             and create an aggregate device that includes it and your speakers."
+                .to_string(),
+        );
+    }
+    Ok(found)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+pub fn list_loopback_devices() -> Result<Vec<LoopbackDeviceInfo>, String> {
+    Err(format!(
+        "System audio capture is not supported on {}",
+        std::env::consts::OS
+    ))
+}
+
+/// Input config for a loopback device opened through cpal.
+#[cfg(target_os = "windows")]
+pub(crate) fn loopback_config(
+    device: &cpal::Device,
+) -> Result<cpal::SupportedStreamConfig, String> {
+    // Render endpoints capture in their mix format
+    device
+        .default_output_config()
+        .map_err(|e| format!("Failed to read output format: {}", e))
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn loopback_config(
+    device: &cpal::Device,
+) -> Result<cpal::SupportedStreamConfig, String> {
+    device
+        .default_input_config()
+        .map_err(|e| format!("Failed to read input format: {}", e))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub(crate) fn loopback_config(
+    _device: &cpal::Device,
+) -> Result<cpal::SupportedStreamConfig, String> {
+    Err("Loopback through cpal is not supported on this platform".to_string())
+}
+
+/// Sample rate `parec` is asked to deliver, so no resampling is needed.
+pub(crate) const MONITOR_SAMPLE_RATE: u32 = crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+/// A running `parec` capture; stops when dropped.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) struct MonitorCapture {
+    child: std::process::Child,
+    reader: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Drop for MonitorCapture {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
+
+/// Record a monitor source as mono f32 at `MONITOR_SAMPLE_RATE`, sending
+/// chunks tagged with `source` on `sample_tx`.
+#[cfg(target_os = "linux")]
+pub(crate) fn start_monitor_capture(
+    monitor: &str,
+    source: usize,
+    sample_tx: mpsc::Sender<(usize, Vec<f32>)>,
+) -> Result<MonitorCapture, String> {
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("parec")
+        .arg(format!("--device={}", monitor))
+        .args([
+            "--format=float32le",
+            "--channels=1",
+            &format!("--rate={}", MONITOR_SAMPLE_RATE),
+            "--latency-msec=30",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start parec: {}", e))?;
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "parec has no output".to_string())?;
+
+    let reader = std::thread::spawn(move || {
+        let mut buf = [0u8; 4 * 480];
+        let mut pending = Vec::<u8>::new();
+        loop {
+            let n = match stdout.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            pending.extend_from_slice(&buf[..n]);
+            let whole = pending.len() - pending.len() % 4;
+            let samples: Vec<f32> = pending[..whole]
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect();
+            pending.drain(..whole);
+            if sample_tx.send((source, samples)).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(MonitorCapture {
+        child,
+        reader: Some(reader),
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn start_monitor_capture(
+    _monitor: &str,
+    _source: usize,
+    _sample_tx: mpsc::Sender<(usize, Vec<f32>)>,
+) -> Result<MonitorCapture, String> {
+    Err("Monitor sources are only available on Linux".to_string())
+}
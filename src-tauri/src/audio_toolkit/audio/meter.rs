@@ -0,0 +1,91 @@
+/// Loudness of a stretch of audio, both on a 0..1 scale.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InputLevel {
+    pub rms: f32,
+    pub peak: f32,
+}
+
+impl InputLevel {
+    pub fn measure(samples: &[f32]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut sum_sq = 0.0f64;
+        let mut peak = 0.0f32;
+        for &s in samples {
+            sum_sq += (s as f64) * (s as f64);
+            peak = peak.max(s.abs());
+        }
+        Self {
+            rms: (sum_sq / samples.len() as f64).sqrt() as f32,
+            peak: peak.min(1.0),
+        }
+    }
+}
+
+/// Accumulates audio and reports its level once per `window` samples, so a
+/// meter can be driven at a fixed rate regardless of buffer sizes.
+pub struct LevelMeter {
+    window: usize,
+    count: usize,
+    sum_sq: f64,
+    peak: f32,
+}
+
+impl LevelMeter {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            count: 0,
+            sum_sq: 0.0,
+            peak: 0.0,
+        }
+    }
+
+    pub fn feed(&mut self, samples: &[f32], mut emit: impl FnMut(InputLevel)) {
+        for &s in samples {
+            self.sum_sq += (s as f64) * (s as f64);
+            self.peak = self.peak.max(s.abs());
+            self.count += 1;
+            if self.count == self.window {
+                emit(InputLevel {
+                    rms: (self.sum_sq / self.count as f64).sqrt() as f32,
+                    peak: self.peak.min(1.0),
+                });
+                self.reset();
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.sum_sq = 0.0;
+        self.peak = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_square_wave() {
+        let level = InputLevel::measure(&[0.5, -0.5, 0.5, -0.5]);
+        assert!((level.rms - 0.5).abs() < 1e-6);
+        assert_eq!(level.peak, 0.5);
+        assert_eq!(InputLevel::measure(&[]), InputLevel::default());
+    }
+
+    #[test]
+    fn test_meter_reports_once_per_window() {
+        let mut meter = LevelMeter::new(4);
+        let mut levels = Vec::new();
+        meter.feed(&[0.1, -0.8, 0.1], |l| levels.push(l));
+        assert!(levels.is_empty());
+
+        meter.feed(&[0.1, 0.2, 0.2, 0.2, 0.2], |l| levels.push(l));
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0].peak, 0.8);
+        assert!((levels[1].rms - 0.2).abs() < 1e-6);
+    }
+}
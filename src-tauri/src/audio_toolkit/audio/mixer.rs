@@ -0,0 +1,120 @@
+/// Mixes several 16kHz mono streams that arrive independently (e.g. a
+/// microphone and system audio) into one, frame by frame.
+///
+/// Sources don't deliver at exactly the same pace, and some (WASAPI loopback)
+/// deliver nothing at all while silent, so a source that falls more than
+/// `max_lag` samples behind is treated as silence for the missing stretch.
+pub struct SourceMixer {
+    queues: Vec<Vec<f32>>,
+    frame_len: usize,
+    max_lag: usize,
+}
+
+impl SourceMixer {
+    pub fn new(sources: usize, frame_len: usize, max_lag: usize) -> Self {
+        Self {
+            queues: vec![Vec::new(); sources],
+            frame_len,
+            max_lag,
+        }
+    }
+
+    pub fn push(&mut self, source: usize, samples: &[f32]) {
+        self.queues[source].extend_from_slice(samples);
+    }
+
+    /// Emit every whole frame for which all sources have audio (or have fallen
+    /// too far behind to wait for).
+    pub fn drain(&mut self, emit: impl FnMut(&[f32])) {
+        let shortest = self.queues.iter().map(Vec::len).min().unwrap_or(0);
+        let longest = self.queues.iter().map(Vec::len).max().unwrap_or(0);
+        let ready = if longest - shortest > self.max_lag {
+            longest
+        } else {
+            shortest
+        };
+        self.mix(ready - ready % self.frame_len, emit);
+    }
+
+    /// Emit everything that is left, padding the last frame with silence.
+    pub fn finish(&mut self, emit: impl FnMut(&[f32])) {
+        let longest = self.queues.iter().map(Vec::len).max().unwrap_or(0);
+        let padded = longest.div_ceil(self.frame_len) * self.frame_len;
+        self.mix(padded, emit);
+    }
+
+    pub fn clear(&mut self) {
+        for queue in &mut self.queues {
+            queue.clear();
+        }
+    }
+
+    fn mix(&mut self, len: usize, mut emit: impl FnMut(&[f32])) {
+        if len == 0 {
+            return;
+        }
+        let mut mixed = vec![0.0f32; len];
+        for queue in &mut self.queues {
+            let take = queue.len().min(len);
+            for (out, sample) in mixed.iter_mut().zip(queue.drain(..take)) {
+                *out += sample;
+            }
+        }
+        for sample in &mut mixed {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+        for frame in mixed.chunks(self.frame_len) {
+            emit(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(mixer: &mut SourceMixer, finish: bool) -> Vec<f32> {
+        let mut out = Vec::new();
+        if finish {
+            mixer.finish(|frame| out.extend_from_slice(frame));
+        } else {
+            mixer.drain(|frame| out.extend_from_slice(frame));
+        }
+        out
+    }
+
+    #[test]
+    fn test_mixes_whole_frames_when_both_sources_have_audio() {
+        let mut mixer = SourceMixer::new(2, 2, 10);
+        mixer.push(0, &[0.1, 0.2, 0.3, 0.4]);
+        mixer.push(1, &[0.5, 0.5, 0.5]);
+
+        let out = collect(&mut mixer, false);
+        assert_eq!(out.len(), 2);
+        assert!((out[0] - 0.6).abs() < 1e-6 && (out[1] - 0.7).abs() < 1e-6);
+
+        mixer.push(1, &[0.9]);
+        let out = collect(&mut mixer, false);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[1], 1.0); // 0.4 + 0.9 clipped
+    }
+
+    #[test]
+    fn test_silent_source_does_not_stall_the_mix() {
+        let mut mixer = SourceMixer::new(2, 2, 4);
+        mixer.push(0, &[0.1; 4]);
+        assert!(collect(&mut mixer, false).is_empty());
+
+        mixer.push(0, &[0.1; 2]);
+        assert_eq!(collect(&mut mixer, false), vec![0.1; 6]);
+    }
+
+    #[test]
+    fn test_finish_pads_the_last_frame() {
+        let mut mixer = SourceMixer::new(2, 4, 100);
+        mixer.push(0, &[0.25; 3]);
+        mixer.push(1, &[0.25]);
+        assert_eq!(collect(&mut mixer, true), vec![0.5, 0.25, 0.25, 0.0]);
+        assert!(collect(&mut mixer, true).is_empty());
+    }
+}
@@ -1,12 +1,24 @@
 // Re-export all audio components
 mod device;
+pub mod loopback;
+mod meter;
+mod mixer;
 mod recorder;
 mod resampler;
+mod spool;
 mod utils;
 mod visualizer;
+mod waveform;
 
 pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
-pub use recorder::AudioRecorder;
-pub use resampler::FrameResampler;
-pub use utils::save_wav_file;
+pub use loopback::{list_loopback_devices, LoopbackDevice, LoopbackDeviceInfo};
+pub use meter::{InputLevel, LevelMeter};
+pub use mixer::SourceMixer;
+pub use recorder::{AudioRecorder, CaptureSource, RecordedAudio};
+pub use resampler::{resample_mono_audio, FrameResampler};
+pub use spool::{read_spooled_wav, PartialSamples, RollingBuffer, SpoolWriter};
+pub use utils::{
+    apply_gain, db_to_gain, quietest_point, save_archival_wav, save_wav_file, ArchivalAudio,
+};
 pub use visualizer::AudioVisualiser;
+pub use waveform::compute_waveform;
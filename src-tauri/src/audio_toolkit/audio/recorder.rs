@@ -1,6 +1,10 @@
 use std::{
     io::Error,
-    sync::{mpsc, Arc, Mutex},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc, Arc, Mutex,
+    },
     time::Duration,
 };
 
@@ -10,26 +14,96 @@ use cpal::{
 };
 
 use crate::audio_toolkit::{
-    audio::{AudioVisualiser, FrameResampler},
+    audio::{
+        apply_gain,
+        loopback::{self, LoopbackDevice, MonitorCapture},
+        read_spooled_wav, ArchivalAudio, AudioVisualiser, FrameResampler, InputLevel, LevelMeter,
+        PartialSamples, RollingBuffer, SourceMixer, SpoolWriter,
+    },
     constants,
     vad::{self, VadFrame},
     VoiceActivityDetector,
 };
 
+/// Where a recorder pulls audio from.
+pub enum CaptureSource {
+    /// A microphone; `None` is the host's default input.
+    Microphone(Option<Device>),
+    /// System audio (see `loopback`).
+    Loopback(LoopbackDevice),
+}
+
+/// Keeps a source's capture running for as long as it is held.
+#[allow(dead_code)] // never read, only dropped
+enum ActiveCapture {
+    Stream(cpal::Stream),
+    Monitor(MonitorCapture),
+}
+
+/// Longest the consumer waits for audio before checking for commands.
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Seconds of audio a spooled recording keeps in memory for `get_partial_samples`.
+const SPOOL_PREVIEW_SECS: usize = 60;
+
+/// What `stop` hands back: the samples, or the WAV they were spooled to.
+pub enum RecordedAudio {
+    Samples(Vec<f32>),
+    /// 16kHz mono 16-bit WAV written while recording (see `start_spooled`).
+    File(PathBuf),
+}
+
+impl RecordedAudio {
+    /// The recording as samples, reading a spooled file into memory. The file
+    /// itself is left in place.
+    pub fn into_samples(self) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+        match self {
+            RecordedAudio::Samples(samples) => Ok(samples),
+            RecordedAudio::File(path) => Ok(read_spooled_wav(&path)?),
+        }
+    }
+}
+
 enum Cmd {
-    Start,
-    Stop(mpsc::Sender<Vec<f32>>),
-    /// Return a clone of the current processed_samples without stopping recording.
-    GetSamples(mpsc::Sender<Vec<f32>>),
+    /// Start recording, spooling to the given WAV when there is one.
+    Start(Option<PathBuf>),
+    Stop(mpsc::Sender<RecordedAudio>),
+    /// Return the audio recorded so far (or its recent part, when spooling)
+    /// without stopping recording.
+    GetSamples(mpsc::Sender<PartialSamples>),
+    /// Finish the archival spool of the last recording and hand it over.
+    TakeArchive(mpsc::Sender<Option<ArchivalAudio>>),
     Shutdown,
 }
 
+/// Settings the capture thread reads on every chunk, so they can change
+/// without reopening the stream.
+#[derive(Clone)]
+struct InputControls {
+    /// Linear gain for microphone sources, as f32 bits.
+    gain: Arc<AtomicU32>,
+    /// Where to spool the first source at its native rate, for an archival
+    /// copy.
+    archive: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl Default for InputControls {
+    fn default() -> Self {
+        Self {
+            gain: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            archive: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
 pub struct AudioRecorder {
     device: Option<Device>,
     cmd_tx: Option<mpsc::Sender<Cmd>>,
     worker_handle: Option<std::thread::JoinHandle<()>>,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    meter_cb: Option<Arc<dyn Fn(InputLevel) + Send + Sync + 'static>>,
+    controls: InputControls,
 }
 
 impl AudioRecorder {
@@ -40,9 +114,23 @@ impl AudioRecorder {
             worker_handle: None,
             vad: None,
             level_cb: None,
+            meter_cb: None,
+            controls: InputControls::default(),
         })
     }
 
+    /// Amplify microphone sources by `gain` (linear). Takes effect immediately,
+    /// and before level metering, so meters show the amplified signal.
+    pub fn set_input_gain(&self, gain: f32) {
+        self.controls.gain.store(gain.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Also spool the first source's audio at its native rate to `path` while
+    /// recording, for `take_archive`. Takes effect from the next `start`.
+    pub fn set_archive_path(&self, path: Option<PathBuf>) {
+        *self.controls.archive.lock().unwrap() = path;
+    }
+
     pub fn with_vad(mut self, vad: Box<dyn VoiceActivityDetector>) -> Self {
         self.vad = Some(Arc::new(Mutex::new(vad)));
         self
@@ -56,88 +144,191 @@ impl AudioRecorder {
         self
     }
 
+    /// Report the input's RMS and peak about ten times a second while recording.
+    pub fn with_meter_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(InputLevel) + Send + Sync + 'static,
+    {
+        self.meter_cb = Some(Arc::new(cb));
+        self
+    }
+
     pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
+        self.open_sources(vec![CaptureSource::Microphone(device)])
+    }
+
+    /// Open one or more sources. With several, their audio is mixed into the
+    /// single recording buffer.
+    pub fn open_sources(
+        &mut self,
+        sources: Vec<CaptureSource>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
         }
+        if sources.is_empty() {
+            return Err("No audio source to open".into());
+        }
 
-        let (sample_tx, sample_rx) = mpsc::channel::<Vec<f32>>();
+        let (sample_tx, sample_rx) = mpsc::channel::<(usize, Vec<f32>)>();
         let (cmd_tx, cmd_rx) = mpsc::channel::<Cmd>();
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<(), String>>();
 
         let host = crate::audio_toolkit::get_cpal_host();
-        let device = match device {
-            Some(dev) => dev,
-            None => host
-                .default_input_device()
-                .ok_or_else(|| Error::new(std::io::ErrorKind::NotFound, "No input device found"))?,
-        };
+        let mut microphone = None;
+        let mut sources = sources;
+        for source in &mut sources {
+            if let CaptureSource::Microphone(device) = source {
+                if device.is_none() {
+                    *device = Some(host.default_input_device().ok_or_else(|| {
+                        Error::new(std::io::ErrorKind::NotFound, "No input device found")
+                    })?);
+                }
+                microphone = device.clone();
+            }
+        }
 
-        let thread_device = device.clone();
         let vad = self.vad.clone();
         // Move the optional level callback into the worker thread
         let level_cb = self.level_cb.clone();
+        let meter_cb = self.meter_cb.clone();
+        let controls = self.controls.clone();
+        let microphones: Vec<bool> = sources
+            .iter()
+            .map(|s| matches!(s, CaptureSource::Microphone(_)))
+            .collect();
 
         let worker = std::thread::spawn(move || {
-            let config = AudioRecorder::get_preferred_config(&thread_device)
-                .expect("failed to fetch preferred config");
-
-            let sample_rate = config.sample_rate().0;
-            let channels = config.channels() as usize;
-
-            log::info!(
-                "Using device: {:?}\nSample rate: {}\nChannels: {}\nFormat: {:?}",
-                thread_device.name(),
-                sample_rate,
-                channels,
-                config.sample_format()
-            );
-
-            let stream = match config.sample_format() {
-                cpal::SampleFormat::U8 => {
-                    AudioRecorder::build_stream::<u8>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::I8 => {
-                    AudioRecorder::build_stream::<i8>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::I16 => {
-                    AudioRecorder::build_stream::<i16>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::I32 => {
-                    AudioRecorder::build_stream::<i32>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::F32 => {
-                    AudioRecorder::build_stream::<f32>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
+            let mut captures = Vec::new();
+            let mut sample_rates = Vec::new();
+            for (index, source) in sources.iter().enumerate() {
+                match AudioRecorder::start_capture(source, index, sample_tx.clone()) {
+                    Ok((capture, sample_rate)) => {
+                        captures.push(capture);
+                        sample_rates.push(sample_rate);
+                    }
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
                 }
-                _ => panic!("unsupported sample format"),
-            };
-
-            stream.play().expect("failed to start stream");
-
-            // keep the stream alive while we process samples
-            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb);
-            // stream is dropped here, after run_consumer returns
+            }
+            drop(sample_tx);
+            let _ = ready_tx.send(Ok(()));
+
+            // keep the streams alive while we process samples
+            run_consumer(
+                sample_rates,
+                microphones,
+                controls,
+                vad,
+                sample_rx,
+                cmd_rx,
+                level_cb,
+                meter_cb,
+            );
+            drop(captures);
         });
 
-        self.device = Some(device);
+        match ready_rx.recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                let _ = worker.join();
+                return Err(e.into());
+            }
+            Err(_) => {
+                let _ = worker.join();
+                return Err("Audio capture thread exited unexpectedly".into());
+            }
+        }
+
+        self.device = microphone;
         self.cmd_tx = Some(cmd_tx);
         self.worker_handle = Some(worker);
 
         Ok(())
     }
 
+    /// Start capturing `source`, sending its mono samples tagged with `index`.
+    /// Returns what keeps the capture alive and the rate of the samples.
+    fn start_capture(
+        source: &CaptureSource,
+        index: usize,
+        sample_tx: mpsc::Sender<(usize, Vec<f32>)>,
+    ) -> Result<(ActiveCapture, u32), String> {
+        let (device, config) = match source {
+            CaptureSource::Microphone(device) => {
+                let device = device.as_ref().ok_or("No input device found")?;
+                let config = AudioRecorder::get_preferred_config(device)
+                    .map_err(|e| format!("Failed to fetch preferred config: {}", e))?;
+                (device, config)
+            }
+            CaptureSource::Loopback(LoopbackDevice::Cpal(device)) => {
+                (device, loopback::loopback_config(device)?)
+            }
+            CaptureSource::Loopback(LoopbackDevice::PulseMonitor(monitor)) => {
+                let capture = loopback::start_monitor_capture(monitor, index, sample_tx)?;
+                return Ok((
+                    ActiveCapture::Monitor(capture),
+                    loopback::MONITOR_SAMPLE_RATE,
+                ));
+            }
+        };
+
+        let sample_rate = config.sample_rate().0;
+        let channels = config.channels() as usize;
+
+        log::info!(
+            "Using device: {:?}\nSample rate: {}\nChannels: {}\nFormat: {:?}",
+            device.name(),
+            sample_rate,
+            channels,
+            config.sample_format()
+        );
+
+        let stream = match config.sample_format() {
+            cpal::SampleFormat::U8 => {
+                AudioRecorder::build_stream::<u8>(device, &config, sample_tx, index, channels)
+            }
+            cpal::SampleFormat::I8 => {
+                AudioRecorder::build_stream::<i8>(device, &config, sample_tx, index, channels)
+            }
+            cpal::SampleFormat::I16 => {
+                AudioRecorder::build_stream::<i16>(device, &config, sample_tx, index, channels)
+            }
+            cpal::SampleFormat::I32 => {
+                AudioRecorder::build_stream::<i32>(device, &config, sample_tx, index, channels)
+            }
+            cpal::SampleFormat::F32 => {
+                AudioRecorder::build_stream::<f32>(device, &config, sample_tx, index, channels)
+            }
+            other => return Err(format!("Unsupported sample format {:?}", other)),
+        }
+        .map_err(|e| format!("Failed to build input stream: {}", e))?;
+
+        stream
+            .play()
+            .map_err(|e| format!("Failed to start stream: {}", e))?;
+        Ok((ActiveCapture::Stream(stream), sample_rate))
+    }
+
     pub fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(tx) = &self.cmd_tx {
-            tx.send(Cmd::Start)?;
+            tx.send(Cmd::Start(None))?;
+        }
+        Ok(())
+    }
+
+    /// Start recording straight to a WAV at `path`, keeping only the last
+    /// minute in memory. Falls back to memory if the file can't be created.
+    pub fn start_spooled(&self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Start(Some(path)))?;
         }
         Ok(())
     }
 
-    pub fn stop(&self) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+    pub fn stop(&self) -> Result<RecordedAudio, Box<dyn std::error::Error>> {
         let (resp_tx, resp_rx) = mpsc::channel();
         if let Some(tx) = &self.cmd_tx {
             tx.send(Cmd::Stop(resp_tx))?;
@@ -145,8 +336,9 @@ impl AudioRecorder {
         Ok(resp_rx.recv()?) // wait for the samples
     }
 
-    /// Get a clone of the current audio buffer without stopping recording.
-    pub fn get_partial_samples(&self) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+    /// Get a copy of the audio recorded so far without stopping recording.
+    /// A spooled recording only returns its most recent part.
+    pub fn get_partial_samples(&self) -> Result<PartialSamples, Box<dyn std::error::Error>> {
         let (resp_tx, resp_rx) = mpsc::channel();
         if let Some(tx) = &self.cmd_tx {
             tx.send(Cmd::GetSamples(resp_tx))?;
@@ -154,6 +346,16 @@ impl AudioRecorder {
         Ok(resp_rx.recv()?)
     }
 
+    /// Full-rate audio of the last recording, if an archive path was set when
+    /// it started. The caller owns the spool file from then on.
+    pub fn take_archive(&self) -> Result<Option<ArchivalAudio>, Box<dyn std::error::Error>> {
+        let (resp_tx, resp_rx) = mpsc::channel();
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::TakeArchive(resp_tx))?;
+        }
+        Ok(resp_rx.recv()?)
+    }
+
     pub fn close(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(tx) = self.cmd_tx.take() {
             let _ = tx.send(Cmd::Shutdown);
@@ -168,7 +370,8 @@ impl AudioRecorder {
     fn build_stream<T>(
         device: &cpal::Device,
         config: &cpal::SupportedStreamConfig,
-        sample_tx: mpsc::Sender<Vec<f32>>,
+        sample_tx: mpsc::Sender<(usize, Vec<f32>)>,
+        source: usize,
         channels: usize,
     ) -> Result<cpal::Stream, cpal::BuildStreamError>
     where
@@ -198,7 +401,7 @@ impl AudioRecorder {
                 }
             }
 
-            if sample_tx.send(output_buffer.clone()).is_err() {
+            if sample_tx.send((source, output_buffer.clone())).is_err() {
                 log::error!("Failed to send samples");
             }
         };
@@ -250,32 +453,56 @@ impl AudioRecorder {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_consumer(
-    in_sample_rate: u32,
+    in_sample_rates: Vec<u32>,
+    microphones: Vec<bool>,
+    controls: InputControls,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
-    sample_rx: mpsc::Receiver<Vec<f32>>,
+    sample_rx: mpsc::Receiver<(usize, Vec<f32>)>,
     cmd_rx: mpsc::Receiver<Cmd>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    meter_cb: Option<Arc<dyn Fn(InputLevel) + Send + Sync + 'static>>,
 ) {
-    let mut frame_resampler = FrameResampler::new(
-        in_sample_rate as usize,
-        constants::WHISPER_SAMPLE_RATE as usize,
-        Duration::from_millis(30),
-    );
+    const FRAME_MS: usize = 30;
+    let mut frame_resamplers: Vec<FrameResampler> = in_sample_rates
+        .iter()
+        .map(|&rate| {
+            FrameResampler::new(
+                rate as usize,
+                constants::WHISPER_SAMPLE_RATE as usize,
+                Duration::from_millis(FRAME_MS as u64),
+            )
+        })
+        .collect();
+
+    // Several sources are mixed at 16kHz before VAD; a single one goes straight through
+    let frame_len = constants::WHISPER_SAMPLE_RATE as usize * FRAME_MS / 1000;
+    let mut mixer = (in_sample_rates.len() > 1)
+        .then(|| SourceMixer::new(in_sample_rates.len(), frame_len, frame_len * 10));
 
     let mut processed_samples = Vec::<f32>::new();
+    // While spooling, processed_samples only holds audio not yet written out
+    let mut spool: Option<SpoolWriter> = None;
+    let mut preview =
+        RollingBuffer::new(SPOOL_PREVIEW_SECS * constants::WHISPER_SAMPLE_RATE as usize);
     let mut recording = false;
+    // Full-rate spool of the first source, kept until it is taken
+    let mut archive: Option<SpoolWriter> = None;
 
     // ---------- spectrum visualisation setup ---------------------------- //
+    // Only the first source drives the level meter
     const BUCKETS: usize = 16;
     const WINDOW_SIZE: usize = 512;
     let mut visualizer = AudioVisualiser::new(
-        in_sample_rate,
+        in_sample_rates[0],
         WINDOW_SIZE,
         BUCKETS,
         400.0,  // vocal_min_hz
         4000.0, // vocal_max_hz
     );
+    const METER_HZ: usize = 10;
+    let mut meter = LevelMeter::new(in_sample_rates[0] as usize / METER_HZ);
 
     fn handle_frame(
         samples: &[f32],
@@ -298,31 +525,127 @@ fn run_consumer(
         }
     }
 
-    loop {
-        let raw = match sample_rx.recv() {
-            Ok(s) => s,
-            Err(_) => break, // stream closed
+    fn push_source(
+        source: usize,
+        raw: &[f32],
+        recording: bool,
+        frame_resamplers: &mut [FrameResampler],
+        mixer: &mut Option<SourceMixer>,
+        vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+        out_buf: &mut Vec<f32>,
+    ) {
+        match mixer {
+            Some(mixer) => {
+                frame_resamplers[source].push(raw, &mut |frame: &[f32]| mixer.push(source, frame));
+                mixer.drain(|frame| handle_frame(frame, recording, vad, out_buf));
+            }
+            None => frame_resamplers[source].push(raw, &mut |frame: &[f32]| {
+                handle_frame(frame, recording, vad, out_buf)
+            }),
+        }
+    }
+
+    /// Move processed audio out to the spool file, if there is one.
+    fn spool_out(
+        spool: &mut Option<SpoolWriter>,
+        preview: &mut RollingBuffer,
+        processed: &mut Vec<f32>,
+    ) {
+        let Some(writer) = spool else {
+            return;
         };
+        if let Err(e) = writer.append(processed) {
+            log::error!("Failed to write recording to disk: {}", e);
+        }
+        preview.push(processed);
+        processed.clear();
+    }
 
-        // ---------- spectrum processing ---------------------------------- //
-        if let Some(buckets) = visualizer.feed(&raw) {
-            if let Some(cb) = &level_cb {
-                cb(buckets);
+    fn archive_out(archive: &mut Option<SpoolWriter>, raw: &[f32]) {
+        if let Some(writer) = archive {
+            if let Err(e) = writer.append(raw) {
+                log::error!("Failed to write archival recording to disk: {}", e);
             }
         }
+    }
 
-        // ---------- existing pipeline ------------------------------------ //
-        frame_resampler.push(&raw, &mut |frame: &[f32]| {
-            handle_frame(frame, recording, &vad, &mut processed_samples)
-        });
+    loop {
+        // A source can go quiet (a loopback device delivers nothing while the
+        // system is silent), so wake up regularly to handle commands
+        let received = match sample_rx.recv_timeout(COMMAND_POLL_INTERVAL) {
+            Ok(s) => Some(s),
+            Err(mpsc::RecvTimeoutError::Timeout) => None,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break, // stream closed
+        };
+        if let Some((source, mut raw)) = received {
+            if microphones[source] {
+                apply_gain(
+                    &mut raw,
+                    f32::from_bits(controls.gain.load(Ordering::Relaxed)),
+                );
+            }
+
+            // ---------- spectrum processing ---------------------------------- //
+            if source == 0 {
+                if let Some(buckets) = visualizer.feed(&raw) {
+                    if let Some(cb) = &level_cb {
+                        cb(buckets);
+                    }
+                }
+                if recording {
+                    if let Some(cb) = &meter_cb {
+                        meter.feed(&raw, cb.as_ref());
+                    }
+                    archive_out(&mut archive, &raw);
+                }
+            }
+
+            // ---------- existing pipeline ------------------------------------ //
+            push_source(
+                source,
+                &raw,
+                recording,
+                &mut frame_resamplers,
+                &mut mixer,
+                &vad,
+                &mut processed_samples,
+            );
+            spool_out(&mut spool, &mut preview, &mut processed_samples);
+        }
 
         // non-blocking check for a command
         while let Ok(cmd) = cmd_rx.try_recv() {
             match cmd {
-                Cmd::Start => {
+                Cmd::Start(spool_path) => {
                     processed_samples.clear();
+                    preview.clear();
+                    spool = spool_path.and_then(|path| match SpoolWriter::create(&path) {
+                        Ok(writer) => Some(writer),
+                        Err(e) => {
+                            log::error!(
+                                "Failed to create {}, recording to memory: {}",
+                                path.display(),
+                                e
+                            );
+                            None
+                        }
+                    });
                     recording = true;
+                    // An archive nobody took belongs to a discarded recording
+                    if let Some(Ok(stale)) = archive.take().map(SpoolWriter::finalize) {
+                        let _ = std::fs::remove_file(stale);
+                    }
+                    let archive_path = controls.archive.lock().unwrap().clone();
+                    archive = archive_path.and_then(|path| {
+                        SpoolWriter::create_archival(&path, in_sample_rates[0])
+                            .map_err(|e| log::error!("Failed to create {}: {}", path.display(), e))
+                            .ok()
+                    });
                     visualizer.reset(); // Reset visualization buffer
+                    meter.reset();
+                    if let Some(m) = &mut mixer {
+                        m.clear();
+                    }
                     if let Some(v) = &vad {
                         v.lock().unwrap().reset();
                     }
@@ -331,20 +654,73 @@ fn run_consumer(
                     recording = false;
 
                     // Drain any audio chunks that were captured but not yet consumed
-                    while let Ok(remaining) = sample_rx.try_recv() {
-                        frame_resampler.push(&remaining, &mut |frame: &[f32]| {
-                            handle_frame(frame, true, &vad, &mut processed_samples)
-                        });
+                    while let Ok((source, mut remaining)) = sample_rx.try_recv() {
+                        if microphones[source] {
+                            let gain = f32::from_bits(controls.gain.load(Ordering::Relaxed));
+                            apply_gain(&mut remaining, gain);
+                        }
+                        if source == 0 {
+                            archive_out(&mut archive, &remaining);
+                        }
+                        push_source(
+                            source,
+                            &remaining,
+                            true,
+                            &mut frame_resamplers,
+                            &mut mixer,
+                            &vad,
+                            &mut processed_samples,
+                        );
                     }
 
-                    frame_resampler.finish(&mut |frame: &[f32]| {
-                        handle_frame(frame, true, &vad, &mut processed_samples)
-                    });
+                    for (source, resampler) in frame_resamplers.iter_mut().enumerate() {
+                        match &mut mixer {
+                            Some(m) => resampler.finish(&mut |frame: &[f32]| m.push(source, frame)),
+                            None => resampler.finish(&mut |frame: &[f32]| {
+                                handle_frame(frame, true, &vad, &mut processed_samples)
+                            }),
+                        }
+                    }
+                    if let Some(m) = &mut mixer {
+                        m.finish(|frame| handle_frame(frame, true, &vad, &mut processed_samples));
+                    }
 
-                    let _ = reply_tx.send(std::mem::take(&mut processed_samples));
+                    spool_out(&mut spool, &mut preview, &mut processed_samples);
+                    preview.clear();
+                    let recorded = match spool.take().map(SpoolWriter::finalize) {
+                        Some(Ok(path)) => RecordedAudio::File(path),
+                        Some(Err(e)) => {
+                            log::error!("Failed to finish recording file: {}", e);
+                            RecordedAudio::Samples(Vec::new())
+                        }
+                        None => RecordedAudio::Samples(std::mem::take(&mut processed_samples)),
+                    };
+                    let _ = reply_tx.send(recorded);
                 }
                 Cmd::GetSamples(reply_tx) => {
-                    let _ = reply_tx.send(processed_samples.clone());
+                    let partial = if spool.is_some() {
+                        preview.snapshot()
+                    } else {
+                        PartialSamples {
+                            start: 0,
+                            samples: processed_samples.clone(),
+                        }
+                    };
+                    let _ = reply_tx.send(partial);
+                }
+                Cmd::TakeArchive(reply_tx) => {
+                    let taken = match archive.take().map(SpoolWriter::finalize) {
+                        Some(Ok(path)) => Some(ArchivalAudio {
+                            path,
+                            sample_rate: in_sample_rates[0],
+                        }),
+                        Some(Err(e)) => {
+                            log::error!("Failed to finish archival recording: {}", e);
+                            None
+                        }
+                        None => None,
+                    };
+                    let _ = reply_tx.send(taken);
                 }
                 Cmd::Shutdown => return,
             }
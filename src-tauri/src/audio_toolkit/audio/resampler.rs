@@ -1,6 +1,12 @@
-use rubato::{FftFixedIn, Resampler};
+use rubato::{
+    calculate_cutoff, FftFixedIn, Resampler, SincFixedIn, SincInterpolationParameters,
+    SincInterpolationType, WindowFunction,
+};
 use std::time::Duration;
 
+use crate::audio_save::linear_resample;
+use crate::settings::ResampleQuality;
+
 // Make this a constant you can tweak
 const RESAMPLER_CHUNK_SIZE: usize = 1024;
 
@@ -97,3 +103,136 @@ impl FrameResampler {
         }
     }
 }
+
+/// Input frames fed to the sinc resampler at a time when resampling a whole file.
+const OFFLINE_CHUNK_SIZE: usize = 4096;
+
+impl ResampleQuality {
+    /// Length of the sinc filter used at this quality.
+    fn sinc_len(self) -> usize {
+        match self {
+            ResampleQuality::Fast => 8,
+            ResampleQuality::Balanced => 64,
+            ResampleQuality::High => 256,
+        }
+    }
+}
+
+/// Resample a whole mono recording from `from_hz` to `to_hz` with a
+/// band-limited sinc filter. Falls back to linear interpolation if the
+/// resampler can't be set up.
+pub fn resample_mono_audio(
+    samples: &[f32],
+    from_hz: u32,
+    to_hz: u32,
+    quality: ResampleQuality,
+) -> Vec<f32> {
+    if from_hz == to_hz || samples.is_empty() {
+        return samples.to_vec();
+    }
+    match sinc_resample(samples, from_hz, to_hz, quality) {
+        Ok(resampled) => resampled,
+        Err(e) => {
+            log::warn!(
+                "Sinc resampling {}Hz -> {}Hz failed ({}), using linear interpolation",
+                from_hz,
+                to_hz,
+                e
+            );
+            linear_resample(samples, from_hz, to_hz)
+        }
+    }
+}
+
+fn sinc_resample(
+    samples: &[f32],
+    from_hz: u32,
+    to_hz: u32,
+    quality: ResampleQuality,
+) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+    let ratio = to_hz as f64 / from_hz as f64;
+    let sinc_len = quality.sinc_len();
+    let window = WindowFunction::BlackmanHarris2;
+    let params = SincInterpolationParameters {
+        sinc_len,
+        f_cutoff: calculate_cutoff(sinc_len, window),
+        oversampling_factor: 256,
+        interpolation: SincInterpolationType::Linear,
+        window,
+    };
+    let mut resampler = SincFixedIn::<f32>::new(ratio, 1.0, params, OFFLINE_CHUNK_SIZE, 1)?;
+
+    let expected = (samples.len() as f64 * ratio).ceil() as usize;
+    // The filter delays its output; that many leading samples are dropped
+    let delay = resampler.output_delay();
+    let mut out = Vec::with_capacity(expected + delay);
+
+    let mut chunks = samples.chunks_exact(OFFLINE_CHUNK_SIZE);
+    for chunk in &mut chunks {
+        out.extend_from_slice(&resampler.process(&[chunk], None)?[0]);
+    }
+    let rest = chunks.remainder();
+    if !rest.is_empty() {
+        out.extend_from_slice(&resampler.process_partial(Some(&[rest]), None)?[0]);
+    }
+    // Flush what is still inside the filter
+    while out.len() < expected + delay {
+        let tail = resampler.process_partial::<&[f32]>(None, None)?;
+        if tail[0].is_empty() {
+            break;
+        }
+        out.extend_from_slice(&tail[0]);
+    }
+
+    out.drain(..delay.min(out.len()));
+    out.truncate(expected);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, rate: u32, secs: f32) -> Vec<f32> {
+        (0..(rate as f32 * secs) as usize)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / rate as f32).sin() * 0.5)
+            .collect()
+    }
+
+    #[test]
+    fn test_resample_keeps_duration_and_level() {
+        for quality in [
+            ResampleQuality::Fast,
+            ResampleQuality::Balanced,
+            ResampleQuality::High,
+        ] {
+            let out = resample_mono_audio(&sine(440.0, 48000, 1.0), 48000, 16000, quality);
+            assert_eq!(out.len(), 16000);
+            // Away from the edges a 440Hz tone passes through unattenuated
+            let peak = out[2000..14000].iter().fold(0.0f32, |m, s| m.max(s.abs()));
+            assert!((peak - 0.5).abs() < 0.02, "{:?}: peak {}", quality, peak);
+        }
+    }
+
+    #[test]
+    fn test_resample_removes_content_above_new_nyquist() {
+        // 12kHz can't be represented at 16kHz and must not alias back in
+        let out = resample_mono_audio(
+            &sine(12000.0, 48000, 1.0),
+            48000,
+            16000,
+            ResampleQuality::High,
+        );
+        let peak = out[2000..14000].iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(peak < 0.01, "aliased peak {}", peak);
+    }
+
+    #[test]
+    fn test_same_rate_is_unchanged() {
+        let samples = vec![0.1, -0.2, 0.3];
+        assert_eq!(
+            resample_mono_audio(&samples, 16000, 16000, ResampleQuality::Fast),
+            samples
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+
+use crate::audio_toolkit::constants;
+
+/// Samples written between header updates, so a crash loses at most about a
+/// second of audio.
+const FLUSH_EVERY: usize = constants::WHISPER_SAMPLE_RATE as usize;
+
+/// Writes a recording to disk as it is captured instead of keeping it in
+/// memory. The file uses the same format as saved recordings (16kHz mono
+/// 16-bit), so it can be moved into place once the recording ends.
+pub struct SpoolWriter {
+    writer: WavWriter<BufWriter<File>>,
+    path: PathBuf,
+    unflushed: usize,
+    float: bool,
+}
+
+impl SpoolWriter {
+    pub fn create(path: &Path) -> Result<Self, hound::Error> {
+        Self::with_spec(
+            path,
+            WavSpec {
+                channels: 1,
+                sample_rate: constants::WHISPER_SAMPLE_RATE,
+                bits_per_sample: 16,
+                sample_format: SampleFormat::Int,
+            },
+        )
+    }
+
+    /// Spool lossless audio at the device's `sample_rate` (32-bit float), the
+    /// source an archival copy is converted from.
+    pub fn create_archival(path: &Path, sample_rate: u32) -> Result<Self, hound::Error> {
+        Self::with_spec(
+            path,
+            WavSpec {
+                channels: 1,
+                sample_rate,
+                bits_per_sample: 32,
+                sample_format: SampleFormat::Float,
+            },
+        )
+    }
+
+    fn with_spec(path: &Path, spec: WavSpec) -> Result<Self, hound::Error> {
+        Ok(Self {
+            writer: WavWriter::create(path, spec)?,
+            path: path.to_path_buf(),
+            unflushed: 0,
+            float: spec.sample_format == SampleFormat::Float,
+        })
+    }
+
+    pub fn append(&mut self, samples: &[f32]) -> Result<(), hound::Error> {
+        for &sample in samples {
+            if self.float {
+                self.writer.write_sample(sample)?;
+            } else {
+                self.writer
+                    .write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+            }
+        }
+        self.unflushed += samples.len();
+        if self.unflushed >= FLUSH_EVERY {
+            // Rewrites the header, so the file stays readable if we crash
+            self.writer.flush()?;
+            self.unflushed = 0;
+        }
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Result<PathBuf, hound::Error> {
+        self.writer.finalize()?;
+        Ok(self.path)
+    }
+}
+
+/// Read a spooled (or any 16-bit mono) WAV back as f32 samples.
+pub fn read_spooled_wav(path: &Path) -> Result<Vec<f32>, hound::Error> {
+    WavReader::open(path)?
+        .into_samples::<i16>()
+        .map(|s| s.map(|s| s as f32 / i16::MAX as f32))
+        .collect()
+}
+
+/// The most recent samples of a recording, with their position in it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartialSamples {
+    /// Index of `samples[0]` within the whole recording.
+    pub start: usize,
+    pub samples: Vec<f32>,
+}
+
+impl PartialSamples {
+    /// Samples recorded so far, including those no longer held.
+    pub fn end(&self) -> usize {
+        self.start + self.samples.len()
+    }
+}
+
+/// Keeps the last `capacity` samples of a recording for the live preview.
+pub struct RollingBuffer {
+    samples: VecDeque<f32>,
+    dropped: usize,
+    capacity: usize,
+}
+
+impl RollingBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            dropped: 0,
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        self.samples.extend(samples);
+        let excess = self.samples.len().saturating_sub(self.capacity);
+        self.samples.drain(..excess);
+        self.dropped += excess;
+    }
+
+    pub fn snapshot(&self) -> PartialSamples {
+        PartialSamples {
+            start: self.dropped,
+            samples: self.samples.iter().copied().collect(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.dropped = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_buffer_keeps_latest_samples_and_their_offset() {
+        let mut buffer = RollingBuffer::new(4);
+        buffer.push(&[0.1, 0.2, 0.3]);
+        assert_eq!(buffer.snapshot().start, 0);
+
+        buffer.push(&[0.4, 0.5, 0.6]);
+        let snapshot = buffer.snapshot();
+        assert_eq!(snapshot.start, 2);
+        assert_eq!(snapshot.samples, vec![0.3, 0.4, 0.5, 0.6]);
+        assert_eq!(snapshot.end(), 6);
+
+        buffer.clear();
+        assert_eq!(buffer.snapshot(), PartialSamples::default());
+    }
+
+    #[test]
+    fn test_spooled_recording_round_trips() {
+        let path = std::env::temp_dir().join(format!("spool-test-{}.wav", std::process::id()));
+        let mut writer = SpoolWriter::create(&path).unwrap();
+        writer.append(&[0.0, 0.5, -0.5]).unwrap();
+        writer.append(&[2.0]).unwrap();
+        let path = writer.finalize().unwrap();
+
+        let samples = read_spooled_wav(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(samples.len(), 4);
+        assert!((samples[1] - 0.5).abs() < 1e-3);
+        assert!((samples[2] + 0.5).abs() < 1e-3);
+        assert_eq!(samples[3], 1.0); // clamped
+    }
+}
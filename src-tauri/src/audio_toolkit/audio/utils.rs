@@ -1,8 +1,190 @@
 use anyhow::Result;
-use std::path::Path;
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::io::{Seek, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::FrameResampler;
 
 /// Save audio samples as a WAV file.
 /// Delegates to the cross-platform `audio_save` module.
 pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
     crate::audio_save::save_wav_file(file_path, samples).await
 }
+
+/// Microphone audio at the device's own rate, spooled to disk while
+/// recording when an archival copy is wanted. The spool file is removed when
+/// this is dropped.
+#[derive(Debug)]
+pub struct ArchivalAudio {
+    pub path: PathBuf,
+    pub sample_rate: u32,
+}
+
+impl Drop for ArchivalAudio {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+pub fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Amplify in place, clamping so a hot signal saturates instead of wrapping
+/// around when it is converted to integers.
+pub fn apply_gain(samples: &mut [f32], gain: f32) {
+    if gain == 1.0 {
+        return;
+    }
+    for sample in samples {
+        *sample = (*sample * gain).clamp(-1.0, 1.0);
+    }
+}
+
+/// Write `audio` as a mono WAV at `sample_rate` Hz with `bits_per_sample`
+/// bits (16 or 24 bit integer, or 32 bit float). The spool is converted a
+/// second at a time, so long recordings are never held in memory. Blocks.
+pub fn save_archival_wav<P: AsRef<Path>>(
+    file_path: P,
+    audio: &ArchivalAudio,
+    sample_rate: u32,
+    bits_per_sample: u16,
+) -> Result<()> {
+    let mut reader = WavReader::open(&audio.path)?;
+    let mut resampler = (audio.sample_rate != sample_rate).then(|| {
+        FrameResampler::new(
+            audio.sample_rate as usize,
+            sample_rate as usize,
+            Duration::from_millis(30),
+        )
+    });
+
+    if let Some(parent) = file_path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample,
+        sample_format: if bits_per_sample == 32 {
+            SampleFormat::Float
+        } else {
+            SampleFormat::Int
+        },
+    };
+    let mut writer = WavWriter::create(file_path.as_ref(), spec)?;
+
+    let chunk_len = audio.sample_rate.max(1) as usize;
+    let mut samples = reader.samples::<f32>();
+    let mut chunk = Vec::with_capacity(chunk_len);
+    let mut out = Vec::new();
+    loop {
+        chunk.clear();
+        for sample in samples.by_ref().take(chunk_len) {
+            chunk.push(sample?);
+        }
+        if chunk.is_empty() {
+            break;
+        }
+        out.clear();
+        match &mut resampler {
+            Some(r) => r.push(&chunk, |frame| out.extend_from_slice(frame)),
+            None => out.extend_from_slice(&chunk),
+        }
+        write_archival_samples(&mut writer, &out, bits_per_sample)?;
+    }
+    if let Some(r) = &mut resampler {
+        out.clear();
+        r.finish(|frame| out.extend_from_slice(frame));
+        write_archival_samples(&mut writer, &out, bits_per_sample)?;
+    }
+    writer.finalize()?;
+    log::debug!("Saved archival WAV file: {:?}", file_path.as_ref());
+    Ok(())
+}
+
+fn write_archival_samples<W: Write + Seek>(
+    writer: &mut WavWriter<W>,
+    samples: &[f32],
+    bits_per_sample: u16,
+) -> Result<(), hound::Error> {
+    let max = ((1i64 << (bits_per_sample.min(31) - 1)) - 1) as f32;
+    for &sample in samples {
+        match bits_per_sample {
+            32 => writer.write_sample(sample)?,
+            16 => writer.write_sample((sample * max) as i16)?,
+            _ => writer.write_sample((sample * max) as i32)?,
+        }
+    }
+    Ok(())
+}
+
+/// Start of the quietest `frame`-sample window within `radius` samples of
+/// `around`: a pause, where audio can be cut without splitting a word.
+pub fn quietest_point(samples: &[f32], around: usize, radius: usize, frame: usize) -> usize {
+    let frame = frame.max(1);
+    let start = around.saturating_sub(radius);
+    let end = (around + radius).min(samples.len().saturating_sub(frame));
+    if start >= end {
+        return around.min(samples.len());
+    }
+    (start..=end)
+        .step_by(frame / 2 + 1)
+        .min_by(|&a, &b| {
+            let energy = |i: usize| samples[i..i + frame].iter().map(|s| s * s).sum::<f32>();
+            energy(a).total_cmp(&energy(b))
+        })
+        .unwrap_or(around)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quietest_point_finds_the_pause() {
+        let mut samples = vec![0.5f32; 1000];
+        samples[620..700].fill(0.0);
+        let cut = quietest_point(&samples, 500, 300, 40);
+        assert!((620..=660).contains(&cut), "cut at {}", cut);
+
+        assert_eq!(quietest_point(&samples[..10], 5, 3, 40), 5);
+    }
+
+    #[test]
+    fn test_archival_copy_is_resampled_from_the_spool() {
+        let dir = std::env::temp_dir().join(format!("archival-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let spool = dir.join("spool.wav");
+        let mut writer =
+            crate::audio_toolkit::audio::SpoolWriter::create_archival(&spool, 48000).unwrap();
+        writer.append(&vec![0.25f32; 48000 * 3]).unwrap();
+        writer.finalize().unwrap();
+
+        let audio = ArchivalAudio {
+            path: spool.clone(),
+            sample_rate: 48000,
+        };
+        let saved = dir.join("archive").join("copy.wav");
+        save_archival_wav(&saved, &audio, 16000, 24).unwrap();
+        drop(audio);
+
+        let reader = WavReader::open(&saved).unwrap();
+        assert_eq!(reader.spec().sample_rate, 16000);
+        assert_eq!(reader.spec().bits_per_sample, 24);
+        let len = reader.len() as i64;
+        assert!((len - 48000).abs() < 1000, "{} samples", len);
+        assert!(!spool.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_gain_clips_instead_of_overflowing() {
+        let mut samples = vec![0.1, -0.4, 0.6];
+        apply_gain(&mut samples, db_to_gain(6.0));
+        assert!((samples[0] - 0.1995).abs() < 1e-3);
+        assert!((samples[1] + 0.798).abs() < 1e-3);
+        assert_eq!(samples[2], 1.0);
+    }
+}
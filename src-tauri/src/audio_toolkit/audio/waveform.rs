@@ -0,0 +1,95 @@
+use anyhow::Result;
+use dashmap::DashMap;
+use hound::{SampleFormat, WavReader};
+use once_cell::sync::Lazy;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Computed waveforms keyed on path, modification time and resolution, so a
+/// re-recorded file with the same name is never served stale peaks.
+static WAVEFORM_CACHE: Lazy<DashMap<String, Vec<f32>>> = Lazy::new(DashMap::new);
+
+/// RMS amplitude of a WAV file in `resolution` evenly sized buckets.
+/// A missing file yields an empty waveform.
+pub fn compute_waveform(path: &Path, resolution: u32) -> Result<Vec<f32>> {
+    let Ok(metadata) = path.metadata() else {
+        return Ok(vec![]);
+    };
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let key = format!("{}:{}:{}", path.display(), mtime, resolution);
+    if let Some(cached) = WAVEFORM_CACHE.get(&key) {
+        return Ok(cached.clone());
+    }
+
+    let reader = WavReader::open(path)?;
+    let spec = reader.spec();
+    let waveform = match spec.sample_format {
+        SampleFormat::Float => bucket_rms(
+            reader.len() as usize,
+            resolution,
+            reader.into_samples::<f32>().filter_map(|s| s.ok()),
+        ),
+        SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            bucket_rms(
+                reader.len() as usize,
+                resolution,
+                reader
+                    .into_samples::<i32>()
+                    .filter_map(|s| s.ok())
+                    .map(|s| s as f32 / scale),
+            )
+        }
+    };
+
+    WAVEFORM_CACHE.insert(key, waveform.clone());
+    Ok(waveform)
+}
+
+/// Split `total` samples into `resolution` buckets and return the RMS of each.
+/// Leftover samples that don't fill a whole bucket are dropped.
+fn bucket_rms(total: usize, resolution: u32, samples: impl Iterator<Item = f32>) -> Vec<f32> {
+    let resolution = resolution.max(1) as usize;
+    let bucket_len = (total / resolution).max(1);
+    let mut waveform = Vec::with_capacity(resolution.min(total));
+    let mut sum_squares = 0.0f64;
+    let mut count = 0;
+
+    for sample in samples {
+        sum_squares += (sample as f64) * (sample as f64);
+        count += 1;
+        if count == bucket_len {
+            waveform.push((sum_squares / count as f64).sqrt() as f32);
+            if waveform.len() == resolution {
+                break;
+            }
+            sum_squares = 0.0;
+            count = 0;
+        }
+    }
+
+    waveform
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_rms() {
+        let samples = [0.5, -0.5, 1.0, -1.0, 0.0, 0.0, 0.3];
+        let waveform = bucket_rms(samples.len(), 3, samples.into_iter());
+        assert_eq!(waveform, vec![0.5, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_compute_waveform_missing_file_is_empty() {
+        let waveform = compute_waveform(Path::new("/nonexistent/clip.wav"), 200).unwrap();
+        assert!(waveform.is_empty());
+    }
+}
@@ -5,8 +5,9 @@ pub mod utils;
 pub mod vad;
 
 pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    compute_waveform, list_input_devices, list_output_devices, resample_mono_audio, save_wav_file,
+    AudioRecorder, CpalDeviceInfo,
 };
-pub use text::{apply_custom_words, filter_transcription_output};
+pub use text::{apply_custom_words, filter_transcription_output, post_process_transcript};
 pub use utils::get_cpal_host;
 pub use vad::{SileroVad, VoiceActivityDetector};
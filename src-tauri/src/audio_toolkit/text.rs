@@ -1,3 +1,4 @@
+use crate::settings::WordReplacement;
 use natural::phonetics::soundex;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -283,10 +284,89 @@ pub fn filter_transcription_output(text: &str) -> String {
     filtered.trim().to_string()
 }
 
+/// Applies the user's word replacement rules, in order.
+///
+/// `from` is matched literally as a whole word (or phrase), so "Tory" does not
+/// rewrite "history". Rules with an empty `from` are ignored.
+pub fn post_process_transcript(text: &str, replacements: &[WordReplacement]) -> String {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut result = text.to_string();
+
+    for rule in replacements {
+        let from = rule.from.trim();
+        if from.is_empty() {
+            continue;
+        }
+
+        // \b only makes sense next to word characters ("C++" has none at its end)
+        let start = if from.starts_with(is_word_char) {
+            r"\b"
+        } else {
+            ""
+        };
+        let end = if from.ends_with(is_word_char) {
+            r"\b"
+        } else {
+            ""
+        };
+        let flags = if rule.case_sensitive { "" } else { "(?i)" };
+        let pattern = format!("{}{}{}{}", flags, start, regex::escape(from), end);
+
+        match Regex::new(&pattern) {
+            Ok(re) => {
+                result = re
+                    .replace_all(&result, regex::NoExpand(&rule.to))
+                    .into_owned();
+            }
+            Err(e) => log::warn!("Skipping word replacement '{}': {}", from, e),
+        }
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn rule(from: &str, to: &str, case_sensitive: bool) -> WordReplacement {
+        WordReplacement {
+            from: from.to_string(),
+            to: to.to_string(),
+            case_sensitive,
+        }
+    }
+
+    #[test]
+    fn test_post_process_transcript_whole_words() {
+        let rules = vec![rule("tory", "Tauri", false)];
+        assert_eq!(
+            post_process_transcript("Tory apps have history", &rules),
+            "Tauri apps have history"
+        );
+    }
+
+    #[test]
+    fn test_post_process_transcript_case_sensitive() {
+        let rules = vec![rule("Rust", "rust-lang", true)];
+        assert_eq!(
+            post_process_transcript("Rust and rust", &rules),
+            "rust-lang and rust"
+        );
+    }
+
+    #[test]
+    fn test_post_process_transcript_literal_replacement() {
+        let rules = vec![
+            rule("see plus plus", "C++ ($1)", false),
+            rule("", "x", false),
+        ];
+        assert_eq!(
+            post_process_transcript("I write see plus plus", &rules),
+            "I write C++ ($1)"
+        );
+    }
+
     #[test]
     fn test_apply_custom_words_exact_match() {
         let text = "hello world";
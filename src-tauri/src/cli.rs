@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
 #[derive(Parser, Debug, Clone, Default)]
 #[command(
@@ -29,4 +29,42 @@ pub struct CliArgs {
     /// Enable debug mode with verbose logging
     #[arg(long)]
     pub debug: bool,
+
+    /// Transcribe an audio file, print the result to stdout and exit (no GUI)
+    #[arg(long, value_name = "PATH")]
+    pub transcribe_file: Option<String>,
+
+    /// Model to use with --transcribe-file (defaults to the selected model)
+    #[arg(long, value_name = "MODEL_ID")]
+    pub model: Option<String>,
+
+    /// Add a journal entry with TEXT (or, without TEXT, stdin) as its body and
+    /// exit (no GUI)
+    #[arg(long, value_name = "TEXT", num_args = 0..=1)]
+    pub journal_add: Option<Option<String>>,
+
+    /// Title for --journal-add (defaults to the first sentence of the text)
+    #[arg(long, value_name = "TITLE", requires = "journal_add")]
+    pub title: Option<String>,
+
+    /// Print the available input and output audio devices and exit (no GUI)
+    #[arg(long)]
+    pub list_audio_devices: bool,
+
+    /// Output format for --transcribe-file and --list-audio-devices
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
+
+    /// Write the --transcribe-file result to this file instead of stdout
+    #[arg(long, value_name = "PATH", requires = "transcribe_file")]
+    pub output: Option<String>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Plain transcript text
+    #[default]
+    Text,
+    /// JSON object with `text` and `detected_language`
+    Json,
 }
@@ -8,19 +8,60 @@ use crate::settings::{get_settings, PostProcessProvider};
 use log::{debug, info};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use reqwest::multipart;
-use serde::Deserialize;
-use tauri::AppHandle;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
 
 #[derive(Debug, Deserialize)]
 struct TranscriptionResponse {
     text: String,
 }
 
+/// Optional Whisper request fields. Anything left unset is omitted from the
+/// request, so the provider's defaults apply.
+#[derive(Debug, Clone, Default)]
+pub struct CloudTranscriptionOptions {
+    /// ISO-639-1 code such as "de". Unset or "auto" lets the provider detect
+    /// it; the Chinese variants "zh-Hans" and "zh-Hant" are sent as "zh".
+    pub language: Option<String>,
+    pub temperature: Option<f32>,
+    /// Text to prime the model with, e.g. names and vocabulary.
+    pub prompt: Option<String>,
+}
+
+impl CloudTranscriptionOptions {
+    fn form_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = Vec::new();
+        if let Some(language) = self
+            .language
+            .as_deref()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && *l != "auto")
+        {
+            let language = match language {
+                "zh-Hans" | "zh-Hant" => "zh",
+                other => other,
+            };
+            fields.push(("language", language.to_string()));
+        }
+        if let Some(temperature) = self.temperature {
+            fields.push(("temperature", temperature.clamp(0.0, 1.0).to_string()));
+        }
+        if let Some(prompt) = self.prompt.as_deref().filter(|p| !p.trim().is_empty()) {
+            fields.push(("prompt", prompt.to_string()));
+        }
+        fields
+    }
+}
+
 /// Transcribe a WAV file using the user's configured post-processing API provider.
 /// Uses the Whisper API format: POST /v1/audio/transcriptions with multipart form data.
 ///
 /// Returns the transcription text, or an error if the API call fails or no provider is configured.
-pub async fn transcribe_audio_cloud(app: &AppHandle, wav_path: &str) -> Result<String, String> {
+pub async fn transcribe_audio_cloud(
+    app: &AppHandle,
+    wav_path: &str,
+    options: CloudTranscriptionOptions,
+) -> Result<String, String> {
     let settings = get_settings(app);
 
     let provider = settings
@@ -42,13 +83,18 @@ pub async fn transcribe_audio_cloud(app: &AppHandle, wav_path: &str) -> Result<S
         );
     }
 
-    transcribe_with_provider(&provider, &api_key, wav_path).await
+    transcribe_with_provider(app, &provider, &api_key, wav_path, &options).await
 }
 
+/// Upload a WAV for transcription, splitting it into several requests when
+/// it is over the providers' size limit. Emits `cloud-transcription-progress`
+/// after each request.
 async fn transcribe_with_provider(
+    app: &AppHandle,
     provider: &PostProcessProvider,
     api_key: &str,
     wav_path: &str,
+    options: &CloudTranscriptionOptions,
 ) -> Result<String, String> {
     let base_url = provider.base_url.trim_end_matches('/');
     let url = format!("{}/audio/transcriptions", base_url);
@@ -68,7 +114,7 @@ async fn transcribe_with_provider(
         );
     }
 
-    let client = reqwest::Client::builder()
+    let client = crate::http::http_client_builder()
         .default_headers(headers)
         .build()
         .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
@@ -76,6 +122,14 @@ async fn transcribe_with_provider(
     // Read the WAV file
     let wav_bytes =
         std::fs::read(wav_path).map_err(|e| format!("Failed to read WAV file: {}", e))?;
+    // Uploads are labelled audio/wav and split as WAV, so anything else would
+    // be sent corrupted; imports decode to WAV before getting here
+    if !wav_bytes.starts_with(b"RIFF") {
+        return Err(format!(
+            "Cloud transcription needs a WAV file: {}",
+            wav_path
+        ));
+    }
 
     let file_name = std::path::Path::new(wav_path)
         .file_name()
@@ -83,19 +137,84 @@ async fn transcribe_with_provider(
         .to_string_lossy()
         .to_string();
 
+    if wav_bytes.len() <= UPLOAD_LIMIT_BYTES {
+        let text = upload_audio(&client, &url, wav_bytes, file_name, options).await?;
+        emit_progress(app, 1, 1);
+        info!("Cloud transcription complete: {} chars", text.len());
+        return Ok(text);
+    }
+
+    // Too big for one request: upload it in overlapping pieces and stitch
+    // the text back together, like `transcribe_chunked` does locally
+    let chunks = split_wav(&wav_bytes, CHUNK_BYTES, OVERLAP_SECS)?;
+    drop(wav_bytes);
+    let total = chunks.len();
+    info!(
+        "Audio exceeds the upload limit; transcribing it in {} chunks",
+        total
+    );
+
+    let mut text = String::new();
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        let chunk_name = format!("chunk-{}-{}", i + 1, file_name);
+        let part = upload_audio(&client, &url, chunk, chunk_name, options)
+            .await
+            .map_err(|e| format!("Chunk {} of {} failed: {}", i + 1, total, e))?;
+        text = join_overlapping(&text, &part);
+        emit_progress(app, i + 1, total);
+    }
+
+    info!("Cloud transcription complete: {} chars", text.len());
+    Ok(text)
+}
+
+/// Whisper-compatible APIs reject uploads over 25MB.
+const UPLOAD_LIMIT_BYTES: usize = 25 * 1024 * 1024;
+
+/// Size of each piece of a split upload, leaving room for the form overhead.
+const CHUNK_BYTES: usize = 20 * 1024 * 1024;
+
+/// Audio repeated at the start of each piece, so a word cut at a boundary is
+/// heard whole in one of them.
+const OVERLAP_SECS: u32 = 2;
+
+/// Payload of `cloud-transcription-progress`, emitted after each upload.
+#[derive(Clone, Serialize)]
+struct CloudTranscriptionProgress {
+    current: usize,
+    total: usize,
+}
+
+fn emit_progress(app: &AppHandle, current: usize, total: usize) {
+    let _ = app.emit(
+        "cloud-transcription-progress",
+        CloudTranscriptionProgress { current, total },
+    );
+}
+
+async fn upload_audio(
+    client: &reqwest::Client,
+    url: &str,
+    wav_bytes: Vec<u8>,
+    file_name: String,
+    options: &CloudTranscriptionOptions,
+) -> Result<String, String> {
     // Build multipart form
     let file_part = multipart::Part::bytes(wav_bytes)
         .file_name(file_name)
         .mime_str("audio/wav")
         .map_err(|e| format!("Failed to create file part: {}", e))?;
 
-    let form = multipart::Form::new()
+    let mut form = multipart::Form::new()
         .part("file", file_part)
         .text("model", "whisper-1")
         .text("response_format", "json");
+    for (name, value) in options.form_fields() {
+        form = form.text(name, value);
+    }
 
     let response = client
-        .post(&url)
+        .post(url)
         .multipart(form)
         .send()
         .await
@@ -115,7 +234,198 @@ async fn transcribe_with_provider(
         .await
         .map_err(|e| format!("Failed to parse transcription response: {}", e))?;
 
-    info!("Cloud transcription complete: {} chars", result.text.len());
-
     Ok(result.text)
 }
+
+/// Split a WAV into mono 16-bit WAVs of at most `chunk_bytes` each, every one
+/// after the first starting `overlap_secs` before the previous one ended.
+fn split_wav(
+    wav_bytes: &[u8],
+    chunk_bytes: usize,
+    overlap_secs: u32,
+) -> Result<Vec<Vec<u8>>, String> {
+    let reader = hound::WavReader::new(std::io::Cursor::new(wav_bytes))
+        .map_err(|e| format!("Only WAV audio can be split for upload: {}", e))?;
+    let spec = reader.spec();
+    let samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Int => {
+            let scale = (1_i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .filter_map(|s| s.ok())
+                .map(|s| s as f32 / scale)
+                .collect()
+        }
+        hound::SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .filter_map(|s| s.ok())
+            .collect(),
+    };
+    // Mixing down to mono also shrinks stereo uploads by half
+    let mono: Vec<f32> = samples
+        .chunks(spec.channels.max(1) as usize)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+
+    let out_spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: spec.sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let chunk_len = chunk_bytes.saturating_sub(44) / 2;
+    let overlap = (spec.sample_rate * overlap_secs) as usize;
+    if chunk_len <= overlap {
+        return Err("Upload chunk size is too small".to_string());
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < mono.len() {
+        let end = (start + chunk_len).min(mono.len());
+        let mut buf = std::io::Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut buf, out_spec)
+            .map_err(|e| format!("Failed to write audio chunk: {}", e))?;
+        for &sample in &mono[start..end] {
+            writer
+                .write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+                .map_err(|e| format!("Failed to write audio chunk: {}", e))?;
+        }
+        writer
+            .finalize()
+            .map_err(|e| format!("Failed to write audio chunk: {}", e))?;
+        chunks.push(buf.into_inner());
+        if end == mono.len() {
+            break;
+        }
+        start = end - overlap;
+    }
+    Ok(chunks)
+}
+
+/// Longest run of words repeated across a chunk boundary that is dropped.
+const MAX_OVERLAP_WORDS: usize = 12;
+
+/// Append `next` to `prev`, dropping words at the start of `next` that repeat
+/// the end of `prev` (the overlapping audio transcribed twice). Words are
+/// compared ignoring case and punctuation.
+fn join_overlapping(prev: &str, next: &str) -> String {
+    fn normalize(word: &str) -> String {
+        word.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    let prev = prev.trim();
+    let next = next.trim();
+    if prev.is_empty() || next.is_empty() {
+        return format!("{}{}", prev, next);
+    }
+
+    let prev_words: Vec<String> = prev.split_whitespace().map(normalize).collect();
+    let next_words: Vec<&str> = next.split_whitespace().collect();
+    let max = MAX_OVERLAP_WORDS
+        .min(prev_words.len())
+        .min(next_words.len());
+    let repeated = (1..=max)
+        .rev()
+        .find(|&n| {
+            prev_words[prev_words.len() - n..]
+                .iter()
+                .zip(&next_words[..n])
+                .all(|(a, b)| *a == normalize(b))
+        })
+        .unwrap_or(0);
+
+    if repeated == next_words.len() {
+        return prev.to_string();
+    }
+    format!("{} {}", prev, next_words[repeated..].join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset_options_add_no_fields() {
+        assert!(CloudTranscriptionOptions::default()
+            .form_fields()
+            .is_empty());
+
+        let auto = CloudTranscriptionOptions {
+            language: Some("auto".to_string()),
+            prompt: Some("  ".to_string()),
+            ..Default::default()
+        };
+        assert!(auto.form_fields().is_empty());
+    }
+
+    #[test]
+    fn test_options_become_form_fields() {
+        let options = CloudTranscriptionOptions {
+            language: Some("de".to_string()),
+            temperature: Some(0.2),
+            prompt: Some("Mutter, Obsidian".to_string()),
+        };
+        assert_eq!(
+            options.form_fields(),
+            vec![
+                ("language", "de".to_string()),
+                ("temperature", "0.2".to_string()),
+                ("prompt", "Mutter, Obsidian".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chinese_variants_are_sent_as_zh() {
+        for variant in ["zh-Hans", "zh-Hant"] {
+            let options = CloudTranscriptionOptions {
+                language: Some(variant.to_string()),
+                ..Default::default()
+            };
+            assert_eq!(options.form_fields(), vec![("language", "zh".to_string())]);
+        }
+    }
+
+    #[test]
+    fn test_join_overlapping_drops_repeated_words() {
+        assert_eq!(
+            join_overlapping("we met on Tuesday to talk", "to talk. About the budget"),
+            "we met on Tuesday to talk About the budget"
+        );
+        assert_eq!(join_overlapping("", "hello"), "hello");
+        assert_eq!(join_overlapping("one two", "three"), "one two three");
+        assert_eq!(join_overlapping("one two", "Two."), "one two");
+    }
+
+    #[test]
+    fn test_split_wav_overlaps_chunks() {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 10,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut buf = std::io::Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut buf, spec).unwrap();
+        for i in 0..100 {
+            writer.write_sample(i as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        // 40 samples per chunk, 1 second (10 samples) of overlap
+        let chunks = split_wav(&buf.into_inner(), 44 + 80, 1).unwrap();
+        let lens: Vec<u32> = chunks
+            .iter()
+            .map(|c| {
+                hound::WavReader::new(std::io::Cursor::new(c))
+                    .unwrap()
+                    .duration()
+            })
+            .collect();
+        assert_eq!(lens, vec![40, 40, 40]);
+    }
+}
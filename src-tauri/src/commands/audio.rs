@@ -1,7 +1,7 @@
 use crate::audio_feedback;
-use crate::audio_toolkit::audio::{list_input_devices, list_output_devices};
+use crate::audio_toolkit::audio::{list_input_devices, list_loopback_devices, list_output_devices};
 use crate::managers::audio::{AudioRecordingManager, MicrophoneMode};
-use crate::settings::{get_settings, write_settings};
+use crate::settings::{get_settings, write_settings, RecordingSource, ResampleQuality};
 use log::warn;
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -115,6 +115,157 @@ pub fn get_selected_microphone(app: AppHandle) -> Result<String, String> {
         .unwrap_or_else(|| "default".to_string()))
 }
 
+/// System-audio sources that can be recorded. Errs on platforms without
+/// loopback capture.
+#[tauri::command]
+#[specta::specta]
+pub fn get_available_loopback_devices() -> Result<Vec<AudioDevice>, String> {
+    let devices = list_loopback_devices()?;
+
+    Ok(devices
+        .into_iter()
+        .enumerate()
+        .map(|(index, d)| AudioDevice {
+            index: index.to_string(),
+            name: d.name,
+            is_default: d.is_default,
+        })
+        .collect())
+}
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct MicrophoneLevel {
+    pub rms: f32,
+    pub peak: f32,
+}
+
+/// Record about two seconds from the selected microphone, outside of any real
+/// recording, and report how loud it was. Backs the meter next to the mic picker.
+#[tauri::command]
+#[specta::specta]
+pub async fn test_microphone_level(app: AppHandle) -> Result<MicrophoneLevel, String> {
+    let rm = app.state::<Arc<AudioRecordingManager>>().inner().clone();
+    let level = tauri::async_runtime::spawn_blocking(move || {
+        rm.measure_microphone_level(std::time::Duration::from_secs(2))
+    })
+    .await
+    .map_err(|e| format!("Microphone test failed: {}", e))?
+    .map_err(|e| e.to_string())?;
+
+    Ok(MicrophoneLevel {
+        rms: level.rms,
+        peak: level.peak,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_selected_loopback_device(app: AppHandle, device_name: String) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.selected_loopback_device = if device_name == "default" {
+        None
+    } else {
+        Some(device_name)
+    };
+    write_settings(&app, settings);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_selected_device()
+        .map_err(|e| format!("Failed to update selected device: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_selected_loopback_device(app: AppHandle) -> Result<String, String> {
+    let settings = get_settings(&app);
+    Ok(settings
+        .selected_loopback_device
+        .unwrap_or_else(|| "default".to_string()))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_recording_source_setting(
+    app: AppHandle,
+    source: RecordingSource,
+) -> Result<(), String> {
+    if source != RecordingSource::Microphone {
+        // Surface the capability error now rather than at the next recording
+        list_loopback_devices()?;
+    }
+
+    let mut settings = get_settings(&app);
+    settings.recording_source = source;
+    write_settings(&app, settings);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_selected_device()
+        .map_err(|e| format!("Failed to switch recording source: {}", e))
+}
+
+/// Set how imported audio is resampled to 16kHz before transcription.
+#[tauri::command]
+#[specta::specta]
+pub fn change_audio_resample_quality_setting(app: AppHandle, quality: ResampleQuality) {
+    let mut settings = get_settings(&app);
+    settings.audio_resample_quality = quality;
+    write_settings(&app, settings);
+}
+
+/// Input gain limits, in dB.
+const MICROPHONE_GAIN_RANGE_DB: (f32, f32) = (-20.0, 30.0);
+
+/// Set the input gain for `device_name` ("default" for the host default).
+/// Applies to the recording in progress as well.
+#[tauri::command]
+#[specta::specta]
+pub fn set_microphone_gain(
+    app: AppHandle,
+    device_name: String,
+    gain_db: f32,
+) -> Result<(), String> {
+    let (min, max) = MICROPHONE_GAIN_RANGE_DB;
+    if !(min..=max).contains(&gain_db) {
+        return Err(format!("Gain must be between {} and {} dB", min, max));
+    }
+
+    let mut settings = get_settings(&app);
+    if gain_db == 0.0 {
+        settings.microphone_gain.remove(&device_name);
+    } else {
+        settings.microphone_gain.insert(device_name, gain_db);
+    }
+    write_settings(&app, settings);
+
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_input_gain();
+    Ok(())
+}
+
+/// Format of the archival WAV saved alongside each recording. 16000 Hz at
+/// 16 bit turns archival copies off.
+#[tauri::command]
+#[specta::specta]
+pub fn change_recording_format_setting(
+    app: AppHandle,
+    sample_rate: u32,
+    bit_depth: u16,
+) -> Result<(), String> {
+    const SAMPLE_RATES: [u32; 5] = [16000, 22050, 44100, 48000, 96000];
+    if !SAMPLE_RATES.contains(&sample_rate) {
+        return Err(format!("Unsupported sample rate: {} Hz", sample_rate));
+    }
+    if ![16, 24, 32].contains(&bit_depth) {
+        return Err(format!("Unsupported bit depth: {}", bit_depth));
+    }
+
+    let mut settings = get_settings(&app);
+    settings.recording_sample_rate = sample_rate;
+    settings.recording_bit_depth = bit_depth;
+    write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_available_output_devices() -> Result<Vec<AudioDevice>, String> {
@@ -1,4 +1,5 @@
 use crate::managers::history::{HistoryEntry, HistoryManager};
+use log::info;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -99,3 +100,64 @@ pub async fn update_recording_retention_period(
 
     Ok(())
 }
+
+/// Write the whole history to `dest_path` as CSV.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_history_to_csv(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    dest_path: String,
+) -> Result<(), String> {
+    let file = std::fs::File::create(&dest_path)
+        .map_err(|e| format!("Failed to create {}: {}", dest_path, e))?;
+    let mut writer = std::io::BufWriter::new(file);
+    history_manager
+        .export_csv(&mut writer)
+        .map_err(|e| e.to_string())?;
+    info!("Exported history to {}", dest_path);
+    Ok(())
+}
+
+/// Write the whole history to `dest_path` as a JSON array of entries.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_history_to_json(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    dest_path: String,
+) -> Result<(), String> {
+    let entries = history_manager
+        .get_history_entries()
+        .await
+        .map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+    std::fs::write(&dest_path, json)
+        .map_err(|e| format!("Failed to write {}: {}", dest_path, e))?;
+    info!("Exported history to {}", dest_path);
+    Ok(())
+}
+
+/// Ask where to save a history export, suggesting `history-export-<timestamp>.csv`.
+/// Returns `None` if the user cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn open_history_export_dialog(app: AppHandle) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let file_name = format!(
+        "history-export-{}.csv",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let Some(path) = app
+        .dialog()
+        .file()
+        .set_file_name(file_name)
+        .add_filter("CSV", &["csv"])
+        .add_filter("JSON", &["json"])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+
+    let path = path.into_path().map_err(|e| e.to_string())?;
+    Ok(Some(path.to_string_lossy().to_string()))
+}
@@ -1,42 +1,70 @@
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
-use crate::commands::video::transcribe_chunked;
+use crate::audio_toolkit::{
+    audio::{quietest_point, RecordedAudio},
+    compute_waveform, post_process_transcript,
+};
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use crate::commands::video::{transcribe_chunked, transcription_error_message, ChunkProgress};
+use crate::error::AppError;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
-use crate::managers::audio::AudioRecordingManager;
+use crate::git_sync::GitSyncStatus;
+use crate::llm_client::{SamplingOptions, CHARS_PER_TOKEN};
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use crate::managers::audio::{pad_short_recording, AudioRecordingManager, PartialTranscript};
 use crate::managers::journal::{
-    ChatMessage, ChatSession, JournalEntry, JournalFolder, JournalManager, JournalRecordingResult,
+    BackupInfo, ChatMessage, ChatSession, JournalEntry, JournalFolder, JournalManager,
+    JournalRecordingResult, MultiEntryChatSession, TrashedEntry,
 };
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
-use crate::managers::transcription::TranscriptionManager;
+use crate::managers::transcription::{
+    emit_transcription_timeout, TranscriptionError, TranscriptionManager,
+};
+use crate::utils::diff::{compute_text_diff, DiffChunk};
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Emitter, State};
+
+/// Collapse runs of `threshold` or more identical consecutive words into one.
+/// Words are compared case-insensitively, ignoring trailing punctuation, and the
+/// last word of a collapsed run is kept so its punctuation survives.
+/// With threshold 3: "your your your thing" → "your thing", "very very good" is unchanged.
+fn dedup_consecutive_words(text: &str, threshold: usize) -> String {
+    fn normalize(word: &str) -> String {
+        word.trim_end_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase()
+    }
+
+    fn flush<'a>(run: &mut Vec<&'a str>, kept: &mut Vec<&'a str>, threshold: usize) {
+        if run.len() >= threshold {
+            kept.push(run[run.len() - 1]);
+        } else {
+            kept.extend_from_slice(run);
+        }
+        run.clear();
+    }
 
-/// Remove consecutively repeated words from text.
-/// "your your your thing" → "your thing"
-fn dedup_consecutive_words(text: &str) -> String {
-    let mut result = String::with_capacity(text.len());
-    let mut prev_word_lower = String::new();
-    let mut first = true;
+    let threshold = threshold.max(2);
+    let mut kept: Vec<&str> = Vec::new();
+    let mut run: Vec<&str> = Vec::new();
+    let mut run_key = String::new();
 
     for word in text.split_whitespace() {
-        let word_lower = word.to_lowercase();
-        if word_lower != prev_word_lower || first {
-            if !first {
-                result.push(' ');
-            }
-            result.push_str(word);
-            first = false;
+        let key = normalize(word);
+        if run.is_empty() || key.is_empty() || key != run_key {
+            flush(&mut run, &mut kept, threshold);
+            run_key = key;
         }
-        prev_word_lower = word_lower;
+        run.push(word);
     }
+    flush(&mut run, &mut kept, threshold);
 
-    result
+    kept.join(" ")
 }
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[tauri::command]
 #[specta::specta]
 pub async fn start_journal_recording(
-    _app: AppHandle,
+    app: AppHandle,
     recording_manager: State<'_, Arc<AudioRecordingManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
 ) -> Result<(), String> {
@@ -44,10 +72,11 @@ pub async fn start_journal_recording(
     transcription_manager.initiate_model_load();
 
     // Start recording with "journal" binding_id
-    let started = recording_manager.try_start_recording("journal");
+    let started = recording_manager.try_start_spooled_recording("journal");
     if !started {
         return Err("Failed to start recording. Another recording may be in progress.".to_string());
     }
+    crate::tray::update_tray_menu(&app, &crate::tray::TrayIconState::Idle, None);
 
     Ok(())
 }
@@ -56,40 +85,201 @@ pub async fn start_journal_recording(
 #[tauri::command]
 #[specta::specta]
 pub async fn stop_journal_recording(
-    _app: AppHandle,
+    app: AppHandle,
     recording_manager: State<'_, Arc<AudioRecordingManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
     journal_manager: State<'_, Arc<JournalManager>>,
+    language: Option<String>,
 ) -> Result<JournalRecordingResult, String> {
-    let samples = recording_manager
-        .stop_recording("journal")
+    let recorded = recording_manager
+        .stop_spooled_recording("journal")
         .ok_or_else(|| "No recording in progress or failed to stop recording".to_string())?;
-
-    // Clone samples before transcription (transcribe takes ownership)
-    let samples_for_wav = samples.clone();
+    crate::tray::update_tray_menu(&app, &crate::tray::TrayIconState::Idle, None);
+    let archive = recording_manager.take_archival_audio();
+
+    // The audio is only loaded now, for transcription; a spooled recording
+    // stays on disk (and recoverable) until it has been transcribed
+    let (samples, spool_path) = match recorded {
+        RecordedAudio::Samples(samples) => (samples, None),
+        RecordedAudio::File(path) => (
+            crate::audio_toolkit::audio::read_spooled_wav(&path)
+                .map_err(|e| format!("Failed to read recording: {}", e))?,
+            Some(path),
+        ),
+    };
+    // Only audio held in memory needs a copy kept back for the WAV
+    let samples_for_wav = if spool_path.is_none() {
+        samples.clone()
+    } else {
+        Vec::new()
+    };
 
     // Transcribe the audio
-    let transcription = transcription_manager
-        .transcribe(samples)
-        .map_err(|e| format!("Transcription failed: {}", e))?;
+    let transcription = match transcription_manager
+        .transcribe_with_timeout(pad_short_recording(samples), language.clone())
+        .await
+    {
+        Ok(text) => text,
+        Err(e) => {
+            if let TranscriptionError::Timeout { .. } = e {
+                emit_transcription_timeout(&app, None);
+            }
+            return Err(format!("Transcription failed: {}", e));
+        }
+    };
 
     // Save WAV file immediately (temporary name; renamed to title-based on save_entry)
     let timestamp = chrono::Utc::now().timestamp();
     let file_name = format!("mutter-{}.wav", timestamp);
-    let file_path = journal_manager.effective_recordings_dir().join(&file_name);
+    let recordings_dir = journal_manager.effective_recordings_dir();
+    let file_path = recordings_dir.join(&file_name);
 
-    crate::audio_toolkit::save_wav_file(file_path, &samples_for_wav)
-        .await
-        .map_err(|e| format!("Failed to save recording: {}", e))?;
+    match spool_path {
+        Some(spool_path) => move_file(&spool_path, &file_path)
+            .map_err(|e| format!("Failed to save recording: {}", e))?,
+        None => crate::audio_toolkit::save_wav_file(file_path, &samples_for_wav)
+            .await
+            .map_err(|e| format!("Failed to save recording: {}", e))?,
+    }
+    if let Some(archive) = archive {
+        let settings = crate::settings::get_settings(&app);
+        crate::managers::audio::save_archival_copy(&settings, &recordings_dir, &file_name, archive)
+            .await;
+    }
 
+    let detected_language =
+        transcription_manager.detected_language(language.as_deref(), &transcription);
     Ok(JournalRecordingResult {
         file_name,
         transcription_text: transcription,
+        language,
+        detected_language,
+    })
+}
+
+/// Move a file, copying it when the destination is on another volume (the
+/// journal can be stored anywhere).
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn move_file(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
+}
+
+/// A recording whose capture was interrupted (the app crashed or was killed)
+/// and whose audio survived on disk.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[derive(Clone, Debug, serde::Serialize, specta::Type)]
+pub struct RecoverableRecording {
+    pub file_name: String,
+    /// When the recording started, in milliseconds since the epoch.
+    pub started_at: i64,
+    pub duration_secs: f64,
+}
+
+/// Recordings left behind by a crash, for the frontend to offer on startup.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recoverable_recordings(
+    recording_manager: State<'_, Arc<AudioRecordingManager>>,
+) -> Result<Vec<RecoverableRecording>, String> {
+    Ok(recording_manager
+        .recoverable_recordings()
+        .into_iter()
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_string_lossy().into_owned();
+            let started_at = file_name
+                .strip_prefix("recording-")
+                .and_then(|rest| rest.strip_suffix(".wav"))
+                .and_then(|ms| ms.parse().ok())
+                .unwrap_or_default();
+            let duration_secs = hound::WavReader::open(&path)
+                .map(|r| r.duration() as f64 / r.spec().sample_rate as f64)
+                .unwrap_or_default();
+            Some(RecoverableRecording {
+                file_name,
+                started_at,
+                duration_secs,
+            })
+        })
+        .collect())
+}
+
+/// Find a recoverable recording by name, rejecting anything that isn't one.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn recoverable_recording_path(
+    recording_manager: &AudioRecordingManager,
+    file_name: &str,
+) -> Result<std::path::PathBuf, String> {
+    recording_manager
+        .recoverable_recordings()
+        .into_iter()
+        .find(|p| p.file_name().is_some_and(|n| n == file_name))
+        .ok_or_else(|| "Recording not found".to_string())
+}
+
+/// Move an interrupted recording into the journal's recordings folder, as if
+/// it had just been stopped. It is not transcribed here (it may be hours
+/// long); save it as an entry and retranscribe it from there.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub async fn recover_recording(
+    recording_manager: State<'_, Arc<AudioRecordingManager>>,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    file_name: String,
+) -> Result<JournalRecordingResult, String> {
+    let spool_path = recoverable_recording_path(&recording_manager, &file_name)?;
+    let new_name = format!("mutter-{}.wav", chrono::Utc::now().timestamp());
+    move_file(
+        &spool_path,
+        &journal_manager.effective_recordings_dir().join(&new_name),
+    )
+    .map_err(|e| format!("Failed to recover recording: {}", e))?;
+    log::info!(
+        "Recovered interrupted recording {} as {}",
+        file_name,
+        new_name
+    );
+
+    Ok(JournalRecordingResult {
+        file_name: new_name,
+        transcription_text: String::new(),
+        language: None,
+        detected_language: None,
     })
 }
 
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub async fn discard_recoverable_recording(
+    recording_manager: State<'_, Arc<AudioRecordingManager>>,
+    file_name: String,
+) -> Result<(), String> {
+    let path = recoverable_recording_path(&recording_manager, &file_name)?;
+    std::fs::remove_file(path).map_err(|e| format!("Failed to delete recording: {}", e))
+}
+
+/// Untranscribed audio longer than this has its older part committed to the
+/// live-preview cache, so each refresh stays about this long.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+const PARTIAL_COMMIT_AFTER_SECS: usize = 20;
+
+/// Audio kept out of a commit, so words at the end of the buffer are
+/// transcribed again once they're complete.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+const PARTIAL_KEEP_SECS: usize = 5;
+
 /// Get a partial transcription of the audio recorded so far (live transcription).
 /// Returns the transcription text, or an empty string if no audio is available yet.
+///
+/// Only audio recorded since the last commit is transcribed; older text comes
+/// from a cache that lives until the recording stops. A call made while another
+/// is still transcribing returns an empty string instead of waiting.
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[tauri::command]
 #[specta::specta]
@@ -98,19 +288,233 @@ pub async fn get_partial_journal_transcription(
     recording_manager: State<'_, Arc<AudioRecordingManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
 ) -> Result<String, String> {
-    let samples = recording_manager
+    let Ok(mut partial) = recording_manager.partial_transcript().try_lock() else {
+        return Ok(String::new());
+    };
+    Ok(
+        advance_partial_transcript(&mut partial, &recording_manager, &transcription_manager)
+            .await?
+            .unwrap_or_default(),
+    )
+}
+
+/// Transcribe the audio recorded since the last commit and return the full
+/// text so far, committing older audio to `partial` as it grows. Returns None
+/// if the recording was replaced while transcribing.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+async fn advance_partial_transcript(
+    partial: &mut PartialTranscript,
+    recording_manager: &AudioRecordingManager,
+    transcription_manager: &TranscriptionManager,
+) -> Result<Option<String>, String> {
+    const SAMPLE_RATE: usize = 16000;
+
+    let generation = recording_manager.recording_generation();
+    if partial.generation != generation {
+        *partial = PartialTranscript {
+            generation,
+            ..Default::default()
+        };
+    }
+
+    let recent = recording_manager
         .get_partial_samples()
         .ok_or_else(|| "No recording in progress".to_string())?;
+    if recent.end() < partial.committed_samples {
+        *partial = PartialTranscript {
+            generation,
+            ..Default::default()
+        };
+    }
+    if partial.committed_samples < recent.start {
+        // A long recording only keeps its last minute in memory; if the
+        // preview fell that far behind, the audio in between is skipped
+        log::warn!(
+            "Live preview skipped {:.1}s of audio it fell behind on",
+            (recent.start - partial.committed_samples) as f64 / SAMPLE_RATE as f64
+        );
+        partial.committed_samples = recent.start;
+    }
 
-    if samples.is_empty() {
-        return Ok(String::new());
+    let mut tail = &recent.samples[partial.committed_samples - recent.start..];
+    if tail.len() > PARTIAL_COMMIT_AFTER_SECS * SAMPLE_RATE {
+        // Cut at a pause near the end so no word is split between commits
+        let cut = quietest_point(
+            tail,
+            tail.len() - PARTIAL_KEEP_SECS * SAMPLE_RATE,
+            SAMPLE_RATE,
+            SAMPLE_RATE / 20,
+        );
+        let committed = transcription_manager
+            .transcribe_with_timeout(tail[..cut].to_vec(), None)
+            .await
+            .map_err(|e| format!("Transcription failed: {}", e))?;
+        if recording_manager.recording_generation() != generation {
+            return Ok(None);
+        }
+        partial.text = join_transcripts(&partial.text, &committed);
+        partial.committed_samples += cut;
+        tail = &tail[cut..];
     }
 
-    let transcription = transcription_manager
-        .transcribe(samples)
+    if tail.is_empty() {
+        return Ok(Some(partial.text.clone()));
+    }
+    let tail_text = transcription_manager
+        .transcribe_with_timeout(tail.to_vec(), None)
+        .await
         .map_err(|e| format!("Transcription failed: {}", e))?;
+    if recording_manager.recording_generation() != generation {
+        return Ok(None);
+    }
 
-    Ok(transcription)
+    Ok(Some(join_transcripts(&partial.text, &tail_text)))
+}
+
+/// Seconds between `live-transcription` updates when the caller doesn't say.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+const LIVE_TRANSCRIPTION_DEFAULT_INTERVAL_SECS: u32 = 2;
+
+/// Payload of the `live-transcription` event.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[derive(Clone, serde::Serialize)]
+struct LiveTranscriptionEvent {
+    /// Everything transcribed since the recording started.
+    text: String,
+    /// Increases with every event of a live session, starting at 1.
+    revision: u64,
+}
+
+/// Managed stop flag for the running live transcription task, if any.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[derive(Default)]
+pub struct LiveTranscription(std::sync::Mutex<Option<Arc<std::sync::atomic::AtomicBool>>>);
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+impl LiveTranscription {
+    /// Stop the running task (if any) and hand out the flag for a new one.
+    fn begin(&self) -> Arc<std::sync::atomic::AtomicBool> {
+        let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        if let Some(previous) = self.0.lock().unwrap().replace(stop.clone()) {
+            previous.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+        stop
+    }
+
+    fn stop(&self) {
+        if let Some(stop) = self.0.lock().unwrap().take() {
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    /// Forget `stop` once its task has ended, unless a newer task replaced it.
+    fn finish(&self, stop: &Arc<std::sync::atomic::AtomicBool>) {
+        let mut current = self.0.lock().unwrap();
+        if current.as_ref().is_some_and(|c| Arc::ptr_eq(c, stop)) {
+            *current = None;
+        }
+    }
+}
+
+/// Push the journal recording's transcript to the frontend as it is spoken,
+/// instead of having it poll `get_partial_journal_transcription`.
+///
+/// Every `interval_secs` the newly captured audio is transcribed and a
+/// `live-transcription` event carrying the cumulative text is emitted. The
+/// task ends by itself when the journal recording stops, skips its turn while
+/// the model is loading, and shares the preview cache's lock so it never
+/// transcribes at the same time as a poll.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub async fn start_live_transcription(
+    app: AppHandle,
+    live: State<'_, LiveTranscription>,
+    recording_manager: State<'_, Arc<AudioRecordingManager>>,
+    interval_secs: Option<u32>,
+) -> Result<(), String> {
+    if recording_manager.active_binding().as_deref() != Some("journal") {
+        return Err("No journal recording in progress".to_string());
+    }
+    let interval = std::time::Duration::from_secs(
+        interval_secs
+            .unwrap_or(LIVE_TRANSCRIPTION_DEFAULT_INTERVAL_SECS)
+            .clamp(1, 60) as u64,
+    );
+    let stop = live.begin();
+
+    tauri::async_runtime::spawn(async move {
+        use std::sync::atomic::Ordering;
+        use tauri::Manager;
+
+        let recording_manager = app.state::<Arc<AudioRecordingManager>>().inner().clone();
+        let transcription_manager = app.state::<Arc<TranscriptionManager>>().inner().clone();
+        let mut revision = 0u64;
+        let mut last_text = String::new();
+
+        loop {
+            tokio::time::sleep(interval).await;
+            if stop.load(Ordering::Relaxed)
+                || recording_manager.active_binding().as_deref() != Some("journal")
+            {
+                break;
+            }
+            // Pause while the model loads rather than queueing behind it
+            if transcription_manager.is_loading() {
+                continue;
+            }
+            if !transcription_manager.is_model_loaded() {
+                transcription_manager.initiate_model_load();
+                continue;
+            }
+
+            let text = {
+                let mut partial = recording_manager.partial_transcript().lock().await;
+                advance_partial_transcript(&mut partial, &recording_manager, &transcription_manager)
+                    .await
+            };
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            match text {
+                Ok(Some(text)) if text != last_text => {
+                    revision += 1;
+                    let _ = app.emit(
+                        "live-transcription",
+                        LiveTranscriptionEvent {
+                            text: text.clone(),
+                            revision,
+                        },
+                    );
+                    last_text = text;
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Live transcription update failed: {}", e),
+            }
+        }
+
+        app.state::<LiveTranscription>().finish(&stop);
+        log::debug!("Live transcription stopped after {} updates", revision);
+    });
+
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_live_transcription(live: State<'_, LiveTranscription>) -> Result<(), String> {
+    live.stop();
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn join_transcripts(prefix: &str, tail: &str) -> String {
+    match (prefix.trim(), tail.trim()) {
+        ("", tail) => tail.to_string(),
+        (prefix, "") => prefix.to_string(),
+        (prefix, tail) => format!("{} {}", prefix, tail),
+    }
 }
 
 // ─── Mobile recording commands ─────────────────────────────────────────────
@@ -129,7 +533,8 @@ pub async fn start_journal_recording() -> Result<(), String> {
 /// Mobile: stop recording — receives raw f32 audio file from frontend, saves as WAV.
 /// The `audio_file_path` points to a temp file containing raw f32 little-endian samples
 /// at 16kHz mono, written by the frontend's Web Audio API.
-/// Attempts cloud transcription if an API key is configured.
+/// Attempts cloud transcription if an API key is configured, passing on
+/// `language`, `prompt` and `temperature` (0.0-1.0) when given.
 #[cfg(any(target_os = "android", target_os = "ios"))]
 #[tauri::command]
 #[specta::specta]
@@ -137,6 +542,9 @@ pub async fn stop_journal_recording(
     app: AppHandle,
     journal_manager: State<'_, Arc<JournalManager>>,
     audio_file_path: String,
+    language: Option<String>,
+    prompt: Option<String>,
+    temperature: Option<f32>,
 ) -> Result<JournalRecordingResult, String> {
     // Read raw f32 samples from the temp file
     let bytes =
@@ -171,9 +579,15 @@ pub async fn stop_journal_recording(
     );
 
     // Try cloud transcription if API key is configured
+    let options = crate::cloud_transcribe::CloudTranscriptionOptions {
+        language: language.clone(),
+        temperature,
+        prompt,
+    };
     let transcription_text = match crate::cloud_transcribe::transcribe_audio_cloud(
         &app,
         file_path.to_str().unwrap_or_default(),
+        options,
     )
     .await
     {
@@ -193,6 +607,8 @@ pub async fn stop_journal_recording(
     Ok(JournalRecordingResult {
         file_name,
         transcription_text,
+        language,
+        detected_language: None,
     })
 }
 
@@ -204,7 +620,8 @@ pub async fn get_partial_journal_transcription() -> Result<String, String> {
     Ok(String::new())
 }
 
-/// Mobile: import audio file — reads WAV, saves copy, returns result.
+/// Mobile: import audio file — decodes it (m4a from iOS, mp3, ogg, ...) into a
+/// 16kHz mono WAV in the recordings directory, the format entries are stored in.
 #[cfg(any(target_os = "android", target_os = "ios"))]
 #[tauri::command]
 #[specta::specta]
@@ -220,18 +637,21 @@ pub async fn import_audio_for_journal(
         return Err(format!("File not found: {}", file_path));
     }
 
-    // Copy the file to recordings directory
     let timestamp = chrono::Utc::now().timestamp();
     let file_name = format!("mutter-{}.wav", timestamp);
     let dest_path = journal_manager.effective_recordings_dir().join(&file_name);
 
-    std::fs::copy(src_path, &dest_path).map_err(|e| format!("Failed to copy audio file: {}", e))?;
+    crate::audio_save::convert_to_wav(src_path, &dest_path)
+        .await
+        .map_err(|e| format!("Failed to import audio file: {}", e))?;
 
     log::info!("Mobile: imported audio as {}", file_name);
 
     Ok(JournalRecordingResult {
         file_name,
         transcription_text: String::new(), // No on-device transcription yet
+        language: None,
+        detected_language: None,
     })
 }
 
@@ -260,8 +680,10 @@ pub async fn save_journal_entry(
     tags: Vec<String>,
     linked_entry_ids: Vec<i64>,
     folder_id: Option<i64>,
+    language: Option<String>,
+    detected_language: Option<String>,
 ) -> Result<JournalEntry, String> {
-    journal_manager
+    let mut entry = journal_manager
         .save_entry(
             file_name,
             title,
@@ -273,7 +695,22 @@ pub async fn save_journal_entry(
             folder_id,
         )
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    if language.is_some() {
+        journal_manager
+            .set_entry_language(entry.id, language.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+        entry.language = language;
+    }
+    if detected_language.is_some() {
+        journal_manager
+            .set_entry_detected_language(entry.id, detected_language.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+        entry.detected_language = detected_language;
+    }
+    Ok(entry)
 }
 
 #[tauri::command]
@@ -288,17 +725,102 @@ pub async fn get_journal_entries(
         .map_err(|e| e.to_string())
 }
 
+/// One page of entries, newest first. `source` of `None` pages across all tabs.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_journal_entries_page(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    source: Option<String>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<JournalEntry>, String> {
+    journal_manager
+        .get_entries_page(source.as_deref(), limit, offset)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Entries whose recording is between `min_ms` and `max_ms` long, newest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_journal_entries_in_duration_range(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    min_ms: i64,
+    max_ms: i64,
+) -> Result<Vec<JournalEntry>, String> {
+    journal_manager
+        .get_entries_in_duration_range(min_ms, max_ms)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn count_journal_entries(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    source: Option<String>,
+) -> Result<i64, String> {
+    journal_manager
+        .count_entries(source.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Entries carrying every tag in `tags`, optionally limited to a source and a
+/// timestamp range (unix seconds, inclusive).
+#[tauri::command]
+#[specta::specta]
+pub async fn filter_journal_entries(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    source: Option<String>,
+    tags: Vec<String>,
+    from_ts: Option<i64>,
+    to_ts: Option<i64>,
+) -> Result<Vec<JournalEntry>, String> {
+    journal_manager
+        .get_entries_filtered(source.as_deref(), &tags, from_ts, to_ts)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// All tags in use with their entry counts, most frequent first.
+/// `source` limits the scan to one tab (e.g. "voice", "video", "meeting").
+#[tauri::command]
+#[specta::specta]
+pub async fn get_journal_tags(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    source: Option<String>,
+) -> Result<Vec<(String, i64)>, String> {
+    journal_manager
+        .get_all_tags(source.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Entries that link to `id`.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_entry_backlinks(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    id: i64,
+) -> Result<Vec<JournalEntry>, AppError> {
+    journal_manager
+        .get_backlinks(id)
+        .await
+        .map_err(AppError::from)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_journal_entry(
     _app: AppHandle,
     journal_manager: State<'_, Arc<JournalManager>>,
     id: i64,
-) -> Result<Option<JournalEntry>, String> {
+) -> Result<Option<JournalEntry>, AppError> {
     journal_manager
         .get_entry_by_id(id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -312,7 +834,7 @@ pub async fn update_journal_entry(
     linked_entry_ids: Vec<i64>,
     folder_id: Option<i64>,
     user_source: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     journal_manager
         .update_entry(
             id,
@@ -323,7 +845,25 @@ pub async fn update_journal_entry(
             user_source.unwrap_or_default(),
         )
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Set the initial prompt used when re-transcribing an entry. Empty or `None`
+/// clears it, so the one in settings is used.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_journal_entry_initial_prompt(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    id: i64,
+    initial_prompt: Option<String>,
+) -> Result<(), AppError> {
+    let initial_prompt = initial_prompt
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+    journal_manager
+        .set_entry_initial_prompt(id, initial_prompt)
+        .await
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -332,42 +872,118 @@ pub async fn delete_journal_entry(
     _app: AppHandle,
     journal_manager: State<'_, Arc<JournalManager>>,
     id: i64,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     journal_manager
         .delete_entry(id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
+/// Trash several entries at once. Returns the ids that could not be trashed.
 #[tauri::command]
 #[specta::specta]
-pub async fn apply_journal_post_process(
-    app: AppHandle,
-    text: String,
-    prompt_id: String,
-) -> Result<String, String> {
-    let settings = crate::settings::get_settings(&app);
-
-    // Find the prompt
-    let prompt = settings
-        .post_process_prompts
-        .iter()
-        .find(|p| p.id == prompt_id)
-        .ok_or_else(|| "Prompt not found".to_string())?
-        .clone();
+pub async fn delete_entries(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    ids: Vec<i64>,
+) -> Result<Vec<i64>, AppError> {
+    journal_manager
+        .delete_entries(&ids)
+        .await
+        .map_err(AppError::from)
+}
 
-    // Get provider (clone to own it across the await boundary)
-    let provider = settings
-        .active_post_process_provider()
-        .ok_or_else(|| {
-            "No post-processing provider configured. Set one up in the Post Process tab."
-                .to_string()
-        })?
-        .clone();
+/// Move several entries into a folder. Returns the ids that could not be moved.
+#[tauri::command]
+#[specta::specta]
+pub async fn move_entries_to_folder(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    ids: Vec<i64>,
+    folder_id: Option<i64>,
+) -> Result<Vec<i64>, AppError> {
+    journal_manager
+        .move_entries_to_folder(&ids, folder_id)
+        .await
+        .map_err(AppError::from)
+}
 
-    // Get API key
-    let api_key = settings
-        .post_process_api_keys
+/// Clone an entry so prompts can be tried on the copy. Returns the new entry.
+#[tauri::command]
+#[specta::specta]
+pub async fn duplicate_journal_entry(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    id: i64,
+) -> Result<JournalEntry, AppError> {
+    journal_manager
+        .duplicate_entry(id)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_journal_entry(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    id: i64,
+) -> Result<(), AppError> {
+    journal_manager
+        .restore_entry(id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Permanently delete an entry and its files.
+#[tauri::command]
+#[specta::specta]
+pub async fn purge_journal_entry(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    id: i64,
+) -> Result<(), AppError> {
+    journal_manager
+        .purge_entry(id)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_trashed_entries(
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<Vec<TrashedEntry>, AppError> {
+    journal_manager
+        .get_trashed_entries()
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_journal_post_process(
+    app: AppHandle,
+    text: String,
+    prompt_id: String,
+) -> Result<String, String> {
+    let settings = crate::settings::get_settings(&app);
+
+    // Find the prompt
+    let prompt = settings
+        .post_process_prompts
+        .iter()
+        .find(|p| p.id == prompt_id)
+        .ok_or_else(|| "Prompt not found".to_string())?
+        .clone();
+
+    // Get provider (clone to own it across the await boundary)
+    let provider = settings
+        .active_post_process_provider()
+        .ok_or_else(|| {
+            "No post-processing provider configured. Set one up in the Post Process tab."
+                .to_string()
+        })?
+        .clone();
+
+    // Get API key
+    let api_key = settings
+        .post_process_api_keys
         .get(&provider.id)
         .cloned()
         .unwrap_or_default();
@@ -385,12 +1001,18 @@ pub async fn apply_journal_post_process(
 
     // Build the prompt with the text
     let processed_prompt = prompt.prompt.replace("${output}", &text);
+    check_prompt_fits_provider(&settings, &provider.id, &processed_prompt)?;
 
     // Call LLM
-    let result =
-        crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
-            .await
-            .map_err(|e| format!("LLM call failed: {}", e))?;
+    let result = crate::llm_client::send_chat_completion_with_options(
+        &provider,
+        api_key,
+        &model,
+        processed_prompt,
+        SamplingOptions::for_prompt(&prompt),
+    )
+    .await
+    .map_err(|e| format!("LLM call failed: {}", e))?;
 
     result.ok_or_else(|| "No response from LLM".to_string())
 }
@@ -431,6 +1053,7 @@ pub async fn apply_prompt_text_to_text(
     }
 
     let processed_prompt = prompt_text.replace("${output}", &text);
+    check_prompt_fits_provider(&settings, &provider.id, &processed_prompt)?;
 
     let result =
         crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
@@ -463,6 +1086,10 @@ pub async fn get_journal_audio_file_path(
     file_name: String,
     folder_id: Option<i64>,
 ) -> Result<String, String> {
+    // Entries without a recording (text imports, transcripts) are named after their .md
+    if !file_name.ends_with(".wav") {
+        return Err("Entry has no audio file".to_string());
+    }
     let path = journal_manager
         .get_audio_file_path_in_folder(&file_name, folder_id)
         .map_err(|e| e.to_string())?;
@@ -471,14 +1098,54 @@ pub async fn get_journal_audio_file_path(
         .map(|s| s.to_string())
 }
 
+/// Number of waveform buckets when the caller doesn't ask for a resolution.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+const DEFAULT_WAVEFORM_RESOLUTION: u32 = 200;
+
+/// RMS waveform of an entry's recording for the playback UI. Empty if the
+/// audio file is missing.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub async fn get_journal_entry_waveform(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    file_name: String,
+    folder_id: Option<i64>,
+    resolution: Option<u32>,
+) -> Result<Vec<f32>, String> {
+    let path = journal_manager
+        .get_audio_file_path_in_folder(&file_name, folder_id)
+        .map_err(|e| e.to_string())?;
+    let resolution = resolution
+        .filter(|r| *r > 0)
+        .unwrap_or(DEFAULT_WAVEFORM_RESOLUTION);
+    tauri::async_runtime::spawn_blocking(move || compute_waveform(&path, resolution))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Read a saved recording back into f32 samples.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn read_wav_samples(path: &std::path::Path) -> Result<Vec<f32>, String> {
+    let reader =
+        hound::WavReader::open(path).map_err(|e| format!("Failed to read WAV file: {}", e))?;
+    Ok(reader
+        .into_samples::<i16>()
+        .filter_map(|s| s.ok())
+        .map(|s| s as f32 / i16::MAX as f32)
+        .collect())
+}
+
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[tauri::command]
 #[specta::specta]
 pub async fn retranscribe_journal_entry(
-    _app: AppHandle,
+    app: AppHandle,
     journal_manager: State<'_, Arc<JournalManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
     id: i64,
+    language: Option<String>,
 ) -> Result<String, String> {
     // Look up the entry to get its file_name and folder_id
     let entry = journal_manager
@@ -492,20 +1159,31 @@ pub async fn retranscribe_journal_entry(
         .get_audio_file_path_in_folder(&entry.file_name, entry.folder_id)
         .map_err(|e| e.to_string())?;
 
-    // Read WAV file back into f32 samples
-    let reader = hound::WavReader::open(&file_path)
-        .map_err(|e| format!("Failed to read WAV file: {}", e))?;
-    let samples: Vec<f32> = reader
-        .into_samples::<i16>()
-        .filter_map(|s| s.ok())
-        .map(|s| s as f32 / i16::MAX as f32)
-        .collect();
+    let samples = read_wav_samples(&file_path)?;
 
     // Ensure model is loaded
     transcription_manager.initiate_model_load();
 
     // Transcribe (chunked to avoid ORT errors on long audio)
-    let transcription = transcribe_chunked(&transcription_manager, samples)?;
+    let progress = ChunkProgress {
+        app: &app,
+        entry_id: Some(id),
+    };
+    // A language given here replaces the one stored on the entry
+    let language = language.or_else(|| entry.language.clone());
+    let transcription = transcribe_chunked(
+        &transcription_manager,
+        samples,
+        language.as_deref(),
+        entry.initial_prompt.as_deref(),
+        Some(&progress),
+    )
+    .await
+    .map_err(|e| transcription_error_message(&app, Some(id), e))?;
+    let transcription = post_process_transcript(
+        &transcription,
+        &crate::settings::get_settings(&app).word_replacements,
+    );
 
     // Update the entry's transcription text in DB (reset prompt_id and clear snapshots)
     journal_manager
@@ -516,10 +1194,109 @@ pub async fn retranscribe_journal_entry(
         .clear_snapshots(id)
         .await
         .map_err(|e| e.to_string())?;
+    let detected_language =
+        transcription_manager.detected_language(language.as_deref(), &transcription);
+    if detected_language != entry.detected_language {
+        journal_manager
+            .set_entry_detected_language(id, detected_language)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    if language != entry.language {
+        journal_manager
+            .set_entry_language(id, language)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
 
     Ok(transcription)
 }
 
+/// Prompt id recorded for translations, so they show up in (and can be undone
+/// through) the entry's prompt history like any other prompt.
+const TRANSLATE_PROMPT_ID: &str = "translate-en";
+
+/// Used when the entry has no recording or the model can't translate.
+const TRANSLATE_PROMPT: &str = "Translate the following transcript into English. \
+Keep speaker labels such as [Speaker 1] unchanged and reply with the translation only.\n\n${output}";
+
+/// Translate an entry's transcript to English and store it with an undo
+/// snapshot. Recordings are run back through the model with its translate
+/// task enabled; entries without audio, or with a model that can't translate,
+/// are translated by the post-processing LLM instead.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub async fn translate_journal_entry(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    id: i64,
+) -> Result<String, String> {
+    const CHUNK_SIZE: usize = 16000 * 30; // 30 seconds at 16kHz
+
+    let entry = journal_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Entry not found".to_string())?;
+
+    let file_path = if entry.file_name.ends_with(".wav") {
+        journal_manager
+            .get_audio_file_path_in_folder(&entry.file_name, entry.folder_id)
+            .ok()
+            .filter(|path| path.exists())
+    } else {
+        None
+    };
+    let Some(file_path) =
+        file_path.filter(|_| transcription_manager.current_model_supports_translation())
+    else {
+        return run_prompt_on_entry(
+            &app,
+            &journal_manager,
+            id,
+            TRANSLATE_PROMPT,
+            TRANSLATE_PROMPT_ID.to_string(),
+        )
+        .await;
+    };
+
+    let samples = read_wav_samples(&file_path)?;
+    transcription_manager.initiate_model_load();
+
+    let mut parts = Vec::new();
+    for (i, chunk) in samples.chunks(CHUNK_SIZE).enumerate() {
+        let text = match transcription_manager
+            .translate_with_timeout(
+                chunk.to_vec(),
+                entry.language.clone(),
+                entry.initial_prompt.clone(),
+            )
+            .await
+        {
+            Ok(text) => text,
+            Err(e @ TranscriptionError::Timeout { .. }) => {
+                emit_transcription_timeout(&app, Some(id));
+                return Err(format!("Translation failed: {}", e));
+            }
+            Err(e) => return Err(format!("Translation failed on chunk {}: {}", i + 1, e)),
+        };
+        let trimmed = text.trim();
+        if !trimmed.is_empty() {
+            parts.push(trimmed.to_string());
+        }
+    }
+    let translation = parts.join(" ");
+
+    journal_manager
+        .apply_prompt_with_snapshot(id, translation.clone(), TRANSLATE_PROMPT_ID.to_string())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(translation)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn apply_prompt_to_journal_entry(
@@ -548,6 +1325,49 @@ pub async fn apply_prompt_to_journal_entry(
     Ok(processed)
 }
 
+/// Run several prompts over an entry in order, each on the previous one's
+/// output. Nothing is saved until every step has succeeded; then the final text
+/// is stored with one snapshot per step. Emits `prompt-chain-progress` as steps
+/// finish. Returns the final text.
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_prompt_chain_to_entry(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    entry_id: i64,
+    prompt_ids: Vec<String>,
+) -> Result<String, String> {
+    if prompt_ids.is_empty() {
+        return Err("Select at least one prompt to apply.".to_string());
+    }
+    let entry = journal_manager
+        .get_entry_by_id(entry_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Entry not found".to_string())?;
+
+    let total = prompt_ids.len();
+    let mut text = entry.transcription_text;
+    let mut steps = Vec::with_capacity(total);
+    for (step, prompt_id) in prompt_ids.into_iter().enumerate() {
+        text = apply_journal_post_process(app.clone(), text, prompt_id.clone())
+            .await
+            .map_err(|e| format!("Step {} of {} failed: {}", step + 1, total, e))?;
+        steps.push((text.clone(), prompt_id));
+        let _ = app.emit(
+            "prompt-chain-progress",
+            serde_json::json!({ "entry_id": entry_id, "step": step + 1, "total": total }),
+        );
+    }
+
+    journal_manager
+        .apply_prompt_chain_with_snapshots(entry_id, steps)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(text)
+}
+
 /// Apply a prompt to a journal entry using the prompt text directly (not by ID lookup).
 /// Used by Mutter which stores its own prompts independently from Handy's settings.
 #[tauri::command]
@@ -558,6 +1378,18 @@ pub async fn apply_prompt_text_to_journal_entry(
     id: i64,
     prompt_text: String,
     prompt_label: String,
+) -> Result<String, String> {
+    run_prompt_on_entry(&app, &journal_manager, id, &prompt_text, prompt_label).await
+}
+
+/// Run a prompt against an entry's transcript (deduplicated, with speaker names
+/// substituted) and store the result with an undo snapshot.
+async fn run_prompt_on_entry(
+    app: &AppHandle,
+    journal_manager: &JournalManager,
+    id: i64,
+    prompt_text: &str,
+    prompt_label: String,
 ) -> Result<String, String> {
     let entry = journal_manager
         .get_entry_by_id(id)
@@ -565,7 +1397,7 @@ pub async fn apply_prompt_text_to_journal_entry(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| "Entry not found".to_string())?;
 
-    let settings = crate::settings::get_settings(&app);
+    let settings = crate::settings::get_settings(app);
 
     let provider = settings
         .active_post_process_provider()
@@ -593,7 +1425,8 @@ pub async fn apply_prompt_text_to_journal_entry(
 
     // Programmatically remove consecutively repeated words before sending to LLM.
     // Local LLMs struggle with many duplicates (e.g. "your your your your ...").
-    let mut clean_text = dedup_consecutive_words(&entry.transcription_text);
+    let mut clean_text =
+        dedup_consecutive_words(&entry.transcription_text, settings.dedup_threshold);
 
     // Substitute speaker names (e.g. [Speaker 1] → [Alice]) if available
     if let Ok(names) = journal_manager.get_speaker_names(id).await {
@@ -606,6 +1439,7 @@ pub async fn apply_prompt_text_to_journal_entry(
     }
 
     let processed_prompt = prompt_text.replace("${output}", &clean_text);
+    check_prompt_fits_provider(&settings, &provider.id, &processed_prompt)?;
 
     let result =
         crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
@@ -622,6 +1456,180 @@ pub async fn apply_prompt_text_to_journal_entry(
     Ok(processed)
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct BatchPromptError {
+    pub id: i64,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct FileImportError {
+    pub file: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct BatchPromptResult {
+    pub succeeded: Vec<i64>,
+    pub failed: Vec<BatchPromptError>,
+}
+
+/// Apply the same prompt text to several entries in turn. A failing entry is
+/// recorded in the result and the batch moves on to the next one.
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_prompt_text_to_entries(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    ids: Vec<i64>,
+    prompt_text: String,
+    prompt_label: String,
+) -> Result<BatchPromptResult, String> {
+    let total = ids.len();
+    let mut result = BatchPromptResult {
+        succeeded: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for (done, id) in ids.into_iter().enumerate() {
+        let _ = app.emit(
+            "batch-prompt-progress",
+            serde_json::json!({ "done": done, "total": total, "current_id": id }),
+        );
+
+        match run_prompt_on_entry(
+            &app,
+            &journal_manager,
+            id,
+            &prompt_text,
+            prompt_label.clone(),
+        )
+        .await
+        {
+            Ok(_) => result.succeeded.push(id),
+            Err(error) => {
+                log::warn!("Batch prompt failed for entry {}: {}", id, error);
+                result.failed.push(BatchPromptError { id, error });
+            }
+        }
+    }
+
+    let _ = app.emit(
+        "batch-prompt-progress",
+        serde_json::json!({ "done": total, "total": total, "current_id": null }),
+    );
+
+    Ok(result)
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct BatchProcessResult {
+    pub processed: u32,
+    pub skipped: u32,
+    /// Entry id and error for each entry that failed.
+    pub failed: Vec<(i64, String)>,
+}
+
+/// Held for the length of a batch so two batches never hit the provider at once.
+static BATCH_PROCESS_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Run a saved prompt over every entry (or every entry in `folder_id`),
+/// applying it like `apply_prompt_to_journal_entry` so each result can be
+/// undone. Entries a prompt was already applied to are skipped unless
+/// `overwrite_existing` is set, as are entries with no transcript. Entries are
+/// processed one at a time, emitting `batch-process-progress`;
+/// `cancel_batch_process` (or `cancel_current_operation` on desktop) stops the
+/// batch before the next entry and the partial result is returned.
+#[tauri::command]
+#[specta::specta]
+pub async fn batch_apply_prompt(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    cancellation: State<'_, crate::utils::BatchProcessCancellation>,
+    prompt_id: String,
+    overwrite_existing: bool,
+    folder_id: Option<i64>,
+) -> Result<BatchProcessResult, String> {
+    let settings = crate::settings::get_settings(&app);
+    if !settings
+        .post_process_prompts
+        .iter()
+        .any(|p| p.id == prompt_id)
+    {
+        return Err("Prompt not found".to_string());
+    }
+
+    let entries = match folder_id {
+        Some(folder_id) => journal_manager.get_entries_in_folder(folder_id).await,
+        None => journal_manager.get_entries().await,
+    }
+    .map_err(|e| e.to_string())?;
+
+    let _guard = BATCH_PROCESS_LOCK.lock().await;
+    let cancelled = cancellation.begin();
+    let total = entries.len();
+    let mut result = BatchProcessResult::default();
+
+    for (current, entry) in entries.into_iter().enumerate() {
+        if cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+            log::info!(
+                "Batch post-process cancelled after {} of {} entries",
+                current,
+                total
+            );
+            break;
+        }
+        let _ = app.emit(
+            "batch-process-progress",
+            serde_json::json!({ "current": current + 1, "total": total, "entry_id": entry.id }),
+        );
+
+        let has_output = entry.post_process_prompt_id.is_some()
+            || entry
+                .post_processed_text
+                .as_deref()
+                .is_some_and(|t| !t.trim().is_empty());
+        if (has_output && !overwrite_existing) || entry.transcription_text.trim().is_empty() {
+            result.skipped += 1;
+            continue;
+        }
+
+        let outcome = match apply_journal_post_process(
+            app.clone(),
+            entry.transcription_text,
+            prompt_id.clone(),
+        )
+        .await
+        {
+            Ok(text) => journal_manager
+                .apply_prompt_with_snapshot(entry.id, text, prompt_id.clone())
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(()) => result.processed += 1,
+            Err(error) => {
+                log::warn!(
+                    "Batch post-process failed for entry {}: {}",
+                    entry.id,
+                    error
+                );
+                result.failed.push((entry.id, error));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Stop a running `batch_apply_prompt` before its next entry.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_batch_process(cancellation: State<'_, crate::utils::BatchProcessCancellation>) {
+    cancellation.cancel();
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn undo_journal_prompt(
@@ -635,6 +1643,38 @@ pub async fn undo_journal_prompt(
         .map_err(|e| e.to_string())
 }
 
+/// Word-level diff between the text before the last applied prompt and the
+/// current transcription.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_prompt_diff(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    entry_id: i64,
+) -> Result<Vec<DiffChunk>, String> {
+    let entry = journal_manager
+        .get_entry_by_id(entry_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Entry not found".to_string())?;
+    let previous = entry
+        .transcript_snapshots
+        .last()
+        .ok_or_else(|| "No prompt has been applied to this entry".to_string())?;
+    Ok(compute_text_diff(previous, &entry.transcription_text))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn redo_journal_prompt(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    id: i64,
+) -> Result<String, String> {
+    journal_manager
+        .redo_last_prompt(id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn update_journal_transcription_text(
@@ -679,7 +1719,7 @@ pub async fn update_entry_after_processing(
 #[tauri::command]
 #[specta::specta]
 pub async fn import_audio_for_journal(
-    _app: AppHandle,
+    app: AppHandle,
     journal_manager: State<'_, Arc<JournalManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
     file_path: String,
@@ -714,8 +1754,7 @@ pub async fn import_audio_for_journal(
         return Err("Audio file contains no samples".to_string());
     }
 
-    // Resample to 16kHz mono if needed
-    let target_rate = 16000u32;
+    // Mix down to mono and resample to 16kHz if needed
     let mono_samples = if spec.channels > 1 {
         // Mix down to mono
         samples
@@ -726,23 +1765,12 @@ pub async fn import_audio_for_journal(
         samples.clone()
     };
 
-    let resampled = if spec.sample_rate != target_rate {
-        // Simple linear resampling
-        let ratio = spec.sample_rate as f64 / target_rate as f64;
-        let new_len = (mono_samples.len() as f64 / ratio) as usize;
-        (0..new_len)
-            .map(|i| {
-                let src_idx = i as f64 * ratio;
-                let idx = src_idx as usize;
-                let frac = src_idx - idx as f64;
-                let a = mono_samples.get(idx).copied().unwrap_or(0.0);
-                let b = mono_samples.get(idx + 1).copied().unwrap_or(a);
-                a + (b - a) * frac as f32
-            })
-            .collect::<Vec<f32>>()
-    } else {
-        mono_samples
-    };
+    let resampled = crate::audio_toolkit::resample_mono_audio(
+        &mono_samples,
+        spec.sample_rate,
+        crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE,
+        crate::settings::get_settings(&app).audio_resample_quality,
+    );
 
     // Clone for WAV saving
     let samples_for_wav = resampled.clone();
@@ -751,8 +1779,21 @@ pub async fn import_audio_for_journal(
     transcription_manager.initiate_model_load();
 
     // Transcribe (chunked to avoid ORT errors on long audio)
-    let transcription = transcribe_chunked(&transcription_manager, resampled)?;
-
+    // No entry exists yet, so progress events carry no entry ID
+    let progress = ChunkProgress {
+        app: &app,
+        entry_id: None,
+    };
+    let transcription = transcribe_chunked(
+        &transcription_manager,
+        resampled,
+        None,
+        None,
+        Some(&progress),
+    )
+    .await
+    .map_err(|e| transcription_error_message(&app, None, e))?;
+
     // Copy to journal recordings dir with new name (temporary; renamed on save_entry)
     let timestamp = chrono::Utc::now().timestamp();
     let file_name = format!("mutter-import-{}.wav", timestamp);
@@ -766,9 +1807,329 @@ pub async fn import_audio_for_journal(
     Ok(JournalRecordingResult {
         file_name,
         transcription_text: transcription,
+        language: None,
+        detected_language: None,
     })
 }
 
+/// Audio formats picked up by `import_audio_directory`.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+const IMPORTABLE_AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "flac"];
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct AudioDirectoryImportResult {
+    pub imported: Vec<i64>,
+    pub failed: Vec<FileImportError>,
+}
+
+/// Decode, transcribe and save one audio file as a journal entry titled after the file.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub(crate) async fn import_audio_file_as_entry(
+    journal_manager: &JournalManager,
+    transcription_manager: &TranscriptionManager,
+    path: &std::path::Path,
+    file_name: String,
+    folder_id: Option<i64>,
+    quality: crate::settings::ResampleQuality,
+) -> Result<i64, String> {
+    use crate::audio_toolkit::{constants::WHISPER_SAMPLE_RATE, resample_mono_audio};
+    use crate::commands::video::extract_audio_from_video;
+
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| "Invalid file path".to_string())?;
+    let (samples, sample_rate) = extract_audio_from_video(path_str)?;
+    if samples.is_empty() {
+        return Err("Audio file contains no samples".to_string());
+    }
+    let samples = resample_mono_audio(&samples, sample_rate, WHISPER_SAMPLE_RATE, quality);
+
+    let transcription =
+        transcribe_chunked(transcription_manager, samples.clone(), None, None, None)
+            .await
+            .map_err(|e| format!("Transcription failed: {}", e))?;
+
+    let dest_path = journal_manager.effective_recordings_dir().join(&file_name);
+    crate::audio_toolkit::save_wav_file(dest_path, &samples)
+        .await
+        .map_err(|e| format!("Failed to save imported audio: {}", e))?;
+
+    let title = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let entry = journal_manager
+        .save_entry(
+            file_name,
+            title,
+            transcription,
+            None,
+            None,
+            vec![],
+            vec![],
+            folder_id,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(entry.id)
+}
+
+/// Import every supported audio file in a directory (not recursive) as its own
+/// journal entry. Emits `import-batch-progress` after each file; a failing file
+/// is recorded in the result and the import moves on to the next one.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub async fn import_audio_directory(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    dir_path: String,
+    folder_id: Option<i64>,
+) -> Result<AudioDirectoryImportResult, String> {
+    let mut files: Vec<std::path::PathBuf> = std::fs::read_dir(&dir_path)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| {
+                        IMPORTABLE_AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str())
+                    })
+        })
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        return Err("No supported audio files found in directory".to_string());
+    }
+
+    transcription_manager.initiate_model_load();
+    let quality = crate::settings::get_settings(&app).audio_resample_quality;
+
+    let total = files.len();
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut imported = Vec::new();
+    let mut failed = Vec::new();
+
+    for (index, path) in files.iter().enumerate() {
+        let display_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match import_audio_file_as_entry(
+            &journal_manager,
+            &transcription_manager,
+            path,
+            format!("mutter-import-{}-{}.wav", timestamp, index + 1),
+            folder_id,
+            quality,
+        )
+        .await
+        {
+            Ok(id) => imported.push(id),
+            Err(error) => {
+                log::warn!("Failed to import {}: {}", path.display(), error);
+                failed.push(FileImportError {
+                    file: display_name.clone(),
+                    error,
+                });
+            }
+        }
+
+        let _ = app.emit(
+            "import-batch-progress",
+            serde_json::json!({
+                "done": index + 1,
+                "total": total,
+                "file": display_name,
+            }),
+        );
+    }
+
+    log::info!(
+        "Imported {}/{} audio files from {}",
+        imported.len(),
+        total,
+        dir_path
+    );
+    Ok(AudioDirectoryImportResult { imported, failed })
+}
+
+// --- Markdown import commands ---
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct MarkdownImportResult {
+    pub imported: Vec<JournalEntry>,
+    pub failed: Vec<FileImportError>,
+}
+
+fn import_markdown_path(
+    path: &std::path::Path,
+) -> Result<crate::markdown_note::MarkdownNote, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(crate::markdown_note::parse_markdown_note(&stem, &content))
+}
+
+/// Import a markdown file as a voice entry without audio.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_markdown_file(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    path: String,
+    folder_id: Option<i64>,
+) -> Result<JournalEntry, String> {
+    let note = import_markdown_path(std::path::Path::new(&path))?;
+    let entry = journal_manager
+        .import_markdown_note(note, folder_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let _ = app.emit("journal-updated", ());
+    Ok(entry)
+}
+
+/// Import a `.txt` or `.md` file as-is as an entry without audio. The file
+/// name (without extension) becomes the title.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_text_as_entry(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    file_path: String,
+    folder_id: Option<i64>,
+) -> Result<JournalEntry, String> {
+    let path = std::path::Path::new(&file_path);
+    let is_text = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("txt") || e.eq_ignore_ascii_case("md"));
+    if !is_text {
+        return Err("Only .txt and .md files can be imported".to_string());
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path, e))?;
+    let title = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    journal_manager
+        .save_entry_with_source(
+            String::new(),
+            title,
+            content.trim().to_string(),
+            None,
+            None,
+            vec![],
+            vec![],
+            folder_id,
+            "import".to_string(),
+            None,
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_imported_entries(
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<Vec<JournalEntry>, String> {
+    journal_manager
+        .get_entries_by_source(Some("import"))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_imported_folders(
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<Vec<JournalFolder>, String> {
+    journal_manager
+        .get_folders_by_source(Some("import"))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_import_folder(
+    name: String,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<JournalFolder, String> {
+    journal_manager
+        .create_folder_with_source(name, "import".to_string(), None)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Import every `.md` file in a directory (not recursive). A file that fails
+/// is recorded in the result and the import moves on to the next one.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_markdown_directory(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    dir: String,
+    folder_id: Option<i64>,
+) -> Result<MarkdownImportResult, String> {
+    let mut files: Vec<std::path::PathBuf> = std::fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+        })
+        .collect();
+    files.sort();
+
+    let mut imported = Vec::new();
+    let mut failed = Vec::new();
+    for path in &files {
+        let result = match import_markdown_path(path) {
+            Ok(note) => journal_manager
+                .import_markdown_note(note, folder_id)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(entry) => imported.push(entry),
+            Err(error) => {
+                log::warn!("Failed to import {}: {}", path.display(), error);
+                failed.push(FileImportError {
+                    file: path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    error,
+                });
+            }
+        }
+    }
+
+    log::info!(
+        "Imported {}/{} markdown files from {}",
+        imported.len(),
+        files.len(),
+        dir
+    );
+    let _ = app.emit("journal-updated", ());
+    Ok(MarkdownImportResult { imported, failed })
+}
+
 // --- Chat command ---
 
 #[tauri::command]
@@ -776,9 +2137,24 @@ pub async fn import_audio_for_journal(
 pub async fn journal_chat(
     app: AppHandle,
     messages: Vec<(String, String)>, // (role, content) pairs
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
 ) -> Result<String, String> {
-    let settings = crate::settings::get_settings(&app);
+    let (provider, api_key, model) = chat_provider(&crate::settings::get_settings(&app))?;
+    let sampling = SamplingOptions::default().overridden_by(temperature, max_tokens);
 
+    let result =
+        crate::llm_client::send_chat_messages(&provider, api_key, &model, messages, sampling)
+            .await
+            .map_err(|e| format!("Chat failed: {}", e))?;
+
+    result.ok_or_else(|| "No response from LLM".to_string())
+}
+
+/// The active chat provider with its API key and model.
+fn chat_provider(
+    settings: &crate::settings::AppSettings,
+) -> Result<(crate::settings::PostProcessProvider, String, String), String> {
     let provider = settings
         .active_post_process_provider()
         .ok_or_else(|| {
@@ -802,7 +2178,146 @@ pub async fn journal_chat(
         return Err("No model configured for the LLM provider.".to_string());
     }
 
-    let result = crate::llm_client::send_chat_messages(&provider, api_key, &model, messages)
+    Ok((provider, api_key, model))
+}
+
+/// Tokens left free in the context window for the model's reply.
+const REPLY_RESERVE_TOKENS: u32 = 4096;
+
+/// Approximate context window, in tokens, of the models a provider usually
+/// serves. None for custom and unknown providers, whose models can be anything;
+/// their prompts are only limited by a user override.
+fn context_window_estimate(provider_id: &str) -> Option<u32> {
+    match provider_id {
+        "anthropic" => Some(200_000),
+        "openai" | "openrouter" | "zai" => Some(128_000),
+        "groq" => Some(131_072),
+        "cerebras" => Some(65_536),
+        "gemini" => Some(1_048_576),
+        crate::settings::APPLE_INTELLIGENCE_PROVIDER_ID => Some(4_096),
+        _ => None,
+    }
+}
+
+/// Tokens a prompt may use with a provider: its context window (the user's
+/// `post_process_max_context_tokens` override, else the estimate above) less
+/// room for the reply. None when the window is unknown.
+fn prompt_token_budget(settings: &crate::settings::AppSettings, provider_id: &str) -> Option<u32> {
+    let window = settings
+        .post_process_max_context_tokens
+        .get(provider_id)
+        .copied()
+        .or_else(|| context_window_estimate(provider_id))?;
+    // Small windows keep three quarters for the prompt
+    Some(window - REPLY_RESERVE_TOKENS.min(window / 4))
+}
+
+/// Fail with how far over it is when `prompt` can't fit the provider's
+/// context window, instead of letting the API reject it.
+fn check_prompt_fits_provider(
+    settings: &crate::settings::AppSettings,
+    provider_id: &str,
+    prompt: &str,
+) -> Result<(), String> {
+    match prompt_token_budget(settings, provider_id) {
+        Some(budget) => crate::llm_client::check_prompt_fits(prompt, budget),
+        None => Ok(()),
+    }
+}
+
+/// Join `(title, text)` entries under `## title` headers, cutting the longest
+/// texts first so the result stays within `max_chars`. Short entries are kept
+/// whole and their unused share goes to the longer ones.
+fn build_multi_entry_context(entries: &[(String, String)], max_chars: usize) -> String {
+    const TRUNCATED: &str = "\n[…truncated]";
+
+    let headers: usize = entries
+        .iter()
+        .map(|(title, _)| title.chars().count() + 8 + TRUNCATED.len())
+        .sum();
+    let mut budget = max_chars.saturating_sub(headers);
+
+    // Largest per-entry length that fits: hand out equal shares, smallest first
+    let mut lengths: Vec<usize> = entries.iter().map(|(_, t)| t.chars().count()).collect();
+    lengths.sort_unstable();
+    let mut cap = usize::MAX;
+    for (i, len) in lengths.iter().enumerate() {
+        let share = budget / (lengths.len() - i);
+        if *len > share {
+            cap = share;
+            break;
+        }
+        budget -= len;
+    }
+
+    entries
+        .iter()
+        .map(|(title, text)| {
+            let text = text.trim();
+            if text.chars().count() > cap {
+                let cut: String = text.chars().take(cap).collect();
+                format!("## {}\n\n{}{}", title, cut.trim_end(), TRUNCATED)
+            } else {
+                format!("## {}\n\n{}", title, text)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_context_window_estimate(provider_id: String) -> Result<Option<u32>, String> {
+    Ok(context_window_estimate(&provider_id))
+}
+
+/// Chat about several journal entries at once. Their transcripts go into a
+/// system prompt, trimmed to fit the provider's context window.
+#[tauri::command]
+#[specta::specta]
+pub async fn multi_entry_journal_chat(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    entry_ids: Vec<i64>,
+    messages: Vec<(String, String)>, // (role, content) pairs
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
+) -> Result<String, String> {
+    if entry_ids.is_empty() {
+        return Err("Select at least one entry to chat about.".to_string());
+    }
+    let settings = crate::settings::get_settings(&app);
+    let (provider, api_key, model) = chat_provider(&settings)?;
+
+    let mut entries = Vec::with_capacity(entry_ids.len());
+    for id in &entry_ids {
+        let entry = journal_manager
+            .get_entry_by_id(*id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Entry {} not found", id))?;
+        entries.push((entry.title, entry.transcription_text));
+    }
+
+    let preamble = "Answer the user's questions using the journal entries below. \
+                    Mention which entry you are drawing on when it matters.";
+    let conversation_chars: usize = messages.iter().map(|(_, c)| c.chars().count()).sum();
+    // With an unknown context window the entries are sent whole
+    let max_chars = prompt_token_budget(&settings, &provider.id)
+        .map_or(usize::MAX, |budget| budget as usize * CHARS_PER_TOKEN)
+        .saturating_sub(conversation_chars + preamble.len());
+    let system_prompt = format!(
+        "{}\n\n{}",
+        preamble,
+        build_multi_entry_context(&entries, max_chars)
+    );
+
+    let mut chat = Vec::with_capacity(messages.len() + 1);
+    chat.push(("system".to_string(), system_prompt));
+    chat.extend(messages);
+
+    let sampling = SamplingOptions::default().overridden_by(temperature, max_tokens);
+    let result = crate::llm_client::send_chat_messages(&provider, api_key, &model, chat, sampling)
         .await
         .map_err(|e| format!("Chat failed: {}", e))?;
 
@@ -887,6 +2402,69 @@ pub async fn delete_chat_session(
         .map_err(|e| e.to_string())
 }
 
+// --- Multi-entry chat session commands ---
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_multi_chat_session(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    entry_ids: Vec<i64>,
+) -> Result<MultiEntryChatSession, String> {
+    journal_manager
+        .create_multi_chat_session(entry_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_multi_chat_sessions(
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<Vec<MultiEntryChatSession>, String> {
+    journal_manager
+        .get_multi_chat_sessions()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_multi_chat_message(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    session_id: i64,
+    role: String,
+    content: String,
+) -> Result<ChatMessage, String> {
+    journal_manager
+        .save_multi_chat_message(session_id, role, content)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_multi_chat_messages(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    session_id: i64,
+) -> Result<Vec<ChatMessage>, String> {
+    journal_manager
+        .get_multi_chat_messages(session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_multi_chat_session(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    session_id: i64,
+) -> Result<(), String> {
+    journal_manager
+        .delete_multi_chat_session(session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // --- Folder commands ---
 
 #[tauri::command]
@@ -895,9 +2473,10 @@ pub async fn create_journal_folder(
     _app: AppHandle,
     journal_manager: State<'_, Arc<JournalManager>>,
     name: String,
+    parent_id: Option<i64>,
 ) -> Result<JournalFolder, String> {
     journal_manager
-        .create_folder(name)
+        .create_folder(name, parent_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -916,6 +2495,20 @@ pub async fn rename_journal_folder(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn move_journal_folder(
+    _app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    id: i64,
+    parent_id: Option<i64>,
+) -> Result<(), String> {
+    journal_manager
+        .move_folder(id, parent_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_journal_folder(
@@ -992,3 +2585,233 @@ pub async fn set_journal_storage_path(
 
     Ok(())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_obsidian_vault_path(app: AppHandle) -> Result<Option<String>, String> {
+    Ok(crate::settings::get_settings(&app).obsidian_vault_path)
+}
+
+// --- Database backup commands ---
+
+#[tauri::command]
+#[specta::specta]
+pub async fn backup_journal_database(
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<String, String> {
+    journal_manager
+        .backup_database()
+        .await
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_journal_backups(
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<Vec<BackupInfo>, String> {
+    journal_manager.list_backups().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_journal_from_backup(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    path: String,
+) -> Result<(), String> {
+    journal_manager
+        .restore_from_backup(&path)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Archive the journal (files plus a `journal.json` of its rows) into a zip at
+/// `dest_path`, optionally limited to one source. Returns the file count.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_journal_zip(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    dest_path: String,
+    source: Option<String>,
+) -> Result<usize, String> {
+    journal_manager
+        .export_zip(std::path::Path::new(&dest_path), source.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// --- Podcast feed commands ---
+
+/// RSS 2.0 feed of a folder's recordings, with episode audio served from
+/// `base_url`. Only entries recorded or imported as WAV audio are included;
+/// video and YouTube imports are skipped.
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_folder_podcast_feed(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    folder_id: i64,
+    title: String,
+    description: String,
+    base_url: String,
+) -> Result<String, String> {
+    let entries: Vec<JournalEntry> = journal_manager
+        .get_entries_in_folder(folder_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|e| e.file_name.ends_with(".wav") && e.source != "video" && e.source != "youtube")
+        .collect();
+
+    let mut lengths = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let path = journal_manager
+            .get_audio_file_path_in_folder(&entry.file_name, entry.folder_id)
+            .map_err(|e| e.to_string())?;
+        lengths.push(std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0));
+    }
+
+    let items: Vec<crate::feed::PodcastItem> = entries
+        .iter()
+        .zip(&lengths)
+        .map(|(entry, &length_bytes)| crate::feed::PodcastItem {
+            title: &entry.title,
+            file_name: &entry.file_name,
+            length_bytes,
+            description: &entry.transcription_text,
+            timestamp: entry.timestamp,
+            tags: &entry.tags,
+        })
+        .collect();
+    let channel = crate::feed::PodcastChannel {
+        title: &title,
+        description: &description,
+        base_url: &base_url,
+    };
+    Ok(crate::feed::build_podcast_feed(&channel, &items))
+}
+
+/// Write a folder's podcast feed to `output_path`. Takes the same channel
+/// details as `generate_folder_podcast_feed`.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_podcast_feed(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    folder_id: i64,
+    title: String,
+    description: String,
+    base_url: String,
+    output_path: String,
+) -> Result<(), String> {
+    let xml =
+        generate_folder_podcast_feed(journal_manager, folder_id, title, description, base_url)
+            .await?;
+    std::fs::write(&output_path, xml).map_err(|e| format!("Failed to write feed: {}", e))?;
+    log::info!(
+        "Wrote podcast feed for folder {} to {}",
+        folder_id,
+        output_path
+    );
+    Ok(())
+}
+
+/// Mirror all existing journal entries into the configured Obsidian vault.
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_entries_to_obsidian(
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<usize, String> {
+    journal_manager
+        .sync_all_to_obsidian()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Commit all pending changes in the journal folder to the git sync repository.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub async fn trigger_git_sync(
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<GitSyncStatus, String> {
+    journal_manager
+        .git_sync_all()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_collapses_stutter_runs() {
+        assert_eq!(
+            dedup_consecutive_words("your your your thing", 3),
+            "your thing"
+        );
+    }
+
+    #[test]
+    fn test_dedup_keeps_short_legitimate_repeats() {
+        assert_eq!(
+            dedup_consecutive_words("very very good", 3),
+            "very very good"
+        );
+    }
+
+    #[test]
+    fn test_multi_entry_context_keeps_short_entries_whole() {
+        let entries = vec![
+            ("Short".to_string(), "tiny note".to_string()),
+            ("Long".to_string(), "x".repeat(1000)),
+        ];
+        let context = build_multi_entry_context(&entries, 300);
+        assert!(context.starts_with("## Short\n\ntiny note\n\n## Long\n\n"));
+        assert!(context.ends_with("[…truncated]"));
+        assert!(context.chars().count() <= 300);
+    }
+
+    #[test]
+    fn test_multi_entry_context_untouched_when_it_fits() {
+        let entries = vec![
+            ("A".to_string(), "first".to_string()),
+            ("B".to_string(), "second".to_string()),
+        ];
+        assert_eq!(
+            build_multi_entry_context(&entries, 10_000),
+            "## A\n\nfirst\n\n## B\n\nsecond"
+        );
+    }
+
+    #[test]
+    fn test_prompt_budget_leaves_room_for_the_reply() {
+        let mut settings = crate::settings::get_default_settings();
+        assert_eq!(
+            prompt_token_budget(&settings, "anthropic"),
+            Some(200_000 - REPLY_RESERVE_TOKENS)
+        );
+        assert_eq!(
+            prompt_token_budget(&settings, crate::settings::APPLE_INTELLIGENCE_PROVIDER_ID),
+            Some(3_072)
+        );
+        // Unknown models aren't capped unless the user sets a limit
+        assert_eq!(prompt_token_budget(&settings, "custom"), None);
+        assert!(check_prompt_fits_provider(&settings, "custom", &"a".repeat(1_000_000)).is_ok());
+
+        settings
+            .post_process_max_context_tokens
+            .insert("custom".to_string(), 32_768);
+        assert_eq!(
+            prompt_token_budget(&settings, "custom"),
+            Some(32_768 - REPLY_RESERVE_TOKENS)
+        );
+    }
+
+    #[test]
+    fn test_dedup_ignores_trailing_punctuation() {
+        assert_eq!(dedup_consecutive_words("the, the cat", 3), "the, the cat");
+        assert_eq!(dedup_consecutive_words("the, the cat", 2), "the cat");
+        assert_eq!(dedup_consecutive_words("The the, THE. cat", 3), "THE. cat");
+    }
+}
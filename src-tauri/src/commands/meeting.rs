@@ -1,25 +1,199 @@
-use crate::commands::video::transcribe_chunked;
+use crate::audio_toolkit::resample_mono_audio;
+use crate::commands::video::{transcribe_chunked, transcribe_chunked_with_timeout};
 use crate::diarize::{self, DiarizedSegment};
-use crate::managers::journal::{JournalEntry, JournalFolder, JournalManager};
-use crate::managers::transcription::TranscriptionManager;
+use crate::error::AppError;
+use crate::managers::journal::{
+    JournalEntry, JournalFolder, JournalManager, SpeakerProfile, SpeakerStats,
+};
+use crate::managers::transcription::{
+    emit_transcription_timeout, TranscriptionError, TranscriptionManager,
+};
 use log::{info, warn};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Sample rate diarization and transcription run at.
+const SAMPLE_RATE: u32 = 16000;
+
+/// Segment boundaries that moved by no more than this between diarization runs
+/// count as the same segment, so its edited text is kept.
+const REDIARIZE_TOLERANCE_MS: i64 = 300;
+
+/// Read a WAV file as mono f32 samples at `SAMPLE_RATE`, resampling with the
+/// `audio_resample_quality` setting.
+fn load_wav_mono_16k(app: &AppHandle, file_path: &std::path::Path) -> Result<Vec<f32>, String> {
+    let reader =
+        hound::WavReader::open(file_path).map_err(|e| format!("Failed to read WAV file: {}", e))?;
+    let spec = reader.spec();
+
+    let raw_samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Int => {
+            let bits = spec.bits_per_sample;
+            reader
+                .into_samples::<i32>()
+                .filter_map(|s| s.ok())
+                .map(move |s| s as f32 / (1_i64 << (bits - 1)) as f32)
+                .collect()
+        }
+        hound::SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .filter_map(|s| s.ok())
+            .collect(),
+    };
+
+    // Mix to mono if multichannel
+    let mono_samples = if spec.channels > 1 {
+        raw_samples
+            .chunks(spec.channels as usize)
+            .map(|frame| frame.iter().sum::<f32>() / spec.channels as f32)
+            .collect::<Vec<f32>>()
+    } else {
+        raw_samples
+    };
+
+    Ok(resample_mono_audio(
+        &mono_samples,
+        spec.sample_rate,
+        SAMPLE_RATE,
+        crate::settings::get_settings(app).audio_resample_quality,
+    ))
+}
+
+/// Run `diarize_audio` on a blocking thread so the async runtime stays free
+/// for other commands. Each embedded segment is reported on `status_event` as
+/// a `diarizing` stage with `current`/`total`. `cancel_diarization` stops the
+/// run between segments and emits `diarize-cancelled`.
+async fn diarize_off_runtime(
+    app: &AppHandle,
+    status_event: &'static str,
+    entry_id: i64,
+    samples: Vec<f32>,
+    max_speakers: usize,
+    threshold: f32,
+    min_segment_ms: i64,
+    merge_gap_ms: i64,
+) -> Result<Vec<diarize::RawDiarizedSegment>, AppError> {
+    let seg_model = diarize::get_seg_model_path(app)?;
+    let emb_model = diarize::get_emb_model_path(app)?;
+
+    let cancellation = app.state::<diarize::DiarizationCancellation>();
+    let cancel_flag = cancellation.begin();
+
+    crate::compute_backend::use_backend(app);
+    let app_handle = app.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        diarize::diarize_audio(
+            &samples,
+            SAMPLE_RATE,
+            &seg_model,
+            &emb_model,
+            max_speakers,
+            threshold,
+            min_segment_ms,
+            merge_gap_ms,
+            &cancel_flag,
+            |current, total| {
+                let _ = app_handle.emit(
+                    status_event,
+                    serde_json::json!({
+                        "entryId": entry_id,
+                        "stage": "diarizing",
+                        "current": current,
+                        "total": total,
+                    }),
+                );
+            },
+        )
+    })
+    .await
+    .map_err(|e| AppError::Other(format!("Diarization task failed: {}", e)))?;
+    cancellation.clear();
+
+    if matches!(result, Err(AppError::Cancelled)) {
+        let _ = app.emit(
+            "diarize-cancelled",
+            serde_json::json!({ "entry_id": entry_id }),
+        );
+    }
+    result
+}
+
+/// Stop the running diarization at its next segment.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_diarization(
+    cancellation: State<'_, diarize::DiarizationCancellation>,
+) -> Result<(), String> {
+    info!("[meeting] Diarization cancel requested");
+    cancellation.cancel();
+    Ok(())
+}
+
+/// Name the speakers of a fresh diarization after matching enrolled profiles.
+/// Failures only cost the suggestions, so they are logged rather than returned.
+async fn apply_speaker_profiles(
+    app: &AppHandle,
+    journal_manager: &JournalManager,
+    entry_id: i64,
+    raw_segments: &[diarize::RawDiarizedSegment],
+) {
+    let profiles = match journal_manager.speaker_profile_embeddings().await {
+        Ok(profiles) if !profiles.is_empty() => profiles,
+        Ok(_) => return,
+        Err(e) => {
+            warn!("[meeting] Failed to load speaker profiles: {}", e);
+            return;
+        }
+    };
+    let threshold = crate::settings::get_settings(app).speaker_match_threshold;
+    let matches = diarize::match_speaker_profiles(
+        &diarize::speaker_centroids(raw_segments),
+        &profiles,
+        threshold,
+    );
+    if matches.is_empty() {
+        return;
+    }
+    match journal_manager
+        .prefill_speaker_names(entry_id, &matches)
+        .await
+    {
+        Ok(filled) => info!(
+            "[meeting] Recognized {} enrolled speakers in entry {}",
+            filled, entry_id
+        ),
+        Err(e) => warn!("[meeting] Failed to save recognized speakers: {}", e),
+    }
+}
 
 // --- Diarize model management ---
 
 #[tauri::command]
 #[specta::specta]
 pub async fn check_diarize_models_installed(app: AppHandle) -> Result<bool, String> {
-    diarize::models_installed(&app)
+    tauri::async_runtime::spawn_blocking(move || diarize::models_installed(&app))
+        .await
+        .map_err(|e| format!("Verification task failed: {}", e))?
 }
 
+/// Download missing diarize models and replace any that fail their checksum.
 #[tauri::command]
 #[specta::specta]
 pub async fn install_diarize_models(app: AppHandle) -> Result<(), String> {
     diarize::install_models(&app).await
 }
 
+/// Hash the installed diarize models to catch truncated or corrupt files.
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_diarize_models(
+    app: AppHandle,
+) -> Result<Vec<diarize::DiarizeModelCheck>, String> {
+    tauri::async_runtime::spawn_blocking(move || diarize::verify_models(&app))
+        .await
+        .map_err(|e| format!("Verification task failed: {}", e))?
+}
+
 // --- Source-filtered CRUD (same pattern as video.rs) ---
 
 #[tauri::command]
@@ -51,7 +225,7 @@ pub async fn create_meeting_folder(
     journal_manager: State<'_, Arc<JournalManager>>,
 ) -> Result<JournalFolder, String> {
     journal_manager
-        .create_folder_with_source(name, "meeting".to_string())
+        .create_folder_with_source(name, "meeting".to_string(), None)
         .await
         .map_err(|e| e.to_string())
 }
@@ -93,11 +267,15 @@ pub async fn transcribe_meeting(
     entry_id: i64,
     max_speakers: Option<usize>,
     threshold: Option<f32>,
+    min_segment_ms: Option<i64>,
+    merge_gap_ms: Option<i64>,
     journal_manager: State<'_, Arc<JournalManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let max_speakers = max_speakers.unwrap_or(6);
     let threshold = threshold.unwrap_or(0.5);
+    let min_segment_ms = min_segment_ms.unwrap_or(diarize::DEFAULT_MIN_SEGMENT_MS);
+    let merge_gap_ms = merge_gap_ms.unwrap_or(diarize::DEFAULT_MERGE_GAP_MS);
     info!(
         "[meeting] Starting diarized transcription for entry {}",
         entry_id
@@ -107,15 +285,18 @@ pub async fn transcribe_meeting(
     let entry = journal_manager
         .get_entry_by_id(entry_id)
         .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "Entry not found".to_string())?;
+        .map_err(AppError::from)?
+        .ok_or(AppError::NotFound)?;
 
     let file_path = journal_manager
         .get_audio_file_path_in_folder(&entry.file_name, entry.folder_id)
-        .map_err(|e| e.to_string())?;
+        .map_err(AppError::from)?;
 
     if !file_path.exists() {
-        return Err(format!("Audio file not found: {}", file_path.display()));
+        return Err(AppError::Io(format!(
+            "Audio file not found: {}",
+            file_path.display()
+        )));
     }
 
     // 2. Read WAV file
@@ -127,53 +308,7 @@ pub async fn transcribe_meeting(
         }),
     );
 
-    let reader = hound::WavReader::open(&file_path)
-        .map_err(|e| format!("Failed to read WAV file: {}", e))?;
-    let spec = reader.spec();
-
-    let raw_samples: Vec<f32> = match spec.sample_format {
-        hound::SampleFormat::Int => {
-            let bits = spec.bits_per_sample;
-            reader
-                .into_samples::<i32>()
-                .filter_map(|s| s.ok())
-                .map(move |s| s as f32 / (1_i64 << (bits - 1)) as f32)
-                .collect()
-        }
-        hound::SampleFormat::Float => reader
-            .into_samples::<f32>()
-            .filter_map(|s| s.ok())
-            .collect(),
-    };
-
-    // Mix to mono if multichannel
-    let mono_samples = if spec.channels > 1 {
-        raw_samples
-            .chunks(spec.channels as usize)
-            .map(|frame| frame.iter().sum::<f32>() / spec.channels as f32)
-            .collect::<Vec<f32>>()
-    } else {
-        raw_samples
-    };
-
-    // Resample to 16kHz if needed
-    let target_rate = 16000u32;
-    let samples = if spec.sample_rate != target_rate {
-        let ratio = spec.sample_rate as f64 / target_rate as f64;
-        let new_len = (mono_samples.len() as f64 / ratio) as usize;
-        (0..new_len)
-            .map(|i| {
-                let src_idx = i as f64 * ratio;
-                let idx = src_idx as usize;
-                let frac = src_idx - idx as f64;
-                let a = mono_samples.get(idx).copied().unwrap_or(0.0);
-                let b = mono_samples.get(idx + 1).copied().unwrap_or(a);
-                a + (b - a) * frac as f32
-            })
-            .collect::<Vec<f32>>()
-    } else {
-        mono_samples
-    };
+    let samples = load_wav_mono_16k(&app, &file_path).map_err(AppError::Io)?;
 
     // 3. Run diarization
     let _ = app.emit(
@@ -184,17 +319,17 @@ pub async fn transcribe_meeting(
         }),
     );
 
-    let seg_model = diarize::get_seg_model_path(&app)?;
-    let emb_model = diarize::get_emb_model_path(&app)?;
-
-    let raw_segments = diarize::diarize_audio(
-        &samples,
-        target_rate,
-        &seg_model,
-        &emb_model,
+    let raw_segments = diarize_off_runtime(
+        &app,
+        "meeting-status",
+        entry_id,
+        samples,
         max_speakers,
         threshold,
-    )?;
+        min_segment_ms,
+        merge_gap_ms,
+    )
+    .await?;
 
     if raw_segments.is_empty() {
         warn!("[meeting] No speech segments found in audio");
@@ -202,7 +337,7 @@ pub async fn transcribe_meeting(
         journal_manager
             .update_entry_after_processing(entry_id, entry.file_name, entry.title, String::new())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         let _ = app.emit(
             "meeting-status",
@@ -227,7 +362,6 @@ pub async fn transcribe_meeting(
     transcription_manager.initiate_model_load();
 
     let mut diarized_segments: Vec<DiarizedSegment> = Vec::new();
-    let mut flat_lines: Vec<String> = Vec::new();
 
     for (i, seg) in raw_segments.iter().enumerate() {
         let _ = app.emit(
@@ -243,45 +377,65 @@ pub async fn transcribe_meeting(
         let text = if seg.samples.is_empty() {
             String::new()
         } else {
-            transcribe_chunked(&transcription_manager, seg.samples.clone()).unwrap_or_else(|e| {
-                warn!("[meeting] Transcription failed for segment {}: {}", i, e);
-                String::new()
-            })
+            match transcribe_chunked_with_timeout(
+                &transcription_manager,
+                seg.samples.clone(),
+                entry.language.as_deref(),
+            )
+            .await
+            {
+                Ok(text) => text,
+                Err(e @ TranscriptionError::Timeout { .. }) => {
+                    emit_transcription_timeout(&app, Some(entry_id));
+                    return Err(e.into());
+                }
+                Err(e) => {
+                    warn!("[meeting] Transcription failed for segment {}: {}", i, e);
+                    String::new()
+                }
+            }
         };
 
         let trimmed = text.trim().to_string();
 
         if !trimmed.is_empty() {
-            let speaker_label = seg
-                .speaker
-                .map(|s| format!("[Speaker {}]", s))
-                .unwrap_or_else(|| "[Unknown]".to_string());
-
-            flat_lines.push(format!("{} {}", speaker_label, trimmed));
-
             diarized_segments.push(DiarizedSegment {
                 id: None,
                 speaker: seg.speaker,
                 start_ms: seg.start_ms,
                 end_ms: seg.end_ms,
                 text: trimmed,
+                confidence: seg.confidence,
             });
         }
     }
 
-    let flat_text = flat_lines.join("\n");
+    apply_speaker_profiles(&app, &journal_manager, entry_id, &raw_segments).await;
+    let speaker_names = journal_manager
+        .get_speaker_names(entry_id)
+        .await
+        .map_err(AppError::from)?;
+    let flat_text = diarize::segments_to_flat_text(&diarized_segments, &speaker_names);
+    // Speaker labels are left out so they don't sway the detection
+    let spoken: Vec<&str> = diarized_segments.iter().map(|s| s.text.as_str()).collect();
+    let detected_language =
+        transcription_manager.detected_language(entry.language.as_deref(), &spoken.join(" "));
 
     // 5. Save segments to DB
     journal_manager
         .save_meeting_segments(entry_id, &diarized_segments)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(AppError::from)?;
+    journal_manager
+        .set_entry_detected_language(entry_id, detected_language)
+        .await
+        .map_err(AppError::from)?;
 
     // 6. Update entry with flattened transcription
     journal_manager
         .update_entry_after_processing(entry_id, entry.file_name, entry.title, flat_text)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(AppError::from)?;
 
     let _ = app.emit(
         "meeting-status",
@@ -309,25 +463,32 @@ pub async fn diarize_entry(
     entry_id: i64,
     max_speakers: Option<usize>,
     threshold: Option<f32>,
+    min_segment_ms: Option<i64>,
+    merge_gap_ms: Option<i64>,
     journal_manager: State<'_, Arc<JournalManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let max_speakers = max_speakers.unwrap_or(6);
     let threshold = threshold.unwrap_or(0.5);
+    let min_segment_ms = min_segment_ms.unwrap_or(diarize::DEFAULT_MIN_SEGMENT_MS);
+    let merge_gap_ms = merge_gap_ms.unwrap_or(diarize::DEFAULT_MERGE_GAP_MS);
     info!("[diarize] Starting diarization for entry {}", entry_id);
 
     let entry = journal_manager
         .get_entry_by_id(entry_id)
         .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "Entry not found".to_string())?;
+        .map_err(AppError::from)?
+        .ok_or(AppError::NotFound)?;
 
     let file_path = journal_manager
         .get_audio_file_path_in_folder(&entry.file_name, entry.folder_id)
-        .map_err(|e| e.to_string())?;
+        .map_err(AppError::from)?;
 
     if !file_path.exists() {
-        return Err(format!("Audio file not found: {}", file_path.display()));
+        return Err(AppError::Io(format!(
+            "Audio file not found: {}",
+            file_path.display()
+        )));
     }
 
     let _ = app.emit(
@@ -335,68 +496,24 @@ pub async fn diarize_entry(
         serde_json::json!({ "entryId": entry_id, "stage": "loading" }),
     );
 
-    let reader = hound::WavReader::open(&file_path)
-        .map_err(|e| format!("Failed to read WAV file: {}", e))?;
-    let spec = reader.spec();
-
-    let raw_samples: Vec<f32> = match spec.sample_format {
-        hound::SampleFormat::Int => {
-            let bits = spec.bits_per_sample;
-            reader
-                .into_samples::<i32>()
-                .filter_map(|s| s.ok())
-                .map(move |s| s as f32 / (1_i64 << (bits - 1)) as f32)
-                .collect()
-        }
-        hound::SampleFormat::Float => reader
-            .into_samples::<f32>()
-            .filter_map(|s| s.ok())
-            .collect(),
-    };
-
-    let mono_samples = if spec.channels > 1 {
-        raw_samples
-            .chunks(spec.channels as usize)
-            .map(|frame| frame.iter().sum::<f32>() / spec.channels as f32)
-            .collect::<Vec<f32>>()
-    } else {
-        raw_samples
-    };
-
-    let target_rate = 16000u32;
-    let samples = if spec.sample_rate != target_rate {
-        let ratio = spec.sample_rate as f64 / target_rate as f64;
-        let new_len = (mono_samples.len() as f64 / ratio) as usize;
-        (0..new_len)
-            .map(|i| {
-                let src_idx = i as f64 * ratio;
-                let idx = src_idx as usize;
-                let frac = src_idx - idx as f64;
-                let a = mono_samples.get(idx).copied().unwrap_or(0.0);
-                let b = mono_samples.get(idx + 1).copied().unwrap_or(a);
-                a + (b - a) * frac as f32
-            })
-            .collect::<Vec<f32>>()
-    } else {
-        mono_samples
-    };
+    let samples = load_wav_mono_16k(&app, &file_path).map_err(AppError::Io)?;
 
     let _ = app.emit(
         "diarize-status",
         serde_json::json!({ "entryId": entry_id, "stage": "diarizing" }),
     );
 
-    let seg_model = diarize::get_seg_model_path(&app)?;
-    let emb_model = diarize::get_emb_model_path(&app)?;
-
-    let raw_segments = diarize::diarize_audio(
-        &samples,
-        target_rate,
-        &seg_model,
-        &emb_model,
+    let raw_segments = diarize_off_runtime(
+        &app,
+        "diarize-status",
+        entry_id,
+        samples,
         max_speakers,
         threshold,
-    )?;
+        min_segment_ms,
+        merge_gap_ms,
+    )
+    .await?;
 
     if raw_segments.is_empty() {
         warn!(
@@ -434,10 +551,23 @@ pub async fn diarize_entry(
         let text = if seg.samples.is_empty() {
             String::new()
         } else {
-            transcribe_chunked(&transcription_manager, seg.samples.clone()).unwrap_or_else(|e| {
-                warn!("[diarize] Transcription failed for segment {}: {}", i, e);
-                String::new()
-            })
+            match transcribe_chunked_with_timeout(
+                &transcription_manager,
+                seg.samples.clone(),
+                entry.language.as_deref(),
+            )
+            .await
+            {
+                Ok(text) => text,
+                Err(e @ TranscriptionError::Timeout { .. }) => {
+                    emit_transcription_timeout(&app, Some(entry_id));
+                    return Err(e.into());
+                }
+                Err(e) => {
+                    warn!("[diarize] Transcription failed for segment {}: {}", i, e);
+                    String::new()
+                }
+            }
         };
 
         let trimmed = text.trim().to_string();
@@ -448,14 +578,17 @@ pub async fn diarize_entry(
                 start_ms: seg.start_ms,
                 end_ms: seg.end_ms,
                 text: trimmed,
+                confidence: seg.confidence,
             });
         }
     }
 
+    apply_speaker_profiles(&app, &journal_manager, entry_id, &raw_segments).await;
+
     journal_manager
         .save_meeting_segments(entry_id, &diarized_segments)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(AppError::from)?;
 
     let _ = app.emit(
         "diarize-status",
@@ -471,6 +604,186 @@ pub async fn diarize_entry(
     Ok(())
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct RediarizeSummary {
+    /// Segments whose time range survived, keeping their edited text and speaker.
+    pub preserved: usize,
+    /// Segments that were transcribed fresh.
+    pub replaced: usize,
+    /// Previous segments with no counterpart in the new run.
+    pub removed: usize,
+}
+
+/// Re-run diarization with new parameters without losing manual work. New
+/// speaker clusters are mapped onto the old ones by shared speaking time so
+/// speaker names still apply, and segments whose boundaries barely moved keep
+/// their edited text and speaker. Everything else is transcribed fresh.
+#[tauri::command]
+#[specta::specta]
+pub async fn rediarize_meeting(
+    app: AppHandle,
+    entry_id: i64,
+    max_speakers: Option<usize>,
+    threshold: Option<f32>,
+    min_segment_ms: Option<i64>,
+    merge_gap_ms: Option<i64>,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+) -> Result<RediarizeSummary, AppError> {
+    let max_speakers = max_speakers.unwrap_or(6);
+    let threshold = threshold.unwrap_or(0.5);
+    let min_segment_ms = min_segment_ms.unwrap_or(diarize::DEFAULT_MIN_SEGMENT_MS);
+    let merge_gap_ms = merge_gap_ms.unwrap_or(diarize::DEFAULT_MERGE_GAP_MS);
+    info!("[rediarize] Starting re-diarization for entry {}", entry_id);
+
+    let entry = journal_manager
+        .get_entry_by_id(entry_id)
+        .await
+        .map_err(AppError::from)?
+        .ok_or(AppError::NotFound)?;
+
+    let file_path = journal_manager
+        .get_audio_file_path_in_folder(&entry.file_name, entry.folder_id)
+        .map_err(AppError::from)?;
+
+    if !file_path.exists() {
+        return Err(AppError::Io(format!(
+            "Audio file not found: {}",
+            file_path.display()
+        )));
+    }
+
+    let old_segments = journal_manager
+        .get_meeting_segments(entry_id)
+        .await
+        .map_err(AppError::from)?;
+
+    let _ = app.emit(
+        "meeting-status",
+        serde_json::json!({ "entryId": entry_id, "stage": "loading" }),
+    );
+    let samples = load_wav_mono_16k(&app, &file_path).map_err(AppError::Io)?;
+
+    let _ = app.emit(
+        "meeting-status",
+        serde_json::json!({ "entryId": entry_id, "stage": "diarizing" }),
+    );
+    let raw_segments = diarize_off_runtime(
+        &app,
+        "meeting-status",
+        entry_id,
+        samples,
+        max_speakers,
+        threshold,
+        min_segment_ms,
+        merge_gap_ms,
+    )
+    .await?;
+
+    let speaker_map = diarize::map_speakers_by_overlap(&old_segments, &raw_segments);
+
+    transcription_manager.initiate_model_load();
+
+    let mut diarized_segments: Vec<DiarizedSegment> = Vec::with_capacity(raw_segments.len());
+    let mut preserved_ids = std::collections::HashSet::new();
+    let mut replaced = 0;
+
+    for (i, seg) in raw_segments.iter().enumerate() {
+        let _ = app.emit(
+            "meeting-status",
+            serde_json::json!({
+                "entryId": entry_id,
+                "stage": "transcribing",
+                "current": i + 1,
+                "total": raw_segments.len(),
+            }),
+        );
+
+        let matched = diarize::find_matching_segment(
+            &old_segments,
+            seg.start_ms,
+            seg.end_ms,
+            REDIARIZE_TOLERANCE_MS,
+        )
+        .filter(|old| !preserved_ids.contains(&old.id));
+
+        if let Some(old) = matched {
+            preserved_ids.insert(old.id);
+            diarized_segments.push(DiarizedSegment {
+                id: None,
+                speaker: old.speaker,
+                start_ms: seg.start_ms,
+                end_ms: seg.end_ms,
+                text: old.text.clone(),
+                confidence: seg.confidence,
+            });
+            continue;
+        }
+
+        let text = if seg.samples.is_empty() {
+            String::new()
+        } else {
+            match transcribe_chunked_with_timeout(
+                &transcription_manager,
+                seg.samples.clone(),
+                entry.language.as_deref(),
+            )
+            .await
+            {
+                Ok(text) => text,
+                Err(e @ TranscriptionError::Timeout { .. }) => {
+                    emit_transcription_timeout(&app, Some(entry_id));
+                    return Err(e.into());
+                }
+                Err(e) => {
+                    warn!("[rediarize] Transcription failed for segment {}: {}", i, e);
+                    String::new()
+                }
+            }
+        };
+
+        let trimmed = text.trim().to_string();
+        if !trimmed.is_empty() {
+            diarized_segments.push(DiarizedSegment {
+                id: None,
+                speaker: seg.speaker.and_then(|s| speaker_map.get(&s).copied()),
+                start_ms: seg.start_ms,
+                end_ms: seg.end_ms,
+                text: trimmed,
+                confidence: seg.confidence,
+            });
+            replaced += 1;
+        }
+    }
+
+    journal_manager
+        .save_meeting_segments(entry_id, &diarized_segments)
+        .await
+        .map_err(AppError::from)?;
+    // Only the transcript text changes; title and applied prompt stay as they are
+    journal_manager
+        .regenerate_meeting_transcript(entry_id)
+        .await
+        .map_err(AppError::from)?;
+
+    let _ = app.emit(
+        "meeting-status",
+        serde_json::json!({ "entryId": entry_id, "stage": "done" }),
+    );
+
+    let summary = RediarizeSummary {
+        preserved: preserved_ids.len(),
+        replaced,
+        removed: old_segments.len() - preserved_ids.len(),
+    };
+    info!(
+        "[rediarize] Complete for entry {}: {} preserved, {} replaced, {} removed",
+        entry_id, summary.preserved, summary.replaced, summary.removed
+    );
+
+    Ok(summary)
+}
+
 // --- Meeting segment queries ---
 
 #[tauri::command]
@@ -478,11 +791,11 @@ pub async fn diarize_entry(
 pub async fn get_meeting_segments(
     entry_id: i64,
     journal_manager: State<'_, Arc<JournalManager>>,
-) -> Result<Vec<DiarizedSegment>, String> {
+) -> Result<Vec<DiarizedSegment>, AppError> {
     journal_manager
         .get_meeting_segments(entry_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -491,11 +804,96 @@ pub async fn update_meeting_segment_text(
     segment_id: i64,
     text: String,
     journal_manager: State<'_, Arc<JournalManager>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     journal_manager
         .update_segment_text(segment_id, text)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Re-transcribe one segment from the entry's audio and store the new text.
+/// `start_ms`/`end_ms` override the segment's boundaries, which are saved too.
+#[tauri::command]
+#[specta::specta]
+pub async fn retranscribe_meeting_segment(
+    app: AppHandle,
+    entry_id: i64,
+    segment_id: i64,
+    start_ms: Option<i64>,
+    end_ms: Option<i64>,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+) -> Result<String, AppError> {
+    let segment = journal_manager
+        .get_meeting_segments(entry_id)
+        .await?
+        .into_iter()
+        .find(|s| s.id == Some(segment_id))
+        .ok_or(AppError::NotFound)?;
+
+    let start_ms = start_ms.unwrap_or(segment.start_ms).max(0);
+    let end_ms = end_ms.unwrap_or(segment.end_ms);
+    if end_ms <= start_ms {
+        return Err(AppError::InvalidInput(
+            "Segment end must be after its start".to_string(),
+        ));
+    }
+
+    let entry = journal_manager
+        .get_entry_by_id(entry_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let file_path =
+        journal_manager.get_audio_file_path_in_folder(&entry.file_name, entry.folder_id)?;
+    if !file_path.exists() {
+        return Err(AppError::Io(format!(
+            "Audio file not found: {}",
+            file_path.display()
+        )));
+    }
+
+    let samples = load_wav_mono_16k(&app, &file_path).map_err(AppError::Io)?;
+    let to_index =
+        |ms: i64| ((ms as u64 * SAMPLE_RATE as u64) / 1000).min(samples.len() as u64) as usize;
+    let range = to_index(start_ms)..to_index(end_ms);
+    if range.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Segment is outside the audio".to_string(),
+        ));
+    }
+
+    transcription_manager.initiate_model_load();
+    let text = transcribe_chunked(
+        &transcription_manager,
+        samples[range].to_vec(),
+        entry.language.as_deref(),
+        entry.initial_prompt.as_deref(),
+        None,
+    )
+    .await
+    .map_err(|e| {
+        if let TranscriptionError::Timeout { .. } = e {
+            emit_transcription_timeout(&app, Some(entry_id));
+        }
+        AppError::from(e)
+    })?
+    .trim()
+    .to_string();
+
+    if (start_ms, end_ms) != (segment.start_ms, segment.end_ms) {
+        journal_manager
+            .update_segment_times(segment_id, start_ms, end_ms)
+            .await?;
+    }
+    journal_manager
+        .update_segment_text(segment_id, text.clone())
+        .await?;
+
+    info!(
+        "[meeting] Re-transcribed segment {} of entry {} ({}..{}ms)",
+        segment_id, entry_id, start_ms, end_ms
+    );
+    Ok(text)
 }
 
 #[tauri::command]
@@ -504,11 +902,86 @@ pub async fn update_meeting_segment_speaker(
     segment_id: i64,
     speaker: Option<i32>,
     journal_manager: State<'_, Arc<JournalManager>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     journal_manager
         .update_segment_speaker(segment_id, speaker)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Collapse consecutive same-speaker segments separated by at most `max_gap_ms`
+/// (default 1000ms). Returns how many segments were merged away.
+#[tauri::command]
+#[specta::specta]
+pub async fn merge_meeting_segments(
+    entry_id: i64,
+    max_gap_ms: Option<i64>,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<usize, AppError> {
+    journal_manager
+        .merge_adjacent_segments(entry_id, max_gap_ms.unwrap_or(1000))
+        .await
+        .map_err(AppError::from)
+}
+
+/// Merge two segments into the earlier one. Returns the surviving segment id.
+#[tauri::command]
+#[specta::specta]
+pub async fn merge_two_segments(
+    first_id: i64,
+    second_id: i64,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<i64, AppError> {
+    journal_manager
+        .merge_two_segments(first_id, second_id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Split a segment in two at `split_ms`. `text_index` is the character index
+/// where the text is cut; without it the text is divided proportionally.
+/// Returns the ids of the two halves.
+#[tauri::command]
+#[specta::specta]
+pub async fn split_meeting_segment(
+    segment_id: i64,
+    split_ms: i64,
+    text_index: Option<usize>,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<(i64, i64), AppError> {
+    journal_manager
+        .split_segment(segment_id, split_ms, text_index)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_meeting_segment(
+    segment_id: i64,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<(), AppError> {
+    journal_manager
+        .delete_segment(segment_id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Add a segment by hand. Returns the new segment id.
+#[tauri::command]
+#[specta::specta]
+pub async fn insert_meeting_segment(
+    entry_id: i64,
+    start_ms: i64,
+    end_ms: i64,
+    speaker: Option<i32>,
+    text: String,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<i64, AppError> {
+    journal_manager
+        .insert_segment(entry_id, start_ms, end_ms, speaker, text)
+        .await
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -518,11 +991,93 @@ pub async fn update_meeting_speaker_name(
     speaker_id: i32,
     name: String,
     journal_manager: State<'_, Arc<JournalManager>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     journal_manager
         .update_speaker_name(entry_id, speaker_id, name)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+// --- Speaker profiles ---
+
+/// Enroll the voice in a segment under `name`, so later meetings can recognize
+/// it. The segment's speaker in this meeting is given the name too.
+#[tauri::command]
+#[specta::specta]
+pub async fn enroll_speaker_from_segment(
+    app: AppHandle,
+    segment_id: i64,
+    name: String,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<SpeakerProfile, AppError> {
+    let check_app = app.clone();
+    let installed =
+        tauri::async_runtime::spawn_blocking(move || diarize::models_installed(&check_app))
+            .await
+            .map_err(|e| AppError::Other(format!("Verification task failed: {}", e)))??;
+    if !installed {
+        return Err(AppError::Other(
+            "Diarization models are not installed".to_string(),
+        ));
+    }
+    let (entry_id, segment) = journal_manager.get_segment(segment_id).await?;
+    let entry = journal_manager
+        .get_entry_by_id(entry_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let file_path =
+        journal_manager.get_audio_file_path_in_folder(&entry.file_name, entry.folder_id)?;
+    if !file_path.exists() {
+        return Err(AppError::Io(format!(
+            "Audio file not found: {}",
+            file_path.display()
+        )));
+    }
+
+    let samples = load_wav_mono_16k(&app, &file_path).map_err(AppError::Io)?;
+    let per_ms = SAMPLE_RATE as i64 / 1000;
+    let start = ((segment.start_ms * per_ms).max(0) as usize).min(samples.len());
+    let end = ((segment.end_ms * per_ms).max(0) as usize).min(samples.len());
+    let emb_model = diarize::get_emb_model_path(&app)?;
+    crate::compute_backend::use_backend(&app);
+    let embedding = tauri::async_runtime::spawn_blocking(move || {
+        diarize::compute_embedding(&samples[start..end], &emb_model)
+    })
+    .await
+    .map_err(|e| format!("Embedding task failed: {}", e))??;
+
+    let profile = journal_manager
+        .enroll_speaker_profile(&name, embedding)
+        .await?;
+    if let Some(speaker) = segment.speaker {
+        journal_manager
+            .update_speaker_name(entry_id, speaker, profile.name.clone())
+            .await?;
+    }
+    Ok(profile)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_speaker_profiles(
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<Vec<SpeakerProfile>, AppError> {
+    journal_manager
+        .list_speaker_profiles()
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_speaker_profile(
+    id: i64,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<(), AppError> {
+    journal_manager
+        .delete_speaker_profile(id)
+        .await
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -536,3 +1091,205 @@ pub async fn get_meeting_speaker_names(
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Talk-time breakdown per speaker, for the meeting stats chart.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_meeting_speaker_stats(
+    entry_id: i64,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<Vec<SpeakerStats>, String> {
+    journal_manager
+        .get_speaker_stats(entry_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// --- Transcript export ---
+
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum ExportFormat {
+    Srt,
+    Vtt,
+    Txt,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Srt => "srt",
+            ExportFormat::Vtt => "vtt",
+            ExportFormat::Txt => "txt",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+async fn render_meeting_transcript(
+    journal_manager: &JournalManager,
+    entry_id: i64,
+    format: ExportFormat,
+) -> Result<String, String> {
+    let result = match format {
+        ExportFormat::Srt => journal_manager.export_meeting_as_srt(entry_id).await,
+        ExportFormat::Vtt => journal_manager.export_meeting_as_vtt(entry_id).await,
+        ExportFormat::Txt => journal_manager.export_meeting_as_txt(entry_id).await,
+        ExportFormat::Markdown => journal_manager.export_meeting_as_markdown(entry_id).await,
+        ExportFormat::Json => {
+            let segments = journal_manager
+                .get_meeting_segments(entry_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            return serde_json::to_string_pretty(&segments).map_err(|e| e.to_string());
+        }
+    };
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_meeting_transcript(
+    entry_id: i64,
+    format: ExportFormat,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<String, String> {
+    render_meeting_transcript(&journal_manager, entry_id, format).await
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct MeetingMarkdownExport {
+    pub markdown: String,
+    /// Where the file was written, when `save_beside_audio` was set.
+    pub path: Option<String>,
+}
+
+/// Render a meeting as Markdown (`**Alice** [00:12:31]: text` per speaker turn).
+/// With `save_beside_audio`, also writes it next to the entry's audio file.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_meeting_markdown(
+    entry_id: i64,
+    save_beside_audio: bool,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<MeetingMarkdownExport, String> {
+    let markdown = journal_manager
+        .export_meeting_as_markdown(entry_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let path = if save_beside_audio {
+        let path = journal_manager
+            .save_meeting_markdown(entry_id, &markdown)
+            .await
+            .map_err(|e| e.to_string())?;
+        Some(path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    Ok(MeetingMarkdownExport { markdown, path })
+}
+
+const MINUTES_PROMPT: &str = "Write concise meeting minutes in Markdown for the transcript below. \
+Include a short summary, the key decisions, and action items with their owners. \
+Use the speaker names as given.\n\n";
+
+/// Summarize a meeting into minutes with the post-processing LLM and store the
+/// result as the entry's post-processed text.
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_meeting_minutes(
+    app: AppHandle,
+    entry_id: i64,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<String, String> {
+    let transcript = journal_manager
+        .export_meeting_as_markdown(entry_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if transcript.trim().is_empty() {
+        return Err("Meeting has no transcribed segments".to_string());
+    }
+
+    let settings = crate::settings::get_settings(&app);
+    let provider = settings
+        .active_post_process_provider()
+        .ok_or_else(|| {
+            "No post-processing provider configured. Set one up in the Post Process tab."
+                .to_string()
+        })?
+        .clone();
+    let api_key = settings
+        .post_process_api_keys
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+    let model = settings
+        .post_process_models
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+    if model.is_empty() {
+        return Err("No model configured for the post-processing provider.".to_string());
+    }
+
+    let prompt = format!("{}{}", MINUTES_PROMPT, transcript);
+    let minutes = crate::llm_client::send_chat_completion(&provider, api_key, &model, prompt)
+        .await
+        .map_err(|e| format!("LLM call failed: {}", e))?
+        .ok_or_else(|| "No response from LLM".to_string())?;
+
+    journal_manager
+        .update_post_processed_text(entry_id, minutes.clone(), "meeting-minutes".to_string())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!("Generated minutes for meeting {}", entry_id);
+    Ok(minutes)
+}
+
+/// Export a meeting transcript and write it to a location chosen via a save dialog.
+/// Returns the saved path, or `None` if the user cancelled the dialog.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_meeting_transcript_to_file(
+    app: AppHandle,
+    entry_id: i64,
+    format: ExportFormat,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let content = render_meeting_transcript(&journal_manager, entry_id, format).await?;
+
+    let title = journal_manager
+        .get_entry_by_id(entry_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|entry| entry.title)
+        .unwrap_or_else(|| "Meeting".to_string());
+    let ext = format.extension();
+
+    let Some(path) = app
+        .dialog()
+        .file()
+        .set_file_name(format!("{}.{}", title.replace(['/', '\\', ':'], "_"), ext))
+        .add_filter(ext.to_uppercase(), &[ext])
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+
+    let path = path.into_path().map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write transcript: {}", e))?;
+    info!(
+        "Exported meeting {} transcript to {}",
+        entry_id,
+        path.display()
+    );
+
+    Ok(Some(path.to_string_lossy().to_string()))
+}
@@ -5,6 +5,8 @@ pub mod journal;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub mod meeting;
 pub mod models;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub mod overlay;
 #[cfg(any(target_os = "android", target_os = "ios"))]
 pub mod share;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
@@ -75,6 +77,113 @@ pub fn set_log_level(app: AppHandle, level: LogLevel) -> Result<(), String> {
     Ok(())
 }
 
+/// Set the proxy used for downloads, cloud transcription, LLM requests and
+/// yt-dlp. An empty value clears it.
+#[specta::specta]
+#[tauri::command]
+pub fn change_http_proxy_setting(app: AppHandle, proxy: Option<String>) -> Result<(), String> {
+    let proxy = proxy
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+    if let Some(url) = &proxy {
+        crate::http::validate_proxy(url)?;
+    }
+    crate::http::set_proxy(proxy.clone());
+
+    let mut settings = get_settings(&app);
+    settings.http_proxy = proxy;
+    write_settings(&app, settings);
+
+    Ok(())
+}
+
+/// Set (or with None, clear) the Obsidian vault journal entries are mirrored
+/// into.
+#[specta::specta]
+#[tauri::command]
+pub fn change_obsidian_vault_path_setting(
+    app: AppHandle,
+    path: Option<String>,
+) -> Result<(), String> {
+    let path = path.filter(|p| !p.trim().is_empty());
+    if let Some(ref p) = path {
+        if !std::path::Path::new(p).is_dir() {
+            return Err(format!("Obsidian vault folder does not exist: {}", p));
+        }
+    }
+    let mut settings = get_settings(&app);
+    settings.obsidian_vault_path = path;
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[specta::specta]
+#[tauri::command]
+pub fn change_auto_generate_title_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.auto_generate_title = enabled;
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[specta::specta]
+#[tauri::command]
+pub fn change_auto_title_use_llm_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.auto_title_use_llm = enabled;
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[specta::specta]
+#[tauri::command]
+pub fn change_trash_retention_days_setting(app: AppHandle, days: u32) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.trash_retention_days = days;
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[specta::specta]
+#[tauri::command]
+pub fn change_backup_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.backup_enabled = enabled;
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[specta::specta]
+#[tauri::command]
+pub fn change_backup_count_setting(app: AppHandle, count: u32) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.backup_count = count.max(1);
+    write_settings(&app, settings);
+    Ok(())
+}
+
+#[specta::specta]
+#[tauri::command]
+pub fn change_markdown_frontmatter_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.markdown_frontmatter = enabled;
+    write_settings(&app, settings);
+    Ok(())
+}
+
+/// Set the transcript `.md` template. An empty template restores the default.
+#[specta::specta]
+#[tauri::command]
+pub fn change_markdown_template_setting(
+    app: AppHandle,
+    template: Option<String>,
+) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.markdown_template = template.filter(|t| !t.trim().is_empty());
+    write_settings(&app, settings);
+    Ok(())
+}
+
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[specta::specta]
 #[tauri::command]
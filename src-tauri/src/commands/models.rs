@@ -1,4 +1,4 @@
-use crate::managers::model::{ModelInfo, ModelManager};
+use crate::managers::model::{MemoryInfo, ModelInfo, ModelManager};
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, write_settings};
@@ -22,6 +22,42 @@ pub async fn get_model_info(
     Ok(model_manager.get_model_info(&model_id))
 }
 
+/// Compute backends whose execution provider initializes on this machine.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub fn get_available_compute_backends(app: AppHandle) -> Vec<crate::settings::ComputeBackend> {
+    crate::compute_backend::available_backends(&app)
+}
+
+/// Backend ONNX models run on in this session. It differs from the setting
+/// until the app is restarted after a change, or when the chosen provider
+/// isn't available and CPU is used instead.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+#[specta::specta]
+pub fn get_active_compute_backend(app: AppHandle) -> crate::settings::ComputeBackend {
+    crate::compute_backend::use_backend(&app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_system_memory_info() -> Result<MemoryInfo, String> {
+    Ok(MemoryInfo::current())
+}
+
+/// Suggest the most accurate model that fits in 70% of the available memory,
+/// favouring ones that are already downloaded.
+#[tauri::command]
+#[specta::specta]
+pub async fn recommend_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<String, String> {
+    model_manager
+        .recommend_model(MemoryInfo::current().available_bytes)
+        .ok_or_else(|| "No models available".to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn download_model(
@@ -34,6 +70,35 @@ pub async fn download_model(
         .map_err(|e| e.to_string())
 }
 
+/// Copy a Whisper model file from `path` into the models directory and list it
+/// as downloaded, for machines that can't reach the model CDN. `name` is an
+/// optional display name.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_local_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    path: String,
+    name: Option<String>,
+) -> Result<ModelInfo, String> {
+    model_manager
+        .import_local_model(std::path::Path::new(&path), name.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Re-check an installed model against its catalog checksum. Emits `model-verified`.
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_model_integrity(
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_id: String,
+) -> Result<bool, String> {
+    model_manager
+        .verify_model_integrity(&model_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 #[tauri::command]
 #[specta::specta]
@@ -198,8 +263,7 @@ pub async fn has_any_models_or_downloads(
     model_manager: State<'_, Arc<ModelManager>>,
 ) -> Result<bool, String> {
     let models = model_manager.get_available_models();
-    // Return true if any models are downloaded OR if any downloads are in progress
-    Ok(models.iter().any(|m| m.is_downloaded))
+    Ok(models.iter().any(|m| m.is_downloaded) || model_manager.has_active_downloads())
 }
 
 #[tauri::command]
@@ -0,0 +1,15 @@
+use tauri::AppHandle;
+
+/// Show the recording overlay, positioned per the overlay setting (near the
+/// cursor, if that is chosen). Does nothing when the overlay is turned off.
+#[tauri::command]
+#[specta::specta]
+pub fn show_recording_overlay(app: AppHandle) {
+    crate::overlay::show_recording_overlay(&app);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn hide_recording_overlay(app: AppHandle) {
+    crate::overlay::hide_recording_overlay(&app);
+}
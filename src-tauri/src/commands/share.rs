@@ -1,13 +1,29 @@
-//! Share intent handling for Android.
+//! Share intent handling.
 //!
 //! On Android, when content is shared to the app via ACTION_SEND,
 //! the Kotlin MainActivity writes a `pending_share.json` file.
 //! These commands let the frontend poll for and consume that data.
+//!
+//! On iOS, the share extension drops audio into the app group container
+//! (see `ios_share`) and the frontend imports it with `process_shared_audio_file`.
+//! `import_shared_audio` goes further and saves the shared audio as an entry.
 
+use crate::managers::journal::{JournalEntry, JournalManager, JournalRecordingResult};
+use crate::settings::get_settings;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+/// Audio formats the share sheet offers to the app. They are decoded to WAV on
+/// import, so cloud transcription and saved entries only ever see WAV.
+const SHARED_AUDIO_EXTENSIONS: [&str; 5] = ["m4a", "wav", "mp3", "ogg", "webm"];
+
+/// Tag on entries saved from shared audio that couldn't be transcribed yet
+/// (no cloud transcription configured, or it failed), so they can be found
+/// and processed later.
+pub const NEEDS_TRANSCRIPTION_TAG: &str = "needs-transcription";
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct ShareData {
@@ -59,3 +75,104 @@ pub async fn clear_pending_share(app: AppHandle) -> Result<(), String> {
     }
     Ok(())
 }
+
+/// Import an audio file shared from another app as a new journal recording.
+/// On iOS `file_path` may be a file name inside the app group's shared folder.
+#[cfg(any(target_os = "android", target_os = "ios"))]
+#[tauri::command]
+#[specta::specta]
+pub async fn process_shared_audio_file(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    file_path: String,
+) -> Result<JournalRecordingResult, String> {
+    #[cfg(target_os = "ios")]
+    let source = crate::ios_share::resolve_shared_file(&app, &file_path)?;
+    #[cfg(not(target_os = "ios"))]
+    let source = PathBuf::from(&file_path);
+
+    let supported = source
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            SHARED_AUDIO_EXTENSIONS
+                .iter()
+                .any(|s| s.eq_ignore_ascii_case(ext))
+        });
+    if !supported {
+        return Err(format!(
+            "Unsupported shared file; expected one of: {}",
+            SHARED_AUDIO_EXTENSIONS.join(", ")
+        ));
+    }
+
+    let result = super::journal::import_audio_for_journal(
+        app.clone(),
+        journal_manager,
+        source.to_string_lossy().into_owned(),
+    )
+    .await?;
+
+    #[cfg(target_os = "ios")]
+    crate::ios_share::remove_shared_file(&source);
+
+    Ok(result)
+}
+
+/// Save an audio file shared from another app (`file_path` of a pending
+/// share) as a journal entry in `folder_id`. The audio is transcribed with
+/// cloud transcription when it's configured; otherwise the entry is saved
+/// without text and tagged `NEEDS_TRANSCRIPTION_TAG`.
+#[cfg(any(target_os = "android", target_os = "ios"))]
+#[tauri::command]
+#[specta::specta]
+pub async fn import_shared_audio(
+    app: AppHandle,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    file_path: String,
+    folder_id: Option<i64>,
+) -> Result<JournalEntry, String> {
+    let recording =
+        process_shared_audio_file(app.clone(), journal_manager.clone(), file_path).await?;
+    let audio_path = journal_manager
+        .effective_recordings_dir()
+        .join(&recording.file_name);
+
+    let transcription = match crate::cloud_transcribe::transcribe_audio_cloud(
+        &app,
+        audio_path.to_str().unwrap_or_default(),
+        Default::default(),
+    )
+    .await
+    {
+        Ok(text) => Some(text),
+        Err(e) => {
+            log::info!("Shared audio saved without transcription: {}", e);
+            None
+        }
+    };
+
+    // An empty title is filled in from the text when auto titles are on
+    let title = if transcription.is_some() && get_settings(&app).auto_generate_title {
+        String::new()
+    } else {
+        chrono::Local::now().format("%B %-d, %Y %H:%M").to_string()
+    };
+    let tags = match transcription {
+        Some(_) => Vec::new(),
+        None => vec![NEEDS_TRANSCRIPTION_TAG.to_string()],
+    };
+    journal_manager
+        .save_entry(
+            recording.file_name,
+            title,
+            transcription.unwrap_or_default(),
+            None,
+            None,
+            tags,
+            Vec::new(),
+            folder_id,
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,54 +1,191 @@
-use crate::managers::journal::{
-    JournalEntry, JournalFolder, JournalManager, JournalRecordingResult,
-};
-use crate::managers::transcription::TranscriptionManager;
-use log::{debug, info};
+use crate::audio_toolkit::{constants::WHISPER_SAMPLE_RATE, resample_mono_audio};
+use crate::managers::journal::{JournalEntry, JournalFolder, JournalManager};
+use crate::managers::transcription::{TranscriptionError, TranscriptionManager};
+use crate::settings::get_settings;
+use log::{debug, info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Listener, State};
+
+/// Payload of the `transcription-chunk-progress` event, emitted after each chunk.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct TranscriptionChunkProgress {
+    /// Entry being transcribed, so concurrent jobs can be told apart.
+    pub entry_id: Option<i64>,
+    pub current: usize,
+    pub total: usize,
+    /// Text of every chunk transcribed so far, for a live preview.
+    pub partial_text: String,
+}
+
+/// Where chunked transcription reports its progress.
+pub struct ChunkProgress<'a> {
+    pub app: &'a AppHandle,
+    pub entry_id: Option<i64>,
+}
+
+impl ChunkProgress<'_> {
+    fn emit(&self, current: usize, total: usize, partial_text: String) {
+        let _ = self.app.emit(
+            "transcription-chunk-progress",
+            TranscriptionChunkProgress {
+                entry_id: self.entry_id,
+                current,
+                total,
+                partial_text,
+            },
+        );
+    }
+}
 
 /// Transcribe audio in chunks to avoid ORT errors with long audio.
-/// Splits into 30-second segments at 16kHz (480,000 samples).
-pub fn transcribe_chunked(
+/// Splits into 30-second segments at 16kHz (480,000 samples). With `progress`,
+/// `transcription-chunk-progress` is emitted after each chunk. `language`
+/// overrides the selected language in settings and `initial_prompt` the
+/// initial prompt; the same prompt is given to every chunk. Gives up as soon
+/// as a chunk hits the transcription timeout.
+pub async fn transcribe_chunked(
     transcription_manager: &TranscriptionManager,
     samples: Vec<f32>,
-) -> Result<String, String> {
+    language: Option<&str>,
+    initial_prompt: Option<&str>,
+    progress: Option<&ChunkProgress>,
+) -> Result<String, TranscriptionError> {
+    let parts: Vec<String> = transcribe_chunked_timed(
+        transcription_manager,
+        samples,
+        language,
+        initial_prompt,
+        progress,
+    )
+    .await?
+    .into_iter()
+    .map(|chunk| chunk.text)
+    .collect();
+    Ok(parts.join(" "))
+}
+
+/// Like `transcribe_chunked`, without the transcript cache, initial prompt or
+/// progress events. Used for live meeting audio.
+pub async fn transcribe_chunked_with_timeout(
+    transcription_manager: &TranscriptionManager,
+    samples: Vec<f32>,
+    language: Option<&str>,
+) -> Result<String, TranscriptionError> {
     const CHUNK_SIZE: usize = 16000 * 30; // 30 seconds at 16kHz
 
-    if samples.len() <= CHUNK_SIZE {
-        return transcription_manager
-            .transcribe(samples)
-            .map_err(|e| format!("Transcription failed: {}", e));
+    let mut parts = Vec::new();
+    for chunk in samples.chunks(CHUNK_SIZE) {
+        let text = transcription_manager
+            .transcribe_with_timeout(chunk.to_vec(), language.map(str::to_string))
+            .await?;
+        let trimmed = text.trim();
+        if !trimmed.is_empty() {
+            parts.push(trimmed.to_string());
+        }
     }
+    Ok(parts.join(" "))
+}
+
+/// The message for a failed chunked transcription. A timeout is reported to
+/// the frontend first (see `emit_transcription_timeout`).
+pub fn transcription_error_message(
+    app: &AppHandle,
+    entry_id: Option<i64>,
+    error: TranscriptionError,
+) -> String {
+    if let TranscriptionError::Timeout { .. } = error {
+        crate::managers::transcription::emit_transcription_timeout(app, entry_id);
+    }
+    format!("Transcription failed: {}", error)
+}
+
+/// Text of one transcribed chunk with its position in the audio.
+pub struct TimedChunk {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+}
 
-    let total_chunks = (samples.len() + CHUNK_SIZE - 1) / CHUNK_SIZE;
+/// Like `transcribe_chunked`, but keeps each 30-second chunk's text with its
+/// start/end offsets. Chunks that transcribe to nothing are dropped.
+pub async fn transcribe_chunked_timed(
+    transcription_manager: &TranscriptionManager,
+    samples: Vec<f32>,
+    language: Option<&str>,
+    initial_prompt: Option<&str>,
+    progress: Option<&ChunkProgress>,
+) -> Result<Vec<TimedChunk>, TranscriptionError> {
+    const CHUNK_SIZE: usize = 16000 * 30; // 30 seconds at 16kHz
+    const SAMPLES_PER_MS: usize = 16;
+
+    let total_chunks = samples.len().div_ceil(CHUNK_SIZE);
     info!(
         "Transcribing {} samples in {} chunks of ~30s each",
         samples.len(),
         total_chunks
     );
 
-    let mut parts: Vec<String> = Vec::new();
+    let mut parts: Vec<TimedChunk> = Vec::new();
     for (i, chunk) in samples.chunks(CHUNK_SIZE).enumerate() {
         debug!("Transcribing chunk {}/{}", i + 1, total_chunks);
         let text = transcription_manager
-            .transcribe(chunk.to_vec())
-            .map_err(|e| format!("Transcription failed on chunk {}: {}", i + 1, e))?;
+            .transcribe_with_prompt_timeout(
+                chunk.to_vec(),
+                language.map(str::to_string),
+                initial_prompt.map(str::to_string),
+            )
+            .await
+            .map_err(|e| match e {
+                TranscriptionError::Failed(e) => {
+                    TranscriptionError::Failed(anyhow::anyhow!("chunk {}: {}", i + 1, e))
+                }
+                timeout => timeout,
+            })?;
         let trimmed = text.trim().to_string();
         if !trimmed.is_empty() {
-            parts.push(trimmed);
+            let start = i * CHUNK_SIZE;
+            parts.push(TimedChunk {
+                start_ms: (start / SAMPLES_PER_MS) as i64,
+                end_ms: ((start + chunk.len()) / SAMPLES_PER_MS) as i64,
+                text: trimmed,
+            });
+        }
+        if let Some(progress) = progress {
+            let partial_text = parts
+                .iter()
+                .map(|c| c.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            progress.emit(i + 1, total_chunks, partial_text);
         }
     }
 
-    Ok(parts.join(" "))
+    Ok(parts)
 }
 
 // --- yt-dlp management commands ---
 
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptSource {
+    /// Existing YouTube subtitles (manual or auto-generated)
+    Captions,
+    /// Local transcription of the downloaded audio
+    Transcription,
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
-pub struct YouTubeDownloadResult {
+pub struct UrlDownloadResult {
     pub title: String,
+    pub uploader: Option<String>,
     pub transcription: String,
+    /// The downloaded recording; for captions, which have no audio, the
+    /// entry's current file name (empty without an `entry_id`).
     pub file_name: String,
+    pub transcript_source: TranscriptSource,
+    /// Sponsor/self-promo ranges cut from the audio before transcription.
+    pub skipped_segments: Vec<crate::sponsorblock::SkippedSegment>,
 }
 
 #[tauri::command]
@@ -65,99 +202,485 @@ pub async fn install_ytdlp(app: AppHandle) -> Result<(), String> {
     crate::ytdlp::download_ytdlp_binary(&app, &version).await
 }
 
+/// The newer yt-dlp release available, if any.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_ytdlp_update(app: AppHandle) -> Result<Option<String>, String> {
+    crate::ytdlp::check_for_update(&app).await
+}
+
+/// Replace the installed yt-dlp with the latest release. Returns its version.
+#[tauri::command]
+#[specta::specta]
+pub async fn update_ytdlp(app: AppHandle) -> Result<String, String> {
+    let version = crate::ytdlp::get_latest_version().await?;
+    info!("Updating yt-dlp to {}", version);
+    crate::ytdlp::download_ytdlp_binary(&app, &version).await?;
+    Ok(version)
+}
+
+/// Run `yt-dlp --get-title` on a URL using the configured cookies so users can
+/// check that age-restricted or members-only videos are reachable.
+#[tauri::command]
+#[specta::specta]
+pub async fn test_ytdlp_auth(app: AppHandle, url: String) -> Result<String, String> {
+    crate::ytdlp::get_video_title(&app, &url).await
+}
+
+/// Kept for existing callers; see `download_url_audio`.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 #[specta::specta]
 pub async fn download_youtube_audio(
     app: AppHandle,
     url: String,
+    prefer_captions: Option<bool>,
+    skip_sponsors: Option<bool>,
+    entry_id: Option<i64>,
+    cookies_from_browser: Option<String>,
+    caption_language: Option<String>,
     journal_manager: State<'_, Arc<JournalManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
-) -> Result<YouTubeDownloadResult, String> {
+) -> Result<UrlDownloadResult, String> {
+    download_url_audio(
+        app,
+        url,
+        prefer_captions,
+        skip_sponsors,
+        entry_id,
+        cookies_from_browser,
+        caption_language,
+        journal_manager,
+        transcription_manager,
+    )
+    .await
+}
+
+/// Download and transcribe audio from any URL yt-dlp supports (YouTube, Vimeo,
+/// SoundCloud, podcast enclosures, ...). yt-dlp errors such as unsupported sites are
+/// returned as reported. When `entry_id` is given, the URL is stored as its `source_url`.
+/// `cookies_from_browser` (e.g. "firefox") overrides the cookie settings for
+/// videos that need a signed-in account. With `prefer_captions`, captions are
+/// fetched in `caption_language`, else in the selected transcription language
+/// (the video's own language when that is "auto").
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+#[specta::specta]
+pub async fn download_url_audio(
+    app: AppHandle,
+    url: String,
+    prefer_captions: Option<bool>,
+    skip_sponsors: Option<bool>,
+    entry_id: Option<i64>,
+    cookies_from_browser: Option<String>,
+    caption_language: Option<String>,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+) -> Result<UrlDownloadResult, String> {
+    info!("[yt-dl] Step 1: Starting audio download for: {}", url);
+
+    // Get title and uploader; this also fails fast for unsupported sites
+    let _ = app.emit("ytdlp-status", "fetching-title");
+    let crate::ytdlp::MediaInfo { title, uploader } =
+        crate::ytdlp::get_media_info(&app, &url, cookies_from_browser.as_deref()).await?;
+    info!("[yt-dl] Step 2: Got title = '{}'", title);
+
+    if let Some(entry_id) = entry_id {
+        journal_manager
+            .update_source_url(entry_id, Some(url.clone()))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let timestamp = chrono::Utc::now().timestamp();
+
+    // Use existing captions when available and skip the audio download entirely
+    if prefer_captions.unwrap_or(false) {
+        let _ = app.emit("ytdlp-status", "fetching-captions");
+        let language = caption_language
+            .unwrap_or_else(|| crate::settings::get_settings(&app).selected_language);
+        let language = Some(language.as_str()).filter(|l| *l != "auto" && !l.is_empty());
+        match fetch_youtube_captions(
+            &app,
+            &url,
+            &format!("mutter-yt-{}", timestamp),
+            language,
+            cookies_from_browser.as_deref(),
+        )
+        .await
+        {
+            Ok(Some(cues)) => {
+                let transcription = crate::subtitles::cues_to_text(&cues);
+                if let Some(entry_id) = entry_id {
+                    let segments: Vec<crate::diarize::DiarizedSegment> = cues
+                        .into_iter()
+                        .map(|cue| crate::diarize::DiarizedSegment {
+                            id: None,
+                            speaker: None,
+                            start_ms: cue.start_ms,
+                            end_ms: cue.end_ms,
+                            text: cue.text,
+                            confidence: None,
+                        })
+                        .collect();
+                    journal_manager
+                        .save_meeting_segments(entry_id, &segments)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                }
+
+                let _ = app.emit("ytdlp-status", "done");
+                info!(
+                    "[yt-dl] DONE from captions: '{}' ({} chars transcript)",
+                    title,
+                    transcription.len()
+                );
+                // There is no audio file: keep the name the entry already has,
+                // or leave it to `save_entry_with_source` to pick one
+                let file_name = match entry_id {
+                    Some(entry_id) => journal_manager
+                        .get_entry_by_id(entry_id)
+                        .await
+                        .map_err(|e| e.to_string())?
+                        .map(|entry| entry.file_name)
+                        .unwrap_or_default(),
+                    None => String::new(),
+                };
+                return Ok(UrlDownloadResult {
+                    file_name,
+                    title,
+                    uploader,
+                    transcription,
+                    transcript_source: TranscriptSource::Captions,
+                    skipped_segments: vec![],
+                });
+            }
+            Ok(None) => info!("[yt-dl] No captions found, falling back to transcription"),
+            Err(e) => warn!(
+                "[yt-dl] Caption download failed, falling back to transcription: {}",
+                e
+            ),
+        }
+    }
+
+    let (transcription, file_name, skipped_segments) = fetch_and_transcribe_youtube(
+        &app,
+        &url,
+        &format!("mutter-yt-{}", timestamp),
+        skip_sponsors.unwrap_or(false),
+        cookies_from_browser.as_deref(),
+        &journal_manager,
+        &transcription_manager,
+    )
+    .await?;
+
+    if let Some(entry_id) = entry_id.filter(|_| !skipped_segments.is_empty()) {
+        journal_manager
+            .set_skipped_segments(entry_id, &skipped_segments)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let _ = app.emit("ytdlp-status", "done");
     info!(
-        "[yt-dl] Step 1: Starting YouTube audio download for: {}",
-        url
+        "[yt-dl] DONE: '{}' ({} chars transcript)",
+        title,
+        transcription.len()
     );
 
-    // Get video title
-    let _ = app.emit("ytdlp-status", "fetching-title");
-    let title = crate::ytdlp::get_video_title(&app, &url)
+    Ok(UrlDownloadResult {
+        title,
+        uploader,
+        transcription,
+        file_name,
+        transcript_source: TranscriptSource::Transcription,
+        skipped_segments,
+    })
+}
+
+/// SponsorBlock ranges that were cut from an imported video's audio.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_skipped_segments(
+    entry_id: i64,
+    journal_manager: State<'_, Arc<JournalManager>>,
+) -> Result<Vec<crate::sponsorblock::SkippedSegment>, String> {
+    journal_manager
+        .get_skipped_segments(entry_id)
         .await
-        .unwrap_or_else(|e| {
-            log::warn!("[yt-dl] get_video_title failed (non-fatal): {}", e);
-            "YouTube Video".to_string()
-        });
-    info!("[yt-dl] Step 2: Got title = '{}'", title);
+        .map_err(|e| e.to_string())
+}
 
+/// Download and parse a video's captions. Returns `None` when the video has none.
+async fn fetch_youtube_captions(
+    app: &AppHandle,
+    url: &str,
+    stem: &str,
+    language: Option<&str>,
+    cookies_from_browser: Option<&str>,
+) -> Result<Option<Vec<crate::subtitles::CaptionCue>>, String> {
+    let out_base = std::env::temp_dir().join(stem);
+    let Some(vtt_path) =
+        crate::ytdlp::download_captions(app, url, &out_base, language, cookies_from_browser)
+            .await?
+    else {
+        return Ok(None);
+    };
+
+    let content = std::fs::read_to_string(&vtt_path);
+    let _ = std::fs::remove_file(&vtt_path);
+    let content = content.map_err(|e| format!("Failed to read captions: {}", e))?;
+
+    let cues = crate::subtitles::parse_vtt(&content);
+    Ok(if cues.is_empty() { None } else { Some(cues) })
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct PlaylistImportResult {
+    pub playlist_title: String,
+    pub folder_id: Option<i64>,
+    /// Entries that were downloaded and transcribed successfully.
+    pub completed_entry_ids: Vec<i64>,
+    /// Entries that failed and were left as pending placeholders.
+    pub failed_entry_ids: Vec<i64>,
+    pub cancelled: bool,
+}
+
+/// Import every video of a YouTube playlist as its own journal entry.
+/// Pending entries are created up front, then each video is downloaded and
+/// transcribed in order. Failed items are skipped; `ytdlp-cancel` stops the
+/// import between items.
+#[tauri::command]
+#[specta::specta]
+pub async fn download_youtube_playlist(
+    app: AppHandle,
+    url: String,
+    create_folder: bool,
+    folder_id: Option<i64>,
+    journal_manager: State<'_, Arc<JournalManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+) -> Result<PlaylistImportResult, String> {
+    info!("[yt-playlist] Starting playlist import for: {}", url);
+
+    let _ = app.emit("ytdlp-status", "fetching-playlist");
+    let (playlist_title, items) = crate::ytdlp::get_playlist_items(&app, &url).await?;
+    if items.is_empty() {
+        return Err("Playlist has no videos".to_string());
+    }
+
+    let folder_id = if create_folder {
+        let folder = journal_manager
+            .create_folder_with_source(playlist_title.clone(), "video".to_string(), None)
+            .await
+            .map_err(|e| e.to_string())?;
+        Some(folder.id)
+    } else {
+        folder_id
+    };
+
+    // Create a pending entry per video so the user sees the whole playlist immediately
+    let mut pending: Vec<(i64, crate::ytdlp::PlaylistItem)> = Vec::with_capacity(items.len());
+    for item in items {
+        let entry = journal_manager
+            .save_entry_with_source(
+                String::new(),
+                item.title.clone(),
+                String::new(),
+                None,
+                None,
+                vec![],
+                vec![],
+                folder_id,
+                "youtube".to_string(),
+                Some(item.url.clone()),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        pending.push((entry.id, item));
+    }
+
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let cancel_flag_clone = cancel_flag.clone();
+    let cancel_listener = app.listen("ytdlp-cancel", move |_| {
+        cancel_flag_clone.store(true, Ordering::Relaxed);
+    });
+
+    let total = pending.len();
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut completed_entry_ids = Vec::new();
+    let mut failed_entry_ids = Vec::new();
+
+    for (index, (entry_id, item)) in pending.into_iter().enumerate() {
+        if cancel_flag.load(Ordering::Relaxed) {
+            info!(
+                "[yt-playlist] Cancelled before item {}/{}",
+                index + 1,
+                total
+            );
+            break;
+        }
+
+        let _ = app.emit(
+            "ytdlp-playlist-progress",
+            serde_json::json!({
+                "entryId": entry_id,
+                "index": index,
+                "total": total,
+                "title": item.title,
+                "status": "processing",
+            }),
+        );
+
+        let result = fetch_and_transcribe_youtube(
+            &app,
+            &item.url,
+            &format!("mutter-yt-{}-{}", timestamp, index + 1),
+            false,
+            None,
+            &journal_manager,
+            &transcription_manager,
+        )
+        .await;
+
+        let error = match result {
+            Ok((transcription, file_name, _)) => journal_manager
+                .update_entry_after_processing(
+                    entry_id,
+                    file_name,
+                    item.title.clone(),
+                    transcription,
+                )
+                .await
+                .err()
+                .map(|e| e.to_string()),
+            Err(e) => Some(e),
+        };
+
+        match &error {
+            None => completed_entry_ids.push(entry_id),
+            Some(e) => {
+                warn!(
+                    "[yt-playlist] Skipping item {}/{} ({}): {}",
+                    index + 1,
+                    total,
+                    item.url,
+                    e
+                );
+                failed_entry_ids.push(entry_id);
+            }
+        }
+
+        let _ = app.emit(
+            "ytdlp-playlist-progress",
+            serde_json::json!({
+                "entryId": entry_id,
+                "index": index,
+                "total": total,
+                "title": item.title,
+                "status": if error.is_none() { "done" } else { "failed" },
+                "error": error,
+            }),
+        );
+    }
+
+    app.unlisten(cancel_listener);
+    let cancelled = cancel_flag.load(Ordering::Relaxed);
+    let _ = app.emit("ytdlp-status", "done");
+
+    info!(
+        "[yt-playlist] DONE: '{}' ({} completed, {} failed, cancelled={})",
+        playlist_title,
+        completed_entry_ids.len(),
+        failed_entry_ids.len(),
+        cancelled
+    );
+
+    Ok(PlaylistImportResult {
+        playlist_title,
+        folder_id,
+        completed_entry_ids,
+        failed_entry_ids,
+        cancelled,
+    })
+}
+
+/// Download a single video's audio with yt-dlp, transcribe it and save a 16kHz WAV named
+/// `<stem>.wav` in the journal recordings dir. With `skip_sponsors`, SponsorBlock
+/// sponsor/self-promo ranges are cut from the audio first; if the lookup fails the
+/// full audio is used. Returns `(transcription, file_name, skipped_segments)`.
+async fn fetch_and_transcribe_youtube(
+    app: &AppHandle,
+    url: &str,
+    stem: &str,
+    skip_sponsors: bool,
+    cookies_from_browser: Option<&str>,
+    journal_manager: &JournalManager,
+    transcription_manager: &TranscriptionManager,
+) -> Result<(String, String, Vec<crate::sponsorblock::SkippedSegment>), String> {
     // Download audio to a temp file
     let _ = app.emit("ytdlp-status", "downloading");
     let temp_dir = std::env::temp_dir();
-    let timestamp = chrono::Utc::now().timestamp();
-    let temp_base = temp_dir.join(format!("mutter-yt-{}", timestamp));
-    let temp_path_with_ext = temp_base.with_extension("m4a");
+    // Let yt-dlp pick the extension; sites other than YouTube rarely serve m4a
+    let temp_template = temp_dir.join(format!("{}.%(ext)s", stem));
     info!(
         "[yt-dl] Step 3: Downloading audio to {}",
-        temp_path_with_ext.display()
+        temp_template.display()
     );
 
-    crate::ytdlp::download_audio(&app, &url, &temp_path_with_ext).await?;
+    crate::ytdlp::download_audio(app, url, &temp_template, cookies_from_browser).await?;
     info!("[yt-dl] Step 4: yt-dlp download finished");
 
-    // yt-dlp may produce a file with a slightly different name; find it
-    let downloaded_file = if temp_path_with_ext.exists() {
-        temp_path_with_ext.clone()
-    } else {
-        let parent = temp_base.parent().unwrap_or(&temp_dir);
-        let base_stem = temp_base
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("mutter-yt");
-        let mut found = None;
-        if let Ok(entries) = std::fs::read_dir(parent) {
-            for entry in entries.flatten() {
-                let name = entry.file_name();
-                let name_str = name.to_string_lossy();
-                if name_str.starts_with(base_stem) {
-                    found = Some(entry.path());
-                    break;
-                }
-            }
-        }
-        found.ok_or_else(|| "Downloaded audio file not found".to_string())?
-    };
+    // Find the file yt-dlp produced, ignoring partial downloads
+    let prefix = format!("{}.", stem);
+    let downloaded_file = std::fs::read_dir(&temp_dir)
+        .ok()
+        .and_then(|entries| {
+            entries.flatten().map(|e| e.path()).find(|path| {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                name.starts_with(&prefix) && !name.ends_with(".part") && !name.ends_with(".ytdl")
+            })
+        })
+        .ok_or_else(|| "Downloaded audio file not found".to_string())?;
     info!(
         "[yt-dl] Step 5: Downloaded file = {}",
         downloaded_file.display()
     );
 
-    // Extract audio from downloaded file using symphonia
+    // Extract audio from downloaded file using symphonia (or ffmpeg)
     let _ = app.emit("ytdlp-status", "extracting");
     let file_path_str = downloaded_file.to_string_lossy().to_string();
     info!("[yt-dl] Step 6: Extracting audio from {}", file_path_str);
-    let (samples, sample_rate) = extract_audio_from_video(&file_path_str)?;
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let cancel_flag_clone = cancel_flag.clone();
+    let cancel_listener = app.listen("ytdlp-cancel", move |_| {
+        cancel_flag_clone.store(true, Ordering::Relaxed);
+    });
+    let extracted = extract_audio_with_fallback(app, &file_path_str, &cancel_flag);
+    app.unlisten(cancel_listener);
+    let (samples, sample_rate) = extracted?;
     info!(
         "[yt-dl] Step 7: Extracted {} samples at {}Hz",
         samples.len(),
         sample_rate
     );
 
-    // Resample to 16kHz mono if needed
-    let target_rate = 16000u32;
-    let resampled = if sample_rate != target_rate {
-        let ratio = sample_rate as f64 / target_rate as f64;
-        let new_len = (samples.len() as f64 / ratio) as usize;
-        (0..new_len)
-            .map(|i| {
-                let src_idx = i as f64 * ratio;
-                let idx = src_idx as usize;
-                let frac = src_idx - idx as f64;
-                let a = samples.get(idx).copied().unwrap_or(0.0);
-                let b = samples.get(idx + 1).copied().unwrap_or(a);
-                a + (b - a) * frac as f32
-            })
-            .collect::<Vec<f32>>()
+    let skipped_segments = if skip_sponsors {
+        fetch_sponsor_segments(url).await
     } else {
-        samples
+        vec![]
     };
+    let samples = crate::sponsorblock::excise_segments(samples, sample_rate, &skipped_segments);
+
+    // Resample to 16kHz mono if needed
+    let resampled = resample_mono_audio(
+        &samples,
+        sample_rate,
+        WHISPER_SAMPLE_RATE,
+        get_settings(app).audio_resample_quality,
+    );
 
     let samples_for_wav = resampled.clone();
 
@@ -166,14 +689,16 @@ pub async fn download_youtube_audio(
     transcription_manager.initiate_model_load();
     info!("[yt-dl] Step 8: Transcribing {} samples", resampled.len());
 
-    let transcription = transcribe_chunked(&transcription_manager, resampled)?;
+    let transcription = transcribe_chunked(transcription_manager, resampled, None, None, None)
+        .await
+        .map_err(|e| transcription_error_message(app, None, e))?;
     info!(
         "[yt-dl] Step 9: Transcription complete ({} chars)",
         transcription.len()
     );
 
     // Save as 16kHz mono WAV in journal recordings dir
-    let file_name = format!("mutter-yt-{}.wav", timestamp);
+    let file_name = format!("{}.wav", stem);
     let dest_path = journal_manager.effective_recordings_dir().join(&file_name);
     info!("[yt-dl] Step 10: Saving WAV to {}", dest_path.display());
 
@@ -184,140 +709,96 @@ pub async fn download_youtube_audio(
     // Clean up temp file
     let _ = std::fs::remove_file(&downloaded_file);
 
-    let _ = app.emit("ytdlp-status", "done");
-    info!(
-        "[yt-dl] DONE: '{}' ({} chars transcript)",
-        title,
-        transcription.len()
-    );
-
-    Ok(YouTubeDownloadResult {
-        title,
-        transcription,
-        file_name,
-    })
+    Ok((transcription, file_name, skipped_segments))
 }
 
-// --- Video file import (extract audio, transcribe) ---
-
-fn extract_audio_from_video(file_path: &str) -> Result<(Vec<f32>, u32), String> {
-    use symphonia::core::audio::SampleBuffer;
-    use symphonia::core::codecs::DecoderOptions;
-    use symphonia::core::formats::FormatOptions;
-    use symphonia::core::io::MediaSourceStream;
-    use symphonia::core::meta::MetadataOptions;
-    use symphonia::core::probe::Hint;
-
-    let file =
-        std::fs::File::open(file_path).map_err(|e| format!("Failed to open video file: {}", e))?;
-
-    let mss = MediaSourceStream::new(Box::new(file), Default::default());
-
-    let mut hint = Hint::new();
-    if let Some(ext) = std::path::Path::new(file_path)
-        .extension()
-        .and_then(|e| e.to_str())
-    {
-        hint.with_extension(ext);
-    }
-
-    let probed = symphonia::default::get_probe()
-        .format(
-            &hint,
-            mss,
-            &FormatOptions::default(),
-            &MetadataOptions::default(),
-        )
-        .map_err(|e| {
-            format!(
-                "Unsupported video format: {}. Supported formats: MP4, MKV, WebM, MP3.",
+/// Look up SponsorBlock segments for a YouTube URL. Any failure yields no segments.
+async fn fetch_sponsor_segments(url: &str) -> Vec<crate::sponsorblock::SkippedSegment> {
+    let Some(video_id) = crate::sponsorblock::extract_video_id(url) else {
+        warn!(
+            "[yt-dl] Could not find a video ID in {}, not skipping sponsors",
+            url
+        );
+        return vec![];
+    };
+    match crate::sponsorblock::fetch_skip_segments(&video_id).await {
+        Ok(segments) => {
+            info!(
+                "[yt-dl] Skipping {} SponsorBlock segments for {}",
+                segments.len(),
+                video_id
+            );
+            segments
+        }
+        Err(e) => {
+            warn!(
+                "[yt-dl] SponsorBlock lookup failed, using full audio: {}",
                 e
-            )
-        })?;
-
-    let mut format = probed.format;
-
-    // Find the first audio track
-    let track = format
-        .tracks()
-        .iter()
-        .find(|t| t.codec_params.codec != symphonia::core::codecs::CODEC_TYPE_NULL)
-        .ok_or_else(|| "No audio track found in video file".to_string())?
-        .clone();
-
-    let sample_rate = track
-        .codec_params
-        .sample_rate
-        .ok_or_else(|| "Unknown sample rate in audio track".to_string())?;
-
-    let mut decoder = symphonia::default::get_codecs()
-        .make(&track.codec_params, &DecoderOptions::default())
-        .map_err(|e| format!("Failed to create audio decoder: {}", e))?;
-
-    let mut all_samples: Vec<f32> = Vec::new();
-
-    loop {
-        let packet = match format.next_packet() {
-            Ok(packet) => packet,
-            Err(symphonia::core::errors::Error::IoError(ref e))
-                if e.kind() == std::io::ErrorKind::UnexpectedEof =>
-            {
-                break; // EOF
-            }
-            Err(symphonia::core::errors::Error::ResetRequired) => {
-                break;
-            }
-            Err(_) => break,
-        };
-
-        if packet.track_id() != track.id {
-            continue;
+            );
+            vec![]
         }
+    }
+}
 
-        let decoded = match decoder.decode(&packet) {
-            Ok(decoded) => decoded,
-            Err(_) => continue,
-        };
-
-        let spec = *decoded.spec();
-        let num_channels = spec.channels.count();
-        let mut sample_buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
-        sample_buf.copy_interleaved_ref(decoded);
+// --- Video file import (extract audio, transcribe) ---
 
-        let samples = sample_buf.samples();
+/// Decode any symphonia-supported audio or video file to mono f32 samples.
+pub fn extract_audio_from_video(file_path: &str) -> Result<(Vec<f32>, u32), String> {
+    crate::audio_save::decode_audio_file(file_path)
+}
 
-        // Mix to mono if multichannel
-        if num_channels > 1 {
-            for frame in samples.chunks(num_channels) {
-                let mono: f32 = frame.iter().sum::<f32>() / num_channels as f32;
-                all_samples.push(mono);
-            }
-        } else {
-            all_samples.extend_from_slice(samples);
-        }
+/// Decode audio with symphonia, falling back to ffmpeg for formats it can't handle
+/// (.mov, .avi, .wmv, some AAC profiles). Setting `cancel` stops an ffmpeg decode.
+pub fn extract_audio_with_fallback(
+    app: &AppHandle,
+    file_path: &str,
+    cancel: &AtomicBool,
+) -> Result<(Vec<f32>, u32), String> {
+    let symphonia_error = match extract_audio_from_video(file_path) {
+        Ok(result) => return Ok(result),
+        Err(e) => e,
+    };
+    if !std::path::Path::new(file_path).is_file() {
+        return Err(symphonia_error);
     }
 
-    if all_samples.is_empty() {
-        return Err("No audio data could be extracted from the video file".to_string());
-    }
+    let settings = get_settings(app);
+    let Some(ffmpeg) = crate::ffmpeg::find_ffmpeg(settings.ffmpeg_path.as_deref()) else {
+        return Err(format!(
+            "{} Install ffmpeg or set its path in settings to import this file.",
+            symphonia_error
+        ));
+    };
 
-    info!(
-        "Extracted {} audio samples at {}Hz from video",
-        all_samples.len(),
-        sample_rate
+    warn!(
+        "symphonia could not decode {} ({}), falling back to ffmpeg",
+        file_path, symphonia_error
     );
+    let samples =
+        crate::ffmpeg::decode_to_mono_16k(&ffmpeg, std::path::Path::new(file_path), cancel)?;
+    Ok((samples, crate::ffmpeg::OUTPUT_SAMPLE_RATE))
+}
 
-    Ok((all_samples, sample_rate))
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct VideoImportResult {
+    pub file_name: String,
+    pub transcription_text: String,
+    /// Per-chunk timed segments were stored for the entry and can be read
+    /// with `get_meeting_segments`.
+    pub has_timed_segments: bool,
 }
 
+/// Extract, transcribe and save a video's audio. With `entry_id`, the transcript is
+/// also stored as timed segments (one per 30-second chunk) for playback sync.
 #[tauri::command]
 #[specta::specta]
 pub async fn import_video_for_journal(
-    _app: AppHandle,
+    app: AppHandle,
     journal_manager: State<'_, Arc<JournalManager>>,
     transcription_manager: State<'_, Arc<TranscriptionManager>>,
     file_path: String,
-) -> Result<JournalRecordingResult, String> {
+    entry_id: Option<i64>,
+) -> Result<VideoImportResult, String> {
     info!("Importing video file: {}", file_path);
 
     let src = std::path::Path::new(&file_path);
@@ -325,27 +806,23 @@ pub async fn import_video_for_journal(
         return Err("Video file not found".to_string());
     }
 
-    // Extract audio from video
-    let (samples, sample_rate) = extract_audio_from_video(&file_path)?;
+    // Extract audio from video; `video-import-cancel` stops an ffmpeg fallback
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let cancel_flag_clone = cancel_flag.clone();
+    let cancel_listener = app.listen("video-import-cancel", move |_| {
+        cancel_flag_clone.store(true, Ordering::Relaxed);
+    });
+    let extracted = extract_audio_with_fallback(&app, &file_path, &cancel_flag);
+    app.unlisten(cancel_listener);
+    let (samples, sample_rate) = extracted?;
 
     // Resample to 16kHz mono if needed
-    let target_rate = 16000u32;
-    let resampled = if sample_rate != target_rate {
-        let ratio = sample_rate as f64 / target_rate as f64;
-        let new_len = (samples.len() as f64 / ratio) as usize;
-        (0..new_len)
-            .map(|i| {
-                let src_idx = i as f64 * ratio;
-                let idx = src_idx as usize;
-                let frac = src_idx - idx as f64;
-                let a = samples.get(idx).copied().unwrap_or(0.0);
-                let b = samples.get(idx + 1).copied().unwrap_or(a);
-                a + (b - a) * frac as f32
-            })
-            .collect::<Vec<f32>>()
-    } else {
-        samples
-    };
+    let resampled = resample_mono_audio(
+        &samples,
+        sample_rate,
+        WHISPER_SAMPLE_RATE,
+        get_settings(&app).audio_resample_quality,
+    );
 
     // Clone for WAV saving
     let samples_for_wav = resampled.clone();
@@ -353,8 +830,47 @@ pub async fn import_video_for_journal(
     // Ensure model is loaded
     transcription_manager.initiate_model_load();
 
-    // Transcribe
-    let transcription = transcribe_chunked(&transcription_manager, resampled)?;
+    // Transcribe, keeping chunk offsets
+    let progress = ChunkProgress {
+        app: &app,
+        entry_id,
+    };
+    let chunks = transcribe_chunked_timed(
+        &transcription_manager,
+        resampled,
+        None,
+        None,
+        Some(&progress),
+    )
+    .await
+    .map_err(|e| transcription_error_message(&app, entry_id, e))?;
+    let transcription = chunks
+        .iter()
+        .map(|c| c.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let has_timed_segments = match entry_id {
+        Some(entry_id) if !chunks.is_empty() => {
+            let segments: Vec<crate::diarize::DiarizedSegment> = chunks
+                .into_iter()
+                .map(|chunk| crate::diarize::DiarizedSegment {
+                    id: None,
+                    speaker: None,
+                    start_ms: chunk.start_ms,
+                    end_ms: chunk.end_ms,
+                    text: chunk.text,
+                    confidence: None,
+                })
+                .collect();
+            journal_manager
+                .save_meeting_segments(entry_id, &segments)
+                .await
+                .map_err(|e| e.to_string())?;
+            true
+        }
+        _ => false,
+    };
 
     // Save as 16kHz mono WAV in journal recordings dir
     let timestamp = chrono::Utc::now().timestamp();
@@ -367,9 +883,104 @@ pub async fn import_video_for_journal(
 
     info!("Video import complete: {}", file_name);
 
-    Ok(JournalRecordingResult {
+    Ok(VideoImportResult {
         file_name,
         transcription_text: transcription,
+        has_timed_segments,
+    })
+}
+
+// --- Subtitle file import ---
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct SubtitleImportResult {
+    pub entry: JournalEntry,
+    pub cue_count: usize,
+    /// Cues dropped because their timestamps could not be parsed.
+    pub skipped_cues: usize,
+}
+
+/// Import an .srt or .vtt file as a journal entry without audio. The flat text becomes
+/// the transcript and each cue is stored as a meeting segment with its timing.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_subtitles_for_journal(
+    journal_manager: State<'_, Arc<JournalManager>>,
+    file_path: String,
+    source: String,
+    folder_id: Option<i64>,
+) -> Result<SubtitleImportResult, String> {
+    if source != "video" && source != "meeting" {
+        return Err(format!(
+            "Unsupported source for subtitle import: {}",
+            source
+        ));
+    }
+
+    let path = std::path::Path::new(&file_path);
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read subtitles: {}", e))?;
+
+    let parsed = crate::subtitles::parse_subtitles(&content);
+    if parsed.cues.is_empty() {
+        return Err("No subtitle cues found in file".to_string());
+    }
+    if parsed.malformed > 0 {
+        warn!(
+            "Skipped {} subtitle cues with malformed timestamps in {}",
+            parsed.malformed, file_path
+        );
+    }
+
+    let title = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported Subtitles".to_string());
+    let transcription = crate::subtitles::cues_to_text(&parsed.cues);
+
+    let entry = journal_manager
+        .save_entry_with_source(
+            String::new(),
+            title,
+            transcription,
+            None,
+            None,
+            vec![],
+            vec![],
+            folder_id,
+            source,
+            None,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let cue_count = parsed.cues.len();
+    let segments: Vec<crate::diarize::DiarizedSegment> = parsed
+        .cues
+        .into_iter()
+        .map(|cue| crate::diarize::DiarizedSegment {
+            id: None,
+            speaker: None,
+            start_ms: cue.start_ms,
+            end_ms: cue.end_ms,
+            text: cue.text,
+            confidence: None,
+        })
+        .collect();
+    journal_manager
+        .save_meeting_segments(entry.id, &segments)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!(
+        "Imported {} subtitle cues from {} as entry {}",
+        cue_count, file_path, entry.id
+    );
+
+    Ok(SubtitleImportResult {
+        entry,
+        cue_count,
+        skipped_cues: parsed.malformed,
     })
 }
 
@@ -404,7 +1015,7 @@ pub async fn create_video_folder(
     journal_manager: State<'_, Arc<JournalManager>>,
 ) -> Result<JournalFolder, String> {
     journal_manager
-        .create_folder_with_source(name, "video".to_string())
+        .create_folder_with_source(name, "video".to_string(), None)
         .await
         .map_err(|e| e.to_string())
 }
@@ -0,0 +1,172 @@
+//! Which ONNX Runtime execution provider runs the ONNX transcription engines
+//! (Parakeet, Moonshine, SenseVoice) and the pyannote diarization models.
+//!
+//! The provider is set on ORT's global environment, which every session
+//! picks up and which ORT only lets us set up once per process. So it is
+//! committed on first use, before any session exists, and a change of the
+//! setting takes effect after a restart. Whisper runs on whisper.cpp and uses
+//! whichever GPU backend it was built with.
+//!
+//! A provider that isn't available here is replaced by CPU with a warning and
+//! a `compute-backend-fallback` event; one that fails to register on a
+//! session is skipped by ORT, leaving that session on CPU.
+
+use log::{debug, warn};
+use ort::execution_providers::{
+    CPUExecutionProvider, CUDAExecutionProvider, CoreMLExecutionProvider,
+    DirectMLExecutionProvider, ExecutionProvider, ExecutionProviderDispatch,
+};
+use ort::session::Session;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter};
+
+use crate::settings::{self, ComputeBackend};
+
+/// Backend committed to the ORT environment for this process.
+static ACTIVE: OnceLock<ComputeBackend> = OnceLock::new();
+
+/// True if `provider` is built into the ORT runtime and registers on a
+/// session, i.e. its drivers and libraries are present. Creating the session
+/// builder sets up the ORT environment, so only call this once it is
+/// committed.
+fn initializes(provider: impl ExecutionProvider) -> bool {
+    if !provider.is_available().unwrap_or(false) {
+        return false;
+    }
+    match Session::builder() {
+        Ok(mut builder) => provider.register(&mut builder).is_ok(),
+        Err(_) => false,
+    }
+}
+
+fn is_supported(backend: ComputeBackend) -> bool {
+    match backend {
+        ComputeBackend::Auto | ComputeBackend::Cpu => true,
+        ComputeBackend::Cuda => {
+            cfg!(any(target_os = "windows", target_os = "linux"))
+                && initializes(CUDAExecutionProvider::default())
+        }
+        ComputeBackend::Directml => {
+            cfg!(target_os = "windows") && initializes(DirectMLExecutionProvider::default())
+        }
+        ComputeBackend::Coreml => {
+            cfg!(target_os = "macos") && initializes(CoreMLExecutionProvider::default())
+        }
+    }
+}
+
+/// Whether `backend`'s provider is built into the ORT runtime for this
+/// platform. Unlike `is_supported` this creates no session, so it can be
+/// checked before the environment is committed.
+fn is_available(backend: ComputeBackend) -> bool {
+    match backend {
+        ComputeBackend::Auto | ComputeBackend::Cpu => true,
+        ComputeBackend::Cuda => {
+            cfg!(any(target_os = "windows", target_os = "linux"))
+                && CUDAExecutionProvider::default()
+                    .is_available()
+                    .unwrap_or(false)
+        }
+        ComputeBackend::Directml => {
+            cfg!(target_os = "windows")
+                && DirectMLExecutionProvider::default()
+                    .is_available()
+                    .unwrap_or(false)
+        }
+        ComputeBackend::Coreml => {
+            cfg!(target_os = "macos")
+                && CoreMLExecutionProvider::default()
+                    .is_available()
+                    .unwrap_or(false)
+        }
+    }
+}
+
+/// Backends that can be used on this machine. `auto` and `cpu` always can.
+pub fn available_backends(app: &AppHandle) -> Vec<ComputeBackend> {
+    use_backend(app);
+    [
+        ComputeBackend::Auto,
+        ComputeBackend::Cpu,
+        ComputeBackend::Cuda,
+        ComputeBackend::Directml,
+        ComputeBackend::Coreml,
+    ]
+    .into_iter()
+    .filter(|&backend| is_supported(backend))
+    .collect()
+}
+
+/// Providers for `backend`, CPU last. Providers that fail to register on a
+/// session are skipped, so that session ends up on CPU.
+fn providers(backend: ComputeBackend) -> Vec<ExecutionProviderDispatch> {
+    match backend {
+        ComputeBackend::Cpu => vec![CPUExecutionProvider::default().build()],
+        ComputeBackend::Auto => vec![
+            CUDAExecutionProvider::default().build(),
+            DirectMLExecutionProvider::default().build(),
+            CoreMLExecutionProvider::default().build(),
+            CPUExecutionProvider::default().build(),
+        ],
+        ComputeBackend::Cuda => vec![
+            CUDAExecutionProvider::default().build(),
+            CPUExecutionProvider::default().build(),
+        ],
+        ComputeBackend::Directml => vec![
+            DirectMLExecutionProvider::default().build(),
+            CPUExecutionProvider::default().build(),
+        ],
+        ComputeBackend::Coreml => vec![
+            CoreMLExecutionProvider::default().build(),
+            CPUExecutionProvider::default().build(),
+        ],
+    }
+}
+
+fn commit(backend: ComputeBackend) {
+    let committed = ort::init()
+        .with_name("handy")
+        .with_execution_providers(providers(backend))
+        .commit();
+    match committed {
+        Ok(true) => debug!("ORT environment committed for {:?}", backend),
+        // Something created a session first; ORT keeps the environment it set
+        // up then, with its default (CPU) provider
+        Ok(false) => warn!(
+            "ORT environment was already set up; {:?} was not applied",
+            backend
+        ),
+        Err(e) => warn!(
+            "Failed to commit the ORT environment for {:?}: {}",
+            backend, e
+        ),
+    }
+}
+
+/// Commit the ORT environment for the `compute_backend` setting if that hasn't
+/// happened yet, and return the backend in use for this process. Call it
+/// before creating ORT sessions. A provider that can't be used here is
+/// replaced by CPU.
+pub fn use_backend(app: &AppHandle) -> ComputeBackend {
+    *ACTIVE.get_or_init(|| {
+        let requested = settings::get_settings(app).compute_backend;
+        let backend = if is_available(requested) {
+            requested
+        } else {
+            warn!(
+                "Compute backend {:?} is not available on this machine, using CPU",
+                requested
+            );
+            let _ = app.emit(
+                "compute-backend-fallback",
+                serde_json::json!({
+                    "requested": requested,
+                    "reason": "the provider is not available on this machine",
+                }),
+            );
+            ComputeBackend::Cpu
+        };
+        commit(backend);
+        backend
+    })
+}
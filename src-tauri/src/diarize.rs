@@ -1,15 +1,33 @@
 use futures_util::StreamExt;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use specta::Type;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::error::AppError;
+use crate::managers::model::DownloadProgress;
+
 const SEGMENTATION_MODEL: &str = "segmentation-3.0.onnx";
 const EMBEDDING_MODEL: &str = "wespeaker_en_voxceleb_CAM++.onnx";
 const SEGMENTATION_URL: &str =
     "https://github.com/thewh1teagle/pyannote-rs/releases/download/v0.1.0/segmentation-3.0.onnx";
 const EMBEDDING_URL: &str = "https://github.com/thewh1teagle/pyannote-rs/releases/download/v0.1.0/wespeaker_en_voxceleb_CAM%2B%2B.onnx";
+/// Expected SHA-256 of each download. With `None` the hash of the first
+/// complete download is recorded beside the model and checked from then on;
+/// that download is only trusted if it matches the size the server reported.
+const SEGMENTATION_SHA256: Option<&str> = None;
+const EMBEDDING_SHA256: Option<&str> = None;
+
+/// Segments shorter than this are usually breaths or clicks rather than speech.
+pub const DEFAULT_MIN_SEGMENT_MS: i64 = 250;
+/// Short segments further than this from their speaker's nearest segment are
+/// dropped instead of merged.
+pub const DEFAULT_MERGE_GAP_MS: i64 = 1000;
 
 /// A single diarized speech segment with speaker assignment and audio samples.
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -20,6 +38,10 @@ pub struct DiarizedSegment {
     pub start_ms: i64,
     pub end_ms: i64,
     pub text: String,
+    /// Cosine similarity between the segment embedding and its assigned speaker (0.0–1.0).
+    /// `None` for segments saved before confidence was recorded.
+    #[serde(default)]
+    pub confidence: Option<f32>,
 }
 
 /// Result of diarization before transcription (internal use).
@@ -28,6 +50,9 @@ pub struct RawDiarizedSegment {
     pub start_ms: i64,
     pub end_ms: i64,
     pub samples: Vec<f32>,
+    pub confidence: Option<f32>,
+    /// Speaker embedding of the segment, used to match enrolled speaker profiles.
+    pub embedding: Vec<f32>,
 }
 
 fn get_models_dir(app: &AppHandle) -> Result<PathBuf, String> {
@@ -47,17 +72,116 @@ fn embedding_model_path(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(get_models_dir(app)?.join(EMBEDDING_MODEL))
 }
 
+/// Both models are present and match their checksums. Hashes the files, so
+/// call it off the async runtime.
 pub fn models_installed(app: &AppHandle) -> Result<bool, String> {
-    let seg = segmentation_model_path(app)?;
-    let emb = embedding_model_path(app)?;
-    Ok(seg.exists() && emb.exists())
+    Ok(verify_models(app)?
+        .iter()
+        .all(|check| check.present && check.valid))
+}
+
+/// SHA-256 a downloaded model is checked against: the built-in hash when one
+/// is known, otherwise the hash recorded when the file was fully downloaded.
+fn expected_sha256(path: &Path, builtin: Option<&str>) -> Option<String> {
+    builtin.map(str::to_string).or_else(|| {
+        std::fs::read_to_string(checksum_path(path))
+            .ok()
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty())
+    })
+}
+
+/// Sidecar file holding the hash of a completed download.
+fn checksum_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".sha256");
+    path.with_file_name(name)
+}
+
+fn file_sha256(path: &Path) -> Result<String, String> {
+    use std::io::Read;
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 16];
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Health of one diarize model file on disk.
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct DiarizeModelCheck {
+    pub label: String,
+    pub present: bool,
+    /// False when the file doesn't match its expected hash. Files with no known
+    /// hash (downloaded before hashes were recorded) are assumed valid.
+    pub valid: bool,
+}
+
+fn check_model(
+    path: &Path,
+    label: &str,
+    builtin: Option<&str>,
+) -> Result<DiarizeModelCheck, String> {
+    if !path.is_file() {
+        return Ok(DiarizeModelCheck {
+            label: label.to_string(),
+            present: false,
+            valid: false,
+        });
+    }
+    let valid = match expected_sha256(path, builtin) {
+        Some(expected) => {
+            let ok = file_sha256(path)?.eq_ignore_ascii_case(&expected);
+            if !ok {
+                warn!("Diarize model '{}' does not match its checksum", label);
+            }
+            ok
+        }
+        None => {
+            warn!(
+                "No known checksum for diarize model '{}', assuming it is intact",
+                label
+            );
+            true
+        }
+    };
+    Ok(DiarizeModelCheck {
+        label: label.to_string(),
+        present: true,
+        valid,
+    })
+}
+
+/// Check both diarize models against their checksums.
+pub fn verify_models(app: &AppHandle) -> Result<Vec<DiarizeModelCheck>, String> {
+    Ok(vec![
+        check_model(
+            &segmentation_model_path(app)?,
+            "segmentation",
+            SEGMENTATION_SHA256,
+        )?,
+        check_model(&embedding_model_path(app)?, "embedding", EMBEDDING_SHA256)?,
+    ])
 }
 
+/// Download a model to `dest`, streaming it through a `.part` file so an
+/// interrupted download never leaves a file that looks installed. The hash of
+/// the finished file is recorded beside it for later verification.
 async fn download_model(
     app: &AppHandle,
     url: &str,
     dest: &Path,
     label: &str,
+    expected_sha256: Option<&str>,
 ) -> Result<(), String> {
     info!("Downloading diarize model '{}' from {}", label, url);
 
@@ -66,10 +190,42 @@ async fn download_model(
             .map_err(|e| format!("Failed to create models directory: {}", e))?;
     }
 
-    let client = reqwest::Client::builder()
-        .user_agent("handyxmutter")
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let part_path = dest.with_extension("onnx.part");
+    let result = download_to_part(app, url, &part_path, label, expected_sha256).await;
+    let (downloaded, actual) = match result {
+        Ok(done) => done,
+        Err(e) => {
+            let _ = std::fs::remove_file(&part_path);
+            return Err(e);
+        }
+    };
+
+    if let Err(e) = std::fs::rename(&part_path, dest) {
+        let _ = std::fs::remove_file(&part_path);
+        return Err(format!("Failed to move model file into place: {}", e));
+    }
+    std::fs::write(checksum_path(dest), &actual)
+        .map_err(|e| format!("Failed to record model checksum: {}", e))?;
+
+    info!(
+        "Downloaded diarize model '{}' ({} bytes)",
+        label, downloaded
+    );
+    Ok(())
+}
+
+/// Stream `url` into `part_path` and check it, returning its size and hash.
+/// The caller removes the part file if this fails.
+async fn download_to_part(
+    app: &AppHandle,
+    url: &str,
+    part_path: &Path,
+    label: &str,
+    expected_sha256: Option<&str>,
+) -> Result<(u64, String), String> {
+    use std::io::Write;
+
+    let client = crate::http::build_http_client()?;
 
     let response = client
         .get(url)
@@ -80,61 +236,160 @@ async fn download_model(
         .map_err(|e| format!("Download failed for {}: {}", label, e))?;
 
     let total_size = response.content_length().unwrap_or(0);
+    if expected_sha256.is_none() && total_size == 0 {
+        return Err(format!(
+            "Cannot verify the {} model download: no checksum and no size reported",
+            label
+        ));
+    }
     let mut stream = response.bytes_stream();
     let mut downloaded: u64 = 0;
-    let mut file_bytes: Vec<u8> = Vec::with_capacity(total_size as usize);
+    let mut hasher = Sha256::new();
+    let mut last_emit = std::time::Instant::now();
+    let throttle_duration = std::time::Duration::from_millis(100);
+    let mut file = std::io::BufWriter::new(
+        std::fs::File::create(part_path)
+            .map_err(|e| format!("Failed to create model file: {}", e))?,
+    );
 
     while let Some(chunk) = stream.next().await {
         let chunk = chunk.map_err(|e| format!("Download stream error: {}", e))?;
-        file_bytes.extend_from_slice(&chunk);
+        hasher.update(&chunk);
+        file.write_all(&chunk)
+            .map_err(|e| format!("Failed to write model file: {}", e))?;
         downloaded += chunk.len() as u64;
 
-        if total_size > 0 {
-            let progress = (downloaded as f64 / total_size as f64 * 100.0) as u32;
+        // Throttled to avoid flooding the UI
+        if last_emit.elapsed() >= throttle_duration {
             let _ = app.emit(
                 "diarize-download-progress",
+                DownloadProgress::new(label, downloaded, total_size),
+            );
+            last_emit = std::time::Instant::now();
+        }
+    }
+    let _ = app.emit(
+        "diarize-download-progress",
+        DownloadProgress::new(label, downloaded, total_size),
+    );
+    file.flush()
+        .map_err(|e| format!("Failed to write model file: {}", e))?;
+    drop(file);
+
+    if total_size > 0 && downloaded != total_size {
+        return Err(format!(
+            "Download of {} model was incomplete ({} of {} bytes)",
+            label, downloaded, total_size
+        ));
+    }
+
+    let actual = format!("{:x}", hasher.finalize());
+    match expected_sha256 {
+        Some(expected) => {
+            let ok = actual.eq_ignore_ascii_case(expected);
+            let _ = app.emit(
+                "model-verified",
                 serde_json::json!({
-                    "label": label,
-                    "progress": progress,
+                    "model_id": label,
+                    "ok": ok,
                 }),
             );
+            if !ok {
+                return Err(format!(
+                    "Checksum mismatch for {} model: the download is corrupted",
+                    label
+                ));
+            }
         }
+        None => warn!(
+            "No known checksum for diarize model '{}', recording the downloaded hash",
+            label
+        ),
     }
 
-    std::fs::write(dest, &file_bytes).map_err(|e| format!("Failed to write model file: {}", e))?;
+    Ok((downloaded, actual))
+}
+
+/// Download any model that is missing or fails its checksum. Corrupt files are
+/// deleted first so a truncated download is fetched again in full.
+pub async fn install_models(app: &AppHandle) -> Result<(), String> {
+    let models = [
+        (
+            segmentation_model_path(app)?,
+            SEGMENTATION_URL,
+            "segmentation",
+            SEGMENTATION_SHA256,
+        ),
+        (
+            embedding_model_path(app)?,
+            EMBEDDING_URL,
+            "embedding",
+            EMBEDDING_SHA256,
+        ),
+    ];
+
+    for (path, url, label, sha256) in models {
+        let check = check_model(&path, label, sha256)?;
+        if check.present && !check.valid {
+            warn!(
+                "Removing corrupt diarize model '{}' before re-downloading",
+                label
+            );
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove corrupt {} model: {}", label, e))?;
+            let _ = std::fs::remove_file(checksum_path(&path));
+        }
+        if !check.valid {
+            download_model(app, url, &path, label, sha256).await?;
+        }
+    }
 
-    info!(
-        "Downloaded diarize model '{}' ({} bytes)",
-        label,
-        file_bytes.len()
-    );
     Ok(())
 }
 
-pub async fn install_models(app: &AppHandle) -> Result<(), String> {
-    let seg_path = segmentation_model_path(app)?;
-    let emb_path = embedding_model_path(app)?;
+/// Managed cancel flag for the running diarization. It is reset when a run
+/// starts and when it ends, so a stale cancel never stops the next one.
+#[derive(Default)]
+pub struct DiarizationCancellation(Arc<AtomicBool>);
 
-    if !seg_path.exists() {
-        download_model(app, SEGMENTATION_URL, &seg_path, "segmentation").await?;
+impl DiarizationCancellation {
+    /// Clear the flag and hand out a handle for the diarization loop to check.
+    pub fn begin(&self) -> Arc<AtomicBool> {
+        self.0.store(false, Ordering::Relaxed);
+        self.0.clone()
     }
-    if !emb_path.exists() {
-        download_model(app, EMBEDDING_URL, &emb_path, "embedding").await?;
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
     }
 
-    let _ = app.emit(
-        "diarize-download-progress",
-        serde_json::json!({
-            "label": "done",
-            "progress": 100,
-        }),
-    );
+    pub fn clear(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
 
-    Ok(())
+/// Start of the error returned when a model's ORT session can't be created.
+const SESSION_INIT_FAILED: &str = "Failed to load diarization model";
+
+/// Create (and drop) a session for `model`, so a model that won't load is
+/// reported as such rather than as a segmentation failure.
+fn check_session(model: &Path) -> Result<(), String> {
+    ort::session::Session::builder()
+        .and_then(|builder| builder.commit_from_file(model))
+        .map(drop)
+        .map_err(|e| format!("{} {}: {}", SESSION_INIT_FAILED, model.display(), e))
 }
 
 /// Run speaker diarization on f32 audio samples at the given sample rate.
 /// Returns segments with speaker IDs and the audio samples for each segment.
+/// Segments shorter than `min_segment_ms` are merged into a neighbour within
+/// `merge_gap_ms` or dropped (see `merge_short_segments`).
+///
+/// This is CPU-heavy and blocking; run it off the async runtime. `on_progress`
+/// is called with `(embedded, total)` after each segment's embedding, and
+/// `cancel` is checked between segments; a cancelled run returns
+/// `AppError::Cancelled`.
+#[allow(clippy::too_many_arguments)]
 pub fn diarize_audio(
     samples: &[f32],
     sample_rate: u32,
@@ -142,13 +397,19 @@ pub fn diarize_audio(
     emb_model: &Path,
     max_speakers: usize,
     threshold: f32,
-) -> Result<Vec<RawDiarizedSegment>, String> {
+    min_segment_ms: i64,
+    merge_gap_ms: i64,
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<Vec<RawDiarizedSegment>, AppError> {
     // pyannote-rs expects i16 samples
     let i16_samples: Vec<i16> = samples
         .iter()
         .map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
         .collect();
 
+    check_session(seg_model)?;
+
     // Get speech segments
     let segments_iter = pyannote_rs::get_segments(&i16_samples, sample_rate, seg_model)
         .map_err(|e| format!("Segmentation failed: {}", e))?;
@@ -171,25 +432,53 @@ pub fn diarize_audio(
 
     // Initialize speaker embedding extractor and manager
     let mut extractor = pyannote_rs::EmbeddingExtractor::new(emb_model)
-        .map_err(|e| format!("Failed to create embedding extractor: {}", e))?;
+        .map_err(|e| format!("{} {}: {}", SESSION_INIT_FAILED, emb_model.display(), e))?;
     let mut manager = pyannote_rs::EmbeddingManager::new(max_speakers);
 
     let mut result = Vec::with_capacity(segments.len());
 
-    for segment in &segments {
+    for (index, segment) in segments.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            info!(
+                "Diarization cancelled after {}/{} segments",
+                index,
+                segments.len()
+            );
+            return Err(AppError::Cancelled);
+        }
+
         // Compute speaker embedding
         let embedding: Vec<f32> = extractor
             .compute(&segment.samples)
             .map_err(|e| format!("Embedding computation failed: {}", e))?
             .collect();
 
+        // Speakers as they were before this segment: assigning it can move the
+        // matched speaker's embedding towards the segment
+        let known_before = manager.get_all_speakers().clone();
+
         // Assign speaker
         let speaker_id = if manager.get_all_speakers().len() >= max_speakers {
             manager
                 .get_best_speaker_match(embedding.clone())
                 .unwrap_or(0)
         } else {
-            manager.search_speaker(embedding, threshold).unwrap_or(0)
+            manager
+                .search_speaker(embedding.clone(), threshold)
+                .unwrap_or(0)
+        };
+
+        // Confidence is how closely this segment matched the assigned speaker's
+        // embedding before it was assigned (a newly created speaker matches
+        // itself exactly).
+        let confidence = match known_before.get(&speaker_id) {
+            Some(known) => {
+                Some(cosine_similarity(&embedding, known.as_slice().unwrap_or(&[])).clamp(0.0, 1.0))
+            }
+            None => manager
+                .get_all_speakers()
+                .contains_key(&speaker_id)
+                .then_some(1.0),
         };
 
         // Convert i16 segment samples back to f32 for transcription
@@ -204,9 +493,14 @@ pub fn diarize_audio(
             start_ms: (segment.start * 1000.0) as i64,
             end_ms: (segment.end * 1000.0) as i64,
             samples: f32_samples,
+            confidence,
+            embedding,
         });
+        on_progress(index + 1, segments.len());
     }
 
+    let result = merge_short_segments(result, min_segment_ms, merge_gap_ms);
+
     info!(
         "Diarization complete: {} segments, {} speakers detected",
         result.len(),
@@ -216,6 +510,341 @@ pub fn diarize_audio(
     Ok(result)
 }
 
+/// Speaker embedding for a stretch of f32 audio, e.g. one segment chosen for
+/// enrolling a speaker profile.
+pub fn compute_embedding(samples: &[f32], emb_model: &Path) -> Result<Vec<f32>, String> {
+    if samples.is_empty() {
+        return Err("No audio to compute an embedding from".to_string());
+    }
+    let i16_samples: Vec<i16> = samples
+        .iter()
+        .map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+        .collect();
+    let mut extractor = pyannote_rs::EmbeddingExtractor::new(emb_model)
+        .map_err(|e| format!("{} {}: {}", SESSION_INIT_FAILED, emb_model.display(), e))?;
+    Ok(extractor
+        .compute(&i16_samples)
+        .map_err(|e| format!("Embedding computation failed: {}", e))?
+        .collect())
+}
+
+/// Mean embedding of each speaker cluster.
+pub fn speaker_centroids(segments: &[RawDiarizedSegment]) -> HashMap<i32, Vec<f32>> {
+    let mut sums: HashMap<i32, (Vec<f32>, usize)> = HashMap::new();
+    for seg in segments {
+        let Some(speaker) = seg.speaker else {
+            continue;
+        };
+        if seg.embedding.is_empty() {
+            continue;
+        }
+        let (sum, count) = sums
+            .entry(speaker)
+            .or_insert_with(|| (vec![0.0; seg.embedding.len()], 0));
+        if sum.len() != seg.embedding.len() {
+            continue;
+        }
+        for (total, value) in sum.iter_mut().zip(&seg.embedding) {
+            *total += value;
+        }
+        *count += 1;
+    }
+    sums.into_iter()
+        .map(|(speaker, (sum, count))| {
+            (speaker, sum.into_iter().map(|v| v / count as f32).collect())
+        })
+        .collect()
+}
+
+/// Name speaker clusters after the enrolled profiles they sound like. Each
+/// cluster takes the profile it is most similar to, provided the cosine
+/// similarity is at least `threshold`; the closest pairs are settled first and
+/// no profile names two clusters.
+pub fn match_speaker_profiles(
+    centroids: &HashMap<i32, Vec<f32>>,
+    profiles: &[(String, Vec<f32>)],
+    threshold: f32,
+) -> HashMap<i32, String> {
+    let mut pairs: Vec<(f32, i32, usize)> = centroids
+        .iter()
+        .flat_map(|(speaker, centroid)| {
+            profiles.iter().enumerate().map(move |(i, (_, embedding))| {
+                (cosine_similarity(centroid, embedding), *speaker, i)
+            })
+        })
+        .filter(|(similarity, _, _)| *similarity >= threshold)
+        .collect();
+    pairs.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    let mut names = HashMap::new();
+    let mut used = HashSet::new();
+    for (_, speaker, profile) in pairs {
+        if names.contains_key(&speaker) || used.contains(&profile) {
+            continue;
+        }
+        used.insert(profile);
+        names.insert(speaker, profiles[profile].0.clone());
+    }
+    names
+}
+
+/// Merge segments shorter than `min_segment_ms` into an adjacent segment from
+/// the same speaker, provided the silence between them is at most
+/// `merge_gap_ms`. A short segment never joins a segment on the far side of
+/// another speaker's turn. Short segments with no such neighbour are dropped.
+pub fn merge_short_segments(
+    mut segments: Vec<RawDiarizedSegment>,
+    min_segment_ms: i64,
+    merge_gap_ms: i64,
+) -> Vec<RawDiarizedSegment> {
+    if min_segment_ms <= 0 {
+        return segments;
+    }
+
+    segments.sort_by_key(|s| s.start_ms);
+    let kept: Vec<bool> = segments
+        .iter()
+        .map(|s| s.end_ms - s.start_ms >= min_segment_ms)
+        .collect();
+
+    // The first long segment in `order` from the same speaker, looking past
+    // that speaker's other short segments but not past anyone else's
+    fn same_speaker_neighbour(
+        segments: &[RawDiarizedSegment],
+        kept: &[bool],
+        speaker: Option<i32>,
+        order: impl Iterator<Item = usize>,
+    ) -> Option<usize> {
+        order
+            .take_while(|&j| segments[j].speaker == speaker)
+            .find(|&j| kept[j])
+    }
+
+    let mut targets = Vec::new();
+    let mut dropped = 0;
+    for i in (0..segments.len()).filter(|&i| !kept[i]) {
+        let speaker = segments[i].speaker;
+        let before = same_speaker_neighbour(&segments, &kept, speaker, (0..i).rev());
+        let after = same_speaker_neighbour(&segments, &kept, speaker, i + 1..segments.len());
+        let nearest = [before, after]
+            .into_iter()
+            .flatten()
+            .map(|j| (segment_gap_ms(&segments[j], &segments[i]), j))
+            .filter(|(gap, _)| *gap <= merge_gap_ms)
+            .min_by_key(|(gap, _)| *gap);
+        match nearest {
+            Some((_, target)) => targets.push((i, target)),
+            None => dropped += 1,
+        }
+    }
+
+    // Closest first, so audio on either side of a target stays in order
+    targets.sort_by_key(|&(i, target)| (target, i.abs_diff(target)));
+    for &(i, target) in &targets {
+        let samples = std::mem::take(&mut segments[i].samples);
+        let (start_ms, end_ms) = (segments[i].start_ms, segments[i].end_ms);
+        let target = &mut segments[target];
+        if start_ms < target.start_ms {
+            target.samples.splice(0..0, samples);
+        } else {
+            target.samples.extend(samples);
+        }
+        target.start_ms = target.start_ms.min(start_ms);
+        target.end_ms = target.end_ms.max(end_ms);
+    }
+
+    if !targets.is_empty() || dropped > 0 {
+        info!(
+            "Short segment cleanup (< {}ms, gap <= {}ms): merged {}, dropped {}",
+            min_segment_ms,
+            merge_gap_ms,
+            targets.len(),
+            dropped
+        );
+    }
+
+    segments
+        .into_iter()
+        .zip(kept)
+        .filter_map(|(segment, kept)| kept.then_some(segment))
+        .collect()
+}
+
+/// Runs of consecutive segments from the same speaker separated by at most
+/// `max_gap_ms` of silence. `segments` must be sorted by start time; the
+/// returned index ranges cover every segment, including single-segment runs.
+pub fn same_speaker_runs(
+    segments: &[DiarizedSegment],
+    max_gap_ms: i64,
+) -> Vec<std::ops::Range<usize>> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..=segments.len() {
+        let continues = i < segments.len() && {
+            let (prev, cur) = (&segments[i - 1], &segments[i]);
+            prev.speaker == cur.speaker && cur.start_ms - prev.end_ms <= max_gap_ms
+        };
+        if !continues {
+            runs.push(start..i);
+            start = i;
+        }
+    }
+    runs
+}
+
+/// Flatten segments into the entry transcript, one `[Name] text` line each.
+/// Speakers without an entry in `names` are labelled `[Speaker N]`.
+pub fn segments_to_flat_text(
+    segments: &[DiarizedSegment],
+    names: &HashMap<String, String>,
+) -> String {
+    segments
+        .iter()
+        .filter(|seg| !seg.text.trim().is_empty())
+        .map(|seg| {
+            let label = match seg.speaker {
+                Some(s) => match names.get(&s.to_string()).map(|n| n.trim()) {
+                    Some(name) if !name.is_empty() => format!("[{}]", name),
+                    _ => format!("[Speaker {}]", s),
+                },
+                None => "[Unknown]".to_string(),
+            };
+            format!("{} {}", label, seg.text.trim())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Map speaker IDs from a fresh diarization run onto the IDs of a previous one,
+/// pairing clusters greedily by how much speaking time they share. New clusters
+/// with no counterpart get IDs above every old one, so they never inherit a
+/// name that belonged to someone else.
+pub fn map_speakers_by_overlap(
+    old: &[DiarizedSegment],
+    new: &[RawDiarizedSegment],
+) -> HashMap<i32, i32> {
+    let mut overlaps: HashMap<(i32, i32), i64> = HashMap::new();
+    for n in new {
+        let Some(new_speaker) = n.speaker else {
+            continue;
+        };
+        for o in old {
+            let Some(old_speaker) = o.speaker else {
+                continue;
+            };
+            let overlap = n.end_ms.min(o.end_ms) - n.start_ms.max(o.start_ms);
+            if overlap > 0 {
+                *overlaps.entry((new_speaker, old_speaker)).or_default() += overlap;
+            }
+        }
+    }
+
+    let mut pairs: Vec<((i32, i32), i64)> = overlaps.into_iter().collect();
+    // Largest overlap first; ties broken by ID so the result is deterministic
+    pairs.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut mapping = HashMap::new();
+    let mut taken_old = HashSet::new();
+    for ((new_speaker, old_speaker), _) in pairs {
+        if mapping.contains_key(&new_speaker) || taken_old.contains(&old_speaker) {
+            continue;
+        }
+        mapping.insert(new_speaker, old_speaker);
+        taken_old.insert(old_speaker);
+    }
+
+    let mut next_id = old.iter().filter_map(|s| s.speaker).max().unwrap_or(-1) + 1;
+    let mut unmatched: Vec<i32> = new
+        .iter()
+        .filter_map(|s| s.speaker)
+        .filter(|s| !mapping.contains_key(s))
+        .collect();
+    unmatched.sort_unstable();
+    unmatched.dedup();
+    for speaker in unmatched {
+        mapping.insert(speaker, next_id);
+        next_id += 1;
+    }
+
+    mapping
+}
+
+/// The old segment covering the same time range as `start_ms..end_ms`, with
+/// both ends within `tolerance_ms`.
+pub fn find_matching_segment(
+    old: &[DiarizedSegment],
+    start_ms: i64,
+    end_ms: i64,
+    tolerance_ms: i64,
+) -> Option<&DiarizedSegment> {
+    old.iter().find(|o| {
+        (o.start_ms - start_ms).abs() <= tolerance_ms && (o.end_ms - end_ms).abs() <= tolerance_ms
+    })
+}
+
+/// Divide a segment's text in two. With `at_char` the text is cut at that
+/// character index (moved back to the nearest word boundary); otherwise the
+/// words are shared out by `fraction`, the share of the segment's time that
+/// goes to the first half.
+pub fn split_segment_text(text: &str, at_char: Option<usize>, fraction: f64) -> (String, String) {
+    let text = text.trim();
+    let cut = match at_char {
+        Some(index) => {
+            let byte = text
+                .char_indices()
+                .nth(index)
+                .map(|(b, _)| b)
+                .unwrap_or(text.len());
+            // Don't cut through a word
+            if text[byte..].starts_with(char::is_whitespace) || byte == text.len() {
+                byte
+            } else {
+                text[..byte].rfind(char::is_whitespace).unwrap_or(byte)
+            }
+        }
+        None => {
+            let words: Vec<(usize, &str)> = text
+                .split_whitespace()
+                .map(|w| (w.as_ptr() as usize - text.as_ptr() as usize, w))
+                .collect();
+            let first_count = ((words.len() as f64) * fraction.clamp(0.0, 1.0)).round() as usize;
+            words
+                .get(first_count)
+                .map(|(offset, _)| *offset)
+                .unwrap_or(text.len())
+        }
+    };
+    (
+        text[..cut].trim().to_string(),
+        text[cut..].trim().to_string(),
+    )
+}
+
+/// Silence between two segments in milliseconds (0 if they overlap).
+fn segment_gap_ms(a: &RawDiarizedSegment, b: &RawDiarizedSegment) -> i64 {
+    if a.end_ms <= b.start_ms {
+        b.start_ms - a.end_ms
+    } else if b.end_ms <= a.start_ms {
+        a.start_ms - b.end_ms
+    } else {
+        0
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
 /// Get the segmentation model path (for use in commands).
 pub fn get_seg_model_path(app: &AppHandle) -> Result<PathBuf, String> {
     segmentation_model_path(app)
@@ -225,3 +854,184 @@ pub fn get_seg_model_path(app: &AppHandle) -> Result<PathBuf, String> {
 pub fn get_emb_model_path(app: &AppHandle) -> Result<PathBuf, String> {
     embedding_model_path(app)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(speaker: Option<i32>, start_ms: i64, end_ms: i64, text: &str) -> DiarizedSegment {
+        DiarizedSegment {
+            id: None,
+            speaker,
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_same_speaker_runs_respects_gap() {
+        let segments = vec![
+            segment(Some(0), 0, 1000, "a"),
+            segment(Some(0), 1500, 2000, "b"),
+            segment(Some(0), 4000, 5000, "c"), // 2s gap
+            segment(Some(1), 5000, 6000, "d"),
+            segment(None, 6000, 7000, "e"),
+            segment(None, 7000, 8000, "f"),
+        ];
+        assert_eq!(
+            same_speaker_runs(&segments, 1000),
+            vec![0..2, 2..3, 3..4, 4..6]
+        );
+        assert!(same_speaker_runs(&[], 1000).is_empty());
+    }
+
+    #[test]
+    fn test_segments_to_flat_text() {
+        let segments = vec![
+            segment(Some(1), 0, 1000, " hello "),
+            segment(None, 1000, 2000, "hi"),
+            segment(Some(2), 2000, 3000, "  "),
+        ];
+        assert_eq!(
+            segments_to_flat_text(&segments, &HashMap::new()),
+            "[Speaker 1] hello\n[Unknown] hi"
+        );
+        let names = HashMap::from([("1".to_string(), "Priya".to_string())]);
+        assert_eq!(
+            segments_to_flat_text(&segments, &names),
+            "[Priya] hello\n[Unknown] hi"
+        );
+    }
+
+    fn raw(speaker: i32, start_ms: i64, end_ms: i64) -> RawDiarizedSegment {
+        RawDiarizedSegment {
+            speaker: Some(speaker),
+            start_ms,
+            end_ms,
+            samples: vec![],
+            confidence: None,
+            embedding: vec![],
+        }
+    }
+
+    #[test]
+    fn test_merge_short_segments_into_same_speaker() {
+        let mut cough = raw(0, 2100, 2300);
+        cough.samples = vec![2.0];
+        let mut first = raw(0, 0, 2000);
+        first.samples = vec![1.0];
+        let segments = vec![first, cough, raw(1, 2500, 4000)];
+
+        let merged = merge_short_segments(segments, 250, 1000);
+        assert_eq!(merged.len(), 2);
+        assert_eq!((merged[0].start_ms, merged[0].end_ms), (0, 2300));
+        assert_eq!(merged[0].samples, vec![1.0, 2.0]);
+        assert_eq!(merged[1].speaker, Some(1));
+    }
+
+    #[test]
+    fn test_merge_short_segments_drops_beyond_gap() {
+        let segments = vec![
+            raw(0, 0, 2000),
+            raw(0, 5000, 5100), // 3s from speaker 0's other segment
+            raw(1, 5200, 5300), // speaker 1 has nothing else
+            raw(1, 9000, 9100), // also short, so not a merge target
+        ];
+        let cleaned = merge_short_segments(segments, 250, 1000);
+        assert_eq!(cleaned.len(), 1);
+        assert_eq!((cleaned[0].start_ms, cleaned[0].end_ms), (0, 2000));
+
+        let segments = vec![raw(0, 0, 2000), raw(0, 5000, 5100)];
+        let merged = merge_short_segments(segments, 250, 5000);
+        assert_eq!((merged[0].start_ms, merged[0].end_ms), (0, 5100));
+    }
+
+    #[test]
+    fn test_merge_short_segments_stays_within_a_turn() {
+        // Speaker 1 talks between speaker 0's long segment and the cough
+        let segments = vec![
+            raw(0, 0, 2000),
+            raw(1, 2100, 3000),
+            raw(0, 3100, 3200),
+            raw(0, 3300, 3400),
+            raw(0, 3500, 6000),
+        ];
+        let merged = merge_short_segments(segments, 250, 1000);
+        assert_eq!(merged.len(), 3);
+        assert_eq!((merged[0].start_ms, merged[0].end_ms), (0, 2000));
+        assert_eq!((merged[2].start_ms, merged[2].end_ms), (3100, 6000));
+
+        let segments = vec![raw(0, 0, 2000), raw(1, 2100, 3000), raw(0, 3100, 3200)];
+        assert_eq!(merge_short_segments(segments, 250, 5000).len(), 2);
+    }
+
+    #[test]
+    fn test_merge_short_segments_disabled() {
+        let segments = vec![raw(0, 0, 100), raw(1, 200, 300)];
+        assert_eq!(merge_short_segments(segments, 0, 1000).len(), 2);
+    }
+
+    #[test]
+    fn test_match_speaker_profiles() {
+        let mut a = raw(0, 0, 1000);
+        a.embedding = vec![1.0, 0.0];
+        let mut b = raw(0, 1000, 2000);
+        b.embedding = vec![0.8, 0.2];
+        let mut c = raw(1, 2000, 3000);
+        c.embedding = vec![0.0, 1.0];
+        let centroids = speaker_centroids(&[a, b, c]);
+        assert_eq!(centroids[&0], vec![0.9, 0.1]);
+
+        let profiles = vec![
+            ("Alice".to_string(), vec![1.0, 0.1]),
+            // Matches nobody above the threshold
+            ("Bob".to_string(), vec![-1.0, 0.0]),
+            ("Carol".to_string(), vec![0.1, 1.0]),
+        ];
+        let names = match_speaker_profiles(&centroids, &profiles, 0.9);
+        assert_eq!(names.get(&0).map(String::as_str), Some("Alice"));
+        assert_eq!(names.get(&1).map(String::as_str), Some("Carol"));
+        assert_eq!(names.len(), 2);
+    }
+
+    #[test]
+    fn test_map_speakers_by_overlap() {
+        let old = vec![
+            segment(Some(0), 0, 4000, "alice"),
+            segment(Some(3), 4000, 8000, "bob"),
+        ];
+        // The new run swapped the cluster numbering and found a third voice
+        let new = vec![raw(1, 0, 3800), raw(0, 4100, 8000), raw(2, 9000, 10000)];
+        let mapping = map_speakers_by_overlap(&old, &new);
+        assert_eq!(mapping.get(&1), Some(&0));
+        assert_eq!(mapping.get(&0), Some(&3));
+        assert_eq!(mapping.get(&2), Some(&4));
+    }
+
+    #[test]
+    fn test_split_segment_text() {
+        let text = "one two three four";
+        assert_eq!(
+            split_segment_text(text, None, 0.5),
+            ("one two".to_string(), "three four".to_string())
+        );
+        // Index 5 falls inside "two", so the cut moves back to the space before it
+        assert_eq!(
+            split_segment_text(text, Some(5), 0.0),
+            ("one".to_string(), "two three four".to_string())
+        );
+        assert_eq!(
+            split_segment_text(text, Some(100), 0.0),
+            (text.to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn test_find_matching_segment_uses_tolerance() {
+        let old = vec![segment(Some(0), 1000, 2000, "edited")];
+        assert!(find_matching_segment(&old, 1200, 1900, 250).is_some());
+        assert!(find_matching_segment(&old, 1300, 2000, 250).is_none());
+    }
+}
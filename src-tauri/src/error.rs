@@ -0,0 +1,115 @@
+//! Structured errors returned by Tauri commands.
+//!
+//! Serialized as `{ kind, message? }` so the frontend can branch on `kind`
+//! instead of matching error strings. Commands are being moved over from
+//! `Result<T, String>` gradually; unmigrated ones keep `.map_err(|e| e.to_string())`.
+
+use serde::Serialize;
+use specta::Type;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(tag = "kind", content = "message")]
+pub enum AppError {
+    /// The requested entry, segment or profile doesn't exist.
+    NotFound,
+    /// The request itself was malformed (bad range, missing field, ...).
+    InvalidInput(String),
+    Io(String),
+    Database(String),
+    Transcription(String),
+    LlmClient(String),
+    /// The user cancelled the operation.
+    Cancelled,
+    Other(String),
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::NotFound => write!(f, "Not found"),
+            AppError::Cancelled => write!(f, "Cancelled"),
+            AppError::InvalidInput(msg)
+            | AppError::Io(msg)
+            | AppError::Database(msg)
+            | AppError::Transcription(msg)
+            | AppError::LlmClient(msg)
+            | AppError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::Io(e.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            rusqlite::Error::QueryReturnedNoRows => AppError::NotFound,
+            e => AppError::Database(e.to_string()),
+        }
+    }
+}
+
+/// Managers report errors through anyhow; recover the kind from the root cause
+/// where we can.
+impl From<anyhow::Error> for AppError {
+    fn from(e: anyhow::Error) -> Self {
+        if let Some(app_error) = e.downcast_ref::<AppError>() {
+            return app_error.clone();
+        }
+        if let Some(sql) = e.downcast_ref::<rusqlite::Error>() {
+            return match sql {
+                rusqlite::Error::QueryReturnedNoRows => AppError::NotFound,
+                _ => AppError::Database(e.to_string()),
+            };
+        }
+        if e.downcast_ref::<std::io::Error>().is_some() {
+            return AppError::Io(e.to_string());
+        }
+        AppError::Other(e.to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+impl From<crate::managers::transcription::TranscriptionError> for AppError {
+    fn from(e: crate::managers::transcription::TranscriptionError) -> Self {
+        AppError::Transcription(e.to_string())
+    }
+}
+
+/// Lets helpers that still return `Result<_, String>` be used with `?`.
+impl From<String> for AppError {
+    fn from(e: String) -> Self {
+        AppError::Other(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anyhow_errors_keep_their_kind() {
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        assert!(matches!(
+            AppError::from(anyhow::Error::new(io)),
+            AppError::Io(_)
+        ));
+
+        let missing = anyhow::Error::new(rusqlite::Error::QueryReturnedNoRows);
+        assert_eq!(AppError::from(missing), AppError::NotFound);
+
+        let wrapped = anyhow::Error::new(AppError::Cancelled).context("while diarizing");
+        assert_eq!(AppError::from(wrapped), AppError::Cancelled);
+
+        assert_eq!(
+            AppError::from(anyhow::anyhow!("boom")),
+            AppError::Other("boom".to_string())
+        );
+    }
+}
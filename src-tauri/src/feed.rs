@@ -0,0 +1,160 @@
+use chrono::DateTime;
+
+/// Channel-level details of a podcast feed.
+pub struct PodcastChannel<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    /// URL the audio files are served from; also used as the channel link.
+    pub base_url: &'a str,
+}
+
+/// One episode, backed by a WAV file published under the channel's `base_url`.
+pub struct PodcastItem<'a> {
+    pub title: &'a str,
+    pub file_name: &'a str,
+    pub length_bytes: u64,
+    pub description: &'a str,
+    /// Unix seconds.
+    pub timestamp: i64,
+    pub tags: &'a [String],
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab/newline are not allowed in XML 1.0
+            c if c.is_control() && c != '\t' && c != '\n' && c != '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Percent-encode a file name for use as the last segment of a URL.
+fn encode_path_segment(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Audio URL of an episode: `base_url` joined with the encoded file name.
+pub fn episode_url(base_url: &str, file_name: &str) -> String {
+    format!(
+        "{}/{}",
+        base_url.trim_end_matches('/'),
+        encode_path_segment(file_name)
+    )
+}
+
+/// Render an RSS 2.0 feed with iTunes tags. Entry tags become
+/// `<itunes:keywords>`.
+pub fn build_podcast_feed(channel: &PodcastChannel, items: &[PodcastItem]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(
+        "<rss version=\"2.0\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\">\n",
+    );
+    xml.push_str("  <channel>\n");
+    xml.push_str(&format!(
+        "    <title>{}</title>\n",
+        escape_xml(channel.title)
+    ));
+    xml.push_str(&format!(
+        "    <link>{}</link>\n",
+        escape_xml(channel.base_url)
+    ));
+    xml.push_str(&format!(
+        "    <description>{}</description>\n",
+        escape_xml(channel.description)
+    ));
+    xml.push_str("    <itunes:block>Yes</itunes:block>\n");
+
+    for item in items {
+        let url = episode_url(channel.base_url, item.file_name);
+        xml.push_str("    <item>\n");
+        xml.push_str(&format!(
+            "      <title>{}</title>\n",
+            escape_xml(item.title)
+        ));
+        xml.push_str(&format!(
+            "      <description>{}</description>\n",
+            escape_xml(item.description)
+        ));
+        xml.push_str(&format!(
+            "      <enclosure url=\"{}\" length=\"{}\" type=\"audio/wav\"/>\n",
+            escape_xml(&url),
+            item.length_bytes
+        ));
+        xml.push_str(&format!(
+            "      <guid isPermaLink=\"false\">{}</guid>\n",
+            escape_xml(&url)
+        ));
+        if let Some(date) = DateTime::from_timestamp(item.timestamp, 0) {
+            xml.push_str(&format!("      <pubDate>{}</pubDate>\n", date.to_rfc2822()));
+        }
+        if !item.tags.is_empty() {
+            xml.push_str(&format!(
+                "      <itunes:keywords>{}</itunes:keywords>\n",
+                escape_xml(&item.tags.join(","))
+            ));
+        }
+        xml.push_str("    </item>\n");
+    }
+
+    xml.push_str("  </channel>\n");
+    xml.push_str("</rss>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_item_fields() {
+        let tags = vec!["ideas".to_string(), "walks".to_string()];
+        let items = [PodcastItem {
+            title: "Morning <walk>",
+            file_name: "Morning walk.wav",
+            length_bytes: 1024,
+            description: "Trees & birds",
+            timestamp: 1_709_285_400,
+            tags: &tags,
+        }];
+        let channel = PodcastChannel {
+            title: "Memos",
+            description: "Private feed",
+            base_url: "https://example.com/memos/",
+        };
+        let xml = build_podcast_feed(&channel, &items);
+
+        assert!(xml.contains("<title>Morning &lt;walk&gt;</title>"));
+        assert!(xml.contains("<description>Trees &amp; birds</description>"));
+        assert!(xml.contains(
+            "<enclosure url=\"https://example.com/memos/Morning%20walk.wav\" length=\"1024\" type=\"audio/wav\"/>"
+        ));
+        assert!(xml.contains("Mar 2024 09:30:00 +0000</pubDate>"));
+        assert!(xml.contains("<itunes:keywords>ideas,walks</itunes:keywords>"));
+    }
+
+    #[test]
+    fn test_episode_url_encodes_file_name() {
+        assert_eq!(
+            episode_url("https://host/feed", "Café (2).wav"),
+            "https://host/feed/Caf%C3%A9%20%282%29.wav"
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use log::{debug, info};
+use std::ffi::OsString;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Sample rate ffmpeg is asked to produce, matching the transcription engines.
+pub const OUTPUT_SAMPLE_RATE: u32 = 16000;
+
+const BINARY_NAME: &str = if cfg!(windows) {
+    "ffmpeg.exe"
+} else {
+    "ffmpeg"
+};
+
+/// Resolve the ffmpeg binary: the configured path if it exists, otherwise the
+/// first `ffmpeg` found on PATH.
+pub fn find_ffmpeg(configured: Option<&str>) -> Option<PathBuf> {
+    if let Some(path) = configured.map(str::trim).filter(|p| !p.is_empty()) {
+        let path = PathBuf::from(path);
+        return path.is_file().then_some(path);
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(BINARY_NAME))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// Arguments that decode the first audio stream of `input` to raw mono
+/// 16kHz f32 little-endian PCM on stdout.
+fn build_args(input: &Path) -> Vec<OsString> {
+    let mut args: Vec<OsString> = ["-nostdin", "-hide_banner", "-loglevel", "error", "-i"]
+        .iter()
+        .map(OsString::from)
+        .collect();
+    args.push(input.as_os_str().to_owned());
+    args.extend(
+        [
+            "-vn",
+            "-ac",
+            "1",
+            "-ar",
+            &OUTPUT_SAMPLE_RATE.to_string(),
+            "-f",
+            "f32le",
+            "-",
+        ]
+        .iter()
+        .map(OsString::from),
+    );
+    args
+}
+
+/// Interpret raw f32le bytes as samples. A trailing partial sample is ignored.
+fn parse_f32le(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+/// Decode any file ffmpeg understands to mono samples at `OUTPUT_SAMPLE_RATE`.
+/// Setting `cancel` kills ffmpeg; on failure its stderr is returned.
+pub fn decode_to_mono_16k(
+    ffmpeg: &Path,
+    input: &Path,
+    cancel: &AtomicBool,
+) -> Result<Vec<f32>, String> {
+    info!(
+        "Decoding {} with ffmpeg ({})",
+        input.display(),
+        ffmpeg.display()
+    );
+
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(build_args(input))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(windows)]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    // Drain stderr on its own thread so a chatty ffmpeg can't block on a full pipe
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stderr.read_to_string(&mut buf);
+        buf
+    });
+
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let mut bytes = Vec::new();
+    let mut chunk = vec![0u8; 64 * 1024];
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("Cancelled".to_string());
+        }
+        match stdout.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => bytes.extend_from_slice(&chunk[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                let _ = child.kill();
+                return Err(format!("Failed to read ffmpeg output: {}", e));
+            }
+        }
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for ffmpeg: {}", e))?;
+    let stderr_output = stderr_reader.join().unwrap_or_default();
+
+    if !status.success() {
+        return Err(format!("ffmpeg failed: {}", stderr_output.trim()));
+    }
+
+    let samples = parse_f32le(&bytes);
+    debug!("ffmpeg produced {} samples", samples.len());
+    if samples.is_empty() {
+        return Err("ffmpeg produced no audio".to_string());
+    }
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_args_requests_mono_16k_f32_on_stdout() {
+        let args = build_args(Path::new("/tmp/clip.mov"));
+        let args: Vec<String> = args
+            .iter()
+            .map(|a| a.to_string_lossy().to_string())
+            .collect();
+
+        let input_pos = args.iter().position(|a| a == "-i").unwrap();
+        assert_eq!(args[input_pos + 1], "/tmp/clip.mov");
+        assert!(args.windows(2).any(|w| w == ["-ac", "1"]));
+        assert!(args.windows(2).any(|w| w == ["-ar", "16000"]));
+        assert!(args.windows(2).any(|w| w == ["-f", "f32le"]));
+        assert_eq!(args.last().map(String::as_str), Some("-"));
+    }
+
+    #[test]
+    fn test_parse_f32le_fixture() {
+        let fixture: Vec<u8> = [0.0f32, 0.5, -1.0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .chain([0xAB, 0xCD]) // truncated trailing sample
+            .collect();
+        assert_eq!(parse_f32le(&fixture), vec![0.0, 0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_find_ffmpeg_rejects_missing_configured_path() {
+        assert_eq!(find_ffmpeg(Some("/nonexistent/ffmpeg")), None);
+    }
+}
@@ -0,0 +1,228 @@
+//! Commit journal files to a git repository, for users who keep their journal
+//! (or the Obsidian vault it mirrors into) under version control.
+//!
+//! Only `git add` and `git commit` are run; pushing is left to the user. Paths
+//! outside the repository are skipped and `.gitignore` is respected.
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use specta::Type;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::Mutex;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Saves can overlap; git refuses to run while another process holds the index lock.
+static GIT_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, Default, Serialize, Type)]
+pub struct GitSyncStatus {
+    /// False when there was nothing new to commit.
+    pub committed: bool,
+    pub files_changed: usize,
+    /// Hash of the new commit, if one was made.
+    pub commit: Option<String>,
+}
+
+fn git(repo: &Path, args: &[&str]) -> Result<Output> {
+    let mut cmd = Command::new("git");
+    cmd.arg("-C").arg(repo).args(args);
+
+    #[cfg(windows)]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    cmd.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            anyhow!("Git sync needs git, but it was not found on PATH")
+        } else {
+            anyhow!("Failed to run git: {}", e)
+        }
+    })
+}
+
+fn git_checked(repo: &Path, args: &[&str]) -> Result<String> {
+    let output = git(repo, args)?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Top level of the work tree containing `repo_path`.
+fn repo_root(repo_path: &Path) -> Result<PathBuf> {
+    if !repo_path.is_dir() {
+        return Err(anyhow!(
+            "Git sync repository does not exist: {}",
+            repo_path.display()
+        ));
+    }
+    let root = git_checked(repo_path, &["rev-parse", "--show-toplevel"])
+        .map_err(|_| anyhow!("Not a git repository: {}", repo_path.display()))?;
+    Ok(PathBuf::from(root.trim()).canonicalize()?)
+}
+
+/// `path` relative to `root`, or None if it lies outside the repository.
+/// Paths that no longer exist (renamed or deleted files) are resolved through
+/// their parent directory.
+fn relative_to(root: &Path, path: &Path) -> Option<PathBuf> {
+    let resolved = match path.canonicalize() {
+        Ok(p) => p,
+        Err(_) => path.parent()?.canonicalize().ok()?.join(path.file_name()?),
+    };
+    resolved.strip_prefix(root).ok().map(Path::to_path_buf)
+}
+
+/// Stage `files_changed` (files or directories, including deletions under
+/// them) and commit just those paths with `message`. Files outside the
+/// repository or ignored by `.gitignore` are skipped; other staged changes in
+/// the repository are left out of the commit.
+pub fn commit(repo_path: &Path, files_changed: &[PathBuf], message: &str) -> Result<GitSyncStatus> {
+    let _guard = GIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let root = repo_root(repo_path)?;
+
+    let mut pathspecs: Vec<String> = Vec::new();
+    for path in files_changed {
+        let Some(rel) = relative_to(&root, path) else {
+            log::debug!("Git sync: {} is outside the repository", path.display());
+            continue;
+        };
+        let rel = if rel.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            rel.to_string_lossy().into_owned()
+        };
+        // Naming an ignored file explicitly makes `git add` fail
+        if path.is_file()
+            && git(&root, &["check-ignore", "-q", "--", &rel])?
+                .status
+                .success()
+        {
+            continue;
+        }
+        pathspecs.push(rel);
+    }
+    if pathspecs.is_empty() {
+        return Ok(GitSyncStatus::default());
+    }
+
+    let mut add_args = vec!["add", "-A", "--ignore-errors", "--"];
+    add_args.extend(pathspecs.iter().map(String::as_str));
+    git_checked(&root, &add_args)?;
+
+    // NUL-separated, so names outside ASCII come back verbatim instead of
+    // quoted and escaped (`core.quotePath`), and can be passed back as paths
+    let mut diff_args = vec!["diff", "--cached", "--name-only", "-z", "--"];
+    diff_args.extend(pathspecs.iter().map(String::as_str));
+    let staged: Vec<String> = git_checked(&root, &diff_args)?
+        .split('\0')
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect();
+    if staged.is_empty() {
+        return Ok(GitSyncStatus::default());
+    }
+
+    let mut commit_args = vec!["commit", "--quiet", "-m", message, "--only", "--"];
+    commit_args.extend(staged.iter().map(String::as_str));
+    git_checked(&root, &commit_args)?;
+
+    let hash = git_checked(&root, &["rev-parse", "HEAD"])?;
+    log::info!("Git sync: committed {} file(s)", staged.len());
+
+    Ok(GitSyncStatus {
+        committed: true,
+        files_changed: staged.len(),
+        commit: Some(hash.trim().to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A fresh repository with an identity, so commits work on CI machines.
+    fn test_repo(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("git-sync-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        git_checked(&dir, &["init", "--quiet"]).unwrap();
+        git_checked(&dir, &["config", "user.name", "Test"]).unwrap();
+        git_checked(&dir, &["config", "user.email", "test@example.com"]).unwrap();
+        git_checked(&dir, &["config", "commit.gpgsign", "false"]).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_commit_only_includes_the_given_paths() {
+        let repo = test_repo("paths");
+        fs::write(repo.join(".gitignore"), "*.wav\n").unwrap();
+        fs::write(repo.join("entry.md"), "hello").unwrap();
+        fs::write(repo.join("entry.wav"), "audio").unwrap();
+        fs::write(repo.join("other.md"), "staged by the user").unwrap();
+        git_checked(&repo, &["add", "other.md"]).unwrap();
+
+        let status = commit(
+            &repo,
+            &[repo.join("entry.md"), repo.join("entry.wav")],
+            "Add entry",
+        )
+        .unwrap();
+        assert!(status.committed);
+        assert_eq!(status.files_changed, 1);
+
+        let committed =
+            git_checked(&repo, &["show", "--name-only", "--format=%s", "HEAD"]).unwrap();
+        assert_eq!(
+            committed.lines().collect::<Vec<_>>(),
+            ["Add entry", "", "entry.md"]
+        );
+        // The user's own staged change is left alone
+        let staged = git_checked(&repo, &["diff", "--cached", "--name-only"]).unwrap();
+        assert_eq!(staged.trim(), "other.md");
+
+        let _ = fs::remove_dir_all(&repo);
+    }
+
+    #[test]
+    fn test_commit_handles_non_ascii_file_names() {
+        let repo = test_repo("unicode");
+        let name = "Café 日記.md";
+        fs::write(repo.join(name), "bonjour").unwrap();
+
+        let status = commit(&repo, &[repo.join(name)], "Add entry").unwrap();
+        assert!(status.committed);
+        assert_eq!(status.files_changed, 1);
+
+        let committed =
+            git_checked(&repo, &["show", "--name-only", "-z", "--format=", "HEAD"]).unwrap();
+        assert_eq!(committed.trim_matches(['\n', '\0']), name);
+
+        let _ = fs::remove_dir_all(&repo);
+    }
+
+    #[test]
+    fn test_commit_with_nothing_new_is_a_no_op() {
+        let repo = test_repo("noop");
+        fs::write(repo.join("entry.md"), "hello").unwrap();
+        assert!(commit(&repo, &[repo.clone()], "First").unwrap().committed);
+
+        let status = commit(&repo, &[repo.clone()], "Second").unwrap();
+        assert!(!status.committed);
+        assert!(status.commit.is_none());
+
+        let outside = std::env::temp_dir().join("git-sync-outside.md");
+        assert!(!commit(&repo, &[outside], "Third").unwrap().committed);
+
+        let _ = fs::remove_dir_all(&repo);
+    }
+}
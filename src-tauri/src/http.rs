@@ -0,0 +1,60 @@
+//! Shared HTTP client setup, so every outbound request honours the user's proxy.
+//!
+//! The `http_proxy` setting (http://, https:// or socks5:// URL) takes
+//! precedence; without it reqwest falls back to the standard `HTTPS_PROXY`,
+//! `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables.
+
+use std::sync::RwLock;
+
+/// Proxy from settings, mirrored here so clients can be built without an
+/// `AppHandle`. Updated at startup and when the setting changes.
+static PROXY: RwLock<Option<String>> = RwLock::new(None);
+
+const USER_AGENT: &str = "handyxmutter";
+
+pub fn set_proxy(proxy: Option<String>) {
+    *PROXY.write().unwrap() = proxy.filter(|p| !p.trim().is_empty());
+}
+
+pub fn proxy() -> Option<String> {
+    PROXY.read().unwrap().clone()
+}
+
+/// Check that `url` can be used as a proxy.
+pub fn validate_proxy(url: &str) -> Result<(), String> {
+    reqwest::Proxy::all(url)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid proxy URL: {}", e))
+}
+
+/// A client builder with the proxy and user agent applied, for callers that
+/// add their own headers or timeouts.
+pub fn http_client_builder() -> reqwest::ClientBuilder {
+    let builder = reqwest::Client::builder().user_agent(USER_AGENT);
+    match proxy() {
+        Some(url) => match reqwest::Proxy::all(&url) {
+            Ok(proxy) => builder.proxy(proxy),
+            Err(e) => {
+                log::warn!("Ignoring invalid proxy {}: {}", url, e);
+                builder
+            }
+        },
+        None => builder,
+    }
+}
+
+pub fn build_http_client() -> Result<reqwest::Client, String> {
+    http_client_builder()
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Arguments that pass the configured proxy on to yt-dlp. yt-dlp reads the
+/// proxy environment variables itself.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub fn ytdlp_proxy_args() -> Vec<String> {
+    match proxy() {
+        Some(url) => vec!["--proxy".to_string(), url],
+        None => Vec::new(),
+    }
+}
@@ -0,0 +1,67 @@
+//! Audio shared to the app from the iOS share sheet (e.g. Voice Memos).
+//!
+//! The share extension runs in its own sandbox, so it copies the shared file
+//! into the `SharedAudio/` folder of the app group container named by
+//! `AppSettings::ios_app_group_id`, where the app can read it.
+
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// Folder inside the app group container the share extension writes to.
+const INBOX_DIR: &str = "SharedAudio";
+
+/// Root of the app group container, or None if the group isn't configured or
+/// the app isn't entitled to it.
+fn app_group_container(group_id: &str) -> Option<PathBuf> {
+    use objc2_foundation::{NSFileManager, NSString};
+
+    let manager = NSFileManager::defaultManager();
+    #[allow(unused_unsafe)]
+    let url = unsafe {
+        manager.containerURLForSecurityApplicationGroupIdentifier(&NSString::from_str(group_id))
+    }?;
+    let path = url.path()?;
+    Some(PathBuf::from(path.to_string()))
+}
+
+fn inbox_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let settings = crate::settings::get_settings(app);
+    let group_id = settings
+        .ios_app_group_id
+        .filter(|id| !id.trim().is_empty())
+        .ok_or_else(|| "No iOS app group is configured for shared audio".to_string())?;
+    let container = app_group_container(&group_id)
+        .ok_or_else(|| format!("App group container for {} is not available", group_id))?;
+    Ok(container.join(INBOX_DIR))
+}
+
+/// Resolve a shared file. Relative paths (usually just the file name the
+/// extension reports) are looked up in the inbox; absolute paths must already
+/// point inside it so this can't be used to read arbitrary files.
+pub fn resolve_shared_file(app: &AppHandle, file_path: &str) -> Result<PathBuf, String> {
+    let inbox = inbox_dir(app)?;
+    let requested = Path::new(file_path);
+    let path = if requested.is_absolute() {
+        requested.to_path_buf()
+    } else {
+        inbox.join(requested)
+    };
+
+    let path = path
+        .canonicalize()
+        .map_err(|_| format!("Shared file not found: {}", file_path))?;
+    let inbox = inbox
+        .canonicalize()
+        .map_err(|e| format!("Failed to open shared audio folder: {}", e))?;
+    if !path.starts_with(&inbox) {
+        return Err("Shared file is outside the app group container".to_string());
+    }
+    Ok(path)
+}
+
+/// Delete a shared file once it has been imported.
+pub fn remove_shared_file(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path) {
+        log::warn!("Failed to remove shared file {}: {}", path.display(), e);
+    }
+}
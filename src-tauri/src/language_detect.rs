@@ -0,0 +1,118 @@
+//! Tell which language a transcript is in. Used when transcription ran with
+//! the language on "auto": the engines don't report what they heard, so the
+//! text they produce is classified instead.
+
+use whatlang::Lang;
+
+/// Characters of the transcript that are looked at; the start of a recording
+/// is enough to tell its language.
+const PROBE_CHARS: usize = 1000;
+
+/// The language `text` is written in, as the code `selected_language` uses
+/// (e.g. "en", "de"), or None if it can't be told reliably.
+pub fn detect_language(text: &str) -> Option<String> {
+    let probe: String = text.chars().take(PROBE_CHARS).collect();
+    let info = whatlang::detect(&probe)?;
+    if !info.is_reliable() {
+        return None;
+    }
+    language_code(info.lang()).map(str::to_string)
+}
+
+/// ISO 639-1 code for a detected language, where Whisper knows one.
+fn language_code(lang: Lang) -> Option<&'static str> {
+    Some(match lang {
+        Lang::Afr => "af",
+        Lang::Ara => "ar",
+        Lang::Aze => "az",
+        Lang::Bel => "be",
+        Lang::Ben => "bn",
+        Lang::Bul => "bg",
+        Lang::Cat => "ca",
+        Lang::Ces => "cs",
+        Lang::Cmn => "zh",
+        Lang::Dan => "da",
+        Lang::Deu => "de",
+        Lang::Ell => "el",
+        Lang::Eng => "en",
+        Lang::Est => "et",
+        Lang::Fin => "fi",
+        Lang::Fra => "fr",
+        Lang::Guj => "gu",
+        Lang::Heb => "he",
+        Lang::Hin => "hi",
+        Lang::Hrv => "hr",
+        Lang::Hun => "hu",
+        Lang::Hye => "hy",
+        Lang::Ind => "id",
+        Lang::Ita => "it",
+        Lang::Jav => "jw",
+        Lang::Jpn => "ja",
+        Lang::Kan => "kn",
+        Lang::Kat => "ka",
+        Lang::Khm => "km",
+        Lang::Kor => "ko",
+        Lang::Lat => "la",
+        Lang::Lav => "lv",
+        Lang::Lit => "lt",
+        Lang::Mal => "ml",
+        Lang::Mar => "mr",
+        Lang::Mkd => "mk",
+        Lang::Mya => "my",
+        Lang::Nep => "ne",
+        Lang::Nld => "nl",
+        Lang::Nob => "no",
+        Lang::Pan => "pa",
+        Lang::Pes => "fa",
+        Lang::Pol => "pl",
+        Lang::Por => "pt",
+        Lang::Ron => "ro",
+        Lang::Rus => "ru",
+        Lang::Sin => "si",
+        Lang::Slk => "sk",
+        Lang::Slv => "sl",
+        Lang::Sna => "sn",
+        Lang::Spa => "es",
+        Lang::Srp => "sr",
+        Lang::Swe => "sv",
+        Lang::Tam => "ta",
+        Lang::Tel => "te",
+        Lang::Tgl => "tl",
+        Lang::Tha => "th",
+        Lang::Tuk => "tk",
+        Lang::Tur => "tr",
+        Lang::Ukr => "uk",
+        Lang::Urd => "ur",
+        Lang::Uzb => "uz",
+        Lang::Vie => "vi",
+        Lang::Yid => "yi",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_common_languages() {
+        assert_eq!(
+            detect_language(
+                "The meeting ran long, so we moved the review to next Tuesday afternoon."
+            )
+            .as_deref(),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language("Wir haben die Besprechung auf nächsten Dienstagnachmittag verschoben, weil sie zu lange dauerte.")
+                .as_deref(),
+            Some("de")
+        );
+    }
+
+    #[test]
+    fn test_short_or_empty_text_is_not_guessed() {
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("ok"), None);
+    }
+}
@@ -15,12 +15,29 @@ mod clipboard;
 pub mod cloud_transcribe;
 mod commands;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod compute_backend;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub mod diarize;
+mod error;
+mod feed;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod ffmpeg;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod git_sync;
 mod helpers;
+mod http;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod input;
+#[cfg(target_os = "ios")]
+mod ios_share;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod language_detect;
 mod llm_client;
 mod managers;
+mod markdown_note;
+mod markdown_template;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod notifications;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod overlay;
 mod settings;
@@ -29,14 +46,19 @@ mod shortcut;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod signal_handle;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod sponsorblock;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod subtitles;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod transcription_coordinator;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod tray;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod tray_i18n;
-#[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod utils;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod watch_folder;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub mod ytdlp;
 
 pub use cli::CliArgs;
@@ -145,6 +167,13 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     let recording_manager = Arc::new(
         AudioRecordingManager::new(app_handle).expect("Failed to initialize recording manager"),
     );
+    let interrupted = recording_manager.recoverable_recordings().len();
+    if interrupted > 0 {
+        log::info!(
+            "Found {} interrupted recording(s) that can be recovered",
+            interrupted
+        );
+    }
     let model_manager =
         Arc::new(ModelManager::new(app_handle).expect("Failed to initialize model manager"));
     let transcription_manager = Arc::new(
@@ -162,6 +191,25 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     app_handle.manage(transcription_manager.clone());
     app_handle.manage(history_manager.clone());
     app_handle.manage(journal_manager.clone());
+    app_handle.manage(diarize::DiarizationCancellation::default());
+    app_handle.manage(utils::BatchProcessCancellation::default());
+    app_handle.manage(commands::journal::LiveTranscription::default());
+    app_handle.manage(watch_folder::WatchFolder::default());
+    watch_external_journal_changes(app_handle, &journal_manager);
+
+    // Empty trash older than the configured retention
+    let trash_manager = journal_manager.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = trash_manager.purge_expired_trash().await {
+            log::warn!("Failed to purge expired journal trash: {}", e);
+        }
+        if let Err(e) = trash_manager.backfill_durations().await {
+            log::warn!("Failed to backfill journal recording durations: {}", e);
+        }
+    });
+
+    spawn_journal_backups(app_handle, journal_manager.clone());
+    watch_folder::restart(app_handle);
 
     // Note: Shortcuts are NOT initialized here.
     // The frontend is responsible for calling the `initialize_shortcuts` command
@@ -214,6 +262,19 @@ fn initialize_core_logic(app_handle: &AppHandle) {
             "copy_last_transcript" => {
                 tray::copy_last_transcript(app);
             }
+            // Recording and saving the entry are driven by the journal view
+            "start_journal" => {
+                let _ = app.emit("tray-start-journal", ());
+            }
+            "stop_journal" => {
+                let _ = app.emit("tray-stop-journal", ());
+            }
+            id if id.starts_with(tray::OPEN_ENTRY_PREFIX) => {
+                if let Ok(entry_id) = id[tray::OPEN_ENTRY_PREFIX.len()..].parse::<i64>() {
+                    show_main_window(app);
+                    let _ = app.emit("open-entry", entry_id);
+                }
+            }
             "unload_model" => {
                 let transcription_manager = app.state::<Arc<TranscriptionManager>>();
                 if !transcription_manager.is_model_loaded() {
@@ -249,11 +310,20 @@ fn initialize_core_logic(app_handle: &AppHandle) {
         tray::set_tray_visibility(app_handle, false);
     }
 
-    // Refresh tray menu when model state changes
-    let app_handle_for_listener = app_handle.clone();
-    app_handle.listen("model-state-changed", move |_| {
-        tray::update_tray_menu(&app_handle_for_listener, &tray::TrayIconState::Idle, None);
-    });
+    // Refresh tray menu when model state changes, when a download or deletion
+    // changes whether journal recording is available, or when the journal
+    // changes what the Recent submenu lists
+    for event in [
+        "model-state-changed",
+        "model-download-complete",
+        "model-deleted",
+        "journal-updated",
+    ] {
+        let app_handle_for_listener = app_handle.clone();
+        app_handle.listen(event, move |_| {
+            tray::refresh_tray_menu(&app_handle_for_listener);
+        });
+    }
 
     // Get the autostart manager and configure based on user setting
     let autostart_manager = app_handle.autolaunch();
@@ -282,8 +352,75 @@ fn initialize_core_logic_mobile(app_handle: &AppHandle) {
         Arc::new(ModelManager::new(app_handle).expect("Failed to initialize model manager"));
 
     app_handle.manage(history_manager);
-    app_handle.manage(journal_manager);
+    app_handle.manage(journal_manager.clone());
     app_handle.manage(model_manager);
+    app_handle.manage(utils::BatchProcessCancellation::default());
+
+    spawn_journal_backups(app_handle, journal_manager.clone());
+
+    // Empty trash older than the configured retention
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = journal_manager.purge_expired_trash().await {
+            log::warn!("Failed to purge expired journal trash: {}", e);
+        }
+        if let Err(e) = journal_manager.backfill_durations().await {
+            log::warn!("Failed to backfill journal recording durations: {}", e);
+        }
+    });
+}
+
+/// Keeps the watcher started by `watch_external_journal_changes` alive.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+struct ExternalJournalChanges {
+    _watcher: notify::RecommendedWatcher,
+}
+
+/// Emit `journal-updated` when another process, i.e. `--journal-add`, changes
+/// the journal (see `JournalManager::mark_external_change`).
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn watch_external_journal_changes(app_handle: &AppHandle, journal_manager: &JournalManager) {
+    use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+    let marker = journal_manager.external_change_marker();
+    let Some(dir) = marker.parent().map(std::path::Path::to_path_buf) else {
+        return;
+    };
+    let app = app_handle.clone();
+    let watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        let touched = event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == marker.file_name());
+        if touched && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+            let _ = app.emit("journal-updated", ());
+        }
+    })
+    .and_then(|mut watcher| {
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+        Ok(watcher)
+    });
+    match watcher {
+        Ok(watcher) => {
+            app_handle.manage(ExternalJournalChanges { _watcher: watcher });
+        }
+        Err(e) => log::warn!("Failed to watch for journal changes: {}", e),
+    }
+}
+
+/// Back up the journal database now and every 24 hours while `backup_enabled` is set.
+fn spawn_journal_backups(app_handle: &AppHandle, journal_manager: Arc<JournalManager>) {
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || loop {
+        if settings::get_settings(&app_handle).backup_enabled {
+            if let Err(e) = tauri::async_runtime::block_on(journal_manager.backup_database()) {
+                log::warn!("Failed to back up journal database: {}", e);
+            }
+        }
+        std::thread::sleep(std::time::Duration::from_secs(24 * 60 * 60));
+    });
 }
 
 #[tauri::command]
@@ -301,17 +438,258 @@ fn trigger_update_check(app: AppHandle) -> Result<(), String> {
 /// Desktop entry point — accepts CLI arguments
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub fn run(cli_args: CliArgs) {
-    run_inner(cli_args);
+    if cli_args.list_audio_devices {
+        list_audio_devices(cli_args.output_format);
+    }
+    let context = tauri::generate_context!();
+    if cli_args.journal_add.is_some() {
+        run_journal_add(cli_args, context);
+    }
+    if cli_args.transcribe_file.is_some() {
+        run_headless(cli_args, context);
+    }
+    run_inner(cli_args, context);
 }
 
 /// Mobile entry point — no CLI arguments
 #[cfg(any(target_os = "android", target_os = "ios"))]
 #[tauri::mobile_entry_point]
 pub fn run() {
-    run_inner(CliArgs::default());
+    run_inner(CliArgs::default(), tauri::generate_context!());
 }
 
-fn run_inner(cli_args: CliArgs) {
+/// Headless `--transcribe-file` mode: transcribe a single file, print the result
+/// (or write it to `--output`) and exit. Skips the regular app setup (windows,
+/// tray, shortcuts, audio, single-instance) and only initialises the model and
+/// transcription managers, so it also works while the app is running.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn run_headless(cli_args: CliArgs, context: tauri::Context<tauri::Wry>) -> ! {
+    let result =
+        transcribe_file_headless(&cli_args, context).and_then(|output| match &cli_args.output {
+            Some(path) => std::fs::write(path, format!("{}\n", output))
+                .map_err(|e| format!("Failed to write {}: {}", path, e)),
+            None => {
+                println!("{}", output);
+                Ok(())
+            }
+        });
+    match result {
+        Ok(()) => std::process::exit(0),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `--journal-add`: save a text entry without audio, print its id and exit.
+/// Like `--transcribe-file` this skips the regular app setup, so it also works
+/// while the app is running (which refreshes through
+/// `watch_external_journal_changes`).
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn run_journal_add(cli_args: CliArgs, context: tauri::Context<tauri::Wry>) -> ! {
+    match journal_add_headless(&cli_args, context) {
+        Ok(entry) => {
+            println!("{}", entry.id);
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn journal_add_headless(
+    cli_args: &CliArgs,
+    context: tauri::Context<tauri::Wry>,
+) -> Result<managers::journal::JournalEntry, String> {
+    use std::io::{IsTerminal, Read};
+
+    let text = match cli_args.journal_add.clone().flatten() {
+        Some(text) => text,
+        None => {
+            let mut stdin = std::io::stdin();
+            if stdin.is_terminal() {
+                return Err("No text given. Pass it after --journal-add or pipe it in.".into());
+            }
+            let mut text = String::new();
+            stdin
+                .read_to_string(&mut text)
+                .map_err(|e| format!("Failed to read stdin: {}", e))?;
+            text
+        }
+    };
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("Entry text is empty".to_string());
+    }
+
+    let app = tauri::Builder::default()
+        .plugin(tauri_plugin_store::Builder::default().build())
+        .build(context)
+        .map_err(|e| format!("Failed to initialise app: {}", e))?;
+    let app_handle = app.handle().clone();
+    let journal_manager = JournalManager::new(&app_handle)
+        .map_err(|e| format!("Failed to initialize journal manager: {}", e))?;
+    // The process exits right after saving, which would cut off a background commit
+    journal_manager.set_git_sync_inline(true);
+
+    let entry = tauri::async_runtime::block_on(async {
+        let title = match cli_args.title.as_deref().map(str::trim) {
+            Some(title) if !title.is_empty() => title.to_string(),
+            _ => JournalManager::generate_title_from_text(&text, false, &app_handle).await,
+        };
+        // No audio file, so the entry is named after its .md transcript
+        journal_manager
+            .save_entry_with_source(
+                String::new(),
+                title,
+                text,
+                None,
+                None,
+                vec![],
+                vec![],
+                None,
+                "voice".to_string(),
+                None,
+            )
+            .await
+            .map_err(|e| format!("Failed to save entry: {}", e))
+    })?;
+    journal_manager.mark_external_change();
+    Ok(entry)
+}
+
+/// `--list-audio-devices`: print the input and output devices and exit. Only
+/// enumerates devices through the audio host; no app, window or tray is set up.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn list_audio_devices(format: cli::OutputFormat) -> ! {
+    let inputs = audio_toolkit::list_input_devices()
+        .map_err(|e| format!("Failed to list audio devices: {}", e));
+    let outputs = audio_toolkit::list_output_devices()
+        .map_err(|e| format!("Failed to list output devices: {}", e));
+    let (inputs, outputs) = match (inputs, outputs) {
+        (Ok(inputs), Ok(outputs)) => (inputs, outputs),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match format {
+        cli::OutputFormat::Text => {
+            for (heading, devices) in [("Input devices:", &inputs), ("Output devices:", &outputs)] {
+                println!("{}", heading);
+                if devices.is_empty() {
+                    println!("  (none)");
+                }
+                for device in devices {
+                    let default = if device.is_default { " (default)" } else { "" };
+                    println!("  {:>3}  {}{}", device.index, device.name, default);
+                }
+            }
+        }
+        cli::OutputFormat::Json => {
+            let to_json = |devices: &[audio_toolkit::CpalDeviceInfo]| {
+                devices
+                    .iter()
+                    .map(|d| {
+                        serde_json::json!({
+                            "id": d.index,
+                            "name": d.name,
+                            "is_default": d.is_default,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            };
+            println!(
+                "{}",
+                serde_json::json!({ "input": to_json(&inputs), "output": to_json(&outputs) })
+            );
+        }
+    }
+    std::process::exit(0);
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn transcribe_file_headless(
+    cli_args: &CliArgs,
+    context: tauri::Context<tauri::Wry>,
+) -> Result<String, String> {
+    let file_path = cli_args
+        .transcribe_file
+        .as_deref()
+        .ok_or_else(|| "No file given".to_string())?;
+    if !std::path::Path::new(file_path).exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    // A bare app is still needed for app paths and the settings store
+    let app = tauri::Builder::default()
+        .plugin(tauri_plugin_store::Builder::default().build())
+        .build(context)
+        .map_err(|e| format!("Failed to initialise app: {}", e))?;
+    let app_handle = app.handle().clone();
+
+    let model_manager = Arc::new(
+        ModelManager::new(&app_handle)
+            .map_err(|e| format!("Failed to initialize model manager: {}", e))?,
+    );
+    let transcription_manager = TranscriptionManager::new(&app_handle, model_manager)
+        .map_err(|e| format!("Failed to initialize transcription manager: {}", e))?;
+
+    let settings = get_settings(&app_handle);
+    let model_id = cli_args
+        .model
+        .clone()
+        .unwrap_or_else(|| settings.selected_model.clone());
+    if model_id.is_empty() {
+        return Err("No model selected. Pass --model or choose one in the app.".to_string());
+    }
+    transcription_manager
+        .load_model(&model_id)
+        .map_err(|e| format!("Failed to load model '{}': {}", model_id, e))?;
+
+    let (samples, sample_rate) = commands::video::extract_audio_with_fallback(
+        &app_handle,
+        file_path,
+        &std::sync::atomic::AtomicBool::new(false),
+    )?;
+    let samples = audio_toolkit::resample_mono_audio(
+        &samples,
+        sample_rate,
+        audio_toolkit::constants::WHISPER_SAMPLE_RATE,
+        settings.audio_resample_quality,
+    );
+    let text = tauri::async_runtime::block_on(commands::video::transcribe_chunked(
+        &transcription_manager,
+        samples,
+        None,
+        None,
+        None,
+    ))
+    .map_err(|e| e.to_string())?;
+
+    match cli_args.output_format {
+        cli::OutputFormat::Text => Ok(text),
+        cli::OutputFormat::Json => {
+            // The configured language, or the one detected from the text when
+            // set to auto-detect (null if it can't be told)
+            let language = transcription_manager
+                .detected_language(None, &text)
+                .or(Some(settings.selected_language).filter(|lang| lang.as_str() != "auto"));
+            Ok(serde_json::json!({
+                "text": text,
+                "detected_language": language,
+            })
+            .to_string())
+        }
+    }
+}
+
+fn run_inner(cli_args: CliArgs, context: tauri::Context<tauri::Wry>) {
     // Parse console logging directives from RUST_LOG, falling back to info-level logging
     // when the variable is unset
     let console_filter = build_console_filter();
@@ -332,25 +710,45 @@ fn run_inner(cli_args: CliArgs) {
         shortcut::change_overlay_position_setting,
         shortcut::change_debug_mode_setting,
         shortcut::change_word_correction_threshold_setting,
+        shortcut::change_dedup_threshold_setting,
+        shortcut::change_transcription_timeout_setting,
+        shortcut::change_notifications_enabled_setting,
+        shortcut::change_notification_on_model_load_setting,
+        shortcut::change_ffmpeg_path_setting,
+        shortcut::change_compute_backend_setting,
+        shortcut::change_watch_folder_setting,
+        shortcut::change_watch_folder_import_folder_setting,
+        shortcut::change_git_sync_repo_path_setting,
+        shortcut::change_git_sync_enabled_setting,
         shortcut::change_paste_method_setting,
         shortcut::get_available_typing_tools,
         shortcut::change_typing_tool_setting,
         shortcut::change_external_script_path_setting,
+        shortcut::change_ytdlp_cookies_file_setting,
+        shortcut::change_ytdlp_cookies_from_browser_setting,
         shortcut::change_clipboard_handling_setting,
         shortcut::change_auto_submit_setting,
         shortcut::change_auto_submit_key_setting,
         shortcut::change_post_process_enabled_setting,
         shortcut::change_experimental_enabled_setting,
         shortcut::change_post_process_base_url_setting,
+        shortcut::change_post_process_api_style_setting,
         shortcut::change_post_process_api_key_setting,
         shortcut::change_post_process_model_setting,
+        shortcut::change_post_process_max_context_tokens_setting,
         shortcut::set_post_process_provider,
         shortcut::fetch_post_process_models,
+        shortcut::test_llm_connection,
         shortcut::add_post_process_prompt,
         shortcut::update_post_process_prompt,
+        shortcut::update_post_process_prompt_sampling,
         shortcut::delete_post_process_prompt,
         shortcut::set_post_process_selected_prompt,
         shortcut::update_custom_words,
+        shortcut::change_transcription_initial_prompt_setting,
+        shortcut::change_initial_prompt_include_custom_words_setting,
+        shortcut::update_word_replacements,
+        shortcut::get_word_replacements,
         shortcut::suspend_binding,
         shortcut::resume_binding,
         shortcut::change_mute_while_recording_setting,
@@ -364,11 +762,14 @@ fn run_inner(cli_args: CliArgs) {
         shortcut::handy_keys::stop_handy_keys_recording,
         trigger_update_check,
         commands::cancel_operation,
+        commands::overlay::show_recording_overlay,
+        commands::overlay::hide_recording_overlay,
         commands::get_app_dir_path,
         commands::get_app_settings,
         commands::get_default_settings,
         commands::get_log_dir_path,
         commands::set_log_level,
+        commands::change_http_proxy_setting,
         commands::open_recordings_folder,
         commands::open_log_dir,
         commands::open_app_data_dir,
@@ -377,7 +778,13 @@ fn run_inner(cli_args: CliArgs) {
         commands::initialize_shortcuts,
         commands::models::get_available_models,
         commands::models::get_model_info,
+        commands::models::get_system_memory_info,
+        commands::models::get_available_compute_backends,
+        commands::models::get_active_compute_backend,
+        commands::models::recommend_model,
         commands::models::download_model,
+        commands::models::import_local_model,
+        commands::models::verify_model_integrity,
         commands::models::delete_model,
         commands::models::cancel_download,
         commands::models::set_active_model,
@@ -391,6 +798,14 @@ fn run_inner(cli_args: CliArgs) {
         commands::audio::get_available_microphones,
         commands::audio::set_selected_microphone,
         commands::audio::get_selected_microphone,
+        commands::audio::get_available_loopback_devices,
+        commands::audio::test_microphone_level,
+        commands::audio::set_selected_loopback_device,
+        commands::audio::get_selected_loopback_device,
+        commands::audio::change_recording_source_setting,
+        commands::audio::change_audio_resample_quality_setting,
+        commands::audio::set_microphone_gain,
+        commands::audio::change_recording_format_setting,
         commands::audio::get_available_output_devices,
         commands::audio::set_selected_output_device,
         commands::audio::get_selected_output_device,
@@ -402,33 +817,77 @@ fn run_inner(cli_args: CliArgs) {
         commands::transcription::set_model_unload_timeout,
         commands::transcription::get_model_load_status,
         commands::transcription::unload_model_manually,
+        commands::transcription::clear_transcription_cache,
         commands::history::get_history_entries,
         commands::history::toggle_history_entry_saved,
         commands::history::get_audio_file_path,
         commands::history::delete_history_entry,
+        commands::history::export_history_to_csv,
+        commands::history::export_history_to_json,
+        commands::history::open_history_export_dialog,
         commands::history::update_history_limit,
         commands::history::update_recording_retention_period,
         commands::journal::start_journal_recording,
         commands::journal::stop_journal_recording,
         commands::journal::get_partial_journal_transcription,
+        commands::journal::start_live_transcription,
+        commands::journal::stop_live_transcription,
+        commands::journal::get_recoverable_recordings,
+        commands::journal::recover_recording,
+        commands::journal::discard_recoverable_recording,
         commands::journal::discard_journal_recording,
         commands::journal::save_journal_entry,
+        commands::journal::set_journal_entry_initial_prompt,
         commands::journal::get_journal_entries,
+        commands::journal::get_journal_entries_in_duration_range,
+        commands::journal::get_journal_tags,
+        commands::journal::get_journal_entries_page,
+        commands::journal::count_journal_entries,
+        commands::journal::filter_journal_entries,
         commands::journal::get_journal_entry,
+        commands::journal::get_entry_backlinks,
         commands::journal::update_journal_entry,
         commands::journal::delete_journal_entry,
+        commands::journal::delete_entries,
+        commands::journal::move_entries_to_folder,
+        commands::journal::duplicate_journal_entry,
+        commands::journal::restore_journal_entry,
+        commands::journal::purge_journal_entry,
+        commands::journal::get_trashed_entries,
         commands::journal::apply_journal_post_process,
         commands::journal::apply_prompt_text_to_text,
         commands::journal::update_journal_post_processed_text,
         commands::journal::get_journal_audio_file_path,
         commands::journal::retranscribe_journal_entry,
+        commands::journal::translate_journal_entry,
+        commands::journal::get_journal_entry_waveform,
         commands::journal::apply_prompt_to_journal_entry,
+        commands::journal::apply_prompt_chain_to_entry,
         commands::journal::apply_prompt_text_to_journal_entry,
+        commands::journal::apply_prompt_text_to_entries,
+        commands::journal::batch_apply_prompt,
+        commands::journal::cancel_batch_process,
         commands::journal::undo_journal_prompt,
+        commands::journal::redo_journal_prompt,
+        commands::journal::get_prompt_diff,
         commands::journal::update_journal_transcription_text,
         commands::journal::update_entry_after_processing,
         commands::journal::import_audio_for_journal,
+        commands::journal::import_markdown_file,
+        commands::journal::import_markdown_directory,
+        commands::journal::import_text_as_entry,
+        commands::journal::get_imported_entries,
+        commands::journal::get_imported_folders,
+        commands::journal::create_import_folder,
+        commands::journal::import_audio_directory,
         commands::journal::journal_chat,
+        commands::journal::multi_entry_journal_chat,
+        commands::journal::get_context_window_estimate,
+        commands::journal::create_multi_chat_session,
+        commands::journal::get_multi_chat_sessions,
+        commands::journal::save_multi_chat_message,
+        commands::journal::get_multi_chat_messages,
+        commands::journal::delete_multi_chat_session,
         commands::journal::create_chat_session,
         commands::journal::get_chat_sessions,
         commands::journal::save_chat_message,
@@ -437,21 +896,47 @@ fn run_inner(cli_args: CliArgs) {
         commands::journal::delete_chat_session,
         commands::journal::create_journal_folder,
         commands::journal::rename_journal_folder,
+        commands::journal::move_journal_folder,
         commands::journal::delete_journal_folder,
         commands::journal::get_journal_folders,
         commands::journal::move_journal_entry_to_folder,
         commands::journal::get_journal_storage_path,
         commands::journal::set_journal_storage_path,
+        commands::journal::get_obsidian_vault_path,
+        commands::change_obsidian_vault_path_setting,
+        commands::journal::sync_entries_to_obsidian,
+        commands::journal::trigger_git_sync,
+        commands::journal::generate_folder_podcast_feed,
+        commands::journal::save_podcast_feed,
+        commands::journal::export_journal_zip,
+        commands::change_auto_generate_title_setting,
+        commands::change_auto_title_use_llm_setting,
+        commands::change_trash_retention_days_setting,
+        commands::change_backup_enabled_setting,
+        commands::change_backup_count_setting,
+        commands::change_markdown_frontmatter_setting,
+        commands::change_markdown_template_setting,
+        commands::journal::backup_journal_database,
+        commands::journal::list_journal_backups,
+        commands::journal::restore_journal_from_backup,
         commands::video::check_ytdlp_installed,
         commands::video::install_ytdlp,
+        commands::video::check_ytdlp_update,
+        commands::video::update_ytdlp,
+        commands::video::test_ytdlp_auth,
         commands::video::download_youtube_audio,
+        commands::video::download_url_audio,
+        commands::video::download_youtube_playlist,
+        commands::video::get_skipped_segments,
         commands::video::import_video_for_journal,
+        commands::video::import_subtitles_for_journal,
         commands::video::get_video_entries,
         commands::video::get_video_folders,
         commands::video::create_video_folder,
         commands::video::save_video_entry,
         commands::meeting::check_diarize_models_installed,
         commands::meeting::install_diarize_models,
+        commands::meeting::verify_diarize_models,
         commands::meeting::get_meeting_entries,
         commands::meeting::get_meeting_folders,
         commands::meeting::create_meeting_folder,
@@ -459,13 +944,37 @@ fn run_inner(cli_args: CliArgs) {
         commands::meeting::transcribe_meeting,
         commands::meeting::get_meeting_segments,
         commands::meeting::update_meeting_segment_text,
+        commands::meeting::retranscribe_meeting_segment,
         commands::meeting::update_meeting_segment_speaker,
         commands::meeting::update_meeting_speaker_name,
+        commands::meeting::merge_meeting_segments,
+        commands::meeting::merge_two_segments,
+        commands::meeting::split_meeting_segment,
+        commands::meeting::delete_meeting_segment,
+        commands::meeting::insert_meeting_segment,
         commands::meeting::get_meeting_speaker_names,
+        commands::meeting::enroll_speaker_from_segment,
+        commands::meeting::list_speaker_profiles,
+        commands::meeting::delete_speaker_profile,
+        shortcut::change_speaker_match_threshold_setting,
+        commands::meeting::get_meeting_speaker_stats,
+        commands::meeting::export_meeting_transcript,
+        commands::meeting::save_meeting_transcript_to_file,
+        commands::meeting::export_meeting_markdown,
+        commands::meeting::generate_meeting_minutes,
         commands::meeting::diarize_entry,
+        commands::meeting::cancel_diarization,
+        commands::meeting::rediarize_meeting,
         helpers::clamshell::is_laptop,
     ]);
 
+    // Event payloads that no command returns
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let specta_builder = specta_builder
+        .typ::<managers::model::DownloadProgress>()
+        .typ::<managers::model::DownloadStarted>()
+        .typ::<managers::model::DownloadComplete>();
+
     // Mobile: only register platform-agnostic commands
     #[cfg(any(target_os = "android", target_os = "ios"))]
     let specta_builder = Builder::<tauri::Wry>::new().commands(collect_commands![
@@ -475,27 +984,60 @@ fn run_inner(cli_args: CliArgs) {
         commands::get_default_settings,
         commands::get_log_dir_path,
         commands::set_log_level,
+        commands::change_http_proxy_setting,
         // Mobile recording commands (audio captured in frontend WebView)
         commands::journal::start_journal_recording,
         commands::journal::stop_journal_recording,
         commands::journal::get_partial_journal_transcription,
         commands::journal::import_audio_for_journal,
+        commands::journal::import_markdown_file,
+        commands::journal::import_markdown_directory,
+        commands::journal::import_text_as_entry,
+        commands::journal::get_imported_entries,
+        commands::journal::get_imported_folders,
+        commands::journal::create_import_folder,
         commands::journal::discard_journal_recording,
         commands::journal::save_journal_entry,
+        commands::journal::set_journal_entry_initial_prompt,
         commands::journal::get_journal_entries,
+        commands::journal::get_journal_entries_in_duration_range,
+        commands::journal::get_journal_tags,
+        commands::journal::get_journal_entries_page,
+        commands::journal::count_journal_entries,
+        commands::journal::filter_journal_entries,
         commands::journal::get_journal_entry,
+        commands::journal::get_entry_backlinks,
         commands::journal::update_journal_entry,
         commands::journal::delete_journal_entry,
+        commands::journal::delete_entries,
+        commands::journal::move_entries_to_folder,
+        commands::journal::duplicate_journal_entry,
+        commands::journal::restore_journal_entry,
+        commands::journal::purge_journal_entry,
+        commands::journal::get_trashed_entries,
         commands::journal::apply_journal_post_process,
         commands::journal::apply_prompt_text_to_text,
         commands::journal::update_journal_post_processed_text,
         commands::journal::get_journal_audio_file_path,
         commands::journal::apply_prompt_to_journal_entry,
+        commands::journal::apply_prompt_chain_to_entry,
         commands::journal::apply_prompt_text_to_journal_entry,
+        commands::journal::apply_prompt_text_to_entries,
+        commands::journal::batch_apply_prompt,
+        commands::journal::cancel_batch_process,
         commands::journal::undo_journal_prompt,
+        commands::journal::redo_journal_prompt,
+        commands::journal::get_prompt_diff,
         commands::journal::update_journal_transcription_text,
         commands::journal::update_entry_after_processing,
         commands::journal::journal_chat,
+        commands::journal::multi_entry_journal_chat,
+        commands::journal::get_context_window_estimate,
+        commands::journal::create_multi_chat_session,
+        commands::journal::get_multi_chat_sessions,
+        commands::journal::save_multi_chat_message,
+        commands::journal::get_multi_chat_messages,
+        commands::journal::delete_multi_chat_session,
         commands::journal::create_chat_session,
         commands::journal::get_chat_sessions,
         commands::journal::save_chat_message,
@@ -504,19 +1046,43 @@ fn run_inner(cli_args: CliArgs) {
         commands::journal::delete_chat_session,
         commands::journal::create_journal_folder,
         commands::journal::rename_journal_folder,
+        commands::journal::move_journal_folder,
         commands::journal::delete_journal_folder,
         commands::journal::get_journal_folders,
         commands::journal::move_journal_entry_to_folder,
         commands::journal::get_journal_storage_path,
         commands::journal::set_journal_storage_path,
+        commands::journal::get_obsidian_vault_path,
+        commands::change_obsidian_vault_path_setting,
+        commands::journal::sync_entries_to_obsidian,
+        commands::journal::generate_folder_podcast_feed,
+        commands::journal::save_podcast_feed,
+        commands::journal::export_journal_zip,
+        commands::change_auto_generate_title_setting,
+        commands::change_auto_title_use_llm_setting,
+        commands::change_trash_retention_days_setting,
+        commands::change_backup_enabled_setting,
+        commands::change_backup_count_setting,
+        commands::change_markdown_frontmatter_setting,
+        commands::change_markdown_template_setting,
+        commands::journal::backup_journal_database,
+        commands::journal::list_journal_backups,
+        commands::journal::restore_journal_from_backup,
         commands::history::get_history_entries,
         commands::history::toggle_history_entry_saved,
         commands::history::delete_history_entry,
+        commands::history::export_history_to_csv,
+        commands::history::export_history_to_json,
+        commands::history::open_history_export_dialog,
         commands::history::update_history_limit,
         commands::history::update_recording_retention_period,
         commands::models::get_available_models,
         commands::models::get_model_info,
+        commands::models::get_system_memory_info,
+        commands::models::recommend_model,
         commands::models::download_model,
+        commands::models::import_local_model,
+        commands::models::verify_model_integrity,
         commands::models::delete_model,
         commands::models::cancel_download,
         commands::models::set_active_model,
@@ -528,6 +1094,8 @@ fn run_inner(cli_args: CliArgs) {
         // Share intent handling
         commands::share::get_pending_share,
         commands::share::clear_pending_share,
+        commands::share::process_shared_audio_file,
+        commands::share::import_shared_audio,
         helpers::clamshell::is_laptop,
     ]);
 
@@ -600,7 +1168,8 @@ fn run_inner(cli_args: CliArgs) {
                 MacosLauncher::LaunchAgent,
                 Some(vec![]),
             ))
-            .plugin(tauri_plugin_macos_permissions::init());
+            .plugin(tauri_plugin_macos_permissions::init())
+            .plugin(tauri_plugin_notification::init());
     }
 
     // Cross-platform plugins
@@ -627,6 +1196,7 @@ fn run_inner(cli_args: CliArgs) {
             let file_log_level: log::Level = tauri_log_level.into();
             // Store the file log level in the atomic for the filter to use
             FILE_LOG_LEVEL.store(file_log_level.to_level_filter() as u8, Ordering::Relaxed);
+            http::set_proxy(settings.http_proxy.clone());
             let app_handle = app.handle().clone();
 
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
@@ -697,6 +1267,17 @@ fn run_inner(cli_args: CliArgs) {
             _ => {}
         })
         .invoke_handler(specta_builder.invoke_handler())
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                #[cfg(not(any(target_os = "android", target_os = "ios")))]
+                {
+                    watch_folder::stop(app);
+                    signal_handle::flush_before_exit(app);
+                }
+                #[cfg(any(target_os = "android", target_os = "ios"))]
+                let _ = app; // journal writes are flushed when the manager drops
+            }
+        });
 }
@@ -1,8 +1,36 @@
-use crate::settings::PostProcessProvider;
+use crate::settings::{ApiStyle, LLMPrompt, PostProcessProvider};
 use log::debug;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, REFERER, USER_AGENT};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use specta::Type;
+use std::time::Instant;
+
+/// Optional sampling settings for a request. Unset values are left out of
+/// the request body so the provider's defaults apply.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SamplingOptions {
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+}
+
+impl SamplingOptions {
+    /// A prompt's own sampling settings.
+    pub fn for_prompt(prompt: &LLMPrompt) -> Self {
+        Self {
+            temperature: prompt.temperature,
+            max_tokens: prompt.max_tokens,
+        }
+    }
+
+    /// Per-call values replace these where given.
+    pub fn overridden_by(self, temperature: Option<f32>, max_tokens: Option<u32>) -> Self {
+        Self {
+            temperature: temperature.or(self.temperature),
+            max_tokens: max_tokens.or(self.max_tokens),
+        }
+    }
+}
 
 #[derive(Debug, Serialize)]
 struct ChatMessage {
@@ -30,6 +58,10 @@ struct ChatCompletionRequest {
     messages: Vec<ChatMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     response_format: Option<ResponseFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,6 +79,140 @@ struct ChatMessageResponse {
     content: Option<String>,
 }
 
+/// Request body for Gemini's `generateContent`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generation_config: Option<GeminiGenerationConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GeminiContent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(default)]
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GeminiPart {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiGenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiResponse {
+    #[serde(default)]
+    candidates: Vec<GeminiCandidate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiCandidate {
+    #[serde(default)]
+    content: Option<GeminiContent>,
+}
+
+/// Request body for Anthropic's Messages API (`/v1/messages`).
+#[derive(Debug, Serialize)]
+struct AnthropicRequest {
+    model: String,
+    /// Required by the API, unlike OpenAI's optional `max_tokens`.
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicResponse {
+    #[serde(default)]
+    content: Vec<AnthropicContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+/// Reply length asked of Anthropic when the prompt doesn't set one.
+const ANTHROPIC_DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// Request body for Ollama's native `/api/chat`.
+#[derive(Debug, Serialize)]
+struct OllamaChatRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+    /// JSON schema the reply must follow (Ollama's structured outputs).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    options: Option<OllamaOptions>,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+}
+
+/// One `/api/chat` response object; a streamed reply is one per line.
+#[derive(Debug, Deserialize)]
+struct OllamaChatChunk {
+    #[serde(default)]
+    message: Option<ChatMessageResponse>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Rough characters per token for English text; good enough to size prompts
+/// against a context window without shipping a tokenizer.
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Estimate how many tokens `text` will use.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Check that `prompt` fits in `max_tokens`, with an error that says by how much
+/// it doesn't.
+pub fn check_prompt_fits(prompt: &str, max_tokens: u32) -> Result<(), String> {
+    let tokens = estimate_tokens(prompt);
+    let max_tokens = max_tokens as usize;
+    if tokens > max_tokens {
+        return Err(format!(
+            "The prompt is about {} tokens, {} over this provider's limit of {}. \
+             Use a shorter transcript or raise the provider's context limit.",
+            tokens,
+            tokens - max_tokens,
+            max_tokens
+        ));
+    }
+    Ok(())
+}
+
 /// Build headers for API requests based on provider type
 fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<HeaderMap, String> {
     let mut headers = HeaderMap::new();
@@ -63,8 +229,8 @@ fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<Header
     );
     headers.insert("X-Title", HeaderValue::from_static("Handy"));
 
-    // Provider-specific auth headers
-    if !api_key.is_empty() {
+    // Provider-specific auth headers (Gemini takes its key as a query parameter)
+    if !api_key.is_empty() && !is_gemini(provider) {
         if provider.id == "anthropic" {
             headers.insert(
                 "x-api-key",
@@ -87,7 +253,7 @@ fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<Header
 /// Create an HTTP client with provider-specific headers
 fn create_client(provider: &PostProcessProvider, api_key: &str) -> Result<reqwest::Client, String> {
     let headers = build_headers(provider, api_key)?;
-    reqwest::Client::builder()
+    crate::http::http_client_builder()
         .default_headers(headers)
         .build()
         .map_err(|e| format!("Failed to build HTTP client: {}", e))
@@ -102,7 +268,19 @@ pub async fn send_chat_completion(
     model: &str,
     prompt: String,
 ) -> Result<Option<String>, String> {
-    send_chat_completion_with_schema(provider, api_key, model, prompt, None, None).await
+    send_chat_completion_with_options(provider, api_key, model, prompt, SamplingOptions::default())
+        .await
+}
+
+/// `send_chat_completion` with sampling settings, e.g. a prompt's temperature.
+pub async fn send_chat_completion_with_options(
+    provider: &PostProcessProvider,
+    api_key: String,
+    model: &str,
+    prompt: String,
+    options: SamplingOptions,
+) -> Result<Option<String>, String> {
+    send_chat_completion_with_schema(provider, api_key, model, prompt, None, None, options).await
 }
 
 /// Send a chat completion request with structured output support
@@ -115,13 +293,22 @@ pub async fn send_chat_completion_with_schema(
     user_content: String,
     system_prompt: Option<String>,
     json_schema: Option<Value>,
+    options: SamplingOptions,
 ) -> Result<Option<String>, String> {
-    let base_url = provider.base_url.trim_end_matches('/');
-    let url = format!("{}/chat/completions", base_url);
-
-    debug!("Sending chat completion request to: {}", url);
-
-    let client = create_client(provider, &api_key)?;
+    if is_anthropic(provider) {
+        if json_schema.is_some() {
+            debug!("Anthropic has no structured output mode; sending the prompt as text");
+        }
+        return send_anthropic_completion(
+            provider,
+            api_key,
+            model,
+            system_prompt,
+            user_content,
+            options,
+        )
+        .await;
+    }
 
     // Build messages vector
     let mut messages = Vec::new();
@@ -140,80 +327,340 @@ pub async fn send_chat_completion_with_schema(
         content: user_content,
     });
 
-    // Build response_format if schema is provided
-    let response_format = json_schema.map(|schema| ResponseFormat {
-        format_type: "json_schema".to_string(),
-        json_schema: JsonSchema {
-            name: "transcription_output".to_string(),
-            strict: true,
-            schema,
-        },
-    });
-
-    let request_body = ChatCompletionRequest {
-        model: model.to_string(),
-        messages,
-        response_format,
-    };
-
-    let response = client
-        .post(&url)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
+    debug!(
+        "Sending chat completion request to: {}",
+        chat_url(provider, model)
+    );
+    post_chat(provider, &api_key, model, messages, json_schema, options).await
+}
 
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to read error response".to_string());
-        return Err(format!(
-            "API request failed with status {}: {}",
-            status, error_text
-        ));
+/// Send a single prompt to Anthropic's Messages API, which takes the system
+/// prompt as a top-level field rather than as a message.
+pub async fn send_anthropic_completion(
+    provider: &PostProcessProvider,
+    api_key: String,
+    model: &str,
+    system: Option<String>,
+    user: String,
+    options: SamplingOptions,
+) -> Result<Option<String>, String> {
+    let mut messages = Vec::new();
+    if let Some(system) = system {
+        messages.push(ChatMessage {
+            role: "system".to_string(),
+            content: system,
+        });
     }
+    messages.push(ChatMessage {
+        role: "user".to_string(),
+        content: user,
+    });
 
-    let completion: ChatCompletionResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse API response: {}", e))?;
-
-    Ok(completion
-        .choices
-        .first()
-        .and_then(|choice| choice.message.content.clone()))
+    debug!(
+        "Sending Anthropic messages request to: {}",
+        chat_url(provider, model)
+    );
+    post_chat(provider, &api_key, model, messages, None, options).await
 }
 
 /// Send a multi-turn chat completion request
-/// Accepts a full message history (system, user, assistant messages)
+/// Accepts a full message history (system, user, assistant messages).
+/// Anthropic providers go through the Messages API (see `anthropic_request`).
 pub async fn send_chat_messages(
     provider: &PostProcessProvider,
     api_key: String,
     model: &str,
     messages: Vec<(String, String)>, // (role, content) pairs
+    options: SamplingOptions,
 ) -> Result<Option<String>, String> {
-    let base_url = provider.base_url.trim_end_matches('/');
-    let url = format!("{}/chat/completions", base_url);
-
-    debug!("Sending multi-turn chat request to: {}", url);
-
-    let client = create_client(provider, &api_key)?;
-
     let chat_messages: Vec<ChatMessage> = messages
         .into_iter()
         .map(|(role, content)| ChatMessage { role, content })
         .collect();
 
-    let request_body = ChatCompletionRequest {
-        model: model.to_string(),
-        messages: chat_messages,
-        response_format: None,
+    debug!(
+        "Sending multi-turn chat request to: {}",
+        chat_url(provider, model)
+    );
+    post_chat(provider, &api_key, model, chat_messages, None, options).await
+}
+
+/// Ollama serves its native API from the server root; also accept a base URL
+/// written for its OpenAI-compatible endpoint (`.../v1`).
+fn ollama_root(base_url: &str) -> &str {
+    let trimmed = base_url.trim_end_matches('/');
+    trimmed.strip_suffix("/v1").unwrap_or(trimmed)
+}
+
+/// Gemini has its own `generateContent` schema regardless of `api_style`.
+fn is_gemini(provider: &PostProcessProvider) -> bool {
+    provider.id == "gemini"
+}
+
+/// Anthropic has its own Messages API regardless of `api_style`.
+fn is_anthropic(provider: &PostProcessProvider) -> bool {
+    provider.id == "anthropic"
+}
+
+fn chat_url(provider: &PostProcessProvider, model: &str) -> String {
+    if is_gemini(provider) {
+        // Model names from `/models` come prefixed with "models/"
+        return format!(
+            "{}/models/{}:generateContent",
+            provider.base_url.trim_end_matches('/'),
+            model.trim_start_matches("models/")
+        );
+    }
+    if is_anthropic(provider) {
+        return format!("{}/messages", provider.base_url.trim_end_matches('/'));
+    }
+    match provider.api_style {
+        ApiStyle::OpenAI => format!(
+            "{}/chat/completions",
+            provider.base_url.trim_end_matches('/')
+        ),
+        ApiStyle::Ollama => format!("{}/api/chat", ollama_root(&provider.base_url)),
+    }
+}
+
+fn models_url(provider: &PostProcessProvider) -> String {
+    match provider.api_style {
+        ApiStyle::OpenAI => format!("{}/models", provider.base_url.trim_end_matches('/')),
+        ApiStyle::Ollama => format!("{}/api/tags", ollama_root(&provider.base_url)),
+    }
+}
+
+/// Attach the API key for providers that expect it in the URL.
+fn with_query_key(
+    provider: &PostProcessProvider,
+    request: reqwest::RequestBuilder,
+    api_key: &str,
+) -> reqwest::RequestBuilder {
+    if is_gemini(provider) && !api_key.is_empty() {
+        request.query(&[("key", api_key)])
+    } else {
+        request
+    }
+}
+
+/// Build a chat request body in the provider's schema.
+fn chat_request_body(
+    provider: &PostProcessProvider,
+    model: &str,
+    messages: Vec<ChatMessage>,
+    json_schema: Option<Value>,
+    options: SamplingOptions,
+) -> Result<Value, String> {
+    if is_gemini(provider) {
+        return serde_json::to_value(gemini_request(messages, options))
+            .map_err(|e| format!("Failed to build request: {}", e));
+    }
+    if is_anthropic(provider) {
+        return serde_json::to_value(anthropic_request(model, messages, options))
+            .map_err(|e| format!("Failed to build request: {}", e));
+    }
+    let body = match provider.api_style {
+        ApiStyle::OpenAI => serde_json::to_value(ChatCompletionRequest {
+            model: model.to_string(),
+            messages,
+            response_format: json_schema.map(|schema| ResponseFormat {
+                format_type: "json_schema".to_string(),
+                json_schema: JsonSchema {
+                    name: "transcription_output".to_string(),
+                    strict: true,
+                    schema,
+                },
+            }),
+            temperature: options.temperature,
+            max_tokens: options.max_tokens,
+        }),
+        ApiStyle::Ollama => serde_json::to_value(OllamaChatRequest {
+            model: model.to_string(),
+            messages,
+            stream: false,
+            format: json_schema,
+            options: (options.temperature.is_some() || options.max_tokens.is_some()).then_some(
+                OllamaOptions {
+                    temperature: options.temperature,
+                    num_predict: options.max_tokens,
+                },
+            ),
+        }),
     };
+    body.map_err(|e| format!("Failed to build request: {}", e))
+}
+
+/// Map chat messages onto Gemini's schema: system messages become the system
+/// instruction, assistant turns take the `model` role and consecutive turns
+/// from the same role are joined into one content.
+fn gemini_request(messages: Vec<ChatMessage>, options: SamplingOptions) -> GeminiRequest {
+    let mut system = Vec::new();
+    let mut contents: Vec<GeminiContent> = Vec::new();
+    for message in messages {
+        let role = match message.role.as_str() {
+            "system" => {
+                system.push(message.content);
+                continue;
+            }
+            "assistant" | "model" => "model",
+            _ => "user",
+        };
+        let part = GeminiPart {
+            text: Some(message.content),
+        };
+        match contents.last_mut() {
+            Some(last) if last.role.as_deref() == Some(role) => last.parts.push(part),
+            _ => contents.push(GeminiContent {
+                role: Some(role.to_string()),
+                parts: vec![part],
+            }),
+        }
+    }
+
+    GeminiRequest {
+        contents,
+        system_instruction: (!system.is_empty()).then(|| GeminiContent {
+            role: None,
+            parts: vec![GeminiPart {
+                text: Some(system.join("\n\n")),
+            }],
+        }),
+        generation_config: (options.temperature.is_some() || options.max_tokens.is_some())
+            .then_some(GeminiGenerationConfig {
+                temperature: options.temperature,
+                max_output_tokens: options.max_tokens,
+            }),
+    }
+}
+
+/// Map chat messages onto Anthropic's schema: system messages become the
+/// top-level `system` field and consecutive turns from the same role are
+/// joined, since the API expects user and assistant turns to alternate.
+fn anthropic_request(
+    model: &str,
+    messages: Vec<ChatMessage>,
+    options: SamplingOptions,
+) -> AnthropicRequest {
+    let mut system = Vec::new();
+    let mut turns: Vec<ChatMessage> = Vec::new();
+    for message in messages {
+        let role = match message.role.as_str() {
+            "system" => {
+                system.push(message.content);
+                continue;
+            }
+            "assistant" => "assistant",
+            _ => "user",
+        };
+        match turns.last_mut() {
+            Some(last) if last.role == role => {
+                last.content.push_str("\n\n");
+                last.content.push_str(&message.content);
+            }
+            _ => turns.push(ChatMessage {
+                role: role.to_string(),
+                content: message.content,
+            }),
+        }
+    }
+
+    AnthropicRequest {
+        model: model.to_string(),
+        max_tokens: options.max_tokens.unwrap_or(ANTHROPIC_DEFAULT_MAX_TOKENS),
+        system: (!system.is_empty()).then(|| system.join("\n\n")),
+        messages: turns,
+        temperature: options.temperature,
+    }
+}
+
+/// The reply text of an Anthropic response, joining its text blocks (the
+/// first, `content[0].text`, in the usual case).
+fn parse_anthropic_response(body: &str) -> Result<Option<String>, String> {
+    let response: AnthropicResponse =
+        serde_json::from_str(body).map_err(|e| format!("Failed to parse API response: {}", e))?;
+    let text: Vec<String> = response
+        .content
+        .into_iter()
+        .filter(|block| block.block_type == "text")
+        .filter_map(|block| block.text)
+        .collect();
+    Ok((!text.is_empty()).then(|| text.concat()))
+}
+
+/// The text of Gemini's first candidate, joining its parts.
+fn parse_gemini_response(body: &str) -> Result<Option<String>, String> {
+    let response: GeminiResponse =
+        serde_json::from_str(body).map_err(|e| format!("Failed to parse API response: {}", e))?;
+    Ok(response
+        .candidates
+        .into_iter()
+        .next()
+        .and_then(|candidate| candidate.content)
+        .map(|content| {
+            content
+                .parts
+                .into_iter()
+                .filter_map(|part| part.text)
+                .collect::<String>()
+        }))
+}
 
-    let response = client
-        .post(&url)
+/// Extract the assistant reply from a successful response body.
+fn parse_chat_response(
+    provider: &PostProcessProvider,
+    body: &str,
+) -> Result<Option<String>, String> {
+    if is_gemini(provider) {
+        return parse_gemini_response(body);
+    }
+    if is_anthropic(provider) {
+        return parse_anthropic_response(body);
+    }
+    match provider.api_style {
+        ApiStyle::OpenAI => {
+            let completion: ChatCompletionResponse = serde_json::from_str(body)
+                .map_err(|e| format!("Failed to parse API response: {}", e))?;
+            Ok(completion
+                .choices
+                .first()
+                .and_then(|choice| choice.message.content.clone()))
+        }
+        ApiStyle::Ollama => parse_ollama_chat(body),
+    }
+}
+
+/// Ollama replies with a single JSON object, or with NDJSON chunks when
+/// streaming; the reply is the concatenated message content of every chunk.
+fn parse_ollama_chat(body: &str) -> Result<Option<String>, String> {
+    let mut content: Option<String> = None;
+    for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let chunk: OllamaChatChunk = serde_json::from_str(line)
+            .map_err(|e| format!("Failed to parse API response: {}", e))?;
+        if let Some(error) = chunk.error {
+            return Err(format!("Ollama error: {}", error));
+        }
+        if let Some(text) = chunk.message.and_then(|m| m.content) {
+            content.get_or_insert_with(String::new).push_str(&text);
+        }
+        if chunk.done {
+            break;
+        }
+    }
+    Ok(content)
+}
+
+async fn post_chat(
+    provider: &PostProcessProvider,
+    api_key: &str,
+    model: &str,
+    messages: Vec<ChatMessage>,
+    json_schema: Option<Value>,
+    options: SamplingOptions,
+) -> Result<Option<String>, String> {
+    let client = create_client(provider, api_key)?;
+    let request_body = chat_request_body(provider, model, messages, json_schema, options)?;
+
+    let response = with_query_key(provider, client.post(chat_url(provider, model)), api_key)
         .json(&request_body)
         .send()
         .await
@@ -221,42 +668,140 @@ pub async fn send_chat_messages(
 
     let status = response.status();
     if !status.is_success() {
+        let retry_after = retry_after_secs(response.headers());
         let error_text = response
             .text()
             .await
             .unwrap_or_else(|_| "Failed to read error response".to_string());
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(rate_limit_error(retry_after, &error_text));
+        }
         return Err(format!(
             "API request failed with status {}: {}",
             status, error_text
         ));
     }
 
-    let completion: ChatCompletionResponse = response
-        .json()
+    let body = response
+        .text()
         .await
-        .map_err(|e| format!("Failed to parse API response: {}", e))?;
+        .map_err(|e| format!("Failed to read API response: {}", e))?;
+    parse_chat_response(provider, &body)
+}
+
+/// Seconds to wait before retrying, from `Retry-After` (seconds or an HTTP
+/// date) or, failing that, OpenAI/Groq's `x-ratelimit-reset-*` headers.
+fn retry_after_secs(headers: &HeaderMap) -> Option<u64> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    if let Some(value) = header(reqwest::header::RETRY_AFTER.as_str()) {
+        let value = value.trim();
+        if let Ok(secs) = value.parse::<f64>() {
+            return Some(secs.max(0.0).ceil() as u64);
+        }
+        if let Ok(date) = chrono::DateTime::parse_from_rfc2822(value) {
+            let secs = (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds();
+            return Some(secs.max(0) as u64);
+        }
+    }
 
-    Ok(completion
-        .choices
-        .first()
-        .and_then(|choice| choice.message.content.clone()))
+    ["x-ratelimit-reset-requests", "x-ratelimit-reset-tokens"]
+        .iter()
+        .filter_map(|name| header(name).and_then(parse_duration_secs))
+        .reduce(f64::max)
+        .map(|secs| secs.ceil() as u64)
 }
 
-/// Fetch available models from an OpenAI-compatible API
+/// Parse durations as providers write them: "20", "6.5s", "250ms", "1m30s", "2h".
+fn parse_duration_secs(text: &str) -> Option<f64> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    if let Ok(secs) = text.parse::<f64>() {
+        return Some(secs);
+    }
+
+    let mut total = 0.0;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let value: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        total += value
+            * match &rest[..unit_len] {
+                "ms" => 0.001,
+                "s" => 1.0,
+                "m" => 60.0,
+                "h" => 3600.0,
+                _ => return None,
+            };
+        rest = &rest[unit_len..];
+    }
+    Some(total)
+}
+
+/// A 429 as "Rate limited; retry in 20s: <provider message>", followed by the
+/// raw body. The wait comes from the headers, else from the error JSON
+/// (Gemini's `retryDelay`, or "try again in 20s" in OpenAI-style messages).
+fn rate_limit_error(retry_after: Option<u64>, body: &str) -> String {
+    let parsed: Option<Value> = serde_json::from_str(body).ok();
+    let error = parsed.as_ref().and_then(|v| match v {
+        // Gemini sometimes wraps the error in an array
+        Value::Array(items) => items.first().and_then(|item| item.get("error")),
+        _ => v.get("error"),
+    });
+    let message = error
+        .and_then(|e| e.get("message").or(Some(e)))
+        .and_then(Value::as_str)
+        .map(str::trim);
+
+    let retry_after = retry_after
+        .or_else(|| {
+            error?
+                .get("details")?
+                .as_array()?
+                .iter()
+                .find_map(|d| d.get("retryDelay")?.as_str().and_then(parse_duration_secs))
+                .map(|secs| secs.ceil() as u64)
+        })
+        .or_else(|| {
+            let message = message?;
+            // ASCII lowercasing keeps byte offsets, so `start` indexes `message`
+            let start = message.to_ascii_lowercase().find("try again in ")? + "try again in ".len();
+            let token = message[start..].split_whitespace().next()?;
+            parse_duration_secs(token.trim_end_matches(['.', ','])).map(|secs| secs.ceil() as u64)
+        });
+
+    let mut summary = match retry_after {
+        Some(secs) => format!("Rate limited; retry in {}s", secs),
+        None => "Rate limited".to_string(),
+    };
+    if let Some(message) = message.filter(|m| !m.is_empty()) {
+        summary.push_str(": ");
+        summary.push_str(message);
+    }
+    format!("{}\n\nRaw response: {}", summary, body.trim())
+}
+
+/// Fetch available models from an OpenAI-compatible API (or Ollama's `/api/tags`)
 /// Returns a list of model IDs
 pub async fn fetch_models(
     provider: &PostProcessProvider,
     api_key: String,
 ) -> Result<Vec<String>, String> {
-    let base_url = provider.base_url.trim_end_matches('/');
-    let url = format!("{}/models", base_url);
+    let url = models_url(provider);
 
     debug!("Fetching models from: {}", url);
 
     let client = create_client(provider, &api_key)?;
 
-    let response = client
-        .get(&url)
+    let response = with_query_key(provider, client.get(&url), &api_key)
         .send()
         .await
         .map_err(|e| format!("Failed to fetch models: {}", e))?;
@@ -278,6 +823,11 @@ pub async fn fetch_models(
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
+    Ok(parse_model_list(&parsed))
+}
+
+/// Extract model IDs from a `/models` (or Ollama `/api/tags`) response body.
+fn parse_model_list(parsed: &Value) -> Vec<String> {
     let mut models = Vec::new();
 
     // Handle OpenAI format: { data: [ { id: "..." }, ... ] }
@@ -290,6 +840,14 @@ pub async fn fetch_models(
             }
         }
     }
+    // Handle Ollama and Gemini format: { models: [ { name: "..." }, ... ] }
+    else if let Some(list) = parsed.get("models").and_then(|m| m.as_array()) {
+        for entry in list {
+            if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
+                models.push(name.trim_start_matches("models/").to_string());
+            }
+        }
+    }
     // Handle array format: [ "model1", "model2", ... ]
     else if let Some(array) = parsed.as_array() {
         for entry in array {
@@ -299,5 +857,317 @@ pub async fn fetch_models(
         }
     }
 
-    Ok(models)
+    models
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct ConnectionTestResult {
+    /// `/models` answered successfully.
+    pub models_reachable: bool,
+    /// The configured model appears in the `/models` list.
+    pub model_exists: bool,
+    /// A one-token completion succeeded.
+    pub can_complete: bool,
+    /// Round trip of the completion request.
+    pub latency_ms: u64,
+    /// Why the first failing step failed, if any.
+    pub error: Option<String>,
+}
+
+fn is_auth_error(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN
+}
+
+/// Check a provider's configuration: list models, then send a minimal completion.
+/// Returns Err when the endpoint cannot be reached at all or the API key is rejected;
+/// other failures are reported in the result.
+pub async fn test_connection(
+    provider: &PostProcessProvider,
+    api_key: String,
+    model: &str,
+) -> Result<ConnectionTestResult, String> {
+    let base_url = provider.base_url.trim_end_matches('/');
+    let client = create_client(provider, &api_key)?;
+    let mut result = ConnectionTestResult {
+        models_reachable: false,
+        model_exists: false,
+        can_complete: false,
+        latency_ms: 0,
+        error: None,
+    };
+
+    let models_url = models_url(provider);
+    debug!("Testing connection via: {}", models_url);
+    let response = with_query_key(provider, client.get(&models_url), &api_key)
+        .send()
+        .await
+        .map_err(|e| format!("Endpoint unreachable ({}): {}", base_url, e))?;
+
+    let status = response.status();
+    if is_auth_error(status) {
+        return Err(format!(
+            "API key rejected by {} ({})",
+            provider.label, status
+        ));
+    }
+    if status.is_success() {
+        result.models_reachable = true;
+        if let Ok(parsed) = response.json::<Value>().await {
+            result.model_exists = parse_model_list(&parsed).iter().any(|m| m == model);
+        }
+    } else {
+        // Some OpenAI-compatible servers don't implement /models; still try a completion
+        result.error = Some(format!("Model list request failed ({})", status));
+    }
+
+    let request_body = chat_request_body(
+        provider,
+        model,
+        vec![ChatMessage {
+            role: "user".to_string(),
+            content: "Say hi".to_string(),
+        }],
+        None,
+        SamplingOptions {
+            temperature: None,
+            max_tokens: Some(1),
+        },
+    )?;
+
+    let start = Instant::now();
+    let response = with_query_key(provider, client.post(chat_url(provider, model)), &api_key)
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("Endpoint unreachable ({}): {}", base_url, e))?;
+    result.latency_ms = start.elapsed().as_millis() as u64;
+
+    let status = response.status();
+    if is_auth_error(status) {
+        return Err(format!(
+            "API key rejected by {} ({})",
+            provider.label, status
+        ));
+    }
+    if status.is_success() {
+        result.can_complete = true;
+    } else {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Failed to read error response".to_string());
+        result.error = Some(format!(
+            "Completion request failed with status {}: {}",
+            status, error_text
+        ));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ollama_chat_single_and_streamed() {
+        let single = r#"{"model":"llama3","message":{"role":"assistant","content":"Hello there"},"done":true}"#;
+        assert_eq!(
+            parse_ollama_chat(single).unwrap(),
+            Some("Hello there".to_string())
+        );
+
+        let streamed = concat!(
+            r#"{"message":{"role":"assistant","content":"Hel"},"done":false}"#,
+            "\n",
+            r#"{"message":{"role":"assistant","content":"lo"},"done":false}"#,
+            "\n",
+            r#"{"message":{"role":"assistant","content":""},"done":true,"eval_count":2}"#,
+            "\n",
+        );
+        assert_eq!(
+            parse_ollama_chat(streamed).unwrap(),
+            Some("Hello".to_string())
+        );
+
+        assert!(parse_ollama_chat(r#"{"error":"model not found"}"#).is_err());
+        assert_eq!(parse_ollama_chat("").unwrap(), None);
+    }
+
+    fn message(role: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_gemini_request_maps_roles() {
+        let request = gemini_request(
+            vec![
+                message("system", "Be brief."),
+                message("user", "Hi"),
+                message("assistant", "Hello"),
+                message("user", "Summarise"),
+                message("user", "this entry"),
+            ],
+            SamplingOptions {
+                temperature: None,
+                max_tokens: Some(1),
+            },
+        );
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "contents": [
+                    {"role": "user", "parts": [{"text": "Hi"}]},
+                    {"role": "model", "parts": [{"text": "Hello"}]},
+                    {"role": "user", "parts": [{"text": "Summarise"}, {"text": "this entry"}]},
+                ],
+                "systemInstruction": {"parts": [{"text": "Be brief."}]},
+                "generationConfig": {"maxOutputTokens": 1},
+            })
+        );
+    }
+
+    #[test]
+    fn test_unset_sampling_options_are_not_sent() {
+        let provider = PostProcessProvider {
+            id: "custom".to_string(),
+            label: "Custom".to_string(),
+            base_url: "http://localhost:11434/v1".to_string(),
+            allow_base_url_edit: true,
+            models_endpoint: None,
+            supports_structured_output: false,
+            api_style: ApiStyle::OpenAI,
+        };
+        let body = chat_request_body(
+            &provider,
+            "m",
+            vec![message("user", "Hi")],
+            None,
+            SamplingOptions::default(),
+        )
+        .unwrap();
+        assert!(body.get("temperature").is_none() && body.get("max_tokens").is_none());
+
+        let options = SamplingOptions {
+            temperature: Some(0.2),
+            max_tokens: None,
+        }
+        .overridden_by(None, Some(50));
+        let body =
+            chat_request_body(&provider, "m", vec![message("user", "Hi")], None, options).unwrap();
+        assert_eq!(body["max_tokens"], 50);
+        assert!((body["temperature"].as_f64().unwrap() - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_parse_gemini_response() {
+        let body = r#"{"candidates":[{"content":{"role":"model","parts":[{"text":"Hello"}]},"finishReason":"STOP"}]}"#;
+        assert_eq!(
+            parse_gemini_response(body).unwrap(),
+            Some("Hello".to_string())
+        );
+        assert_eq!(parse_gemini_response(r#"{"candidates":[]}"#).unwrap(), None);
+    }
+
+    #[test]
+    fn test_anthropic_request_and_response() {
+        let provider = PostProcessProvider {
+            id: "anthropic".to_string(),
+            label: "Anthropic".to_string(),
+            base_url: "https://api.anthropic.com/v1".to_string(),
+            allow_base_url_edit: false,
+            models_endpoint: Some("/models".to_string()),
+            supports_structured_output: false,
+            api_style: ApiStyle::OpenAI,
+        };
+        assert_eq!(
+            chat_url(&provider, "claude"),
+            "https://api.anthropic.com/v1/messages"
+        );
+
+        let body = chat_request_body(
+            &provider,
+            "claude",
+            vec![
+                message("system", "Be brief."),
+                message("user", "Summarise"),
+                message("user", "this entry"),
+                message("assistant", "Sure"),
+            ],
+            None,
+            SamplingOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "model": "claude",
+                "max_tokens": ANTHROPIC_DEFAULT_MAX_TOKENS,
+                "system": "Be brief.",
+                "messages": [
+                    {"role": "user", "content": "Summarise\n\nthis entry"},
+                    {"role": "assistant", "content": "Sure"},
+                ],
+            })
+        );
+
+        let response = r#"{"id":"msg_01","type":"message","role":"assistant","model":"claude","content":[{"type":"text","text":"Hello"}],"stop_reason":"end_turn","usage":{"input_tokens":10,"output_tokens":1}}"#;
+        assert_eq!(
+            parse_chat_response(&provider, response).unwrap(),
+            Some("Hello".to_string())
+        );
+        assert_eq!(parse_anthropic_response(r#"{"content":[]}"#).unwrap(), None);
+    }
+
+    #[test]
+    fn test_rate_limit_error_finds_the_wait() {
+        assert_eq!(parse_duration_secs("1m30s"), Some(90.0));
+        assert_eq!(parse_duration_secs("250ms"), Some(0.25));
+        assert_eq!(parse_duration_secs("soon"), None);
+
+        let openai = r#"{"error":{"message":"Rate limit reached. Please try again in 6.5s.","type":"requests"}}"#;
+        let err = rate_limit_error(None, openai);
+        assert!(
+            err.starts_with("Rate limited; retry in 7s: Rate limit reached."),
+            "{}",
+            err
+        );
+        assert!(err.ends_with(openai));
+
+        let gemini = r#"{"error":{"code":429,"message":"Quota exceeded","details":[{"@type":"type.googleapis.com/google.rpc.RetryInfo","retryDelay":"20s"}]}}"#;
+        assert!(rate_limit_error(None, gemini)
+            .starts_with("Rate limited; retry in 20s: Quota exceeded"));
+
+        assert!(rate_limit_error(Some(3), "busy").starts_with("Rate limited; retry in 3s\n"));
+
+        // Lowercasing İ takes an extra byte, which mustn't shift the match
+        let localized = r#"{"error":{"message":"İstek sınırı aşıldı. Try again in 20s."}}"#;
+        assert!(rate_limit_error(None, localized).starts_with("Rate limited; retry in 20s: "));
+    }
+
+    #[test]
+    fn test_check_prompt_fits_reports_overflow() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert!(check_prompt_fits(&"a".repeat(400), 100).is_ok());
+
+        let err = check_prompt_fits(&"a".repeat(500), 100).unwrap_err();
+        assert!(err.contains("125 tokens, 25 over"), "{}", err);
+    }
+
+    #[test]
+    fn test_ollama_urls_accept_openai_style_base() {
+        assert_eq!(
+            ollama_root("http://localhost:11434/v1/"),
+            "http://localhost:11434"
+        );
+        assert_eq!(
+            ollama_root("http://localhost:11434"),
+            "http://localhost:11434"
+        );
+    }
 }
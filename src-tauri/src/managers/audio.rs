@@ -1,11 +1,22 @@
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
+use crate::audio_toolkit::{
+    audio::{
+        db_to_gain, list_loopback_devices, save_archival_wav, ArchivalAudio, CaptureSource,
+        InputLevel, LoopbackDevice, PartialSamples, RecordedAudio,
+    },
+    list_input_devices,
+    vad::SmoothedVad,
+    AudioRecorder, SileroVad,
+};
 use crate::helpers::clamshell;
-use crate::settings::{get_settings, AppSettings};
+use crate::settings::{get_settings, AppSettings, RecordingSource};
 use crate::utils;
 use log::{debug, error, info};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 fn set_mute(mute: bool) {
     // Expected behavior:
@@ -114,9 +125,20 @@ pub enum MicrophoneMode {
 
 /* ──────────────────────────────────────────────────────────────── */
 
+/// Payload of the `audio-level` event, sent about ten times a second while
+/// recording.
+#[derive(Clone, Serialize)]
+struct AudioLevelEvent {
+    binding_id: String,
+    rms: f32,
+    peak: f32,
+}
+
 fn create_audio_recorder(
     vad_path: &str,
     app_handle: &tauri::AppHandle,
+    state: Arc<Mutex<RecordingState>>,
+    started_at: Arc<Mutex<Option<Instant>>>,
 ) -> Result<AudioRecorder, anyhow::Error> {
     let silero = SileroVad::new(vad_path, 0.3)
         .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?;
@@ -132,11 +154,95 @@ fn create_audio_recorder(
             move |levels| {
                 utils::emit_levels(&app_handle, &levels);
             }
+        })
+        .with_meter_callback({
+            let app_handle = app_handle.clone();
+            move |level| {
+                let binding_id = match &*state.lock().unwrap() {
+                    RecordingState::Recording { binding_id } => binding_id.clone(),
+                    RecordingState::Idle => return,
+                };
+                let _ = app_handle.emit(
+                    "audio-level",
+                    AudioLevelEvent {
+                        binding_id,
+                        rms: level.rms,
+                        peak: level.peak,
+                    },
+                );
+                if let Some(started) = *started_at.lock().unwrap() {
+                    let level_db = (20.0 * level.rms.max(1e-5).log10()).max(-100.0);
+                    utils::emit_overlay_update(
+                        &app_handle,
+                        started.elapsed().as_millis() as u64,
+                        level_db,
+                    );
+                }
+            }
         });
 
     Ok(recorder)
 }
 
+fn read_spool_file(path: &Path) -> Vec<f32> {
+    crate::audio_toolkit::audio::read_spooled_wav(path).unwrap_or_else(|e| {
+        error!("Failed to read recording {}: {}", path.display(), e);
+        Vec::new()
+    })
+}
+
+/// Save `audio` as `<dir>/archive/<file_name>` in the archival format from
+/// settings, then remove its spool file. Failures are logged; the 16kHz
+/// recording is what matters.
+pub async fn save_archival_copy(
+    settings: &AppSettings,
+    dir: &std::path::Path,
+    file_name: &str,
+    audio: ArchivalAudio,
+) {
+    let path = crate::audio_save::archival_copy_path(dir, file_name);
+    let (sample_rate, bits_per_sample) =
+        (settings.recording_sample_rate, settings.recording_bit_depth);
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        save_archival_wav(&path, &audio, sample_rate, bits_per_sample).map_err(|e| (path, e))
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err((path, e))) => error!("Failed to save archival copy {}: {}", path.display(), e),
+        Err(e) => error!("Archival copy task failed: {}", e),
+    }
+}
+
+/// Folder that spooled recordings are written to while they are captured.
+/// Anything left here at startup is from a recording that never finished.
+pub fn spool_dir(app: &tauri::AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("recording-spool"))
+}
+
+/// Pad a recording shorter than a second with silence, since the engines
+/// handle very short input poorly.
+pub fn pad_short_recording(mut samples: Vec<f32>) -> Vec<f32> {
+    if samples.len() < WHISPER_SAMPLE_RATE && !samples.is_empty() {
+        samples.resize(WHISPER_SAMPLE_RATE * 5 / 4, 0.0);
+    }
+    samples
+}
+
+/// What the live preview has already transcribed of the current recording, so
+/// each refresh only transcribes audio recorded since.
+#[derive(Default)]
+pub struct PartialTranscript {
+    /// Recording this belongs to; see `AudioRecordingManager::recording_generation`.
+    pub generation: u64,
+    /// Samples already transcribed into `text`.
+    pub committed_samples: usize,
+    pub text: String,
+}
+
 /* ──────────────────────────────────────────────────────────────── */
 
 #[derive(Clone)]
@@ -149,6 +255,18 @@ pub struct AudioRecordingManager {
     is_open: Arc<Mutex<bool>>,
     is_recording: Arc<Mutex<bool>>,
     did_mute: Arc<Mutex<bool>>,
+
+    /// Bumped whenever a recording starts, stops or is cancelled, which
+    /// invalidates `partial_transcript` without waiting for its lock.
+    recording_generation: Arc<AtomicU64>,
+    partial_transcript: Arc<tokio::sync::Mutex<PartialTranscript>>,
+    /// File the current recording is spooled to, if it is.
+    active_spool: Arc<Mutex<Option<PathBuf>>>,
+    /// Archival audio of the last recording, taken from the recorder as it
+    /// stopped (the stream may close right after) until `take_archival_audio`.
+    last_archive: Arc<Mutex<Option<ArchivalAudio>>>,
+    /// When the current recording started, for the overlay's timer.
+    recording_started: Arc<Mutex<Option<Instant>>>,
 }
 
 impl AudioRecordingManager {
@@ -171,8 +289,20 @@ impl AudioRecordingManager {
             is_open: Arc::new(Mutex::new(false)),
             is_recording: Arc::new(Mutex::new(false)),
             did_mute: Arc::new(Mutex::new(false)),
+
+            recording_generation: Arc::new(AtomicU64::new(0)),
+            partial_transcript: Arc::new(tokio::sync::Mutex::new(PartialTranscript::default())),
+            active_spool: Arc::new(Mutex::new(None)),
+            last_archive: Arc::new(Mutex::new(None)),
+            recording_started: Arc::new(Mutex::new(None)),
         };
 
+        // Archival spools are only kept until their recording is saved, so
+        // any left over are from a recording that was interrupted
+        if let Some(dir) = spool_dir(app) {
+            let _ = std::fs::remove_dir_all(dir.join(crate::audio_save::ARCHIVE_DIR));
+        }
+
         // Always-on?  Open immediately.
         if matches!(mode, MicrophoneMode::AlwaysOn) {
             manager.start_microphone_stream()?;
@@ -183,7 +313,8 @@ impl AudioRecordingManager {
 
     /* ---------- helper methods --------------------------------------------- */
 
-    fn get_effective_microphone_device(&self, settings: &AppSettings) -> Option<cpal::Device> {
+    /// Name of the microphone recordings use, or None for the host default.
+    fn effective_microphone_name(settings: &AppSettings) -> Option<&String> {
         // Check if we're in clamshell mode and have a clamshell microphone configured
         let use_clamshell_mic = if let Ok(is_clamshell) = clamshell::is_clamshell() {
             is_clamshell && settings.clamshell_microphone.is_some()
@@ -191,11 +322,21 @@ impl AudioRecordingManager {
             false
         };
 
-        let device_name = if use_clamshell_mic {
-            settings.clamshell_microphone.as_ref().unwrap()
+        if use_clamshell_mic {
+            settings.clamshell_microphone.as_ref()
         } else {
-            settings.selected_microphone.as_ref()?
-        };
+            settings.selected_microphone.as_ref()
+        }
+    }
+
+    /// Linear input gain configured for the microphone recordings use.
+    fn effective_input_gain(settings: &AppSettings) -> f32 {
+        let device = Self::effective_microphone_name(settings).map_or("default", String::as_str);
+        db_to_gain(settings.microphone_gain_db(device))
+    }
+
+    fn get_effective_microphone_device(&self, settings: &AppSettings) -> Option<cpal::Device> {
+        let device_name = Self::effective_microphone_name(settings)?;
 
         // Find the device by name
         match list_input_devices() {
@@ -210,6 +351,26 @@ impl AudioRecordingManager {
         }
     }
 
+    /// The system-audio device from settings, falling back to the default one.
+    fn get_effective_loopback_device(
+        &self,
+        settings: &AppSettings,
+    ) -> Result<LoopbackDevice, anyhow::Error> {
+        let devices = list_loopback_devices().map_err(|e| anyhow::anyhow!(e))?;
+        let selected = settings
+            .selected_loopback_device
+            .as_ref()
+            .and_then(|name| devices.iter().position(|d| d.name == *name));
+        let index = selected
+            .or_else(|| devices.iter().position(|d| d.is_default))
+            .unwrap_or(0);
+        devices
+            .into_iter()
+            .nth(index)
+            .map(|d| d.device)
+            .ok_or_else(|| anyhow::anyhow!("No system audio device found"))
+    }
+
     /* ---------- microphone life-cycle -------------------------------------- */
 
     /// Applies mute if mute_while_recording is enabled and stream is open
@@ -217,7 +378,11 @@ impl AudioRecordingManager {
         let settings = get_settings(&self.app_handle);
         let mut did_mute_guard = self.did_mute.lock().unwrap();
 
-        if settings.mute_while_recording && *self.is_open.lock().unwrap() {
+        // Muting output would silence the system audio being recorded
+        if settings.mute_while_recording
+            && settings.recording_source == RecordingSource::Microphone
+            && *self.is_open.lock().unwrap()
+        {
             set_mute(true);
             *did_mute_guard = true;
             debug!("Mute applied");
@@ -261,15 +426,28 @@ impl AudioRecordingManager {
             *recorder_opt = Some(create_audio_recorder(
                 vad_path.to_str().unwrap(),
                 &self.app_handle,
+                self.state.clone(),
+                self.recording_started.clone(),
             )?);
         }
 
         // Get the selected device from settings, considering clamshell mode
         let settings = get_settings(&self.app_handle);
-        let selected_device = self.get_effective_microphone_device(&settings);
+        let sources = match settings.recording_source {
+            RecordingSource::Microphone => vec![CaptureSource::Microphone(
+                self.get_effective_microphone_device(&settings),
+            )],
+            RecordingSource::System => vec![CaptureSource::Loopback(
+                self.get_effective_loopback_device(&settings)?,
+            )],
+            RecordingSource::Both => vec![
+                CaptureSource::Microphone(self.get_effective_microphone_device(&settings)),
+                CaptureSource::Loopback(self.get_effective_loopback_device(&settings)?),
+            ],
+        };
 
         if let Some(rec) = recorder_opt.as_mut() {
-            rec.open(selected_device)
+            rec.open_sources(sources)
                 .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
         }
 
@@ -332,7 +510,37 @@ impl AudioRecordingManager {
 
     /* ---------- recording --------------------------------------------------- */
 
+    pub fn recording_generation(&self) -> u64 {
+        self.recording_generation.load(Ordering::SeqCst)
+    }
+
+    /// Live-preview state. Held for the whole of a refresh, so a refresh that
+    /// finds it locked should skip rather than queue up.
+    pub fn partial_transcript(&self) -> &tokio::sync::Mutex<PartialTranscript> {
+        &self.partial_transcript
+    }
+
+    fn reset_partial_transcript(&self) {
+        self.recording_generation.fetch_add(1, Ordering::SeqCst);
+        // Free the text now if no refresh is running; otherwise the next
+        // refresh sees the new generation and starts over
+        if let Ok(mut partial) = self.partial_transcript.try_lock() {
+            *partial = PartialTranscript::default();
+        }
+    }
+
     pub fn try_start_recording(&self, binding_id: &str) -> bool {
+        self.start_recording(binding_id, false)
+    }
+
+    /// Like `try_start_recording`, but the audio is written to a WAV in
+    /// `spool_dir` as it is captured rather than held in memory. Use for
+    /// recordings that may run for hours; stop with `stop_spooled_recording`.
+    pub fn try_start_spooled_recording(&self, binding_id: &str) -> bool {
+        self.start_recording(binding_id, true)
+    }
+
+    fn start_recording(&self, binding_id: &str, spooled: bool) -> bool {
         let mut state = self.state.lock().unwrap();
 
         if let RecordingState::Idle = *state {
@@ -345,7 +553,23 @@ impl AudioRecordingManager {
             }
 
             if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
-                if rec.start().is_ok() {
+                let settings = get_settings(&self.app_handle);
+                rec.set_input_gain(Self::effective_input_gain(&settings));
+                rec.set_archive_path(
+                    settings
+                        .wants_archival_copy()
+                        .then(|| self.new_archive_spool_path())
+                        .flatten(),
+                );
+                let spool_path = spooled.then(|| self.new_spool_path()).flatten();
+                let started = match &spool_path {
+                    Some(path) => rec.start_spooled(path.clone()),
+                    None => rec.start(),
+                };
+                if started.is_ok() {
+                    *self.active_spool.lock().unwrap() = spool_path;
+                    *self.recording_started.lock().unwrap() = Some(Instant::now());
+                    self.reset_partial_transcript();
                     *self.is_recording.lock().unwrap() = true;
                     *state = RecordingState::Recording {
                         binding_id: binding_id.to_string(),
@@ -361,6 +585,45 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Record `duration` from the selected microphone on a separate stream and
+    /// return its level, without touching the recording state.
+    pub fn measure_microphone_level(
+        &self,
+        duration: std::time::Duration,
+    ) -> Result<InputLevel, anyhow::Error> {
+        let settings = get_settings(&self.app_handle);
+        let mut recorder = AudioRecorder::new()
+            .map_err(|e| anyhow::anyhow!("Failed to create AudioRecorder: {}", e))?;
+        recorder.set_input_gain(Self::effective_input_gain(&settings));
+        recorder
+            .open(self.get_effective_microphone_device(&settings))
+            .map_err(|e| anyhow::anyhow!("Failed to open microphone: {}", e))?;
+        let samples = recorder
+            .start()
+            .and_then(|_| {
+                std::thread::sleep(duration);
+                recorder.stop()
+            })
+            .and_then(RecordedAudio::into_samples)
+            .map_err(|e| anyhow::anyhow!("Failed to record from microphone: {}", e));
+        let _ = recorder.close();
+        Ok(InputLevel::measure(&samples?))
+    }
+
+    /// Re-read the microphone gain, e.g. after it changed mid-recording.
+    pub fn update_input_gain(&self) {
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            rec.set_input_gain(Self::effective_input_gain(&get_settings(&self.app_handle)));
+        }
+    }
+
+    /// Full-rate audio of the recording that just stopped, when the settings
+    /// asked for an archival copy as it started. Call right after
+    /// `stop_recording`; dropping the result deletes its spool file.
+    pub fn take_archival_audio(&self) -> Option<ArchivalAudio> {
+        self.last_archive.lock().unwrap().take()
+    }
+
     pub fn update_selected_device(&self) -> Result<(), anyhow::Error> {
         // If currently open, restart the microphone stream to use the new device
         if *self.is_open.lock().unwrap() {
@@ -370,7 +633,69 @@ impl AudioRecordingManager {
         Ok(())
     }
 
+    /// A fresh spool file name, creating `spool_dir` if needed.
+    fn new_spool_path(&self) -> Option<PathBuf> {
+        let dir = spool_dir(&self.app_handle)?;
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            error!("Failed to create {}: {}", dir.display(), e);
+            return None;
+        }
+        Some(dir.join(format!(
+            "recording-{}.wav",
+            chrono::Utc::now().timestamp_millis()
+        )))
+    }
+
+    /// A fresh file name for an archival spool, creating its folder (inside
+    /// `spool_dir`, so it is not mistaken for a recoverable recording).
+    fn new_archive_spool_path(&self) -> Option<PathBuf> {
+        let dir = spool_dir(&self.app_handle)?.join(crate::audio_save::ARCHIVE_DIR);
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            error!("Failed to create {}: {}", dir.display(), e);
+            return None;
+        }
+        Some(dir.join(format!(
+            "recording-{}.wav",
+            chrono::Utc::now().timestamp_millis()
+        )))
+    }
+
+    /// Spool files from recordings that never finished (the app crashed or
+    /// was killed mid-recording), oldest first.
+    pub fn recoverable_recordings(&self) -> Vec<PathBuf> {
+        let Some(dir) = spool_dir(&self.app_handle) else {
+            return Vec::new();
+        };
+        let active = self.active_spool.lock().unwrap().clone();
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok().map(|e| e.path()))
+                    .filter(|p| p.extension().is_some_and(|ext| ext == "wav"))
+                    .filter(|p| active.as_ref() != Some(p))
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort();
+        files
+    }
+
     pub fn stop_recording(&self, binding_id: &str) -> Option<Vec<f32>> {
+        let samples = match self.stop_spooled_recording(binding_id)? {
+            RecordedAudio::Samples(samples) => samples,
+            RecordedAudio::File(path) => {
+                let samples = read_spool_file(&path);
+                let _ = std::fs::remove_file(&path);
+                samples
+            }
+        };
+        Some(pad_short_recording(samples))
+    }
+
+    /// Stop a recording and hand back its audio as recorded: the spool file
+    /// for a spooled recording, which the caller then owns, or the samples.
+    /// Short recordings are not padded.
+    pub fn stop_spooled_recording(&self, binding_id: &str) -> Option<RecordedAudio> {
         let mut state = self.state.lock().unwrap();
 
         match *state {
@@ -379,44 +704,41 @@ impl AudioRecordingManager {
             } if active == binding_id => {
                 *state = RecordingState::Idle;
                 drop(state);
+                self.reset_partial_transcript();
 
-                let samples = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
-                    match rec.stop() {
-                        Ok(buf) => buf,
+                let recorded = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+                    let recorded = match rec.stop() {
+                        Ok(recorded) => recorded,
                         Err(e) => {
                             error!("stop() failed: {e}");
-                            Vec::new()
+                            RecordedAudio::Samples(Vec::new())
                         }
-                    }
+                    };
+                    *self.last_archive.lock().unwrap() = rec.take_archive().ok().flatten();
+                    recorded
                 } else {
                     error!("Recorder not available");
-                    Vec::new()
+                    RecordedAudio::Samples(Vec::new())
                 };
 
                 *self.is_recording.lock().unwrap() = false;
+                *self.active_spool.lock().unwrap() = None;
+                *self.recording_started.lock().unwrap() = None;
 
                 // In on-demand mode turn the mic off again
                 if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) {
                     self.stop_microphone_stream();
                 }
 
-                // Pad if very short
-                let s_len = samples.len();
-                // debug!("Got {} samples", s_len);
-                if s_len < WHISPER_SAMPLE_RATE && s_len > 0 {
-                    let mut padded = samples;
-                    padded.resize(WHISPER_SAMPLE_RATE * 5 / 4, 0.0);
-                    Some(padded)
-                } else {
-                    Some(samples)
-                }
+                Some(recorded)
             }
             _ => None,
         }
     }
-    /// Get a clone of the current audio buffer without stopping recording.
+    /// Get the audio recorded so far without stopping recording. For a
+    /// spooled recording only the last minute is returned, with its offset.
     /// Returns None if not currently recording.
-    pub fn get_partial_samples(&self) -> Option<Vec<f32>> {
+    pub fn get_partial_samples(&self) -> Option<PartialSamples> {
         if !*self.is_recording.lock().unwrap() {
             return None;
         }
@@ -433,6 +755,14 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Binding the current recording was started for, if one is running.
+    pub fn active_binding(&self) -> Option<String> {
+        match &*self.state.lock().unwrap() {
+            RecordingState::Recording { binding_id } => Some(binding_id.clone()),
+            RecordingState::Idle => None,
+        }
+    }
+
     pub fn is_recording(&self) -> bool {
         matches!(
             *self.state.lock().unwrap(),
@@ -440,6 +770,14 @@ impl AudioRecordingManager {
         )
     }
 
+    /// How long the current recording has been running, if one is.
+    pub fn recording_elapsed(&self) -> Option<std::time::Duration> {
+        self.recording_started
+            .lock()
+            .unwrap()
+            .map(|started| started.elapsed())
+    }
+
     /// Cancel any ongoing recording without returning audio samples
     pub fn cancel_recording(&self) {
         let mut state = self.state.lock().unwrap();
@@ -447,12 +785,19 @@ impl AudioRecordingManager {
         if let RecordingState::Recording { .. } = *state {
             *state = RecordingState::Idle;
             drop(state);
+            self.reset_partial_transcript();
 
             if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
-                let _ = rec.stop(); // Discard the result
+                // Discard the result, and its archival spool with it
+                if let Ok(RecordedAudio::File(path)) = rec.stop() {
+                    let _ = std::fs::remove_file(path);
+                }
+                let _ = rec.take_archive();
             }
 
             *self.is_recording.lock().unwrap() = false;
+            *self.active_spool.lock().unwrap() = None;
+            *self.recording_started.lock().unwrap() = None;
 
             // In on-demand mode turn the mic off again
             if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) {
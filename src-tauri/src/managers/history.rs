@@ -6,6 +6,7 @@ use rusqlite_migration::{Migrations, M};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::fs;
+use std::io::Write;
 use std::path::PathBuf;
 use tauri::{AppHandle, Emitter, Manager};
 
@@ -32,6 +33,7 @@ static MIGRATIONS: &[M] = &[
     ),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_processed_text TEXT;"),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN detected_language TEXT;"),
 ];
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -44,6 +46,8 @@ pub struct HistoryEntry {
     pub transcription_text: String,
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
+    /// Language detected when transcribing with the language on "auto".
+    pub detected_language: Option<String>,
 }
 
 pub struct HistoryManager {
@@ -185,6 +189,8 @@ impl HistoryManager {
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        detected_language: Option<String>,
+        archive: Option<crate::audio_toolkit::audio::ArchivalAudio>,
     ) -> Result<()> {
         let timestamp = Utc::now().timestamp();
         let file_name = format!("handy-{}.wav", timestamp);
@@ -193,6 +199,16 @@ impl HistoryManager {
         // Save WAV file
         let file_path = self.recordings_dir.join(&file_name);
         save_wav_file(file_path, &audio_samples).await?;
+        if let Some(archive) = archive {
+            let settings = crate::settings::get_settings(&self.app_handle);
+            crate::managers::audio::save_archival_copy(
+                &settings,
+                &self.recordings_dir,
+                &file_name,
+                archive,
+            )
+            .await;
+        }
 
         // Save to database
         self.save_to_database(
@@ -202,6 +218,7 @@ impl HistoryManager {
             transcription_text,
             post_processed_text,
             post_process_prompt,
+            detected_language,
         )?;
 
         // Clean up old entries
@@ -223,11 +240,12 @@ impl HistoryManager {
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        detected_language: Option<String>,
     ) -> Result<()> {
         let conn = self.get_connection()?;
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, detected_language) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt, detected_language],
         )?;
 
         debug!("Saved transcription to database");
@@ -279,6 +297,10 @@ impl HistoryManager {
                     deleted_count += 1;
                 }
             }
+            let _ = fs::remove_file(crate::audio_save::archival_copy_path(
+                &self.recordings_dir,
+                &file_name,
+            ));
         }
 
         Ok(deleted_count)
@@ -356,8 +378,12 @@ impl HistoryManager {
 
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
+        Self::get_history_entries_with_conn(&conn)
+    }
+
+    fn get_history_entries_with_conn(conn: &Connection) -> Result<Vec<HistoryEntry>> {
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, detected_language FROM transcription_history ORDER BY timestamp DESC"
         )?;
 
         let rows = stmt.query_map([], |row| {
@@ -370,6 +396,7 @@ impl HistoryManager {
                 transcription_text: row.get("transcription_text")?,
                 post_processed_text: row.get("post_processed_text")?,
                 post_process_prompt: row.get("post_process_prompt")?,
+                detected_language: row.get("detected_language")?,
             })
         })?;
 
@@ -381,6 +408,12 @@ impl HistoryManager {
         Ok(entries)
     }
 
+    /// Write every history entry, newest first, as CSV.
+    pub fn export_csv(&self, writer: &mut dyn Write) -> Result<()> {
+        let conn = self.get_connection()?;
+        write_csv(&Self::get_history_entries_with_conn(&conn)?, writer)
+    }
+
     pub fn get_latest_entry(&self) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
         Self::get_latest_entry_with_conn(&conn)
@@ -388,7 +421,7 @@ impl HistoryManager {
 
     fn get_latest_entry_with_conn(conn: &Connection) -> Result<Option<HistoryEntry>> {
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, detected_language
              FROM transcription_history
              ORDER BY timestamp DESC
              LIMIT 1",
@@ -405,6 +438,7 @@ impl HistoryManager {
                     transcription_text: row.get("transcription_text")?,
                     post_processed_text: row.get("post_processed_text")?,
                     post_process_prompt: row.get("post_process_prompt")?,
+                    detected_language: row.get("detected_language")?,
                 })
             })
             .optional()?;
@@ -446,7 +480,7 @@ impl HistoryManager {
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, detected_language
              FROM transcription_history WHERE id = ?1",
         )?;
 
@@ -461,6 +495,7 @@ impl HistoryManager {
                     transcription_text: row.get("transcription_text")?,
                     post_processed_text: row.get("post_processed_text")?,
                     post_process_prompt: row.get("post_process_prompt")?,
+                    detected_language: row.get("detected_language")?,
                 })
             })
             .optional()?;
@@ -481,6 +516,10 @@ impl HistoryManager {
                     // Continue with database deletion even if file deletion fails
                 }
             }
+            let _ = fs::remove_file(crate::audio_save::archival_copy_path(
+                &self.recordings_dir,
+                &entry.file_name,
+            ));
         }
 
         // Delete from database
@@ -510,6 +549,45 @@ impl HistoryManager {
     }
 }
 
+const CSV_HEADER: [&str; 8] = [
+    "id",
+    "timestamp",
+    "title",
+    "transcription_text",
+    "post_processed_text",
+    "post_process_prompt",
+    "saved",
+    "file_name",
+];
+
+/// Quote a CSV field when it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_csv(entries: &[HistoryEntry], writer: &mut dyn Write) -> Result<()> {
+    writeln!(writer, "{}", CSV_HEADER.join(","))?;
+    for entry in entries {
+        let fields = [
+            entry.id.to_string(),
+            entry.timestamp.to_string(),
+            csv_field(&entry.title),
+            csv_field(&entry.transcription_text),
+            csv_field(entry.post_processed_text.as_deref().unwrap_or_default()),
+            csv_field(entry.post_process_prompt.as_deref().unwrap_or_default()),
+            entry.saved.to_string(),
+            csv_field(&entry.file_name),
+        ];
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -526,7 +604,8 @@ mod tests {
                 title TEXT NOT NULL,
                 transcription_text TEXT NOT NULL,
                 post_processed_text TEXT,
-                post_process_prompt TEXT
+                post_process_prompt TEXT,
+                detected_language TEXT
             );",
         )
         .expect("create transcription_history table");
@@ -571,4 +650,21 @@ mod tests {
         assert_eq!(entry.transcription_text, "second");
         assert_eq!(entry.post_processed_text.as_deref(), Some("processed"));
     }
+
+    #[test]
+    fn export_csv_quotes_fields_that_need_it() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "plain", None);
+        insert_entry(&conn, 200, "says \"hi\", then\nleaves", Some("Cleaned"));
+
+        let entries = HistoryManager::get_history_entries_with_conn(&conn).unwrap();
+        let mut out = Vec::new();
+        write_csv(&entries, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+
+        let expected = "id,timestamp,title,transcription_text,post_processed_text,post_process_prompt,saved,file_name\n\
+            2,200,Recording 200,\"says \"\"hi\"\", then\nleaves\",Cleaned,,false,handy-200.wav\n\
+            1,100,Recording 100,plain,,,false,handy-100.wav\n";
+        assert_eq!(csv, expected);
+    }
 }
@@ -5,8 +5,13 @@ use rusqlite::{params, Connection, OptionalExtension};
 use rusqlite_migration::{Migrations, M};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
 static MIGRATIONS: &[M] = &[
@@ -71,6 +76,48 @@ static MIGRATIONS: &[M] = &[
     M::up(
         "ALTER TABLE journal_entries ADD COLUMN user_source TEXT NOT NULL DEFAULT '';",
     ),
+    M::up("ALTER TABLE meeting_segments ADD COLUMN confidence REAL;"),
+    M::up(
+        "ALTER TABLE journal_entries ADD COLUMN redo_snapshots TEXT NOT NULL DEFAULT '[]';",
+    ),
+    M::up(
+        "ALTER TABLE journal_entries ADD COLUMN skipped_segments TEXT NOT NULL DEFAULT '[]';",
+    ),
+    M::up(
+        "CREATE INDEX IF NOT EXISTS idx_journal_entries_timestamp ON journal_entries(timestamp);",
+    ),
+    M::up("ALTER TABLE journal_entries ADD COLUMN deleted_at INTEGER;"),
+    M::up("ALTER TABLE journal_folders ADD COLUMN parent_id INTEGER REFERENCES journal_folders(id);"),
+    M::up(
+        "CREATE TABLE IF NOT EXISTS multi_chat_sessions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            chat_entry_ids TEXT NOT NULL DEFAULT '[]',
+            title TEXT NOT NULL DEFAULT '',
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS multi_chat_messages (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id INTEGER NOT NULL REFERENCES multi_chat_sessions(id) ON DELETE CASCADE,
+            role TEXT NOT NULL,
+            content TEXT NOT NULL,
+            created_at INTEGER NOT NULL
+        );",
+    ),
+    M::up(
+        "CREATE TABLE IF NOT EXISTS speaker_profiles (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            embedding TEXT NOT NULL,
+            sample_count INTEGER NOT NULL DEFAULT 1,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );",
+    ),
+    M::up("ALTER TABLE journal_entries ADD COLUMN language TEXT;"),
+    M::up("ALTER TABLE journal_entries ADD COLUMN detected_language TEXT;"),
+    M::up("ALTER TABLE journal_entries ADD COLUMN initial_prompt TEXT;"),
+    M::up("ALTER TABLE journal_entries ADD COLUMN duration_ms INTEGER;"),
 ];
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -90,6 +137,69 @@ pub struct JournalEntry {
     pub source_url: Option<String>,
     pub speaker_names: String,
     pub user_source: String,
+    /// States undone by `undo_last_prompt`, most recent last.
+    pub redo_snapshots: Vec<PromptSnapshot>,
+    /// Language the entry is transcribed in, or `None` to follow the
+    /// selected language in settings.
+    pub language: Option<String>,
+    /// Language detected when the entry was transcribed with the language on
+    /// "auto".
+    pub detected_language: Option<String>,
+    /// Replaces the initial prompt from settings when re-transcribing, or
+    /// `None` to use the one in settings.
+    pub initial_prompt: Option<String>,
+    /// Length of the entry's recording, or `None` for entries without audio.
+    pub duration_ms: Option<i64>,
+}
+
+/// An entry in the trash, with when it was deleted (unix seconds).
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct TrashedEntry {
+    pub entry: JournalEntry,
+    pub deleted_at: i64,
+}
+
+/// A rolling copy of the journal database in the `backups/` directory.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct BackupInfo {
+    pub path: String,
+    pub file_name: String,
+    /// When the backup was taken (unix seconds).
+    pub created_at: i64,
+    pub size_bytes: u64,
+}
+
+/// A named voice enrolled from meeting segments. Its embedding centroid stays
+/// in the database and is only used for matching.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct SpeakerProfile {
+    pub id: i64,
+    pub name: String,
+    /// Number of segments averaged into the centroid.
+    pub sample_count: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Talk-time totals for one speaker in a meeting.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct SpeakerStats {
+    /// `None` for segments with no speaker assigned (reported as "Unknown").
+    pub speaker: Option<i32>,
+    pub name: String,
+    pub total_ms: i64,
+    pub segment_count: i64,
+    pub word_count: i64,
+    /// Share of the meeting's total speaking time, 0.0–100.0.
+    pub percentage: f64,
+}
+
+/// A transcript state together with the prompt that produced it (used for redo).
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct PromptSnapshot {
+    pub text: String,
+    pub prompt_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -98,12 +208,19 @@ pub struct JournalFolder {
     pub name: String,
     pub created_at: i64,
     pub source: String,
+    /// Containing folder, or `None` for a top-level folder.
+    pub parent_id: Option<i64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct JournalRecordingResult {
     pub file_name: String,
     pub transcription_text: String,
+    /// Language the recording was transcribed in, if one was requested; pass
+    /// it to `save_journal_entry` to keep it on the entry.
+    pub language: Option<String>,
+    /// Language detected when transcribing with the language on "auto".
+    pub detected_language: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -116,6 +233,17 @@ pub struct ChatSession {
     pub updated_at: i64,
 }
 
+/// A chat that draws on several journal entries at once. Its messages are
+/// `ChatMessage`s whose `session_id` points at this session.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct MultiEntryChatSession {
+    pub id: i64,
+    pub chat_entry_ids: Vec<i64>,
+    pub title: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct ChatMessage {
     pub id: i64,
@@ -128,7 +256,7 @@ pub struct ChatMessage {
 // --- Filename helpers ---
 
 /// Sanitize a string for use as a filename (replace unsafe chars, trim, limit length).
-fn sanitize_filename(s: &str) -> String {
+pub(crate) fn sanitize_filename(s: &str) -> String {
     let sanitized: String = s
         .chars()
         .map(|c| match c {
@@ -163,6 +291,48 @@ fn unique_path(dir: &Path, base: &str, ext: &str) -> PathBuf {
     dir.join(format!("{} ({}){}", base, ts, ext))
 }
 
+/// Move the archival copy of the recording `file_name` in `src_dir`, if it
+/// has one, to `dest_dir` under `dest_name`.
+fn move_archival_copy(
+    src_dir: &Path,
+    file_name: &str,
+    dest_dir: &Path,
+    dest_name: &str,
+) -> Result<()> {
+    let src = crate::audio_save::archival_copy_path(src_dir, file_name);
+    if !src.exists() {
+        return Ok(());
+    }
+    let dest = crate::audio_save::archival_copy_path(dest_dir, dest_name);
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&src, &dest)?;
+    debug!("Moved archival copy: {:?} -> {:?}", src, dest);
+    Ok(())
+}
+
+/// Commit message for a git-synced entry, e.g.
+/// `Add journal entry "Standup notes" (2025-03-14 09:30)`.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn git_commit_message(action: &str, entry: &JournalEntry) -> String {
+    let when = chrono::DateTime::from_timestamp(entry.timestamp, 0)
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    format!("{} journal entry \"{}\" ({})", action, entry.title, when)
+}
+
+/// Length of a WAV file in milliseconds, read from its header.
+fn wav_duration_ms(path: &Path) -> Option<i64> {
+    let reader = hound::WavReader::open(path).ok()?;
+    let sample_rate = reader.spec().sample_rate as i64;
+    (sample_rate > 0).then(|| reader.duration() as i64 * 1000 / sample_rate)
+}
+
 /// Extract base name from a file_name (strip the extension).
 fn entry_base_name(file_name: &str) -> &str {
     file_name.strip_suffix(".wav").unwrap_or(file_name)
@@ -177,10 +347,278 @@ fn capitalize_first(s: &str) -> String {
     }
 }
 
+// --- Title helpers ---
+
+const AUTO_TITLE_MAX_CHARS: usize = 60;
+/// Shorter transcripts keep the first-sentence title even when LLM titles are enabled.
+const AUTO_TITLE_LLM_MIN_WORDS: usize = 40;
+
+/// Title from the first sentence of `text`, cut at a word boundary to
+/// `AUTO_TITLE_MAX_CHARS` with an ellipsis when it is too long.
+fn first_sentence_title(text: &str) -> String {
+    let text = text.trim();
+    let end = text
+        .char_indices()
+        .find(|&(i, c)| {
+            c == '\n'
+                || (matches!(c, '.' | '!' | '?')
+                    && (i + 1 == text.len() || text[i + 1..].starts_with(char::is_whitespace)))
+        })
+        .map_or(text.len(), |(i, _)| i);
+    let sentence = text[..end].split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if sentence.chars().count() <= AUTO_TITLE_MAX_CHARS {
+        return sentence;
+    }
+
+    // Leave room for the ellipsis
+    let mut title = String::new();
+    for word in sentence.split(' ') {
+        let len = title.chars().count() + usize::from(!title.is_empty()) + word.chars().count();
+        if len > AUTO_TITLE_MAX_CHARS - 1 {
+            break;
+        }
+        if !title.is_empty() {
+            title.push(' ');
+        }
+        title.push_str(word);
+    }
+    if title.is_empty() {
+        // A single word longer than the limit
+        title = sentence.chars().take(AUTO_TITLE_MAX_CHARS - 1).collect();
+    }
+    let mut title = title
+        .trim_end_matches(|c: char| matches!(c, ',' | ';' | ':' | '-'))
+        .to_string();
+    title.push('…');
+    title
+}
+
+/// Ask the configured post-processing LLM for a five-word title.
+async fn llm_title(text: &str, app: &AppHandle) -> Result<String, String> {
+    let settings = crate::settings::get_settings(app);
+    let provider = settings
+        .active_post_process_provider()
+        .ok_or_else(|| "No post-processing provider configured".to_string())?
+        .clone();
+    let api_key = settings
+        .post_process_api_keys
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+    let model = settings
+        .post_process_models
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+    if model.is_empty() {
+        return Err("No model configured for the post-processing provider".to_string());
+    }
+
+    // The opening of the transcript is plenty for a title
+    let excerpt = text
+        .split_whitespace()
+        .take(1500)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let prompt = format!(
+        "Write a title of at most five words summarizing this journal entry. Reply with the title only, without quotes.\n\n{}",
+        excerpt
+    );
+
+    let response = crate::llm_client::send_chat_completion(&provider, api_key, &model, prompt)
+        .await?
+        .ok_or_else(|| "No response from LLM".to_string())?;
+    let title = response
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_matches(|c: char| c == '"' || c == '\'' || c == '*')
+        .trim()
+        .to_string();
+    if title.is_empty() {
+        return Err("LLM returned an empty title".to_string());
+    }
+    Ok(title)
+}
+
+/// YAML frontmatter for a transcript `.md` file. Strings are JSON-quoted,
+/// which YAML reads as double-quoted scalars.
+fn transcript_frontmatter(entry: &JournalEntry) -> Result<String> {
+    let timestamp = chrono::DateTime::from_timestamp(entry.timestamp, 0)
+        .map(|dt| dt.with_timezone(&chrono::Local).to_rfc3339())
+        .ok_or_else(|| anyhow::anyhow!("Invalid timestamp: {}", entry.timestamp))?;
+    Ok(format!(
+        "---\ntitle: {}\ntimestamp: {}\ntags: {}\nsource: {}\nsource_url: {}\n---\n\n",
+        serde_json::to_string(&entry.title)?,
+        timestamp,
+        serde_json::to_string(&entry.tags)?,
+        serde_json::to_string(&entry.source)?,
+        entry
+            .source_url
+            .as_deref()
+            .map(serde_json::to_string)
+            .transpose()?
+            .unwrap_or_else(|| "null".to_string()),
+    ))
+}
+
+/// Body of a transcript `.md` file rendered from the user's template.
+fn render_transcript_template(template: &str, entry: &JournalEntry) -> String {
+    let local = chrono::DateTime::from_timestamp(entry.timestamp, 0)
+        .map(|dt| dt.with_timezone(&chrono::Local));
+    let values = [
+        ("title", entry.title.clone()),
+        (
+            "date",
+            local
+                .map(|dt| dt.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+        ),
+        (
+            "time",
+            local
+                .map(|dt| dt.format("%H:%M").to_string())
+                .unwrap_or_default(),
+        ),
+        ("transcript", entry.transcription_text.clone()),
+        (
+            "post_processed",
+            entry.post_processed_text.clone().unwrap_or_default(),
+        ),
+        (
+            "tags",
+            entry
+                .tags
+                .iter()
+                .map(|t| format!("#{}", t))
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        ("source", entry.source.clone()),
+        ("source_url", entry.source_url.clone().unwrap_or_default()),
+    ];
+    crate::markdown_template::render_template(template, &values)
+}
+
+// --- Subtitle export helpers ---
+
+/// Display name for a speaker, preferring the user-assigned name.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn speaker_name(names: &std::collections::HashMap<String, String>, speaker: Option<i32>) -> String {
+    match speaker {
+        Some(id) => match names.get(&id.to_string()).filter(|n| !n.is_empty()) {
+            Some(name) => name.clone(),
+            None => format!("Speaker {}", id),
+        },
+        None => "Unknown".to_string(),
+    }
+}
+
+/// Label for a speaker, preferring the user-assigned name (e.g. `[Alice]`).
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn speaker_label(
+    names: &std::collections::HashMap<String, String>,
+    speaker: Option<i32>,
+) -> String {
+    format!("[{}]", speaker_name(names, speaker))
+}
+
+/// Format milliseconds as `HH:MM:SS`.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn format_clock_time(ms: i64) -> String {
+    let secs = ms.max(0) / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
+
+/// Render meeting segments as Markdown, merging consecutive segments from the
+/// same speaker into one `**Alice** [00:12:31]: text` paragraph.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn render_meeting_markdown(
+    title: &str,
+    segments: &[crate::diarize::DiarizedSegment],
+    names: &std::collections::HashMap<String, String>,
+) -> String {
+    let mut blocks: Vec<(Option<i32>, i64, String)> = Vec::new();
+    for seg in segments {
+        let text = seg.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        match blocks.last_mut() {
+            Some((speaker, _, block)) if *speaker == seg.speaker => {
+                block.push(' ');
+                block.push_str(text);
+            }
+            _ => blocks.push((seg.speaker, seg.start_ms, text.to_string())),
+        }
+    }
+
+    let mut out = String::new();
+    if !title.trim().is_empty() {
+        out.push_str(&format!("# {}\n\n", title.trim()));
+    }
+    for (speaker, start_ms, text) in blocks {
+        out.push_str(&format!(
+            "**{}** [{}]: {}\n\n",
+            speaker_name(names, speaker),
+            format_clock_time(start_ms),
+            text
+        ));
+    }
+    out
+}
+
+/// Format milliseconds as `HH:MM:SS<sep>mmm` (SRT uses `,`, VTT uses `.`).
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn format_subtitle_time(ms: i64, separator: char) -> String {
+    let ms = ms.max(0);
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        (ms / 60_000) % 60,
+        (ms / 1000) % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+/// How long `update_transcription_text` waits for further updates to the same
+/// entry before writing. The live preview can update many times a second.
+const PENDING_WRITE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Written next to the database by `mark_external_change`, so a running app
+/// (which gets no events from another process) knows to refresh.
+const EXTERNAL_CHANGE_MARKER: &str = "journal.changed";
+
+/// The latest transcription update for an entry that hasn't been written yet.
+struct PendingWrite {
+    text: String,
+    prompt_id: Option<String>,
+    /// Bumped on every update so only the most recently scheduled flush writes.
+    generation: u64,
+}
+
 pub struct JournalManager {
     app_handle: AppHandle,
     recordings_dir: PathBuf,
     db_path: PathBuf,
+    pending_writes: Mutex<HashMap<i64, PendingWrite>>,
+    /// Run git sync commits on the saving thread; see `set_git_sync_inline`.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    git_sync_inline: AtomicBool,
+}
+
+impl Drop for JournalManager {
+    fn drop(&mut self) {
+        self.write_all_pending();
+    }
 }
 
 impl JournalManager {
@@ -198,6 +636,9 @@ impl JournalManager {
             app_handle: app_handle.clone(),
             recordings_dir,
             db_path,
+            pending_writes: Mutex::new(HashMap::new()),
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            git_sync_inline: AtomicBool::new(false),
         };
 
         manager.init_database()?;
@@ -271,8 +712,7 @@ impl JournalManager {
         let root = self.effective_recordings_dir();
         match folder_id {
             Some(fid) => {
-                let name = self.get_folder_name(fid)?;
-                let dir = root.join(&name);
+                let dir = root.join(self.folder_rel_path(fid)?);
                 if !dir.exists() {
                     fs::create_dir_all(&dir)?;
                 }
@@ -292,18 +732,233 @@ impl JournalManager {
                 entry.id, e
             );
         }
+        self.write_obsidian_mirror(entry);
     }
 
     fn _write_transcript_md(&self, entry: &JournalEntry) -> Result<()> {
         let dir = self.resolve_entry_dir(entry.folder_id)?;
         let base = entry_base_name(&entry.file_name);
         let md_path = dir.join(format!("{}.md", base));
-        let content = &entry.transcription_text;
+        let settings = crate::settings::get_settings(&self.app_handle);
+        let body = match settings.markdown_template.as_deref() {
+            Some(template) => render_transcript_template(template, entry),
+            None => entry.transcription_text.clone(),
+        };
+        let content = if settings.markdown_frontmatter {
+            format!("{}{}", transcript_frontmatter(entry)?, body)
+        } else {
+            body
+        };
         fs::write(&md_path, content)?;
         debug!("Wrote transcript markdown: {:?}", md_path);
         Ok(())
     }
 
+    // --- Obsidian vault mirror ---
+
+    /// Configured Obsidian vault, if any.
+    fn obsidian_vault_dir(&self) -> Option<PathBuf> {
+        crate::settings::get_settings(&self.app_handle)
+            .obsidian_vault_path
+            .filter(|p| !p.trim().is_empty())
+            .map(PathBuf::from)
+    }
+
+    /// Mirror an entry into `<vault>/voice-journal/<YYYY-MM-DD>/` when a vault is configured.
+    fn write_obsidian_mirror(&self, entry: &JournalEntry) {
+        let Some(vault) = self.obsidian_vault_dir() else {
+            return;
+        };
+        if let Err(e) = Self::_write_obsidian_mirror(&vault, entry) {
+            error!(
+                "Failed to write Obsidian mirror for entry {}: {}",
+                entry.id, e
+            );
+        }
+    }
+
+    fn _write_obsidian_mirror(vault: &Path, entry: &JournalEntry) -> Result<()> {
+        if !vault.is_dir() {
+            return Err(anyhow::anyhow!(
+                "Obsidian vault does not exist: {:?}",
+                vault
+            ));
+        }
+
+        let date = chrono::DateTime::from_timestamp(entry.timestamp, 0)
+            .map(|dt| dt.with_timezone(&chrono::Local))
+            .ok_or_else(|| anyhow::anyhow!("Invalid timestamp: {}", entry.timestamp))?;
+        let dir = vault
+            .join("voice-journal")
+            .join(date.format("%Y-%m-%d").to_string());
+        fs::create_dir_all(&dir)?;
+
+        // Remove a stale mirror left behind by a title change
+        Self::remove_obsidian_mirror_in(&dir, entry.id);
+
+        let front_matter = format!(
+            "---\nid: {}\ntitle: {}\ntimestamp: {}\ntags: {}\nsource: {}\nlinked_entry_ids: {}\n---\n\n",
+            entry.id,
+            serde_json::to_string(&entry.title)?,
+            date.to_rfc3339(),
+            serde_json::to_string(&entry.tags)?,
+            serde_json::to_string(&entry.source)?,
+            serde_json::to_string(&entry.linked_entry_ids)?,
+        );
+        let md_path = dir.join(format!(
+            "{} - {}.md",
+            entry.id,
+            sanitize_filename(&entry.title)
+        ));
+        fs::write(
+            &md_path,
+            format!("{}{}\n", front_matter, entry.transcription_text),
+        )?;
+        debug!("Wrote Obsidian mirror: {:?}", md_path);
+        Ok(())
+    }
+
+    /// Remove any mirror file for `entry_id` from a vault date directory.
+    fn remove_obsidian_mirror_in(dir: &Path, entry_id: i64) {
+        let prefix = format!("{} - ", entry_id);
+        if let Ok(files) = fs::read_dir(dir) {
+            for file in files.flatten() {
+                if file.file_name().to_string_lossy().starts_with(&prefix) {
+                    let _ = fs::remove_file(file.path());
+                }
+            }
+        }
+    }
+
+    /// Remove an entry's Obsidian mirror file (e.g. when the entry is deleted).
+    fn delete_obsidian_mirror(&self, entry: &JournalEntry) {
+        let Some(vault) = self.obsidian_vault_dir() else {
+            return;
+        };
+        if let Some(date) = chrono::DateTime::from_timestamp(entry.timestamp, 0) {
+            let dir = vault.join("voice-journal").join(
+                date.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+                    .to_string(),
+            );
+            Self::remove_obsidian_mirror_in(&dir, entry.id);
+        }
+    }
+
+    // --- Git sync ---
+
+    /// Repository to commit journal files to, if git sync is set up.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    fn git_sync_repo(&self, require_enabled: bool) -> Option<PathBuf> {
+        let settings = crate::settings::get_settings(&self.app_handle);
+        if (require_enabled && !settings.git_sync_enabled)
+            || settings.git_sync_repo_path.trim().is_empty()
+        {
+            return None;
+        }
+        Some(PathBuf::from(settings.git_sync_repo_path))
+    }
+
+    /// Commit git sync changes before returning rather than in the background,
+    /// for one-shot CLI runs that exit as soon as the entry is saved.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub fn set_git_sync_inline(&self, inline: bool) {
+        self.git_sync_inline.store(inline, Ordering::Relaxed);
+    }
+
+    /// Path of the file `mark_external_change` writes.
+    pub fn external_change_marker(&self) -> PathBuf {
+        self.db_path.with_file_name(EXTERNAL_CHANGE_MARKER)
+    }
+
+    /// Let a running app know that this process changed the journal.
+    pub fn mark_external_change(&self) {
+        let stamp = Utc::now().timestamp_millis().to_string();
+        if let Err(e) = fs::write(self.external_change_marker(), stamp) {
+            warn!("Failed to signal the journal change: {}", e);
+        }
+    }
+
+    /// Commit `paths` (plus the entry's Obsidian mirror) when git sync is on.
+    /// Runs in the background so a slow repository doesn't hold up saving,
+    /// unless `set_git_sync_inline` is on.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    fn git_sync_entry(&self, entry: &JournalEntry, action: &str, mut paths: Vec<PathBuf>) {
+        let Some(repo) = self.git_sync_repo(true) else {
+            return;
+        };
+        if let Some(vault) = self.obsidian_vault_dir() {
+            if let Some(date) = chrono::DateTime::from_timestamp(entry.timestamp, 0) {
+                paths.push(
+                    vault.join("voice-journal").join(
+                        date.with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d")
+                            .to_string(),
+                    ),
+                );
+            }
+        }
+        let message = git_commit_message(action, entry);
+        let commit = move || {
+            if let Err(e) = crate::git_sync::commit(&repo, &paths, &message) {
+                warn!("Git sync failed: {}", e);
+            }
+        };
+        if self.git_sync_inline.load(Ordering::Relaxed) {
+            commit();
+        } else {
+            tauri::async_runtime::spawn_blocking(commit);
+        }
+    }
+
+    /// Git sync needs the git CLI, which mobile doesn't have.
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    fn git_sync_entry(&self, _entry: &JournalEntry, _action: &str, _paths: Vec<PathBuf>) {}
+
+    /// Commit every change in the journal folder (and its Obsidian mirror) now.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn git_sync_all(&self) -> Result<crate::git_sync::GitSyncStatus> {
+        let repo = self
+            .git_sync_repo(false)
+            .ok_or_else(|| anyhow::anyhow!("No git sync repository configured"))?;
+        let mut paths = vec![self.effective_recordings_dir()];
+        if let Some(vault) = self.obsidian_vault_dir() {
+            paths.push(vault.join("voice-journal"));
+        }
+        let message = format!(
+            "Sync journal ({})",
+            chrono::Local::now().format("%Y-%m-%d %H:%M")
+        );
+        tauri::async_runtime::spawn_blocking(move || {
+            crate::git_sync::commit(&repo, &paths, &message)
+        })
+        .await?
+    }
+
+    /// Write Obsidian mirror files for every journal entry. Returns the number written.
+    pub async fn sync_all_to_obsidian(&self) -> Result<usize> {
+        let vault = self
+            .obsidian_vault_dir()
+            .ok_or_else(|| anyhow::anyhow!("No Obsidian vault configured"))?;
+
+        let entries = self.get_entries().await?;
+        let mut written = 0;
+        for entry in &entries {
+            match Self::_write_obsidian_mirror(&vault, entry) {
+                Ok(()) => written += 1,
+                Err(e) => warn!("Failed to sync entry {} to Obsidian: {}", entry.id, e),
+            }
+        }
+
+        info!(
+            "Synced {}/{} journal entries to Obsidian vault {:?}",
+            written,
+            entries.len(),
+            vault
+        );
+        Ok(written)
+    }
+
     /// Write a chat session's messages to a markdown file.
     pub fn write_chat_md(
         &self,
@@ -411,32 +1066,43 @@ impl JournalManager {
             return Ok(entry.file_name.clone());
         }
 
-        // Rename audio file
         let new_wav_path = unique_path(&dir, &new_base, ".wav");
         let new_wav_name = new_wav_path
             .file_name()
             .ok_or_else(|| anyhow::anyhow!("Path has no filename: {:?}", new_wav_path))?
             .to_string_lossy()
             .to_string();
-        let old_wav_path = dir.join(&entry.file_name);
+        Self::rename_entry_files_in(&dir, &entry.file_name, &new_wav_name)?;
+
+        Ok(new_wav_name)
+    }
+
+    /// Rename the audio, archival copy, transcript and chat/jot files of the
+    /// entry stored as `old_name` in `dir` to go with `new_name`.
+    fn rename_entry_files_in(dir: &Path, old_name: &str, new_name: &str) -> Result<()> {
+        // Rename audio file
+        let old_wav_path = dir.join(old_name);
+        let new_wav_path = dir.join(new_name);
         if old_wav_path.exists() {
             fs::rename(&old_wav_path, &new_wav_path)?;
             debug!("Renamed audio: {:?} -> {:?}", old_wav_path, new_wav_path);
         }
+        move_archival_copy(dir, old_name, dir, new_name)?;
 
         // Rename transcript .md
+        let old_base = entry_base_name(old_name);
+        let new_base = entry_base_name(new_name);
         let old_md = dir.join(format!("{}.md", old_base));
-        let actual_new_base = entry_base_name(&new_wav_name);
-        let new_md = dir.join(format!("{}.md", actual_new_base));
+        let new_md = dir.join(format!("{}.md", new_base));
         if old_md.exists() {
             fs::rename(&old_md, &new_md)?;
             debug!("Renamed transcript: {:?} -> {:?}", old_md, new_md);
         }
 
         // Rename chat/jot .md files (find by prefix)
-        if let Ok(read_dir) = fs::read_dir(&dir) {
+        if let Ok(read_dir) = fs::read_dir(dir) {
             let old_prefix = format!("{} - ", old_base);
-            let new_prefix = format!("{} - ", actual_new_base);
+            let new_prefix = format!("{} - ", new_base);
             for dir_entry in read_dir.flatten() {
                 let name = dir_entry.file_name().to_string_lossy().to_string();
                 if name.starts_with(&old_prefix) && name.ends_with(".md") {
@@ -453,7 +1119,52 @@ impl JournalManager {
             }
         }
 
-        Ok(new_wav_name)
+        Ok(())
+    }
+
+    /// Move a trashed entry's files from `trash_dir` back into `dest_dir`. If a
+    /// live entry has since taken its name there, the files are renamed to the
+    /// next free name first. Returns the file name the entry ends up with.
+    fn restore_entry_files(
+        entry: &JournalEntry,
+        trash_dir: &Path,
+        dest_dir: &Path,
+    ) -> Result<String> {
+        if entry.file_name.is_empty() {
+            return Ok(String::new());
+        }
+
+        let base = entry_base_name(&entry.file_name);
+        let taken = dest_dir.join(&entry.file_name).exists()
+            || dest_dir.join(format!("{}.md", base)).exists();
+        let file_name = if taken {
+            let path = Path::new(&entry.file_name);
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let ext = path
+                .extension()
+                .map(|e| format!(".{}", e.to_string_lossy()))
+                .unwrap_or_default();
+            let free = unique_path(dest_dir, &stem, &ext);
+            let free_name = free
+                .file_name()
+                .ok_or_else(|| anyhow::anyhow!("Path has no filename: {:?}", free))?
+                .to_string_lossy()
+                .to_string();
+            Self::rename_entry_files_in(trash_dir, &entry.file_name, &free_name)?;
+            free_name
+        } else {
+            entry.file_name.clone()
+        };
+
+        let restored = JournalEntry {
+            file_name: file_name.clone(),
+            ..entry.clone()
+        };
+        Self::move_entry_files_between(&restored, trash_dir, dest_dir)?;
+        Ok(file_name)
     }
 
     /// Move all files for an entry between folders (audio, transcript md, chat/jot mds).
@@ -465,8 +1176,17 @@ impl JournalManager {
     ) -> Result<()> {
         let src_dir = self.resolve_entry_dir(old_folder_id)?;
         let dest_dir = self.resolve_entry_dir(new_folder_id)?;
+        Self::move_entry_files_between(entry, &src_dir, &dest_dir)
+    }
 
-        if src_dir == dest_dir {
+    /// Move an entry's audio, transcript and chat/jot files between directories.
+    fn move_entry_files_between(
+        entry: &JournalEntry,
+        src_dir: &Path,
+        dest_dir: &Path,
+    ) -> Result<()> {
+        // Entries still being processed have no files yet
+        if src_dir == dest_dir || entry.file_name.is_empty() {
             return Ok(());
         }
 
@@ -479,6 +1199,7 @@ impl JournalManager {
             fs::rename(&src_wav, &dest_wav)?;
             debug!("Moved audio: {:?} -> {:?}", src_wav, dest_wav);
         }
+        move_archival_copy(src_dir, &entry.file_name, dest_dir, &entry.file_name)?;
 
         // Move transcript .md
         let src_md = src_dir.join(format!("{}.md", base));
@@ -505,15 +1226,12 @@ impl JournalManager {
         Ok(())
     }
 
-    /// Delete all associated files for an entry (audio, transcript md, chat/jot mds).
-    fn delete_all_entry_files(&self, entry: &JournalEntry) {
-        let dir = match self.resolve_entry_dir(entry.folder_id) {
-            Ok(d) => d,
-            Err(e) => {
-                error!("Failed to resolve entry dir for deletion: {}", e);
-                return;
-            }
-        };
+    /// Delete all associated files for an entry (audio, transcript md, chat/jot mds)
+    /// from `dir`.
+    fn delete_entry_files_in(entry: &JournalEntry, dir: &Path) {
+        if entry.file_name.is_empty() {
+            return;
+        }
 
         let base = entry_base_name(&entry.file_name);
 
@@ -524,6 +1242,12 @@ impl JournalManager {
                 error!("Failed to delete audio {:?}: {}", wav_path, e);
             }
         }
+        let archive_path = crate::audio_save::archival_copy_path(dir, &entry.file_name);
+        if archive_path.exists() {
+            if let Err(e) = fs::remove_file(&archive_path) {
+                error!("Failed to delete archival copy {:?}: {}", archive_path, e);
+            }
+        }
 
         // Delete transcript .md
         let md_path = dir.join(format!("{}.md", base));
@@ -535,7 +1259,7 @@ impl JournalManager {
 
         // Delete chat/jot .md files
         let prefix = format!("{} - ", base);
-        if let Ok(read_dir) = fs::read_dir(&dir) {
+        if let Ok(read_dir) = fs::read_dir(dir) {
             for dir_entry in read_dir.flatten() {
                 let name = dir_entry.file_name().to_string_lossy().to_string();
                 if name.starts_with(&prefix) && name.ends_with(".md") {
@@ -595,24 +1319,83 @@ impl JournalManager {
         folder_id: Option<i64>,
     ) -> Result<PathBuf> {
         let root = self.effective_recordings_dir();
+        let root_path = root.join(file_name);
         if let Some(fid) = folder_id {
-            let conn = self.get_connection()?;
-            let folder_name: Option<String> = conn
-                .query_row(
-                    "SELECT name FROM journal_folders WHERE id = ?1",
-                    [fid],
-                    |row| row.get(0),
-                )
-                .optional()?;
-            if let Some(name) = folder_name {
-                let folder_path = root.join(&name).join(file_name);
-                if folder_path.exists() {
+            if let Ok(rel_path) = self.folder_rel_path(fid) {
+                let folder_path = root.join(rel_path).join(file_name);
+                // Older entries kept their audio in the root; a file that is in
+                // neither place (e.g. imported without audio) is reported where
+                // it would be in the folder, and callers check it exists
+                if folder_path.exists() || !root_path.exists() {
                     return Ok(folder_path);
                 }
             }
         }
-        // Fallback to root
-        Ok(root.join(file_name))
+        Ok(root_path)
+    }
+
+    /// Derive a title from transcription text. Uses the first sentence, or with
+    /// `use_llm` a short LLM summary for longer entries (falling back to the first
+    /// sentence if the LLM call fails).
+    pub async fn generate_title_from_text(text: &str, use_llm: bool, app: &AppHandle) -> String {
+        let fallback = first_sentence_title(text);
+        if !use_llm || text.split_whitespace().count() < AUTO_TITLE_LLM_MIN_WORDS {
+            return fallback;
+        }
+        match llm_title(text, app).await {
+            Ok(title) => title,
+            Err(e) => {
+                warn!("LLM title generation failed, using first sentence: {}", e);
+                fallback
+            }
+        }
+    }
+
+    /// Fill in a blank title from the transcription when `auto_generate_title` is on.
+    /// Returns the title to store and whether an LLM title should be fetched afterwards.
+    async fn auto_title(&self, title: String, transcription_text: &str) -> (String, bool) {
+        let settings = crate::settings::get_settings(&self.app_handle);
+        if !settings.auto_generate_title
+            || !title.trim().is_empty()
+            || transcription_text.trim().is_empty()
+        {
+            return (title, false);
+        }
+        let title =
+            Self::generate_title_from_text(transcription_text, false, &self.app_handle).await;
+        (title, settings.auto_title_use_llm)
+    }
+
+    /// Replace an auto-generated title with an LLM title in the background. The
+    /// entry is left alone if the user renamed it in the meantime.
+    fn spawn_llm_title(&self, id: i64, current_title: String, transcription_text: String) {
+        let app = self.app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let title = Self::generate_title_from_text(&transcription_text, true, &app).await;
+            if title == current_title {
+                return;
+            }
+            let journal_manager = app.state::<Arc<JournalManager>>();
+            match journal_manager.get_entry_by_id(id).await {
+                Ok(Some(entry)) if entry.title == current_title => {
+                    if let Err(e) = journal_manager
+                        .update_entry(
+                            id,
+                            title,
+                            entry.tags,
+                            entry.linked_entry_ids,
+                            entry.folder_id,
+                            entry.user_source,
+                        )
+                        .await
+                    {
+                        error!("Failed to update generated title for entry {}: {}", id, e);
+                    }
+                }
+                Ok(_) => debug!("Entry {} was renamed or deleted, keeping its title", id),
+                Err(e) => error!("Failed to load entry {} for title update: {}", id, e),
+            }
+        });
     }
 
     pub async fn save_entry(
@@ -654,6 +1437,7 @@ impl JournalManager {
         source: String,
         source_url: Option<String>,
     ) -> Result<JournalEntry> {
+        let (title, use_llm_title) = self.auto_title(title, &transcription_text).await;
         let timestamp = Utc::now().timestamp();
         let tags_json = serde_json::to_string(&tags)?;
         let linked_json = serde_json::to_string(&linked_entry_ids)?;
@@ -664,17 +1448,16 @@ impl JournalManager {
         let sanitized = sanitize_filename(&title);
         let dest_dir = match folder_id {
             Some(fid) => {
-                let name = self.get_folder_name(fid)?;
-                let dir = root.join(&name);
+                let dir = root.join(self.folder_rel_path(fid)?);
                 if !dir.exists() {
                     fs::create_dir_all(&dir)?;
                 }
                 dir
             }
-            None => root,
+            None => root.clone(),
         };
 
-        let new_file_name = if !file_name.is_empty() && src_path.is_file() {
+        let (new_file_name, duration_ms) = if !file_name.is_empty() && src_path.is_file() {
             let new_wav_path = unique_path(&dest_dir, &sanitized, ".wav");
             let name = new_wav_path
                 .file_name()
@@ -686,16 +1469,23 @@ impl JournalManager {
                 "Renamed audio to title-based: {:?} -> {:?}",
                 src_path, new_wav_path
             );
-            name
+            move_archival_copy(&root, &file_name, &dest_dir, &name)?;
+            (name, wav_duration_ms(&new_wav_path))
         } else {
             // No audio file (e.g. pending entry or YouTube transcript) — use sanitized title as file_name
-            format!("{}.md", sanitized)
+            let md_path = unique_path(&dest_dir, &sanitized, ".md");
+            let name = md_path
+                .file_name()
+                .ok_or_else(|| anyhow::anyhow!("Path has no filename: {:?}", md_path))?
+                .to_string_lossy()
+                .to_string();
+            (name, None)
         };
 
         let conn = self.get_connection()?;
         conn.execute(
-            "INSERT INTO journal_entries (file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, source, source_url) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-            params![new_file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags_json, linked_json, folder_id, source, source_url],
+            "INSERT INTO journal_entries (file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, source, source_url, duration_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![new_file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags_json, linked_json, folder_id, source, source_url, duration_ms],
         )?;
 
         let id = conn.last_insert_rowid();
@@ -717,46 +1507,184 @@ impl JournalManager {
             source_url,
             speaker_names: "{}".to_string(),
             user_source: String::new(),
+            redo_snapshots: vec![],
+            language: None,
+            detected_language: None,
+            initial_prompt: None,
+            duration_ms,
         };
 
         // Write transcript markdown file
         self.write_transcript_md(&entry);
 
+        if let Ok(dir) = self.resolve_entry_dir(entry.folder_id) {
+            let md = dir.join(format!("{}.md", entry_base_name(&entry.file_name)));
+            self.git_sync_entry(&entry, "Add", vec![dir.join(&entry.file_name), md]);
+        }
+
         if let Err(e) = self.app_handle.emit("journal-updated", ()) {
             error!("Failed to emit journal-updated event: {}", e);
         }
 
+        if use_llm_title {
+            self.spawn_llm_title(
+                entry.id,
+                entry.title.clone(),
+                entry.transcription_text.clone(),
+            );
+        }
+
         Ok(entry)
     }
 
-    fn parse_entry_row(row: &rusqlite::Row) -> rusqlite::Result<JournalEntry> {
-        let tags_json: String = row.get("tags")?;
-        let linked_json: String = row.get("linked_entry_ids")?;
-        let snapshots_json: String = row.get("transcript_snapshots")?;
-        let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
-        let linked_entry_ids: Vec<i64> = serde_json::from_str(&linked_json).unwrap_or_default();
-        let transcript_snapshots: Vec<String> =
-            serde_json::from_str(&snapshots_json).unwrap_or_default();
-        Ok(JournalEntry {
-            id: row.get("id")?,
-            file_name: row.get("file_name")?,
-            timestamp: row.get("timestamp")?,
-            title: row.get("title")?,
-            transcription_text: row.get("transcription_text")?,
-            post_processed_text: row.get("post_processed_text")?,
-            post_process_prompt_id: row.get("post_process_prompt_id")?,
-            tags,
-            linked_entry_ids,
-            folder_id: row.get("folder_id")?,
-            transcript_snapshots,
+    /// Save a parsed markdown note as a voice entry without audio. The note's
+    /// own timestamp is kept when it has one. Doesn't emit `journal-updated`, so
+    /// callers importing a batch can emit once at the end.
+    pub async fn import_markdown_note(
+        &self,
+        note: crate::markdown_note::MarkdownNote,
+        folder_id: Option<i64>,
+    ) -> Result<JournalEntry> {
+        let timestamp = note.timestamp.unwrap_or_else(|| Utc::now().timestamp());
+        let dest_dir = self.resolve_entry_dir(folder_id)?;
+        let md_path = unique_path(&dest_dir, &sanitize_filename(&note.title), ".md");
+        let file_name = md_path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("Path has no filename: {:?}", md_path))?
+            .to_string_lossy()
+            .to_string();
+        let source = "voice".to_string();
+
+        let conn = self.get_connection()?;
+        conn.execute(
+            "INSERT INTO journal_entries (file_name, timestamp, title, transcription_text, tags, linked_entry_ids, folder_id, source) VALUES (?1, ?2, ?3, ?4, ?5, '[]', ?6, ?7)",
+            params![file_name, timestamp, note.title, note.body, serde_json::to_string(&note.tags)?, folder_id, source],
+        )?;
+        let id = conn.last_insert_rowid();
+        debug!("Imported markdown note as journal entry {}", id);
+
+        let entry = self
+            .get_entry_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Entry not found"))?;
+        self.write_transcript_md(&entry);
+        Ok(entry)
+    }
+
+    /// Copy an entry, its audio and its transcript `.md` into a new entry titled
+    /// "<title> (copy)" in the same folder. Prompt history is reset on the copy,
+    /// and chat sessions stay with the original. Meeting segments are copied so
+    /// the duplicate keeps its speaker view.
+    pub async fn duplicate_entry(&self, id: i64) -> Result<JournalEntry> {
+        let original = self
+            .get_entry_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Entry not found"))?;
+        let title = format!("{} (copy)", original.title);
+        let dir = self.resolve_entry_dir(original.folder_id)?;
+        let base = sanitize_filename(&title);
+
+        let src_wav =
+            self.get_audio_file_path_in_folder(&original.file_name, original.folder_id)?;
+        let dest_path = if original.file_name.ends_with(".wav") && src_wav.is_file() {
+            let dest = unique_path(&dir, &base, ".wav");
+            fs::copy(&src_wav, &dest)?;
+            dest
+        } else {
+            unique_path(&dir, &base, ".md")
+        };
+        let file_name = dest_path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("Path has no filename: {:?}", dest_path))?
+            .to_string_lossy()
+            .to_string();
+
+        let new_id = {
+            let mut conn = self.get_connection()?;
+            let tx = conn.transaction()?;
+            tx.execute(
+                "INSERT INTO journal_entries (file_name, timestamp, title, transcription_text, post_processed_text, tags, linked_entry_ids, folder_id, source, source_url, speaker_names, user_source, duration_ms, language, detected_language, initial_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    file_name,
+                    original.timestamp,
+                    title,
+                    original.transcription_text,
+                    original.post_processed_text,
+                    serde_json::to_string(&original.tags)?,
+                    serde_json::to_string(&original.linked_entry_ids)?,
+                    original.folder_id,
+                    original.source,
+                    original.source_url,
+                    original.speaker_names,
+                    original.user_source,
+                    original.duration_ms,
+                    original.language,
+                    original.detected_language,
+                    original.initial_prompt,
+                ],
+            )?;
+            let new_id = tx.last_insert_rowid();
+            tx.execute(
+                "UPDATE journal_entries SET skipped_segments = (SELECT skipped_segments FROM journal_entries WHERE id = ?2) WHERE id = ?1",
+                params![new_id, id],
+            )?;
+            tx.execute(
+                "INSERT INTO meeting_segments (entry_id, speaker, start_ms, end_ms, text, confidence) SELECT ?1, speaker, start_ms, end_ms, text, confidence FROM meeting_segments WHERE entry_id = ?2 ORDER BY start_ms ASC",
+                params![new_id, id],
+            )?;
+            tx.commit()?;
+            new_id
+        };
+        debug!("Duplicated journal entry {} as {}", id, new_id);
+
+        let entry = self
+            .get_entry_by_id(new_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Entry not found"))?;
+        // Generated rather than copied, so its title is the copy's
+        self.write_transcript_md(&entry);
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+        Ok(entry)
+    }
+
+    fn parse_entry_row(row: &rusqlite::Row) -> rusqlite::Result<JournalEntry> {
+        let tags_json: String = row.get("tags")?;
+        let linked_json: String = row.get("linked_entry_ids")?;
+        let snapshots_json: String = row.get("transcript_snapshots")?;
+        let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+        let linked_entry_ids: Vec<i64> = serde_json::from_str(&linked_json).unwrap_or_default();
+        let transcript_snapshots: Vec<String> =
+            serde_json::from_str(&snapshots_json).unwrap_or_default();
+        let redo_json: String = row.get("redo_snapshots")?;
+        let redo_snapshots: Vec<PromptSnapshot> =
+            serde_json::from_str(&redo_json).unwrap_or_default();
+        Ok(JournalEntry {
+            id: row.get("id")?,
+            file_name: row.get("file_name")?,
+            timestamp: row.get("timestamp")?,
+            title: row.get("title")?,
+            transcription_text: row.get("transcription_text")?,
+            post_processed_text: row.get("post_processed_text")?,
+            post_process_prompt_id: row.get("post_process_prompt_id")?,
+            tags,
+            linked_entry_ids,
+            folder_id: row.get("folder_id")?,
+            transcript_snapshots,
             source: row.get("source")?,
             source_url: row.get("source_url")?,
             speaker_names: row.get("speaker_names")?,
             user_source: row.get("user_source")?,
+            redo_snapshots,
+            language: row.get("language")?,
+            detected_language: row.get("detected_language")?,
+            initial_prompt: row.get("initial_prompt")?,
+            duration_ms: row.get("duration_ms")?,
         })
     }
 
-    #[allow(dead_code)]
     pub async fn get_entries(&self) -> Result<Vec<JournalEntry>> {
         self.get_entries_by_source(None).await
     }
@@ -767,7 +1695,7 @@ impl JournalManager {
 
         let placeholders: Vec<String> = (1..=sources.len()).map(|i| format!("?{}", i)).collect();
         let sql = format!(
-            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source FROM journal_entries WHERE source IN ({}) ORDER BY timestamp DESC",
+            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms FROM journal_entries WHERE source IN ({}) AND deleted_at IS NULL ORDER BY timestamp DESC",
             placeholders.join(", ")
         );
         let mut stmt = conn.prepare(&sql)?;
@@ -780,7 +1708,32 @@ impl JournalManager {
             entries.push(row?);
         }
 
-        Ok(entries)
+        Ok(self.with_pending_writes(entries))
+    }
+
+    /// Entries with a recording between `min_ms` and `max_ms` long (inclusive),
+    /// newest first. Entries without audio have no duration and are left out.
+    pub async fn get_entries_in_duration_range(
+        &self,
+        min_ms: i64,
+        max_ms: i64,
+    ) -> Result<Vec<JournalEntry>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms FROM journal_entries WHERE duration_ms BETWEEN ?1 AND ?2 AND deleted_at IS NULL ORDER BY timestamp DESC",
+        )?;
+        let rows = stmt.query_map(params![min_ms, max_ms], |row| Self::parse_entry_row(row))?;
+        Ok(self.with_pending_writes(rows.collect::<rusqlite::Result<_>>()?))
+    }
+
+    /// Entries directly inside a folder (not its subfolders), newest first.
+    pub async fn get_entries_in_folder(&self, folder_id: i64) -> Result<Vec<JournalEntry>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms FROM journal_entries WHERE folder_id = ?1 AND deleted_at IS NULL ORDER BY timestamp DESC",
+        )?;
+        let rows = stmt.query_map([folder_id], |row| Self::parse_entry_row(row))?;
+        Ok(self.with_pending_writes(rows.collect::<rusqlite::Result<_>>()?))
     }
 
     pub async fn get_entries_by_source(
@@ -793,7 +1746,7 @@ impl JournalManager {
         match source_filter {
             Some(source) => {
                 let mut stmt = conn.prepare(
-                    "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source FROM journal_entries WHERE source = ?1 ORDER BY timestamp DESC",
+                    "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms FROM journal_entries WHERE source = ?1 AND deleted_at IS NULL ORDER BY timestamp DESC",
                 )?;
                 let rows = stmt.query_map([source], |row| Self::parse_entry_row(row))?;
                 for row in rows {
@@ -802,7 +1755,7 @@ impl JournalManager {
             }
             None => {
                 let mut stmt = conn.prepare(
-                    "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source FROM journal_entries ORDER BY timestamp DESC",
+                    "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms FROM journal_entries WHERE deleted_at IS NULL ORDER BY timestamp DESC",
                 )?;
                 let rows = stmt.query_map([], |row| Self::parse_entry_row(row))?;
                 for row in rows {
@@ -811,13 +1764,185 @@ impl JournalManager {
             }
         }
 
-        Ok(entries)
+        Ok(self.with_pending_writes(entries))
+    }
+
+    /// Entries matching all of `tags` within an optional `[from_ts, to_ts]` range
+    /// (unix seconds, inclusive), newest first.
+    pub async fn get_entries_filtered(
+        &self,
+        source: Option<&str>,
+        tags: &[String],
+        from_ts: Option<i64>,
+        to_ts: Option<i64>,
+    ) -> Result<Vec<JournalEntry>> {
+        let conn = self.get_connection()?;
+
+        let mut conditions: Vec<String> = vec!["deleted_at IS NULL".to_string()];
+        let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        if let Some(source) = source {
+            params.push(Box::new(source.to_string()));
+            conditions.push(format!("source = ?{}", params.len()));
+        }
+
+        // Range conditions on the indexed timestamp column
+        match (from_ts, to_ts) {
+            (Some(from), Some(to)) => {
+                params.push(Box::new(from));
+                params.push(Box::new(to));
+                conditions.push(format!(
+                    "timestamp BETWEEN ?{} AND ?{}",
+                    params.len() - 1,
+                    params.len()
+                ));
+            }
+            (Some(from), None) => {
+                params.push(Box::new(from));
+                conditions.push(format!("timestamp >= ?{}", params.len()));
+            }
+            (None, Some(to)) => {
+                params.push(Box::new(to));
+                conditions.push(format!("timestamp <= ?{}", params.len()));
+            }
+            (None, None) => {}
+        }
+
+        // Tags are a JSON array, so match each quoted tag inside it
+        for tag in tags {
+            let quoted = serde_json::to_string(tag)?
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            params.push(Box::new(format!("%{}%", quoted)));
+            conditions.push(format!("tags LIKE ?{} ESCAPE '\\'", params.len()));
+        }
+
+        let sql = format!(
+            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms FROM journal_entries WHERE {} ORDER BY timestamp DESC",
+            conditions.join(" AND ")
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(params.iter()),
+            Self::parse_entry_row,
+        )?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let entry = row?;
+            // LIKE is a substring match; confirm exact tag membership
+            if tags.iter().all(|t| entry.tags.contains(t)) {
+                entries.push(entry);
+            }
+        }
+
+        Ok(self.with_pending_writes(entries))
+    }
+
+    /// One page of entries, newest first. `id` breaks timestamp ties so pages
+    /// don't overlap.
+    pub async fn get_entries_page(
+        &self,
+        source_filter: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<JournalEntry>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms FROM journal_entries WHERE (?1 IS NULL OR source = ?1) AND deleted_at IS NULL ORDER BY timestamp DESC, id DESC LIMIT ?2 OFFSET ?3",
+        )?;
+        let rows = stmt.query_map(params![source_filter, limit, offset.max(0)], |row| {
+            Self::parse_entry_row(row)
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(self.with_pending_writes(entries))
+    }
+
+    /// Ids and titles of the `limit` newest entries, for the tray's Recent
+    /// submenu. Synchronous and reads only those columns, since the tray menu
+    /// is rebuilt often.
+    pub fn get_recent_titles(&self, limit: i64) -> Result<Vec<(i64, String)>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, title FROM journal_entries WHERE deleted_at IS NULL ORDER BY timestamp DESC, id DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map([limit], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        let mut titles = Vec::new();
+        for row in rows {
+            titles.push(row?);
+        }
+        Ok(titles)
+    }
+
+    pub async fn count_entries(&self, source_filter: Option<&str>) -> Result<i64> {
+        let conn = self.get_connection()?;
+        let count = conn.query_row(
+            "SELECT COUNT(*) FROM journal_entries WHERE (?1 IS NULL OR source = ?1) AND deleted_at IS NULL",
+            [source_filter],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Distinct tags with the number of entries using each, most frequent first.
+    /// Tags are stored as a JSON array per entry, so this scans every matching row;
+    /// a normalized tags table maintained on save/update would avoid that if needed.
+    pub async fn get_all_tags(&self, source_filter: Option<&str>) -> Result<Vec<(String, i64)>> {
+        let conn = self.get_connection()?;
+        let mut stmt =
+            conn.prepare(
+            "SELECT tags FROM journal_entries WHERE (?1 IS NULL OR source = ?1) AND deleted_at IS NULL",
+        )?;
+        let rows = stmt.query_map([source_filter], |row| row.get::<_, String>(0))?;
+
+        let mut counts: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
+        for row in rows {
+            let tags: Vec<String> = serde_json::from_str(&row?).unwrap_or_default();
+            for tag in tags {
+                *counts.entry(tag).or_insert(0) += 1;
+            }
+        }
+
+        let mut tags: Vec<(String, i64)> = counts.into_iter().collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(tags)
     }
 
+    /// The entry as stored, plus any transcription update still waiting to be written.
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<JournalEntry>> {
+        Ok(self
+            .query_entry(id)?
+            .map(|entry| self.with_pending_writes(vec![entry]).remove(0)))
+    }
+
+    /// Apply transcription updates still waiting to be written, so every
+    /// query returns the latest text. A buffered edit also drops the redo
+    /// stack, as the write will.
+    fn with_pending_writes(&self, mut entries: Vec<JournalEntry>) -> Vec<JournalEntry> {
+        let pending_writes = self.pending_writes.lock().unwrap();
+        if !pending_writes.is_empty() {
+            for entry in &mut entries {
+                if let Some(pending) = pending_writes.get(&entry.id) {
+                    entry.transcription_text = pending.text.clone();
+                    entry.post_process_prompt_id = pending.prompt_id.clone();
+                    entry.redo_snapshots.clear();
+                }
+            }
+        }
+        entries
+    }
+
+    fn query_entry(&self, id: i64) -> Result<Option<JournalEntry>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source FROM journal_entries WHERE id = ?1",
+            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms FROM journal_entries WHERE id = ?1",
         )?;
 
         let entry = stmt
@@ -827,6 +1952,23 @@ impl JournalManager {
         Ok(entry)
     }
 
+    /// Entries whose `linked_entry_ids` contain `entry_id`, newest first.
+    /// Computed on every call rather than stored, so it can't drift from the
+    /// forward links.
+    pub async fn get_backlinks(&self, entry_id: i64) -> Result<Vec<JournalEntry>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms FROM journal_entries WHERE id != ?1 AND deleted_at IS NULL AND EXISTS (SELECT 1 FROM json_each(journal_entries.linked_entry_ids) WHERE json_each.value = ?1) ORDER BY timestamp DESC",
+        )?;
+        let rows = stmt.query_map([entry_id], |row| Self::parse_entry_row(row))?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(self.with_pending_writes(entries))
+    }
+
     pub async fn update_entry(
         &self,
         id: i64,
@@ -837,8 +1979,11 @@ impl JournalManager {
         user_source: String,
     ) -> Result<()> {
         let mut file_name_update: Option<String> = None;
+        // Renames and moves touch several files, so sync the whole folder(s)
+        let mut touched_dirs = Vec::new();
 
         if let Some(entry) = self.get_entry_by_id(id).await? {
+            touched_dirs.extend(self.resolve_entry_dir(entry.folder_id).ok());
             // If folder is changing, move all associated files
             if entry.folder_id != folder_id {
                 self.move_all_entry_files(&entry, entry.folder_id, folder_id)?;
@@ -875,6 +2020,13 @@ impl JournalManager {
 
         debug!("Updated journal entry {}", id);
 
+        if let Ok(Some(entry)) = self.get_entry_by_id(id).await {
+            self.write_obsidian_mirror(&entry);
+            touched_dirs.extend(self.resolve_entry_dir(entry.folder_id).ok());
+            touched_dirs.dedup();
+            self.git_sync_entry(&entry, "Update", touched_dirs);
+        }
+
         if let Err(e) = self.app_handle.emit("journal-updated", ()) {
             error!("Failed to emit journal-updated event: {}", e);
         }
@@ -891,11 +2043,24 @@ impl JournalManager {
         title: String,
         transcription_text: String,
     ) -> Result<()> {
+        let (title, use_llm_title) = self.auto_title(title, &transcription_text).await;
+        self.discard_pending_write(id);
         let conn = self.get_connection()?;
 
+        let folder_id: Option<i64> = conn.query_row(
+            "SELECT folder_id FROM journal_entries WHERE id = ?1",
+            [id],
+            |row| row.get(0),
+        )?;
+        let duration_ms = self
+            .get_audio_file_path_in_folder(&file_name, folder_id)
+            .ok()
+            .filter(|_| file_name.ends_with(".wav"))
+            .and_then(|path| wav_duration_ms(&path));
+
         conn.execute(
-            "UPDATE journal_entries SET file_name = ?1, title = ?2, transcription_text = ?3 WHERE id = ?4",
-            params![file_name, title, transcription_text, id],
+            "UPDATE journal_entries SET file_name = ?1, title = ?2, transcription_text = ?3, duration_ms = ?4, redo_snapshots = '[]' WHERE id = ?5",
+            params![file_name, title, transcription_text, duration_ms, id],
         )?;
 
         // Write the transcript .md file
@@ -909,6 +2074,20 @@ impl JournalManager {
             error!("Failed to emit journal-updated event: {}", e);
         }
 
+        if use_llm_title {
+            self.spawn_llm_title(id, title, transcription_text);
+        }
+
+        Ok(())
+    }
+
+    pub async fn update_source_url(&self, id: i64, source_url: Option<String>) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE journal_entries SET source_url = ?1 WHERE id = ?2",
+            params![source_url, id],
+        )?;
+        debug!("Updated source URL for entry {}", id);
         Ok(())
     }
 
@@ -934,23 +2113,108 @@ impl JournalManager {
         Ok(())
     }
 
+    /// Set an entry's transcription text. The write is debounced: it lands
+    /// `PENDING_WRITE_DEBOUNCE` after the last update to the entry, but
+    /// `get_entry_by_id` sees the new text straight away.
     pub async fn update_transcription_text(
         &self,
         id: i64,
         text: String,
         prompt_id: Option<String>,
+    ) -> Result<()> {
+        let generation = {
+            let mut pending_writes = self.pending_writes.lock().unwrap();
+            let generation = pending_writes.get(&id).map_or(0, |p| p.generation + 1);
+            pending_writes.insert(
+                id,
+                PendingWrite {
+                    text,
+                    prompt_id,
+                    generation,
+                },
+            );
+            generation
+        };
+
+        // Queries already see the buffered text, so announce the first update
+        // of a burst now; the flush announces the last one
+        if generation == 0 {
+            if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+                error!("Failed to emit journal-updated event: {}", e);
+            }
+        }
+
+        let app = self.app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(PENDING_WRITE_DEBOUNCE).await;
+            let Some(journal_manager) = app.try_state::<Arc<JournalManager>>() else {
+                return;
+            };
+            if let Err(e) = journal_manager.flush_pending_write(id, Some(generation)) {
+                error!("Failed to save transcription for entry {}: {}", id, e);
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Write the buffered update for `id`, if any. With a `generation`, only
+    /// the update that scheduled this flush is written; a newer one waits for
+    /// its own.
+    fn flush_pending_write(&self, id: i64, generation: Option<u64>) -> Result<()> {
+        let pending = {
+            let mut pending_writes = self.pending_writes.lock().unwrap();
+            match pending_writes.get(&id) {
+                Some(p) if generation.is_none() || generation == Some(p.generation) => {
+                    pending_writes.remove(&id)
+                }
+                _ => None,
+            }
+        };
+        match pending {
+            Some(pending) => self.write_transcription_text(id, &pending.text, pending.prompt_id),
+            None => Ok(()),
+        }
+    }
+
+    /// Drop the buffered update for `id`, for writers that replace the text
+    /// themselves (they read it through `get_entry_by_id` first).
+    fn discard_pending_write(&self, id: i64) {
+        self.pending_writes.lock().unwrap().remove(&id);
+    }
+
+    /// Write every buffered transcription update now. Called on shutdown.
+    pub async fn flush_pending_writes(&self) {
+        self.write_all_pending();
+    }
+
+    fn write_all_pending(&self) {
+        let pending: Vec<(i64, PendingWrite)> =
+            self.pending_writes.lock().unwrap().drain().collect();
+        for (id, pending) in pending {
+            if let Err(e) = self.write_transcription_text(id, &pending.text, pending.prompt_id) {
+                error!("Failed to save transcription for entry {}: {}", id, e);
+            }
+        }
+    }
+
+    fn write_transcription_text(
+        &self,
+        id: i64,
+        text: &str,
+        prompt_id: Option<String>,
     ) -> Result<()> {
         let conn = self.get_connection()?;
 
         conn.execute(
-            "UPDATE journal_entries SET transcription_text = ?1, post_process_prompt_id = ?2 WHERE id = ?3",
+            "UPDATE journal_entries SET transcription_text = ?1, post_process_prompt_id = ?2, redo_snapshots = '[]' WHERE id = ?3",
             params![text, prompt_id, id],
         )?;
 
         debug!("Updated transcription text for journal entry {}", id);
 
         // Update the transcript .md file
-        if let Ok(Some(entry)) = self.get_entry_by_id(id).await {
+        if let Ok(Some(entry)) = self.query_entry(id) {
             self.write_transcript_md(&entry);
         }
 
@@ -967,25 +2231,49 @@ impl JournalManager {
         id: i64,
         new_text: String,
         prompt_id: String,
+    ) -> Result<()> {
+        self.apply_prompt_chain_with_snapshots(id, vec![(new_text, prompt_id)])
+            .await
+    }
+
+    /// Apply the outputs of several prompts run one after another, as
+    /// `(text, prompt_id)` steps. Each step's input is kept as a snapshot, so
+    /// undo walks back one prompt at a time, but only the last output is
+    /// written as the entry's text. All or nothing: a failure leaves the entry
+    /// untouched.
+    pub async fn apply_prompt_chain_with_snapshots(
+        &self,
+        id: i64,
+        steps: Vec<(String, String)>,
     ) -> Result<()> {
         let entry = self
             .get_entry_by_id(id)
             .await?
             .ok_or_else(|| anyhow::anyhow!("Entry not found"))?;
+        let Some((final_text, final_prompt_id)) = steps.last().cloned() else {
+            return Ok(());
+        };
 
         let mut snapshots = entry.transcript_snapshots;
         snapshots.push(entry.transcription_text);
+        snapshots.extend(
+            steps[..steps.len() - 1]
+                .iter()
+                .map(|(text, _)| text.clone()),
+        );
         let snapshots_json = serde_json::to_string(&snapshots)?;
 
+        self.discard_pending_write(id);
         let conn = self.get_connection()?;
         conn.execute(
-            "UPDATE journal_entries SET transcription_text = ?1, post_process_prompt_id = ?2, transcript_snapshots = ?3 WHERE id = ?4",
-            params![new_text, prompt_id, snapshots_json, id],
+            "UPDATE journal_entries SET transcription_text = ?1, post_process_prompt_id = ?2, transcript_snapshots = ?3, redo_snapshots = '[]' WHERE id = ?4",
+            params![final_text, final_prompt_id, snapshots_json, id],
         )?;
 
         debug!(
-            "Applied prompt {} to journal entry {} (snapshot saved)",
-            prompt_id, id
+            "Applied {} prompt(s) to journal entry {} (snapshots saved)",
+            steps.len(),
+            id
         );
 
         // Update the transcript .md file
@@ -1001,6 +2289,7 @@ impl JournalManager {
     }
 
     /// Undo the last prompt: pop the last snapshot, restore text, and set prompt_id to the previous level.
+    /// The current state is pushed onto the redo stack.
     pub async fn undo_last_prompt(
         &self,
         id: i64,
@@ -1017,10 +2306,18 @@ impl JournalManager {
             .ok_or_else(|| anyhow::anyhow!("No snapshots to undo"))?;
         let snapshots_json = serde_json::to_string(&snapshots)?;
 
+        let mut redo = entry.redo_snapshots;
+        redo.push(PromptSnapshot {
+            text: entry.transcription_text,
+            prompt_id: entry.post_process_prompt_id,
+        });
+        let redo_json = serde_json::to_string(&redo)?;
+
+        self.discard_pending_write(id);
         let conn = self.get_connection()?;
         conn.execute(
-            "UPDATE journal_entries SET transcription_text = ?1, post_process_prompt_id = ?2, transcript_snapshots = ?3 WHERE id = ?4",
-            params![restored_text, previous_prompt_id, snapshots_json, id],
+            "UPDATE journal_entries SET transcription_text = ?1, post_process_prompt_id = ?2, transcript_snapshots = ?3, redo_snapshots = ?4 WHERE id = ?5",
+            params![restored_text, previous_prompt_id, snapshots_json, redo_json, id],
         )?;
 
         debug!("Undid prompt for journal entry {} (restored snapshot)", id);
@@ -1037,61 +2334,707 @@ impl JournalManager {
         Ok(restored_text)
     }
 
-    /// Clear all snapshots (used when re-transcribing).
-    pub async fn clear_snapshots(&self, id: i64) -> Result<()> {
+    /// Redo the last undone prompt: pop the redo stack, restore its text and prompt_id,
+    /// and push the current text back onto the undo snapshots.
+    pub async fn redo_last_prompt(&self, id: i64) -> Result<String> {
+        let entry = self
+            .get_entry_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Entry not found"))?;
+
+        let mut redo = entry.redo_snapshots;
+        let restored = redo
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("No snapshots to redo"))?;
+        let redo_json = serde_json::to_string(&redo)?;
+
+        let mut snapshots = entry.transcript_snapshots;
+        snapshots.push(entry.transcription_text);
+        let snapshots_json = serde_json::to_string(&snapshots)?;
+
+        self.discard_pending_write(id);
         let conn = self.get_connection()?;
         conn.execute(
-            "UPDATE journal_entries SET transcript_snapshots = '[]' WHERE id = ?1",
-            params![id],
+            "UPDATE journal_entries SET transcription_text = ?1, post_process_prompt_id = ?2, transcript_snapshots = ?3, redo_snapshots = ?4 WHERE id = ?5",
+            params![restored.text, restored.prompt_id, snapshots_json, redo_json, id],
         )?;
-        Ok(())
-    }
-
-    pub async fn delete_entry(&self, id: i64) -> Result<()> {
-        if let Some(entry) = self.get_entry_by_id(id).await? {
-            // Delete all associated files (audio, transcript md, chat/jot mds)
-            self.delete_all_entry_files(&entry);
-        }
 
-        let conn = self.get_connection()?;
-        conn.execute("DELETE FROM journal_entries WHERE id = ?1", params![id])?;
+        debug!(
+            "Redid prompt for journal entry {} (restored redo snapshot)",
+            id
+        );
 
-        debug!("Deleted journal entry with id: {}", id);
+        // Update the transcript .md file
+        if let Ok(Some(updated)) = self.get_entry_by_id(id).await {
+            self.write_transcript_md(&updated);
+        }
 
         if let Err(e) = self.app_handle.emit("journal-updated", ()) {
             error!("Failed to emit journal-updated event: {}", e);
         }
 
+        Ok(restored.text)
+    }
+
+    /// Clear all snapshots (used when re-transcribing).
+    pub async fn clear_snapshots(&self, id: i64) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE journal_entries SET transcript_snapshots = '[]', redo_snapshots = '[]' WHERE id = ?1",
+            params![id],
+        )?;
         Ok(())
     }
 
-    pub fn delete_recording_file(&self, file_name: &str) -> Result<()> {
-        let root = self.effective_recordings_dir();
+    // --- Trash ---
+
+    /// Where a trashed entry's files are kept until restored or purged: a
+    /// directory of its own under `.trash`, so entries with the same file name
+    /// don't collide there.
+    fn entry_trash_dir(&self, id: i64) -> Result<PathBuf> {
+        let dir = self
+            .effective_recordings_dir()
+            .join(".trash")
+            .join(id.to_string());
+        if !dir.exists() {
+            fs::create_dir_all(&dir)?;
+        }
+        Ok(dir)
+    }
+
+    /// Where a trashed entry's files are now. Entries trashed before each had
+    /// its own directory are still in `.trash` itself.
+    fn trashed_files_dir(&self, id: i64) -> PathBuf {
+        let trash = self.effective_recordings_dir().join(".trash");
+        let own = trash.join(id.to_string());
+        if own.is_dir() {
+            own
+        } else {
+            trash
+        }
+    }
+
+    /// Remove a trashed entry's own directory once its files have left it.
+    fn remove_entry_trash_dir(&self, id: i64) {
+        let dir = self
+            .effective_recordings_dir()
+            .join(".trash")
+            .join(id.to_string());
+        if dir.is_dir() {
+            if let Err(e) = fs::remove_dir_all(&dir) {
+                error!("Failed to remove trash directory {:?}: {}", dir, e);
+            }
+        }
+    }
+
+    fn deleted_at(&self, id: i64) -> Result<Option<i64>> {
+        let conn = self.get_connection()?;
+        let deleted_at = conn
+            .query_row(
+                "SELECT deleted_at FROM journal_entries WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        Ok(deleted_at)
+    }
+
+    /// Move an entry to the trash. Its files go to `.trash/<id>` and it is hidden
+    /// from listings until restored with `restore_entry` or removed with
+    /// `purge_entry`.
+    pub async fn delete_entry(&self, id: i64) -> Result<()> {
+        if !self.trash_entry(id).await? {
+            return Ok(());
+        }
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Trash several entries, emitting `journal-updated` once. Each entry is
+    /// trashed on its own, so a failure leaves the others trashed; the ids that
+    /// could not be trashed are returned. Unknown ids count as failed.
+    pub async fn delete_entries(&self, ids: &[i64]) -> Result<Vec<i64>> {
+        let mut failed = Vec::new();
+        for &id in ids {
+            match self.trash_entry(id).await {
+                Ok(true) => {}
+                Ok(false) => failed.push(id),
+                Err(e) => {
+                    error!("Failed to move entry {} to trash: {}", id, e);
+                    failed.push(id);
+                }
+            }
+        }
+
+        debug!(
+            "Moved {} journal entries to trash ({} failed)",
+            ids.len() - failed.len(),
+            failed.len()
+        );
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+        Ok(failed)
+    }
+
+    /// Move one entry to the trash without emitting `journal-updated`. The row
+    /// is marked deleted before its files move, and unmarked (with whatever
+    /// moved put back) if moving fails, so a live entry never points at files
+    /// in the trash. False if the entry doesn't exist.
+    async fn trash_entry(&self, id: i64) -> Result<bool> {
+        self.flush_pending_write(id, None)?;
+        let Some(entry) = self.get_entry_by_id(id).await? else {
+            return Ok(false);
+        };
+        if self.deleted_at(id)?.is_some() {
+            return Ok(true);
+        }
+
+        let src_dir = self.resolve_entry_dir(entry.folder_id)?;
+        let trash_dir = self.entry_trash_dir(id)?;
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE journal_entries SET deleted_at = ?1 WHERE id = ?2",
+            params![Utc::now().timestamp(), id],
+        )?;
+
+        if let Err(e) = Self::move_entry_files_between(&entry, &src_dir, &trash_dir) {
+            if let Err(restore_err) = Self::move_entry_files_between(&entry, &trash_dir, &src_dir) {
+                error!(
+                    "Failed to move files of entry {} back out of the trash: {}",
+                    id, restore_err
+                );
+            } else {
+                self.remove_entry_trash_dir(id);
+            }
+            conn.execute(
+                "UPDATE journal_entries SET deleted_at = NULL WHERE id = ?1",
+                [id],
+            )?;
+            return Err(e);
+        }
+        self.delete_obsidian_mirror(&entry);
+
+        debug!("Moved journal entry {} to trash", id);
+        Ok(true)
+    }
+
+    /// Bring a trashed entry back, moving its files into its folder again. If
+    /// its file name has been taken there in the meantime, it gets a free one.
+    pub async fn restore_entry(&self, id: i64) -> Result<()> {
+        let mut entry = self
+            .get_entry_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Entry not found"))?;
+        if self.deleted_at(id)?.is_none() {
+            return Ok(());
+        }
+
+        let dest_dir = self.resolve_entry_dir(entry.folder_id)?;
+        entry.file_name =
+            Self::restore_entry_files(&entry, &self.trashed_files_dir(id), &dest_dir)?;
+        self.remove_entry_trash_dir(id);
+
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE journal_entries SET deleted_at = NULL, file_name = ?1 WHERE id = ?2",
+            params![entry.file_name, id],
+        )?;
+        self.write_obsidian_mirror(&entry);
+
+        debug!("Restored journal entry {} from trash", id);
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Permanently delete an entry and its files, whether or not it is in the trash.
+    pub async fn purge_entry(&self, id: i64) -> Result<()> {
+        // A delayed write would otherwise land on a row that no longer exists
+        self.discard_pending_write(id);
+        if let Some(entry) = self.get_entry_by_id(id).await? {
+            let dir = if self.deleted_at(id)?.is_some() {
+                self.trashed_files_dir(id)
+            } else {
+                self.resolve_entry_dir(entry.folder_id)?
+            };
+            Self::delete_entry_files_in(&entry, &dir);
+            self.remove_entry_trash_dir(id);
+            self.delete_obsidian_mirror(&entry);
+        }
+
+        let conn = self.get_connection()?;
+        conn.execute("DELETE FROM journal_entries WHERE id = ?1", params![id])?;
+
+        debug!("Purged journal entry with id: {}", id);
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Trashed entries, most recently deleted first.
+    pub async fn get_trashed_entries(&self) -> Result<Vec<TrashedEntry>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, file_name, timestamp, title, transcription_text, post_processed_text, post_process_prompt_id, tags, linked_entry_ids, folder_id, transcript_snapshots, source, source_url, speaker_names, user_source, redo_snapshots, language, detected_language, initial_prompt, duration_ms, deleted_at FROM journal_entries WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(TrashedEntry {
+                entry: Self::parse_entry_row(row)?,
+                deleted_at: row.get("deleted_at")?,
+            })
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
+
+    /// Purge trashed entries older than the `trash_retention_days` setting
+    /// (0 keeps them until purged by hand). Returns how many were removed.
+    pub async fn purge_expired_trash(&self) -> Result<usize> {
+        let days = crate::settings::get_settings(&self.app_handle).trash_retention_days;
+        if days == 0 {
+            return Ok(0);
+        }
+        let cutoff = Utc::now().timestamp() - i64::from(days) * 86_400;
+
+        let ids: Vec<i64> = {
+            let conn = self.get_connection()?;
+            let mut stmt = conn.prepare(
+                "SELECT id FROM journal_entries WHERE deleted_at IS NOT NULL AND deleted_at < ?1",
+            )?;
+            let rows = stmt.query_map([cutoff], |row| row.get(0))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        for id in &ids {
+            self.purge_entry(*id).await?;
+        }
+        if !ids.is_empty() {
+            info!("Purged {} expired journal entries from trash", ids.len());
+        }
+        Ok(ids.len())
+    }
+
+    /// Fill in `duration_ms` for recordings saved before it was stored, from
+    /// their WAV headers, so duration filters include them. Entries whose audio
+    /// can't be read are left without one. Returns how many were updated.
+    pub async fn backfill_durations(&self) -> Result<usize> {
+        let missing: Vec<(i64, String, Option<i64>)> = {
+            let conn = self.get_connection()?;
+            let mut stmt = conn.prepare(
+                "SELECT id, file_name, folder_id FROM journal_entries WHERE duration_ms IS NULL AND file_name LIKE '%.wav' AND deleted_at IS NULL",
+            )?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        let mut updated = 0;
+        let conn = self.get_connection()?;
+        for (id, file_name, folder_id) in missing {
+            let path = self.get_audio_file_path_in_folder(&file_name, folder_id)?;
+            if let Some(duration_ms) = wav_duration_ms(&path) {
+                conn.execute(
+                    "UPDATE journal_entries SET duration_ms = ?1 WHERE id = ?2",
+                    params![duration_ms, id],
+                )?;
+                updated += 1;
+            }
+        }
+        if updated > 0 {
+            info!("Stored the duration of {} journal recordings", updated);
+            if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+                error!("Failed to emit journal-updated event: {}", e);
+            }
+        }
+        Ok(updated)
+    }
+
+    // --- Zip export ---
+
+    /// Write the journal to a zip at `dest`: every file under the recordings
+    /// directory (folders kept as directories, the trash left out) under
+    /// `recordings/`, plus a `journal.json` dump of the folders, entries,
+    /// chats, multi-entry chats, meeting segments and speaker profiles. With
+    /// `source`, only that source's entries and their files are included.
+    /// Files are streamed into the archive one at a time on a blocking thread,
+    /// emitting `export-progress`. Returns the number of files archived.
+    pub async fn export_zip(&self, dest: &Path, source: Option<&str>) -> Result<usize> {
+        let folders = self.get_folders_by_source(source).await?;
+        let entries = self.get_entries_by_source(source).await?;
+        let mut chat_sessions = Vec::new();
+        for entry in &entries {
+            for session in self.get_chat_sessions_for_entry(entry.id).await? {
+                let messages = self.get_chat_messages(session.id).await?;
+                chat_sessions.push(serde_json::json!({
+                    "session": session,
+                    "messages": messages,
+                }));
+            }
+        }
+        let entry_ids: HashSet<i64> = entries.iter().map(|e| e.id).collect();
+        let mut multi_chat_sessions = Vec::new();
+        for session in self.get_multi_chat_sessions().await? {
+            if source.is_some()
+                && !session
+                    .chat_entry_ids
+                    .iter()
+                    .any(|id| entry_ids.contains(id))
+            {
+                continue;
+            }
+            let messages = self.get_multi_chat_messages(session.id).await?;
+            multi_chat_sessions.push(serde_json::json!({
+                "session": session,
+                "messages": messages,
+            }));
+        }
+        // Diarization data only exists on desktop
+        #[cfg(not(any(target_os = "android", target_os = "ios")))]
+        let (meeting_segments, speaker_profiles) = {
+            let mut segments_by_entry = serde_json::Map::new();
+            for entry in &entries {
+                let segments = self.get_meeting_segments(entry.id).await?;
+                if !segments.is_empty() {
+                    segments_by_entry.insert(entry.id.to_string(), serde_json::to_value(segments)?);
+                }
+            }
+            let profiles = if source.is_none() || source == Some("meeting") {
+                self.list_speaker_profiles().await?
+            } else {
+                Vec::new()
+            };
+            (
+                serde_json::Value::Object(segments_by_entry),
+                serde_json::to_value(profiles)?,
+            )
+        };
+        #[cfg(any(target_os = "android", target_os = "ios"))]
+        let (meeting_segments, speaker_profiles) = (serde_json::json!({}), serde_json::json!([]));
+        let manifest = serde_json::json!({
+            "version": 1,
+            "exported_at": Utc::now().timestamp(),
+            "source": source,
+            "folders": folders,
+            "entries": entries,
+            "chat_sessions": chat_sessions,
+            "multi_chat_sessions": multi_chat_sessions,
+            "meeting_segments": meeting_segments,
+            "speaker_profiles": speaker_profiles,
+        });
+
+        let root = self.effective_recordings_dir();
+        let mut files = Vec::new();
+        Self::collect_export_files(&root, &mut files)?;
+        if source.is_some() {
+            // Keep only files that belong to an exported entry
+            let mut owned: std::collections::HashMap<PathBuf, Vec<String>> =
+                std::collections::HashMap::new();
+            for entry in entries.iter().filter(|e| !e.file_name.is_empty()) {
+                let dir = match entry.folder_id {
+                    Some(fid) => root.join(self.folder_rel_path(fid)?),
+                    None => root.clone(),
+                };
+                owned
+                    .entry(dir)
+                    .or_default()
+                    .push(entry_base_name(&entry.file_name).to_string());
+            }
+            // An entry owns its audio (and archival copy), its transcript
+            // `.md` and `<base> - …` chat files
+            files.retain(|path| {
+                let (Some(mut dir), Some(name)) = (path.parent(), path.file_name()) else {
+                    return false;
+                };
+                if dir
+                    .file_name()
+                    .is_some_and(|d| d == crate::audio_save::ARCHIVE_DIR)
+                {
+                    dir = dir.parent().unwrap_or(dir);
+                }
+                let name = name.to_string_lossy();
+                let stem = name.strip_suffix(".wav").unwrap_or(&name);
+                let stem = stem.strip_suffix(".md").unwrap_or(stem);
+                owned.get(dir).is_some_and(|bases| {
+                    bases
+                        .iter()
+                        .any(|base| stem == base || name.starts_with(&format!("{} - ", base)))
+                })
+            });
+        }
+
+        let app_handle = self.app_handle.clone();
+        let dest = dest.to_path_buf();
+        tauri::async_runtime::spawn_blocking(move || {
+            Self::write_export_zip(&app_handle, &dest, &root, &files, &manifest)
+        })
+        .await?
+    }
+
+    /// Write `manifest` as `journal.json` and `files` (all under `root`) to a
+    /// new zip at `dest`. Blocks.
+    fn write_export_zip(
+        app_handle: &AppHandle,
+        dest: &Path,
+        root: &Path,
+        files: &[PathBuf],
+        manifest: &serde_json::Value,
+    ) -> Result<usize> {
+        let file = fs::File::create(dest)?;
+        let mut zip = zip::ZipWriter::new(std::io::BufWriter::new(file));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        zip.start_file("journal.json", options)?;
+        serde_json::to_writer_pretty(&mut zip, manifest)?;
+
+        let total = files.len();
+        for (index, path) in files.iter().enumerate() {
+            let rel = path.strip_prefix(root)?;
+            let name = format!(
+                "recordings/{}",
+                rel.components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            );
+            let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+            zip.start_file(&name, options.large_file(size > u32::MAX as u64))?;
+            let mut src = fs::File::open(path)?;
+            std::io::copy(&mut src, &mut zip)?;
+
+            let _ = app_handle.emit(
+                "export-progress",
+                serde_json::json!({
+                    "done": index + 1,
+                    "total": total,
+                    "file": name,
+                }),
+            );
+        }
+        zip.finish()?;
+
+        info!("Exported {} files to {:?}", total, dest);
+        Ok(total)
+    }
+
+    /// Files under `dir`, recursing into folders. Hidden entries such as the
+    /// `.trash` directory are skipped.
+    fn collect_export_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        let mut children: Vec<_> = fs::read_dir(dir)?.flatten().collect();
+        children.sort_by_key(|c| c.file_name());
+        for child in children {
+            if child.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let path = child.path();
+            if path.is_dir() {
+                Self::collect_export_files(&path, files)?;
+            } else if path.is_file() {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    // --- Database backups ---
+
+    fn backups_dir(&self) -> PathBuf {
+        self.db_path
+            .parent()
+            .map(|dir| dir.join("backups"))
+            .unwrap_or_else(|| PathBuf::from("backups"))
+    }
+
+    /// Write a consistent copy of the database to `backups/journal.backup.<timestamp>.db`,
+    /// then delete the oldest backups beyond the configured `backup_count`.
+    pub async fn backup_database(&self) -> Result<PathBuf> {
+        let dir = self.backups_dir();
+        fs::create_dir_all(&dir)?;
+
+        let created_at = Utc::now().timestamp();
+        let path = dir.join(format!("journal.backup.{}.db", created_at));
+        if path.exists() {
+            // Two backups in the same second hold the same data
+            return Ok(path);
+        }
+
+        let conn = self.get_connection()?;
+        conn.execute("VACUUM INTO ?1", [path.to_string_lossy().as_ref()])?;
+        let size_bytes = fs::metadata(&path)?.len();
+        info!(
+            "Backed up journal database to {:?} ({} bytes)",
+            path, size_bytes
+        );
+
+        let keep = crate::settings::get_settings(&self.app_handle).backup_count as usize;
+        for old in self.list_backups()?.into_iter().skip(keep.max(1)) {
+            if let Err(e) = fs::remove_file(&old.path) {
+                warn!("Failed to remove old backup {}: {}", old.path, e);
+            }
+        }
+
+        if let Err(e) = self.app_handle.emit(
+            "journal-backup-complete",
+            serde_json::json!({
+                "path": path.to_string_lossy(),
+                "size_bytes": size_bytes,
+            }),
+        ) {
+            error!("Failed to emit journal-backup-complete event: {}", e);
+        }
+
+        Ok(path)
+    }
+
+    /// Backups in the `backups/` directory, newest first.
+    pub fn list_backups(&self) -> Result<Vec<BackupInfo>> {
+        let dir = self.backups_dir();
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut backups: Vec<BackupInfo> = fs::read_dir(&dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                let created_at = file_name
+                    .strip_prefix("journal.backup.")?
+                    .strip_suffix(".db")?
+                    .parse()
+                    .ok()?;
+                Some(BackupInfo {
+                    path: entry.path().to_string_lossy().to_string(),
+                    size_bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                    file_name,
+                    created_at,
+                })
+            })
+            .collect();
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(backups)
+    }
+
+    /// Replace the live database with one of the backups listed by `list_backups`.
+    /// The current database is backed up first, and migrations run on the
+    /// restored copy so an older backup is brought up to the current schema.
+    pub async fn restore_from_backup(&self, path: &str) -> Result<()> {
+        let backup = self
+            .list_backups()?
+            .into_iter()
+            .find(|b| b.path == path)
+            .ok_or_else(|| anyhow::anyhow!("Not a journal backup: {}", path))?;
+
+        // Stage the copy before taking a safety backup, whose rotation may
+        // delete the file being restored
+        let staged = self.db_path.with_extension("db.restore");
+        fs::copy(&backup.path, &staged)?;
+        let valid = Connection::open(&staged).and_then(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM journal_entries", [], |row| {
+                row.get::<_, i64>(0)
+            })
+        });
+        if let Err(e) = valid {
+            let _ = fs::remove_file(&staged);
+            anyhow::bail!("Backup is not a valid journal database: {}", e);
+        }
+
+        // Buffered edits belong to the database being replaced; writing them
+        // into the restored one later would overwrite its text. The safety
+        // backup taken next still gets them.
+        self.write_all_pending();
+        self.backup_database().await?;
+        fs::rename(&staged, &self.db_path)?;
+        self.init_database()?;
+        info!("Restored journal database from {}", backup.path);
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    pub fn delete_recording_file(&self, file_name: &str) -> Result<()> {
+        let root = self.effective_recordings_dir();
         let file_path = root.join(file_name);
         if file_path.exists() {
             fs::remove_file(&file_path)?;
             debug!("Deleted journal recording file: {}", file_name);
         }
+        let archive_path = crate::audio_save::archival_copy_path(&root, file_name);
+        if archive_path.exists() {
+            fs::remove_file(&archive_path)?;
+        }
         Ok(())
     }
 
     // --- Folder operations ---
 
-    fn get_folder_name(&self, folder_id: i64) -> Result<String> {
+    /// The folder followed by its ancestors as `(id, name, source)`, innermost first.
+    /// Fails if the parent links form a cycle.
+    fn folder_chain(&self, folder_id: i64) -> Result<Vec<(i64, String, String)>> {
         let conn = self.get_connection()?;
-        let name: String = conn.query_row(
-            "SELECT name FROM journal_folders WHERE id = ?1",
-            [folder_id],
-            |row| row.get(0),
-        )?;
-        Ok(name)
+        let mut chain = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = Some(folder_id);
+        while let Some(id) = current {
+            if !visited.insert(id) {
+                anyhow::bail!("Folder {} has a cyclic parent chain", folder_id);
+            }
+            let (name, source, parent): (String, String, Option<i64>) = conn.query_row(
+                "SELECT name, source, parent_id FROM journal_folders WHERE id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )?;
+            chain.push((id, name, source));
+            current = parent;
+        }
+        Ok(chain)
+    }
+
+    /// Folder directory relative to the recordings root, e.g. `Work/Project A`.
+    fn folder_rel_path(&self, folder_id: i64) -> Result<PathBuf> {
+        Ok(self
+            .folder_chain(folder_id)?
+            .iter()
+            .rev()
+            .map(|(_, name, _)| name)
+            .collect())
+    }
+
+    /// Check that `parent_id` can hold a folder from `source`.
+    fn validate_parent_folder(&self, parent_id: i64, source: &str) -> Result<()> {
+        let chain = self.folder_chain(parent_id)?;
+        if chain[0].2 != source {
+            anyhow::bail!("Parent folder belongs to a different tab");
+        }
+        Ok(())
     }
 
     // Note: move_file_to_folder removed — save_entry now handles file placement directly,
     // and move_all_entry_files handles folder moves.
 
-    pub async fn create_folder(&self, name: String) -> Result<JournalFolder> {
-        self.create_folder_with_source(name, "voice".to_string())
+    pub async fn create_folder(
+        &self,
+        name: String,
+        parent_id: Option<i64>,
+    ) -> Result<JournalFolder> {
+        self.create_folder_with_source(name, "voice".to_string(), parent_id)
             .await
     }
 
@@ -1099,12 +3042,19 @@ impl JournalManager {
         &self,
         name: String,
         source: String,
+        parent_id: Option<i64>,
     ) -> Result<JournalFolder> {
         let created_at = Utc::now().timestamp();
 
         // Create actual directory
-        let root = self.effective_recordings_dir();
-        let folder_path = root.join(&name);
+        let parent_dir = match parent_id {
+            Some(pid) => {
+                self.validate_parent_folder(pid, &source)?;
+                self.resolve_entry_dir(Some(pid))?
+            }
+            None => self.effective_recordings_dir(),
+        };
+        let folder_path = parent_dir.join(&name);
         if !folder_path.exists() {
             fs::create_dir_all(&folder_path)?;
             debug!("Created journal folder directory: {:?}", folder_path);
@@ -1112,8 +3062,8 @@ impl JournalManager {
 
         let conn = self.get_connection()?;
         conn.execute(
-            "INSERT INTO journal_folders (name, created_at, source) VALUES (?1, ?2, ?3)",
-            params![name, created_at, source],
+            "INSERT INTO journal_folders (name, created_at, source, parent_id) VALUES (?1, ?2, ?3, ?4)",
+            params![name, created_at, source, parent_id],
         )?;
         let id = conn.last_insert_rowid();
         debug!(
@@ -1130,14 +3080,16 @@ impl JournalManager {
             name,
             created_at,
             source,
+            parent_id,
         })
     }
 
     pub async fn rename_folder(&self, id: i64, new_name: String) -> Result<()> {
         let root = self.effective_recordings_dir();
-        let old_name = self.get_folder_name(id)?;
-        let old_path = root.join(&old_name);
-        let new_path = root.join(&new_name);
+        let old_rel_path = self.folder_rel_path(id)?;
+        let old_name = old_rel_path.to_string_lossy().to_string();
+        let old_path = root.join(&old_rel_path);
+        let new_path = old_path.with_file_name(&new_name);
 
         if old_path.exists() && old_path != new_path {
             fs::rename(&old_path, &new_path)?;
@@ -1162,7 +3114,7 @@ impl JournalManager {
         // Prevent deletion of folders that contain entries
         let conn = self.get_connection()?;
         let entry_count: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM journal_entries WHERE folder_id = ?1",
+            "SELECT COUNT(*) FROM journal_entries WHERE folder_id = ?1 AND deleted_at IS NULL",
             [id],
             |row| row.get(0),
         )?;
@@ -1174,15 +3126,33 @@ impl JournalManager {
             );
         }
 
-        let root = self.effective_recordings_dir();
-        let folder_name = self.get_folder_name(id)?;
-        let folder_path = root.join(&folder_name);
+        let child_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM journal_folders WHERE parent_id = ?1",
+            [id],
+            |row| row.get(0),
+        )?;
+        if child_count > 0 {
+            anyhow::bail!(
+                "Cannot delete folder: it contains {} subfolder{}. Delete or move them first.",
+                child_count,
+                if child_count == 1 { "" } else { "s" }
+            );
+        }
+
+        let folder_path = self
+            .effective_recordings_dir()
+            .join(self.folder_rel_path(id)?);
 
         if folder_path.exists() {
             fs::remove_dir_all(&folder_path)?;
             debug!("Removed journal folder directory: {:?}", folder_path);
         }
 
+        // Trashed entries from this folder restore to the root instead
+        conn.execute(
+            "UPDATE journal_entries SET folder_id = NULL WHERE folder_id = ?1",
+            params![id],
+        )?;
         conn.execute("DELETE FROM journal_folders WHERE id = ?1", params![id])?;
         debug!("Deleted journal folder {}", id);
 
@@ -1193,6 +3163,56 @@ impl JournalManager {
         Ok(())
     }
 
+    /// Move a folder (with its entries and subfolders) under `new_parent_id`, or
+    /// to the top level with `None`. A folder can't be moved into its own subtree.
+    pub async fn move_folder(&self, id: i64, new_parent_id: Option<i64>) -> Result<()> {
+        let chain = self.folder_chain(id)?;
+        let (_, name, source) = &chain[0];
+        let current_parent = chain.get(1).map(|(pid, _, _)| *pid);
+        if current_parent == new_parent_id {
+            return Ok(());
+        }
+
+        let root = self.effective_recordings_dir();
+        let new_parent_dir = match new_parent_id {
+            Some(pid) => {
+                if self
+                    .folder_chain(pid)?
+                    .iter()
+                    .any(|(ancestor, _, _)| *ancestor == id)
+                {
+                    anyhow::bail!("Cannot move a folder into itself or one of its subfolders");
+                }
+                self.validate_parent_folder(pid, source)?;
+                self.resolve_entry_dir(Some(pid))?
+            }
+            None => root.clone(),
+        };
+
+        let old_path = root.join(self.folder_rel_path(id)?);
+        let new_path = new_parent_dir.join(name);
+        if new_path.exists() {
+            anyhow::bail!("A folder named '{}' already exists there", name);
+        }
+        if old_path.exists() {
+            fs::rename(&old_path, &new_path)?;
+            debug!("Moved folder directory {:?} -> {:?}", old_path, new_path);
+        }
+
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE journal_folders SET parent_id = ?1 WHERE id = ?2",
+            params![new_parent_id, id],
+        )?;
+        debug!("Moved journal folder {} under {:?}", id, new_parent_id);
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
     #[allow(dead_code)]
     pub async fn get_folders(&self) -> Result<Vec<JournalFolder>> {
         self.get_folders_by_source(None).await
@@ -1208,7 +3228,7 @@ impl JournalManager {
         match source_filter {
             Some(source) => {
                 let mut stmt = conn.prepare(
-                    "SELECT id, name, created_at, source FROM journal_folders WHERE source = ?1 ORDER BY name ASC",
+                    "SELECT id, name, created_at, source, parent_id FROM journal_folders WHERE source = ?1 ORDER BY name ASC",
                 )?;
                 let rows = stmt.query_map([source], |row| {
                     Ok(JournalFolder {
@@ -1216,6 +3236,7 @@ impl JournalManager {
                         name: row.get(1)?,
                         created_at: row.get(2)?,
                         source: row.get(3)?,
+                        parent_id: row.get(4)?,
                     })
                 })?;
                 for row in rows {
@@ -1224,7 +3245,7 @@ impl JournalManager {
             }
             None => {
                 let mut stmt = conn.prepare(
-                    "SELECT id, name, created_at, source FROM journal_folders ORDER BY name ASC",
+                    "SELECT id, name, created_at, source, parent_id FROM journal_folders ORDER BY name ASC",
                 )?;
                 let rows = stmt.query_map([], |row| {
                     Ok(JournalFolder {
@@ -1232,6 +3253,7 @@ impl JournalManager {
                         name: row.get(1)?,
                         created_at: row.get(2)?,
                         source: row.get(3)?,
+                        parent_id: row.get(4)?,
                     })
                 })?;
                 for row in rows {
@@ -1271,9 +3293,65 @@ impl JournalManager {
         Ok(())
     }
 
-    // --- Chat session operations ---
+    /// Move several entries into `folder_id`, emitting `journal-updated` once.
+    /// Returns the ids that could not be moved; the rest are moved regardless.
+    pub async fn move_entries_to_folder(
+        &self,
+        ids: &[i64],
+        folder_id: Option<i64>,
+    ) -> Result<Vec<i64>> {
+        let mut moved = Vec::with_capacity(ids.len());
+        let mut failed = Vec::new();
+        for &id in ids {
+            let entry = match self.get_entry_by_id(id).await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => {
+                    failed.push(id);
+                    continue;
+                }
+                Err(e) => {
+                    error!("Failed to load entry {} for moving: {}", id, e);
+                    failed.push(id);
+                    continue;
+                }
+            };
+            if entry.folder_id == folder_id {
+                continue;
+            }
+            match self.move_all_entry_files(&entry, entry.folder_id, folder_id) {
+                Ok(()) => moved.push(id),
+                Err(e) => {
+                    error!("Failed to move files of entry {}: {}", id, e);
+                    failed.push(id);
+                }
+            }
+        }
 
-    pub async fn create_chat_session(&self, entry_id: i64, mode: String) -> Result<ChatSession> {
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        for id in &moved {
+            tx.execute(
+                "UPDATE journal_entries SET folder_id = ?1 WHERE id = ?2",
+                params![folder_id, id],
+            )?;
+        }
+        tx.commit()?;
+
+        debug!(
+            "Moved {} journal entries to folder {:?} ({} failed)",
+            moved.len(),
+            folder_id,
+            failed.len()
+        );
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+        Ok(failed)
+    }
+
+    // --- Chat session operations ---
+
+    pub async fn create_chat_session(&self, entry_id: i64, mode: String) -> Result<ChatSession> {
         let now = Utc::now().timestamp();
         let conn = self.get_connection()?;
         conn.execute(
@@ -1502,6 +3580,142 @@ impl JournalManager {
         Ok(())
     }
 
+    // --- Multi-entry chat session operations ---
+
+    pub async fn create_multi_chat_session(
+        &self,
+        entry_ids: Vec<i64>,
+    ) -> Result<MultiEntryChatSession> {
+        if entry_ids.is_empty() {
+            return Err(anyhow::anyhow!("A chat needs at least one entry"));
+        }
+        let now = Utc::now().timestamp();
+        let conn = self.get_connection()?;
+        conn.execute(
+            "INSERT INTO multi_chat_sessions (chat_entry_ids, title, created_at, updated_at) VALUES (?1, '', ?2, ?3)",
+            params![serde_json::to_string(&entry_ids)?, now, now],
+        )?;
+        let id = conn.last_insert_rowid();
+        debug!(
+            "Created multi-entry chat session {} over {} entries",
+            id,
+            entry_ids.len()
+        );
+
+        Ok(MultiEntryChatSession {
+            id,
+            chat_entry_ids: entry_ids,
+            title: String::new(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_multi_chat_sessions(&self) -> Result<Vec<MultiEntryChatSession>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, chat_entry_ids, title, created_at, updated_at FROM multi_chat_sessions ORDER BY updated_at DESC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let ids_json: String = row.get(1)?;
+            Ok(MultiEntryChatSession {
+                id: row.get(0)?,
+                chat_entry_ids: serde_json::from_str(&ids_json).unwrap_or_default(),
+                title: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })?;
+        let mut sessions = Vec::new();
+        for row in rows {
+            sessions.push(row?);
+        }
+        Ok(sessions)
+    }
+
+    pub async fn save_multi_chat_message(
+        &self,
+        session_id: i64,
+        role: String,
+        content: String,
+    ) -> Result<ChatMessage> {
+        let now = Utc::now().timestamp();
+        let conn = self.get_connection()?;
+        conn.execute(
+            "INSERT INTO multi_chat_messages (session_id, role, content, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![session_id, role, content, now],
+        )?;
+        let id = conn.last_insert_rowid();
+        conn.execute(
+            "UPDATE multi_chat_sessions SET updated_at = ?1 WHERE id = ?2",
+            params![now, session_id],
+        )?;
+
+        // Title the session from its first user message, as single-entry chats do
+        if role == "user" {
+            let title: String = content.chars().take(50).collect();
+            let title = if title.len() < content.len() {
+                format!("{}...", title)
+            } else {
+                title
+            };
+            conn.execute(
+                "UPDATE multi_chat_sessions SET title = ?1 WHERE id = ?2 AND title = ''",
+                params![title, session_id],
+            )?;
+        }
+
+        debug!(
+            "Saved chat message {} to multi-entry session {}",
+            id, session_id
+        );
+        Ok(ChatMessage {
+            id,
+            session_id,
+            role,
+            content,
+            created_at: now,
+        })
+    }
+
+    pub async fn get_multi_chat_messages(&self, session_id: i64) -> Result<Vec<ChatMessage>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, role, content, created_at FROM multi_chat_messages WHERE session_id = ?1 ORDER BY created_at ASC, id ASC",
+        )?;
+        let rows = stmt.query_map([session_id], |row| {
+            Ok(ChatMessage {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                role: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?;
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(row?);
+        }
+        Ok(messages)
+    }
+
+    pub async fn delete_multi_chat_session(&self, session_id: i64) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        // Foreign keys aren't enforced on these connections, so clear messages explicitly
+        tx.execute(
+            "DELETE FROM multi_chat_messages WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        tx.execute(
+            "DELETE FROM multi_chat_sessions WHERE id = ?1",
+            params![session_id],
+        )?;
+        tx.commit()?;
+        debug!("Deleted multi-entry chat session {}", session_id);
+        Ok(())
+    }
+
     // --- Meeting segment operations ---
 
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
@@ -1520,8 +3734,8 @@ impl JournalManager {
 
         for seg in segments {
             conn.execute(
-                "INSERT INTO meeting_segments (entry_id, speaker, start_ms, end_ms, text) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![entry_id, seg.speaker, seg.start_ms, seg.end_ms, seg.text],
+                "INSERT INTO meeting_segments (entry_id, speaker, start_ms, end_ms, text, confidence) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![entry_id, seg.speaker, seg.start_ms, seg.end_ms, seg.text, seg.confidence],
             )?;
         }
 
@@ -1540,7 +3754,7 @@ impl JournalManager {
     ) -> Result<Vec<crate::diarize::DiarizedSegment>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, speaker, start_ms, end_ms, text FROM meeting_segments WHERE entry_id = ?1 ORDER BY start_ms ASC",
+            "SELECT id, speaker, start_ms, end_ms, text, confidence FROM meeting_segments WHERE entry_id = ?1 ORDER BY start_ms ASC",
         )?;
         let rows = stmt.query_map([entry_id], |row| {
             Ok(crate::diarize::DiarizedSegment {
@@ -1549,6 +3763,7 @@ impl JournalManager {
                 start_ms: row.get(2)?,
                 end_ms: row.get(3)?,
                 text: row.get(4)?,
+                confidence: row.get(5)?,
             })
         })?;
         let mut segments = Vec::new();
@@ -1558,6 +3773,416 @@ impl JournalManager {
         Ok(segments)
     }
 
+    /// Export diarized meeting segments as SRT subtitles, with speaker names substituted.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn export_meeting_as_srt(&self, entry_id: i64) -> Result<String> {
+        let segments = self.get_meeting_segments(entry_id).await?;
+        let names = self.get_speaker_names(entry_id).await?;
+
+        let mut out = String::new();
+        for (i, seg) in segments.iter().enumerate() {
+            out.push_str(&format!(
+                "{}\n{} --> {}\n{}: {}\n\n",
+                i + 1,
+                format_subtitle_time(seg.start_ms, ','),
+                format_subtitle_time(seg.end_ms, ','),
+                speaker_label(&names, seg.speaker),
+                seg.text.trim()
+            ));
+        }
+        Ok(out)
+    }
+
+    /// Export diarized meeting segments as WebVTT, with speaker names substituted.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn export_meeting_as_vtt(&self, entry_id: i64) -> Result<String> {
+        let segments = self.get_meeting_segments(entry_id).await?;
+        let names = self.get_speaker_names(entry_id).await?;
+
+        let mut out = String::from("WEBVTT\n\n");
+        for seg in &segments {
+            out.push_str(&format!(
+                "{} --> {}\n{}: {}\n\n",
+                format_subtitle_time(seg.start_ms, '.'),
+                format_subtitle_time(seg.end_ms, '.'),
+                speaker_label(&names, seg.speaker),
+                seg.text.trim()
+            ));
+        }
+        Ok(out)
+    }
+
+    /// Export diarized meeting segments as plain text, one `[Speaker]: text` line per segment.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn export_meeting_as_txt(&self, entry_id: i64) -> Result<String> {
+        let segments = self.get_meeting_segments(entry_id).await?;
+        let names = self.get_speaker_names(entry_id).await?;
+
+        let lines: Vec<String> = segments
+            .iter()
+            .map(|seg| {
+                format!(
+                    "{}: {}",
+                    speaker_label(&names, seg.speaker),
+                    seg.text.trim()
+                )
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    /// Export a meeting as Markdown with speaker names and timestamps.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn export_meeting_as_markdown(&self, entry_id: i64) -> Result<String> {
+        let segments = self.get_meeting_segments(entry_id).await?;
+        let names = self.get_speaker_names(entry_id).await?;
+        let title = self
+            .get_entry_by_id(entry_id)
+            .await?
+            .map(|entry| entry.title)
+            .unwrap_or_default();
+        Ok(render_meeting_markdown(&title, &segments, &names))
+    }
+
+    /// Per-speaker talk time, segment and word counts, most talkative first.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn get_speaker_stats(&self, entry_id: i64) -> Result<Vec<SpeakerStats>> {
+        let names = self.get_speaker_names(entry_id).await?;
+        let conn = self.get_connection()?;
+        // Words are approximated as spaces + 1 in the trimmed text
+        let mut stmt = conn.prepare(
+            "SELECT speaker,
+                    SUM(MAX(end_ms - start_ms, 0)) AS total_ms,
+                    COUNT(*) AS segment_count,
+                    SUM(CASE WHEN TRIM(text) = '' THEN 0
+                             ELSE LENGTH(TRIM(text)) - LENGTH(REPLACE(TRIM(text), ' ', '')) + 1 END) AS word_count,
+                    COALESCE(SUM(MAX(end_ms - start_ms, 0)) * 100.0
+                             / NULLIF(SUM(SUM(MAX(end_ms - start_ms, 0))) OVER (), 0), 0.0) AS percentage
+             FROM meeting_segments
+             WHERE entry_id = ?1
+             GROUP BY speaker
+             ORDER BY total_ms DESC",
+        )?;
+        let rows = stmt.query_map([entry_id], |row| {
+            let speaker: Option<i32> = row.get("speaker")?;
+            Ok(SpeakerStats {
+                speaker,
+                name: speaker_name(&names, speaker),
+                total_ms: row.get("total_ms")?,
+                segment_count: row.get("segment_count")?,
+                word_count: row.get("word_count")?,
+                percentage: row.get("percentage")?,
+            })
+        })?;
+
+        let mut stats = Vec::new();
+        for row in rows {
+            stats.push(row?);
+        }
+        Ok(stats)
+    }
+
+    /// Write exported Markdown as `<audio name>.minutes.md` beside the entry's audio.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn save_meeting_markdown(&self, entry_id: i64, markdown: &str) -> Result<PathBuf> {
+        let entry = self
+            .get_entry_by_id(entry_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Entry not found"))?;
+        if entry.file_name.is_empty() {
+            return Err(anyhow::anyhow!("Entry has no audio file to save beside"));
+        }
+        let dir = self.resolve_entry_dir(entry.folder_id)?;
+        let path = dir.join(format!("{}.minutes.md", entry_base_name(&entry.file_name)));
+        fs::write(&path, markdown)?;
+        debug!("Wrote meeting markdown: {:?}", path);
+        Ok(path)
+    }
+
+    /// Fold `absorbed` into `keep`: the kept row spans all of them and carries their
+    /// text joined in time order. Ids of the kept rows never change.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    fn merge_segment_rows(
+        tx: &rusqlite::Transaction,
+        group: &[&crate::diarize::DiarizedSegment],
+    ) -> Result<()> {
+        let Some((keep, absorbed)) = group.split_first() else {
+            return Ok(());
+        };
+        let keep_id = keep
+            .id
+            .ok_or_else(|| anyhow::anyhow!("Segment has no id"))?;
+        let text = group
+            .iter()
+            .map(|seg| seg.text.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let start_ms = group.iter().map(|seg| seg.start_ms).min().unwrap_or(0);
+        let end_ms = group.iter().map(|seg| seg.end_ms).max().unwrap_or(0);
+
+        tx.execute(
+            "UPDATE meeting_segments SET text = ?1, start_ms = ?2, end_ms = ?3 WHERE id = ?4",
+            params![text, start_ms, end_ms, keep_id],
+        )?;
+        for seg in absorbed {
+            tx.execute(
+                "DELETE FROM meeting_segments WHERE id = ?1",
+                params![seg.id],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite the entry's flat transcript from its current segments and speaker names.
+    /// The result is raw transcript again, so the prompt id is cleared; prompted
+    /// text it replaces is kept as a snapshot so it can be restored with undo.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn regenerate_meeting_transcript(&self, entry_id: i64) -> Result<()> {
+        let entry = self
+            .get_entry_by_id(entry_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Entry not found"))?;
+        let segments = self.get_meeting_segments(entry_id).await?;
+        let names = self.get_speaker_names(entry_id).await?;
+        let text = crate::diarize::segments_to_flat_text(&segments, &names);
+
+        // Raw text can always be rebuilt from the segments, so only prompted
+        // output needs a snapshot
+        let mut snapshots = entry.transcript_snapshots;
+        if entry.post_process_prompt_id.is_some() {
+            snapshots.push(entry.transcription_text);
+        }
+        let snapshots_json = serde_json::to_string(&snapshots)?;
+
+        self.discard_pending_write(entry_id);
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE journal_entries SET transcription_text = ?1, post_process_prompt_id = NULL, transcript_snapshots = ?2, redo_snapshots = '[]' WHERE id = ?3",
+            params![text, snapshots_json, entry_id],
+        )?;
+
+        if let Ok(Some(updated)) = self.get_entry_by_id(entry_id).await {
+            self.write_transcript_md(&updated);
+        }
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Collapse runs of same-speaker segments separated by at most `max_gap_ms`
+    /// into single rows. Returns how many rows were merged away.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn merge_adjacent_segments(&self, entry_id: i64, max_gap_ms: i64) -> Result<usize> {
+        let segments = self.get_meeting_segments(entry_id).await?;
+        let runs: Vec<_> = crate::diarize::same_speaker_runs(&segments, max_gap_ms)
+            .into_iter()
+            .filter(|run| run.len() > 1)
+            .collect();
+        if runs.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        let mut removed = 0;
+        for run in runs {
+            let group: Vec<_> = segments[run].iter().collect();
+            Self::merge_segment_rows(&tx, &group)?;
+            removed += group.len() - 1;
+        }
+        tx.commit()?;
+
+        debug!("Merged away {} segments for entry {}", removed, entry_id);
+        self.regenerate_meeting_transcript(entry_id).await?;
+        Ok(removed)
+    }
+
+    /// Merge two segments of the same entry into the earlier one, keeping its id
+    /// and speaker. Returns the surviving segment id.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn merge_two_segments(&self, first_id: i64, second_id: i64) -> Result<i64> {
+        if first_id == second_id {
+            return Err(anyhow::anyhow!("Cannot merge a segment with itself"));
+        }
+
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        let (entry_id, group) = {
+            let mut stmt = tx.prepare(
+                "SELECT id, entry_id, speaker, start_ms, end_ms, text, confidence FROM meeting_segments WHERE id IN (?1, ?2) ORDER BY start_ms ASC, id ASC",
+            )?;
+            let rows = stmt.query_map(params![first_id, second_id], |row| {
+                Ok((
+                    row.get::<_, i64>(1)?,
+                    crate::diarize::DiarizedSegment {
+                        id: Some(row.get(0)?),
+                        speaker: row.get(2)?,
+                        start_ms: row.get(3)?,
+                        end_ms: row.get(4)?,
+                        text: row.get(5)?,
+                        confidence: row.get(6)?,
+                    },
+                ))
+            })?;
+            let rows: Vec<_> = rows.collect::<rusqlite::Result<_>>()?;
+            if rows.len() != 2 {
+                return Err(anyhow::anyhow!("Segment not found"));
+            }
+            if rows[0].0 != rows[1].0 {
+                return Err(anyhow::anyhow!("Segments belong to different entries"));
+            }
+            (
+                rows[0].0,
+                rows.into_iter().map(|(_, seg)| seg).collect::<Vec<_>>(),
+            )
+        };
+        // The earlier segment survives, so its speaker is kept
+        let keep_id = group[0].id.unwrap_or(first_id);
+        Self::merge_segment_rows(&tx, &group.iter().collect::<Vec<_>>())?;
+        tx.commit()?;
+
+        debug!(
+            "Merged segments {} and {} for entry {}",
+            first_id, second_id, entry_id
+        );
+        self.regenerate_meeting_transcript(entry_id).await?;
+        Ok(keep_id)
+    }
+
+    /// Look up a single segment and the entry it belongs to.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    fn get_segment_row(
+        conn: &Connection,
+        segment_id: i64,
+    ) -> Result<(i64, crate::diarize::DiarizedSegment)> {
+        conn.query_row(
+            "SELECT id, entry_id, speaker, start_ms, end_ms, text, confidence FROM meeting_segments WHERE id = ?1",
+            params![segment_id],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(1)?,
+                    crate::diarize::DiarizedSegment {
+                        id: Some(row.get(0)?),
+                        speaker: row.get(2)?,
+                        start_ms: row.get(3)?,
+                        end_ms: row.get(4)?,
+                        text: row.get(5)?,
+                        confidence: row.get(6)?,
+                    },
+                ))
+            },
+        )
+        .optional()?
+        .ok_or_else(|| anyhow::anyhow!("Segment not found"))
+    }
+
+    /// A single segment and the id of the entry it belongs to.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn get_segment(
+        &self,
+        segment_id: i64,
+    ) -> Result<(i64, crate::diarize::DiarizedSegment)> {
+        Self::get_segment_row(&self.get_connection()?, segment_id)
+    }
+
+    /// Split a segment at `split_ms`. The original row keeps `start..split_ms` and
+    /// a new row with the same speaker takes `split_ms..end`. The text is cut at
+    /// `text_index` (a character index) when given, otherwise in proportion to
+    /// the time on each side. Returns the ids of the first and second halves.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn split_segment(
+        &self,
+        segment_id: i64,
+        split_ms: i64,
+        text_index: Option<usize>,
+    ) -> Result<(i64, i64)> {
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        let (entry_id, seg) = Self::get_segment_row(&tx, segment_id)?;
+        if split_ms <= seg.start_ms || split_ms >= seg.end_ms {
+            return Err(anyhow::anyhow!(
+                "Split time must fall inside the segment ({}..{}ms)",
+                seg.start_ms,
+                seg.end_ms
+            ));
+        }
+
+        let fraction = (split_ms - seg.start_ms) as f64 / (seg.end_ms - seg.start_ms) as f64;
+        let (first_text, second_text) =
+            crate::diarize::split_segment_text(&seg.text, text_index, fraction);
+        tx.execute(
+            "UPDATE meeting_segments SET end_ms = ?1, text = ?2 WHERE id = ?3",
+            params![split_ms, first_text, segment_id],
+        )?;
+        tx.execute(
+            "INSERT INTO meeting_segments (entry_id, speaker, start_ms, end_ms, text, confidence) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![entry_id, seg.speaker, split_ms, seg.end_ms, second_text, seg.confidence],
+        )?;
+        let new_id = tx.last_insert_rowid();
+        tx.commit()?;
+
+        debug!(
+            "Split segment {} at {}ms into {} and {} for entry {}",
+            segment_id, split_ms, segment_id, new_id, entry_id
+        );
+        self.regenerate_meeting_transcript(entry_id).await?;
+        Ok((segment_id, new_id))
+    }
+
+    /// Remove a segment and rebuild its entry's transcript without it.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn delete_segment(&self, segment_id: i64) -> Result<()> {
+        let entry_id = {
+            let conn = self.get_connection()?;
+            let (entry_id, _) = Self::get_segment_row(&conn, segment_id)?;
+            conn.execute(
+                "DELETE FROM meeting_segments WHERE id = ?1",
+                params![segment_id],
+            )?;
+            entry_id
+        };
+
+        debug!("Deleted segment {} from entry {}", segment_id, entry_id);
+        self.regenerate_meeting_transcript(entry_id).await
+    }
+
+    /// Add a hand-written segment to a meeting entry. It takes its place in the
+    /// transcript by `start_ms` like any diarized segment. Returns the new id.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn insert_segment(
+        &self,
+        entry_id: i64,
+        start_ms: i64,
+        end_ms: i64,
+        speaker: Option<i32>,
+        text: String,
+    ) -> Result<i64> {
+        if start_ms < 0 || end_ms <= start_ms {
+            return Err(anyhow::anyhow!("Segment must end after it starts"));
+        }
+        if self.get_entry_by_id(entry_id).await?.is_none() {
+            return Err(anyhow::anyhow!("Entry not found"));
+        }
+
+        let segment_id = {
+            let conn = self.get_connection()?;
+            conn.execute(
+                "INSERT INTO meeting_segments (entry_id, speaker, start_ms, end_ms, text, confidence) VALUES (?1, ?2, ?3, ?4, ?5, NULL)",
+                params![entry_id, speaker, start_ms, end_ms, text.trim()],
+            )?;
+            conn.last_insert_rowid()
+        };
+
+        debug!(
+            "Inserted segment {} at {}..{}ms for entry {}",
+            segment_id, start_ms, end_ms, entry_id
+        );
+        self.regenerate_meeting_transcript(entry_id).await?;
+        Ok(segment_id)
+    }
+
     pub async fn update_segment_text(&self, segment_id: i64, text: String) -> Result<()> {
         let conn = self.get_connection()?;
         conn.execute(
@@ -1568,6 +4193,24 @@ impl JournalManager {
         Ok(())
     }
 
+    pub async fn update_segment_times(
+        &self,
+        segment_id: i64,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE meeting_segments SET start_ms = ?1, end_ms = ?2 WHERE id = ?3",
+            params![start_ms, end_ms, segment_id],
+        )?;
+        debug!(
+            "Updated time range for segment {} to {}..{}ms",
+            segment_id, start_ms, end_ms
+        );
+        Ok(())
+    }
+
     pub async fn update_segment_speaker(
         &self,
         segment_id: i64,
@@ -1600,6 +4243,12 @@ impl JournalManager {
 
         let mut names: std::collections::HashMap<String, String> =
             serde_json::from_str(&current).unwrap_or_default();
+        let label = |name: Option<&String>| match name.map(|n| n.trim()) {
+            Some(n) if !n.is_empty() => format!("[{}]", n),
+            _ => format!("[Speaker {}]", speaker_id),
+        };
+        let old_label = label(names.get(&speaker_id.to_string()));
+        let new_label = label(Some(&name));
         names.insert(speaker_id.to_string(), name);
         let updated = serde_json::to_string(&names)?;
 
@@ -1613,6 +4262,27 @@ impl JournalManager {
             entry_id, speaker_id
         );
 
+        // Keep the flat transcript (and its .md file) in step with the new name.
+        // Meetings are rebuilt from their segments; other entries get the old
+        // label swapped for the new one.
+        let entry = self
+            .get_entry_by_id(entry_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Entry not found"))?;
+        #[cfg(not(any(target_os = "android", target_os = "ios")))]
+        if entry.source == "meeting" && !self.get_meeting_segments(entry_id).await?.is_empty() {
+            return self.regenerate_meeting_transcript(entry_id).await;
+        }
+        if old_label != new_label && entry.transcription_text.contains(&old_label) {
+            return self
+                .update_transcription_text(
+                    entry_id,
+                    entry.transcription_text.replace(&old_label, &new_label),
+                    entry.post_process_prompt_id,
+                )
+                .await;
+        }
+
         if let Err(e) = self.app_handle.emit("journal-updated", ()) {
             error!("Failed to emit journal-updated event: {}", e);
         }
@@ -1620,6 +4290,37 @@ impl JournalManager {
         Ok(())
     }
 
+    /// Fill in names for speakers that don't have one yet. Names the user has
+    /// already set are left alone. Doesn't touch the flat transcript.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn prefill_speaker_names(
+        &self,
+        entry_id: i64,
+        suggested: &std::collections::HashMap<i32, String>,
+    ) -> Result<usize> {
+        let mut names = self.get_speaker_names(entry_id).await?;
+        let mut filled = 0;
+        for (speaker, name) in suggested {
+            let current = names.entry(speaker.to_string()).or_default();
+            if current.trim().is_empty() {
+                *current = name.clone();
+                filled += 1;
+            }
+        }
+        if filled > 0 {
+            let conn = self.get_connection()?;
+            conn.execute(
+                "UPDATE journal_entries SET speaker_names = ?1 WHERE id = ?2",
+                params![serde_json::to_string(&names)?, entry_id],
+            )?;
+            debug!(
+                "Filled {} speaker names from profiles for entry {}",
+                filled, entry_id
+            );
+        }
+        Ok(filled)
+    }
+
     pub async fn get_speaker_names(
         &self,
         entry_id: i64,
@@ -1634,4 +4335,331 @@ impl JournalManager {
             serde_json::from_str(&json).unwrap_or_default();
         Ok(names)
     }
+
+    // --- Speaker profile operations ---
+
+    /// Add a segment's embedding to the profile called `name`, creating it if
+    /// needed. The stored centroid is the running mean of every enrolled sample.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn enroll_speaker_profile(
+        &self,
+        name: &str,
+        embedding: Vec<f32>,
+    ) -> Result<SpeakerProfile> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Speaker name cannot be empty"));
+        }
+        let now = Utc::now().timestamp();
+        let conn = self.get_connection()?;
+        let existing: Option<(String, i64)> = conn
+            .query_row(
+                "SELECT embedding, sample_count FROM speaker_profiles WHERE name = ?1",
+                [name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        match existing {
+            Some((json, count)) => {
+                let centroid: Vec<f32> = serde_json::from_str(&json).unwrap_or_default();
+                let (merged, count) = if centroid.len() == embedding.len() {
+                    let mean: Vec<f32> = centroid
+                        .iter()
+                        .zip(&embedding)
+                        .map(|(c, e)| (c * count as f32 + e) / (count + 1) as f32)
+                        .collect();
+                    (mean, count + 1)
+                } else {
+                    // The embedding model changed; start the profile over
+                    (embedding, 1)
+                };
+                conn.execute(
+                    "UPDATE speaker_profiles SET embedding = ?1, sample_count = ?2, updated_at = ?3 WHERE name = ?4",
+                    params![serde_json::to_string(&merged)?, count, now, name],
+                )?;
+            }
+            None => {
+                conn.execute(
+                    "INSERT INTO speaker_profiles (name, embedding, sample_count, created_at, updated_at) VALUES (?1, ?2, 1, ?3, ?4)",
+                    params![name, serde_json::to_string(&embedding)?, now, now],
+                )?;
+            }
+        }
+        debug!("Enrolled a sample for speaker profile '{}'", name);
+
+        conn.query_row(
+            "SELECT id, name, sample_count, created_at, updated_at FROM speaker_profiles WHERE name = ?1",
+            [name],
+            |row| {
+                Ok(SpeakerProfile {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    sample_count: row.get(2)?,
+                    created_at: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            },
+        )
+        .map_err(Into::into)
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn list_speaker_profiles(&self) -> Result<Vec<SpeakerProfile>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, sample_count, created_at, updated_at FROM speaker_profiles ORDER BY name COLLATE NOCASE ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(SpeakerProfile {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                sample_count: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Every profile's name and embedding centroid, for matching.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn speaker_profile_embeddings(&self) -> Result<Vec<(String, Vec<f32>)>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare("SELECT name, embedding FROM speaker_profiles")?;
+        let rows = stmt.query_map([], |row| {
+            let json: String = row.get(1)?;
+            Ok((
+                row.get::<_, String>(0)?,
+                serde_json::from_str(&json).unwrap_or_default(),
+            ))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn delete_speaker_profile(&self, id: i64) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute("DELETE FROM speaker_profiles WHERE id = ?1", params![id])?;
+        debug!("Deleted speaker profile {}", id);
+        Ok(())
+    }
+
+    /// Set the language an entry is transcribed in (`None` follows settings).
+    pub async fn set_entry_language(&self, id: i64, language: Option<String>) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE journal_entries SET language = ?1 WHERE id = ?2",
+            params![language, id],
+        )?;
+        debug!("Set language of entry {} to {:?}", id, language);
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Set the initial prompt used when re-transcribing an entry (`None`
+    /// follows settings).
+    pub async fn set_entry_initial_prompt(
+        &self,
+        id: i64,
+        initial_prompt: Option<String>,
+    ) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE journal_entries SET initial_prompt = ?1 WHERE id = ?2",
+            params![initial_prompt, id],
+        )?;
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Record the language detected while transcribing an entry.
+    pub async fn set_entry_detected_language(
+        &self,
+        id: i64,
+        detected_language: Option<String>,
+    ) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "UPDATE journal_entries SET detected_language = ?1 WHERE id = ?2",
+            params![detected_language, id],
+        )?;
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Record the SponsorBlock ranges that were cut from an entry's audio.
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn set_skipped_segments(
+        &self,
+        entry_id: i64,
+        segments: &[crate::sponsorblock::SkippedSegment],
+    ) -> Result<()> {
+        let conn = self.get_connection()?;
+        let json = serde_json::to_string(segments)?;
+        conn.execute(
+            "UPDATE journal_entries SET skipped_segments = ?1 WHERE id = ?2",
+            params![json, entry_id],
+        )?;
+
+        debug!(
+            "Recorded {} skipped segments for entry {}",
+            segments.len(),
+            entry_id
+        );
+
+        if let Err(e) = self.app_handle.emit("journal-updated", ()) {
+            error!("Failed to emit journal-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    pub async fn get_skipped_segments(
+        &self,
+        entry_id: i64,
+    ) -> Result<Vec<crate::sponsorblock::SkippedSegment>> {
+        let conn = self.get_connection()?;
+        let json: String = conn.query_row(
+            "SELECT skipped_segments FROM journal_entries WHERE id = ?1",
+            [entry_id],
+            |row| row.get(0),
+        )?;
+        Ok(serde_json::from_str(&json).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_sentence_title_stops_at_sentence_end() {
+        assert_eq!(
+            first_sentence_title("Hello world. More text"),
+            "Hello world"
+        );
+        assert_eq!(first_sentence_title("Is this it? Yes"), "Is this it");
+        assert_eq!(first_sentence_title("Wow!"), "Wow");
+        assert_eq!(first_sentence_title("Line one\nLine two"), "Line one");
+        // A full stop inside a word doesn't end the sentence
+        assert_eq!(
+            first_sentence_title("  Version 1.5 is   out. Next"),
+            "Version 1.5 is out"
+        );
+    }
+
+    #[test]
+    fn test_first_sentence_title_shortens_long_sentences() {
+        let title = first_sentence_title(
+            "The quick brown fox jumps over the lazy dog and a sly cat, while everyone watches",
+        );
+        assert_eq!(
+            title,
+            "The quick brown fox jumps over the lazy dog and a sly cat…"
+        );
+        assert!(title.chars().count() <= AUTO_TITLE_MAX_CHARS);
+
+        let word = "a".repeat(100);
+        let title = first_sentence_title(&word);
+        assert_eq!(title.chars().count(), AUTO_TITLE_MAX_CHARS);
+        assert!(title.ends_with('…'));
+    }
+
+    #[test]
+    fn test_first_sentence_title_of_empty_text() {
+        assert_eq!(first_sentence_title(""), "");
+        assert_eq!(first_sentence_title("  \n "), "");
+    }
+
+    fn test_entry(id: i64, title: &str) -> JournalEntry {
+        JournalEntry {
+            id,
+            file_name: format!("{}.wav", title),
+            timestamp: 1_741_944_600,
+            title: title.to_string(),
+            transcription_text: String::new(),
+            post_processed_text: None,
+            post_process_prompt_id: None,
+            tags: vec![],
+            linked_entry_ids: vec![],
+            folder_id: None,
+            transcript_snapshots: vec![],
+            source: "voice".to_string(),
+            source_url: None,
+            speaker_names: "{}".to_string(),
+            user_source: String::new(),
+            redo_snapshots: vec![],
+            language: None,
+            detected_language: None,
+            initial_prompt: None,
+            duration_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_same_named_entries_are_trashed_and_restored_apart() {
+        let root = tempfile::tempdir().unwrap();
+        let (work, home) = (root.path().join("Work"), root.path().join("Home"));
+        let trash = root.path().join(".trash");
+        let (work_trash, home_trash) = (trash.join("1"), trash.join("2"));
+        for dir in [&work, &home, &work_trash, &home_trash] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        for (dir, label) in [(&work, "work"), (&home, "home")] {
+            fs::write(dir.join("Note.wav"), format!("{} audio", label)).unwrap();
+            fs::write(dir.join("Note.md"), format!("{} transcript", label)).unwrap();
+            fs::write(
+                dir.join("Note - Chat - Ideas.md"),
+                format!("{} chat", label),
+            )
+            .unwrap();
+        }
+        let (work_entry, home_entry) = (test_entry(1, "Note"), test_entry(2, "Note"));
+
+        JournalManager::move_entry_files_between(&work_entry, &work, &work_trash).unwrap();
+        JournalManager::move_entry_files_between(&home_entry, &home, &home_trash).unwrap();
+        // A new entry takes the name while the first one is in the trash
+        fs::write(work.join("Note.wav"), "newer audio").unwrap();
+
+        let work_name =
+            JournalManager::restore_entry_files(&work_entry, &work_trash, &work).unwrap();
+        let home_name =
+            JournalManager::restore_entry_files(&home_entry, &home_trash, &home).unwrap();
+
+        assert_eq!(work_name, "Note (2).wav");
+        assert_eq!(home_name, "Note.wav");
+        let read = |path: PathBuf| fs::read_to_string(path).unwrap();
+        assert_eq!(read(work.join("Note.wav")), "newer audio");
+        assert_eq!(read(work.join("Note (2).wav")), "work audio");
+        assert_eq!(read(work.join("Note (2).md")), "work transcript");
+        assert_eq!(read(work.join("Note (2) - Chat - Ideas.md")), "work chat");
+        assert_eq!(read(home.join("Note.wav")), "home audio");
+        assert_eq!(read(home.join("Note.md")), "home transcript");
+        assert_eq!(read(home.join("Note - Chat - Ideas.md")), "home chat");
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    #[test]
+    fn test_git_commit_message_names_the_entry_and_its_time() {
+        let entry = test_entry(1, "Standup notes");
+        let when = chrono::DateTime::from_timestamp(entry.timestamp, 0)
+            .unwrap()
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M");
+        assert_eq!(
+            git_commit_message("Add", &entry),
+            format!("Add journal entry \"Standup notes\" ({})", when)
+        );
+    }
 }
@@ -5,3 +5,5 @@ pub mod journal;
 pub mod model;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub mod transcription;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub mod transcription_cache;
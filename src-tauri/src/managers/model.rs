@@ -3,14 +3,16 @@ use anyhow::Result;
 use flate2::read::GzDecoder;
 use futures_util::StreamExt;
 use log::{debug, info, warn};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use specta::Type;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tar::Archive;
@@ -32,6 +34,9 @@ pub struct ModelInfo {
     pub description: String,
     pub filename: String,
     pub url: Option<String>,
+    /// Expected SHA-256 of the downloaded file (the archive for directory models).
+    /// `None` when the catalog has no published hash.
+    pub sha256: Option<String>,
     pub size_mb: u64,
     pub is_downloaded: bool,
     pub is_downloading: bool,
@@ -44,14 +49,160 @@ pub struct ModelInfo {
     pub is_recommended: bool,       // Whether this is the recommended model for new users
     pub supported_languages: Vec<String>, // Languages this model can transcribe
     pub is_custom: bool,            // Whether this is a user-provided custom model
+    /// RAM the model needs to run comfortably; see `min_ram_mb`.
+    pub recommended_min_ram_mb: u64,
 }
 
+/// Total and currently available system memory.
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct MemoryInfo {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl MemoryInfo {
+    pub fn current() -> Self {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        Self {
+            total_bytes: system.total_memory(),
+            available_bytes: system.available_memory(),
+        }
+    }
+}
+
+/// Approximate peak RAM, in MB, for transcribing with each catalog model.
+static MODEL_MEMORY_REQUIREMENTS_MB: Lazy<HashMap<String, u64>> = Lazy::new(|| {
+    [
+        ("small", 1_000),
+        ("medium", 2_600),
+        ("turbo", 2_700),
+        ("large", 4_700),
+        ("breeze-asr", 4_700),
+        ("parakeet-tdt-0.6b-v2", 2_000),
+        ("parakeet-tdt-0.6b-v3", 2_000),
+        ("moonshine-base", 700),
+        ("moonshine-tiny-streaming-en", 300),
+        ("moonshine-small-streaming-en", 500),
+        ("moonshine-medium-streaming-en", 1_000),
+        ("sense-voice-int8", 800),
+    ]
+    .into_iter()
+    .map(|(id, mb)| (id.to_string(), mb))
+    .collect()
+});
+
+/// RAM a model needs. Models missing from the table (custom ones) are assumed
+/// to need about twice their file size.
+fn min_ram_mb(model_id: &str, size_mb: u64) -> u64 {
+    MODEL_MEMORY_REQUIREMENTS_MB
+        .get(model_id)
+        .copied()
+        .unwrap_or(size_mb * 2)
+}
+
+/// Smallest file accepted by `import_local_model`; even the quantised tiny
+/// Whisper models are tens of MB.
+const MIN_LOCAL_MODEL_BYTES: u64 = 1024 * 1024;
+
+/// First bytes of a whisper.cpp GGML model (the magic 0x67676d6c, little-endian).
+const GGML_MAGIC: [u8; 4] = *b"lmgg";
+
+/// A model id (and file stem) for a user-given name: lowercase, with runs of
+/// anything but letters and digits turned into single dashes.
+fn model_id_from_name(name: &str) -> String {
+    let id = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if id.is_empty() {
+        "custom-model".to_string()
+    } else {
+        id
+    }
+}
+
+/// Fraction of available memory a recommended model may use.
+const RECOMMENDED_RAM_SHARE: f64 = 0.7;
+
+/// The most accurate model that fits in `budget_mb`, preferring models that
+/// are already downloaded. With nothing fitting, the lightest model.
+fn pick_recommended_model(models: &[ModelInfo], budget_mb: u64) -> Option<&ModelInfo> {
+    let catalog = models.iter().filter(|m| !m.is_custom);
+    catalog
+        .clone()
+        .filter(|m| m.recommended_min_ram_mb <= budget_mb)
+        .max_by(|a, b| {
+            a.is_downloaded.cmp(&b.is_downloaded).then(
+                a.accuracy_score
+                    .partial_cmp(&b.accuracy_score)
+                    .unwrap_or(std::cmp::Ordering::Equal),
+            )
+        })
+        .or_else(|| catalog.min_by_key(|m| m.recommended_min_ram_mb))
+}
+
+/// Payload of the `model-download-progress` event. `total_bytes` is 0 when the
+/// server doesn't report a size.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct DownloadProgress {
     pub model_id: String,
-    pub downloaded: u64,
-    pub total: u64,
-    pub percentage: f64,
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+    pub percent: f64,
+}
+
+impl DownloadProgress {
+    pub fn new(model_id: &str, bytes_downloaded: u64, total_bytes: u64) -> Self {
+        Self {
+            model_id: model_id.to_string(),
+            bytes_downloaded,
+            total_bytes,
+            percent: if total_bytes > 0 {
+                (bytes_downloaded as f64 / total_bytes as f64) * 100.0
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Payload of the `model-download-started` event, emitted once the size of the
+/// download is known.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DownloadStarted {
+    pub model_id: String,
+    pub total_bytes: u64,
+}
+
+/// Payload of the `model-download-complete` event.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DownloadComplete {
+    pub model_id: String,
+}
+
+/// Payload of the `model-verified` event, emitted after a checksum comparison.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelVerified {
+    pub model_id: String,
+    pub ok: bool,
+}
+
+/// Hex-encoded SHA-256 of a file, streamed so large models aren't read into memory.
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Hash a file on the blocking pool and compare it with `expected`.
+async fn file_matches_sha256(path: &Path, expected: &str) -> Result<bool> {
+    let path = path.to_path_buf();
+    let actual = tauri::async_runtime::spawn_blocking(move || sha256_file(&path)).await??;
+    Ok(actual.eq_ignore_ascii_case(expected.trim()))
 }
 
 pub struct ModelManager {
@@ -60,6 +211,35 @@ pub struct ModelManager {
     available_models: Mutex<HashMap<String, ModelInfo>>,
     cancel_flags: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     extracting_models: Arc<Mutex<HashSet<String>>>,
+    /// Downloads currently running in `download_model`.
+    active_downloads: Arc<AtomicUsize>,
+}
+
+/// Counts a download as active for as long as it is held, so every way out
+/// of `download_model` (success, error, cancellation) ends it. Its
+/// cancellation flag goes with it.
+struct ActiveDownload {
+    counter: Arc<AtomicUsize>,
+    cancel_flags: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    model_id: String,
+}
+
+impl ActiveDownload {
+    fn start(manager: &ModelManager, model_id: &str) -> Self {
+        manager.active_downloads.fetch_add(1, Ordering::SeqCst);
+        Self {
+            counter: manager.active_downloads.clone(),
+            cancel_flags: manager.cancel_flags.clone(),
+            model_id: model_id.to_string(),
+        }
+    }
+}
+
+impl Drop for ActiveDownload {
+    fn drop(&mut self) {
+        self.cancel_flags.lock().unwrap().remove(&self.model_id);
+        self.counter.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl ModelManager {
@@ -75,6 +255,36 @@ impl ModelManager {
             fs::create_dir_all(&models_dir)?;
         }
 
+        let mut available_models = Self::builtin_models();
+
+        // Auto-discover custom Whisper models (.bin files) in the models directory
+        if let Err(e) = Self::discover_custom_whisper_models(&models_dir, &mut available_models) {
+            warn!("Failed to discover custom models: {}", e);
+        }
+
+        let manager = Self {
+            app_handle: app_handle.clone(),
+            models_dir,
+            available_models: Mutex::new(available_models),
+            cancel_flags: Arc::new(Mutex::new(HashMap::new())),
+            extracting_models: Arc::new(Mutex::new(HashSet::new())),
+            active_downloads: Arc::new(AtomicUsize::new(0)),
+        };
+
+        // Migrate any bundled models to user directory
+        manager.migrate_bundled_models()?;
+
+        // Check which models are already downloaded
+        manager.update_download_status()?;
+
+        // Auto-select a model if none is currently selected
+        manager.auto_select_model_if_needed()?;
+
+        Ok(manager)
+    }
+
+    /// The models offered for download, keyed by id.
+    fn builtin_models() -> HashMap<String, ModelInfo> {
         let mut available_models = HashMap::new();
 
         // Whisper supported languages (99 languages from tokenizer)
@@ -102,6 +312,7 @@ impl ModelManager {
                 description: "Fast and fairly accurate.".to_string(),
                 filename: "ggml-small.bin".to_string(),
                 url: Some("https://blob.handy.computer/ggml-small.bin".to_string()),
+                sha256: None,
                 size_mb: 487,
                 is_downloaded: false,
                 is_downloading: false,
@@ -114,6 +325,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: whisper_languages.clone(),
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("small", 487),
             },
         );
 
@@ -126,6 +338,7 @@ impl ModelManager {
                 description: "Good accuracy, medium speed".to_string(),
                 filename: "whisper-medium-q4_1.bin".to_string(),
                 url: Some("https://blob.handy.computer/whisper-medium-q4_1.bin".to_string()),
+                sha256: None,
                 size_mb: 492, // Approximate size
                 is_downloaded: false,
                 is_downloading: false,
@@ -138,6 +351,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: whisper_languages.clone(),
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("medium", 492),
             },
         );
 
@@ -149,6 +363,7 @@ impl ModelManager {
                 description: "Balanced accuracy and speed.".to_string(),
                 filename: "ggml-large-v3-turbo.bin".to_string(),
                 url: Some("https://blob.handy.computer/ggml-large-v3-turbo.bin".to_string()),
+                sha256: None,
                 size_mb: 1600, // Approximate size
                 is_downloaded: false,
                 is_downloading: false,
@@ -161,6 +376,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: whisper_languages.clone(),
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("turbo", 1600),
             },
         );
 
@@ -172,6 +388,7 @@ impl ModelManager {
                 description: "Good accuracy, but slow.".to_string(),
                 filename: "ggml-large-v3-q5_0.bin".to_string(),
                 url: Some("https://blob.handy.computer/ggml-large-v3-q5_0.bin".to_string()),
+                sha256: None,
                 size_mb: 1100, // Approximate size
                 is_downloaded: false,
                 is_downloading: false,
@@ -184,6 +401,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: whisper_languages.clone(),
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("large", 1100),
             },
         );
 
@@ -196,6 +414,7 @@ impl ModelManager {
                     .to_string(),
                 filename: "breeze-asr-q5_k.bin".to_string(),
                 url: Some("https://blob.handy.computer/breeze-asr-q5_k.bin".to_string()),
+                sha256: None,
                 size_mb: 1080,
                 is_downloaded: false,
                 is_downloading: false,
@@ -208,6 +427,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: whisper_languages,
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("breeze-asr", 1080),
             },
         );
 
@@ -220,6 +440,7 @@ impl ModelManager {
                 description: "English only. The best model for English speakers.".to_string(),
                 filename: "parakeet-tdt-0.6b-v2-int8".to_string(), // Directory name
                 url: Some("https://blob.handy.computer/parakeet-v2-int8.tar.gz".to_string()),
+                sha256: None,
                 size_mb: 473, // Approximate size for int8 quantized model
                 is_downloaded: false,
                 is_downloading: false,
@@ -232,6 +453,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: vec!["en".to_string()],
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("parakeet-tdt-0.6b-v2", 473),
             },
         );
 
@@ -253,6 +475,7 @@ impl ModelManager {
                 description: "Fast and accurate. Supports 25 European languages.".to_string(),
                 filename: "parakeet-tdt-0.6b-v3-int8".to_string(), // Directory name
                 url: Some("https://blob.handy.computer/parakeet-v3-int8.tar.gz".to_string()),
+                sha256: None,
                 size_mb: 478, // Approximate size for int8 quantized model
                 is_downloaded: false,
                 is_downloading: false,
@@ -265,6 +488,7 @@ impl ModelManager {
                 is_recommended: true,
                 supported_languages: parakeet_v3_languages,
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("parakeet-tdt-0.6b-v3", 478),
             },
         );
 
@@ -276,6 +500,7 @@ impl ModelManager {
                 description: "Very fast, English only. Handles accents well.".to_string(),
                 filename: "moonshine-base".to_string(),
                 url: Some("https://blob.handy.computer/moonshine-base.tar.gz".to_string()),
+                sha256: None,
                 size_mb: 58,
                 is_downloaded: false,
                 is_downloading: false,
@@ -288,6 +513,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: vec!["en".to_string()],
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("moonshine-base", 58),
             },
         );
 
@@ -301,6 +527,7 @@ impl ModelManager {
                 url: Some(
                     "https://blob.handy.computer/moonshine-tiny-streaming-en.tar.gz".to_string(),
                 ),
+                sha256: None,
                 size_mb: 31,
                 is_downloaded: false,
                 is_downloading: false,
@@ -313,6 +540,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: vec!["en".to_string()],
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("moonshine-tiny-streaming-en", 31),
             },
         );
 
@@ -326,6 +554,7 @@ impl ModelManager {
                 url: Some(
                     "https://blob.handy.computer/moonshine-small-streaming-en.tar.gz".to_string(),
                 ),
+                sha256: None,
                 size_mb: 100,
                 is_downloaded: false,
                 is_downloading: false,
@@ -338,6 +567,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: vec!["en".to_string()],
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("moonshine-small-streaming-en", 100),
             },
         );
 
@@ -351,6 +581,7 @@ impl ModelManager {
                 url: Some(
                     "https://blob.handy.computer/moonshine-medium-streaming-en.tar.gz".to_string(),
                 ),
+                sha256: None,
                 size_mb: 192,
                 is_downloaded: false,
                 is_downloading: false,
@@ -363,6 +594,7 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: vec!["en".to_string()],
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("moonshine-medium-streaming-en", 192),
             },
         );
 
@@ -382,6 +614,7 @@ impl ModelManager {
                     .to_string(),
                 filename: "sense-voice-int8".to_string(),
                 url: Some("https://blob.handy.computer/sense-voice-int8.tar.gz".to_string()),
+                sha256: None,
                 size_mb: 160,
                 is_downloaded: false,
                 is_downloading: false,
@@ -394,32 +627,11 @@ impl ModelManager {
                 is_recommended: false,
                 supported_languages: sense_voice_languages,
                 is_custom: false,
+                recommended_min_ram_mb: min_ram_mb("sense-voice-int8", 160),
             },
         );
 
-        // Auto-discover custom Whisper models (.bin files) in the models directory
-        if let Err(e) = Self::discover_custom_whisper_models(&models_dir, &mut available_models) {
-            warn!("Failed to discover custom models: {}", e);
-        }
-
-        let manager = Self {
-            app_handle: app_handle.clone(),
-            models_dir,
-            available_models: Mutex::new(available_models),
-            cancel_flags: Arc::new(Mutex::new(HashMap::new())),
-            extracting_models: Arc::new(Mutex::new(HashSet::new())),
-        };
-
-        // Migrate any bundled models to user directory
-        manager.migrate_bundled_models()?;
-
-        // Check which models are already downloaded
-        manager.update_download_status()?;
-
-        // Auto-select a model if none is currently selected
-        manager.auto_select_model_if_needed()?;
-
-        Ok(manager)
+        available_models
     }
 
     pub fn get_available_models(&self) -> Vec<ModelInfo> {
@@ -432,6 +644,13 @@ impl ModelManager {
         models.get(model_id).cloned()
     }
 
+    /// The model best suited to a machine with `available_bytes` of free memory.
+    pub fn recommend_model(&self, available_bytes: u64) -> Option<String> {
+        let budget_mb = (available_bytes as f64 * RECOMMENDED_RAM_SHARE / (1024.0 * 1024.0)) as u64;
+        let models = self.get_available_models();
+        pick_recommended_model(&models, budget_mb).map(|m| m.id.clone())
+    }
+
     fn migrate_bundled_models(&self) -> Result<()> {
         // Check for bundled models and copy them to user directory
         let bundled_models = ["ggml-small.bin"]; // Add other bundled models here if any
@@ -617,20 +836,6 @@ impl ModelManager {
                 continue;
             }
 
-            // Generate display name: replace - and _ with space, capitalize words
-            let display_name = model_id
-                .replace(['-', '_'], " ")
-                .split_whitespace()
-                .map(|word| {
-                    let mut chars = word.chars();
-                    match chars.next() {
-                        None => String::new(),
-                        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(" ");
-
             // Get file size in MB
             let size_mb = match path.metadata() {
                 Ok(meta) => meta.len() / (1024 * 1024),
@@ -647,31 +852,139 @@ impl ModelManager {
 
             available_models.insert(
                 model_id.clone(),
-                ModelInfo {
-                    id: model_id,
-                    name: display_name,
-                    description: "Not officially supported".to_string(),
-                    filename,
-                    url: None, // Custom models have no download URL
-                    size_mb,
-                    is_downloaded: true, // Already present on disk
-                    is_downloading: false,
-                    partial_size: 0,
-                    is_directory: false,
-                    engine_type: EngineType::Whisper,
-                    accuracy_score: 0.0, // Sentinel: UI hides score bars when both are 0
-                    speed_score: 0.0,
-                    supports_translation: false,
-                    is_recommended: false,
-                    supported_languages: vec![],
-                    is_custom: true,
-                },
+                Self::custom_whisper_model(model_id, filename, size_mb),
             );
         }
 
         Ok(())
     }
 
+    /// Catalog entry for a user-provided Whisper `.bin` in the models directory.
+    fn custom_whisper_model(model_id: String, filename: String, size_mb: u64) -> ModelInfo {
+        // Generate display name: replace - and _ with space, capitalize words
+        let display_name = model_id
+            .replace(['-', '_'], " ")
+            .split_whitespace()
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let recommended_min_ram_mb = min_ram_mb(&model_id, size_mb);
+
+        ModelInfo {
+            id: model_id,
+            name: display_name,
+            description: "Not officially supported".to_string(),
+            filename,
+            url: None, // Custom models have no download URL
+            sha256: None,
+            size_mb,
+            is_downloaded: true, // Already present on disk
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: false,
+            engine_type: EngineType::Whisper,
+            accuracy_score: 0.0, // Sentinel: UI hides score bars when both are 0
+            speed_score: 0.0,
+            supports_translation: false,
+            is_recommended: false,
+            supported_languages: vec![],
+            is_custom: true,
+            recommended_min_ram_mb,
+        }
+    }
+
+    /// Check that `path` looks like a Whisper model we can load: a `.bin` file
+    /// of a plausible size starting with the GGML magic number.
+    pub fn validate_local_model(path: &Path) -> Result<()> {
+        let metadata = fs::metadata(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path.display(), e))?;
+        if !metadata.is_file() {
+            return Err(anyhow::anyhow!("Not a file: {}", path.display()));
+        }
+        if path.extension().and_then(|e| e.to_str()) != Some("bin") {
+            return Err(anyhow::anyhow!(
+                "Only Whisper GGML models (.bin files) can be imported"
+            ));
+        }
+        if metadata.len() < MIN_LOCAL_MODEL_BYTES {
+            return Err(anyhow::anyhow!(
+                "{} is too small to be a Whisper model",
+                path.display()
+            ));
+        }
+        let mut magic = [0u8; 4];
+        std::io::Read::read_exact(&mut File::open(path)?, &mut magic)?;
+        if magic != GGML_MAGIC {
+            return Err(anyhow::anyhow!(
+                "{} is not a Whisper GGML model",
+                path.display()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Copy a model file the user obtained themselves into the models directory
+    /// and list it alongside the catalog. The original is never modified, so
+    /// deleting the model later only removes the copy. `name` sets the display
+    /// name (and the copy's file name); it defaults to the file's own name.
+    pub async fn import_local_model(&self, path: &Path, name: Option<&str>) -> Result<ModelInfo> {
+        Self::validate_local_model(path)?;
+
+        let stem = name
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .or_else(|| path.file_stem().and_then(|s| s.to_str()))
+            .unwrap_or("custom-model");
+        let base_id = model_id_from_name(stem);
+        let model_id = {
+            let models = self.available_models.lock().unwrap();
+            let mut candidate = base_id.clone();
+            let mut n = 2;
+            while models.contains_key(&candidate)
+                || self.models_dir.join(format!("{}.bin", candidate)).exists()
+            {
+                candidate = format!("{}-{}", base_id, n);
+                n += 1;
+            }
+            candidate
+        };
+        let filename = format!("{}.bin", model_id);
+
+        // Copy under a .partial name so discovery never sees a half-copied file
+        let dest = self.models_dir.join(&filename);
+        let partial = self.models_dir.join(format!("{}.partial", filename));
+        let source = path.to_path_buf();
+        let copy_dest = partial.clone();
+        let copied =
+            tauri::async_runtime::spawn_blocking(move || fs::copy(&source, &copy_dest)).await?;
+        if let Err(e) = copied {
+            let _ = fs::remove_file(&partial);
+            return Err(anyhow::anyhow!("Failed to copy model: {}", e));
+        }
+        fs::rename(&partial, &dest)?;
+
+        let size_mb = dest.metadata()?.len() / (1024 * 1024);
+        let model = Self::custom_whisper_model(model_id.clone(), filename, size_mb);
+        self.available_models
+            .lock()
+            .unwrap()
+            .insert(model_id.clone(), model.clone());
+        info!(
+            "Imported local model {} from {} ({} MB)",
+            model_id,
+            path.display(),
+            size_mb
+        );
+
+        Ok(model)
+    }
+
     pub async fn download_model(&self, model_id: &str) -> Result<()> {
         let model_info = {
             let models = self.available_models.lock().unwrap();
@@ -716,6 +1029,7 @@ impl ModelManager {
                 model.is_downloading = true;
             }
         }
+        let _active = ActiveDownload::start(self, model_id);
 
         // Create cancellation flag for this download
         let cancel_flag = Arc::new(AtomicBool::new(false));
@@ -725,7 +1039,7 @@ impl ModelManager {
         }
 
         // Create HTTP client with range request for resuming
-        let client = reqwest::Client::new();
+        let client = crate::http::build_http_client().map_err(|e| anyhow::anyhow!(e))?;
         let mut request = client.get(&url);
 
         if resume_from > 0 {
@@ -789,20 +1103,18 @@ impl ModelManager {
             std::fs::File::create(&partial_path)?
         };
 
-        // Emit initial progress
-        let initial_progress = DownloadProgress {
-            model_id: model_id.to_string(),
-            downloaded,
-            total: total_size,
-            percentage: if total_size > 0 {
-                (downloaded as f64 / total_size as f64) * 100.0
-            } else {
-                0.0
+        let _ = self.app_handle.emit(
+            "model-download-started",
+            DownloadStarted {
+                model_id: model_id.to_string(),
+                total_bytes: total_size,
             },
-        };
-        let _ = self
-            .app_handle
-            .emit("model-download-progress", &initial_progress);
+        );
+        // Emit initial progress
+        let _ = self.app_handle.emit(
+            "model-download-progress",
+            DownloadProgress::new(model_id, downloaded, total_size),
+        );
 
         // Throttle progress events to max 10/sec (100ms intervals)
         let mut last_emit = Instant::now();
@@ -848,36 +1160,19 @@ impl ModelManager {
             file.write_all(&chunk)?;
             downloaded += chunk.len() as u64;
 
-            let percentage = if total_size > 0 {
-                (downloaded as f64 / total_size as f64) * 100.0
-            } else {
-                0.0
-            };
-
             // Emit progress event (throttled to avoid UI freeze)
             if last_emit.elapsed() >= throttle_duration {
-                let progress = DownloadProgress {
-                    model_id: model_id.to_string(),
-                    downloaded,
-                    total: total_size,
-                    percentage,
-                };
+                let progress = DownloadProgress::new(model_id, downloaded, total_size);
                 let _ = self.app_handle.emit("model-download-progress", &progress);
                 last_emit = Instant::now();
             }
         }
 
         // Emit final progress to ensure 100% is shown
-        let final_progress = DownloadProgress {
-            model_id: model_id.to_string(),
-            downloaded,
-            total: total_size,
-            percentage: if total_size > 0 {
-                (downloaded as f64 / total_size as f64) * 100.0
-            } else {
-                100.0
-            },
-        };
+        let mut final_progress = DownloadProgress::new(model_id, downloaded, total_size);
+        if total_size == 0 {
+            final_progress.percent = 100.0;
+        }
         let _ = self
             .app_handle
             .emit("model-download-progress", &final_progress);
@@ -905,6 +1200,36 @@ impl ModelManager {
             }
         }
 
+        // Verify the checksum before the file is moved into place or extracted
+        match model_info.sha256.as_deref() {
+            Some(expected) => {
+                let ok = file_matches_sha256(&partial_path, expected).await?;
+                self.emit_verified(model_id, ok);
+                if !ok {
+                    let _ = fs::remove_file(&partial_path);
+                    {
+                        let mut models = self.available_models.lock().unwrap();
+                        if let Some(model) = models.get_mut(model_id) {
+                            model.is_downloading = false;
+                        }
+                    }
+                    {
+                        let mut flags = self.cancel_flags.lock().unwrap();
+                        flags.remove(model_id);
+                    }
+                    return Err(anyhow::anyhow!(
+                        "Checksum mismatch for model {}: the download is corrupted",
+                        model_id
+                    ));
+                }
+                info!("Verified checksum for model {}", model_id);
+            }
+            None => warn!(
+                "No known checksum for model {}, skipping verification",
+                model_id
+            ),
+        }
+
         // Handle directory-based models (extract tar.gz) vs file-based models
         if model_info.is_directory {
             // Track that this model is being extracted
@@ -1012,7 +1337,12 @@ impl ModelManager {
         }
 
         // Emit completion event
-        let _ = self.app_handle.emit("model-download-complete", model_id);
+        let _ = self.app_handle.emit(
+            "model-download-complete",
+            DownloadComplete {
+                model_id: model_id.to_string(),
+            },
+        );
 
         info!(
             "Successfully downloaded model {} to {:?}",
@@ -1022,6 +1352,42 @@ impl ModelManager {
         Ok(())
     }
 
+    fn emit_verified(&self, model_id: &str, ok: bool) {
+        let _ = self.app_handle.emit(
+            "model-verified",
+            &ModelVerified {
+                model_id: model_id.to_string(),
+                ok,
+            },
+        );
+    }
+
+    /// Re-hash an installed model and compare it with its catalog checksum.
+    /// Directory models are extracted from an archive, so they can only be
+    /// verified while downloading.
+    pub async fn verify_model_integrity(&self, model_id: &str) -> Result<bool> {
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        let expected = model_info
+            .sha256
+            .ok_or_else(|| anyhow::anyhow!("No known checksum for model: {}", model_id))?;
+        if model_info.is_directory {
+            return Err(anyhow::anyhow!(
+                "Model {} is extracted from an archive and can only be verified during download",
+                model_id
+            ));
+        }
+
+        let model_path = self.get_model_path(model_id)?;
+        let ok = file_matches_sha256(&model_path, &expected).await?;
+        self.emit_verified(model_id, ok);
+        if !ok {
+            warn!("Checksum mismatch for installed model {}", model_id);
+        }
+        Ok(ok)
+    }
+
     pub fn delete_model(&self, model_id: &str) -> Result<()> {
         debug!("ModelManager: delete_model called for: {}", model_id);
 
@@ -1137,18 +1503,26 @@ impl ModelManager {
         }
     }
 
+    /// Whether any model download is in progress.
+    pub fn has_active_downloads(&self) -> bool {
+        self.active_downloads.load(Ordering::SeqCst) > 0
+    }
+
     pub fn cancel_download(&self, model_id: &str) -> Result<()> {
         debug!("ModelManager: cancel_download called for: {}", model_id);
 
         // Set the cancellation flag to stop the download loop
         {
             let flags = self.cancel_flags.lock().unwrap();
-            if let Some(flag) = flags.get(model_id) {
-                flag.store(true, Ordering::Relaxed);
-                info!("Cancellation flag set for: {}", model_id);
-            } else {
+            let Some(flag) = flags.get(model_id) else {
                 warn!("No active download found for: {}", model_id);
-            }
+                return Err(anyhow::anyhow!(
+                    "No active download for model: {}",
+                    model_id
+                ));
+            };
+            flag.store(true, Ordering::Relaxed);
+            info!("Cancellation flag set for: {}", model_id);
         }
 
         // Update state immediately for UI responsiveness
@@ -1176,6 +1550,37 @@ mod tests {
     use std::io::Write;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_validate_local_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let write = |name: &str, magic: &[u8], len: usize| {
+            let path = temp_dir.path().join(name);
+            let mut data = magic.to_vec();
+            data.resize(len, 0);
+            fs::write(&path, data).unwrap();
+            path
+        };
+        let size = MIN_LOCAL_MODEL_BYTES as usize;
+
+        assert!(ModelManager::validate_local_model(&write("ok.bin", &GGML_MAGIC, size)).is_ok());
+        assert!(
+            ModelManager::validate_local_model(&write("model.onnx", &GGML_MAGIC, size)).is_err()
+        );
+        assert!(ModelManager::validate_local_model(&write("small.bin", &GGML_MAGIC, 64)).is_err());
+        assert!(ModelManager::validate_local_model(&write("gguf.bin", b"GGUF", size)).is_err());
+        assert!(ModelManager::validate_local_model(&temp_dir.path().join("missing.bin")).is_err());
+    }
+
+    #[test]
+    fn test_model_id_from_name() {
+        assert_eq!(
+            model_id_from_name("Whisper Medical v2"),
+            "whisper-medical-v2"
+        );
+        assert_eq!(model_id_from_name("  ggml_large (q5) "), "ggml-large-q5");
+        assert_eq!(model_id_from_name("!!!"), "custom-model");
+    }
+
     #[test]
     fn test_discover_custom_whisper_models() {
         let temp_dir = TempDir::new().unwrap();
@@ -1204,6 +1609,7 @@ mod tests {
                 description: "Test".to_string(),
                 filename: "ggml-small.bin".to_string(),
                 url: Some("https://example.com".to_string()),
+                sha256: None,
                 size_mb: 100,
                 is_downloaded: false,
                 is_downloading: false,
@@ -1216,6 +1622,7 @@ mod tests {
                 is_recommended: false,
                 supported_languages: vec!["en".to_string()],
                 is_custom: false,
+                recommended_min_ram_mb: 0,
             },
         );
 
@@ -1247,6 +1654,40 @@ mod tests {
         assert!(!models.contains_key("some-directory"));
     }
 
+    #[test]
+    fn test_sha256_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("abc.bin");
+        fs::write(&path, b"abc").unwrap();
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    #[ignore = "the published SHA-256 of each catalog model still has to be filled in"]
+    fn test_catalog_models_have_checksums() {
+        let unverified: Vec<String> = ModelManager::builtin_models()
+            .into_values()
+            .filter(|m| m.url.is_some() && m.sha256.is_none())
+            .map(|m| m.id)
+            .collect();
+        assert!(
+            unverified.is_empty(),
+            "catalog models without a checksum: {:?}",
+            unverified
+        );
+    }
+
+    #[test]
+    fn test_download_progress_percent() {
+        let progress = DownloadProgress::new("small", 25, 200);
+        assert_eq!(progress.percent, 12.5);
+        // Unknown size reports no progress rather than dividing by zero
+        assert_eq!(DownloadProgress::new("small", 25, 0).percent, 0.0);
+    }
+
     #[test]
     fn test_discover_custom_models_empty_dir() {
         let temp_dir = TempDir::new().unwrap();
@@ -1273,4 +1714,56 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(models.len(), count_before);
     }
+
+    fn catalog_model(id: &str, ram_mb: u64, accuracy: f32, downloaded: bool) -> ModelInfo {
+        ModelInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            filename: format!("{}.bin", id),
+            url: None,
+            sha256: None,
+            size_mb: ram_mb / 2,
+            is_downloaded: downloaded,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: false,
+            engine_type: EngineType::Whisper,
+            accuracy_score: accuracy,
+            speed_score: 0.5,
+            supports_translation: false,
+            is_recommended: false,
+            supported_languages: vec![],
+            is_custom: false,
+            recommended_min_ram_mb: ram_mb,
+        }
+    }
+
+    #[test]
+    fn test_recommendation_fits_memory_and_prefers_downloaded() {
+        let models = vec![
+            catalog_model("tiny", 300, 0.4, false),
+            catalog_model("small", 1_000, 0.6, true),
+            catalog_model("medium", 2_600, 0.75, false),
+            catalog_model("large", 4_700, 0.9, false),
+        ];
+        let pick = |budget| pick_recommended_model(&models, budget).map(|m| m.id.as_str());
+
+        assert_eq!(pick(3_000), Some("small"));
+        assert_eq!(pick(800), Some("tiny"));
+        assert_eq!(pick(100), Some("tiny"));
+
+        let none_downloaded: Vec<_> = models
+            .iter()
+            .cloned()
+            .map(|m| ModelInfo {
+                is_downloaded: false,
+                ..m
+            })
+            .collect();
+        assert_eq!(
+            pick_recommended_model(&none_downloaded, 3_000).map(|m| m.id.as_str()),
+            Some("medium")
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use crate::audio_toolkit::{apply_custom_words, filter_transcription_output};
 use crate::managers::model::{EngineType, ModelManager};
+use crate::managers::transcription_cache::{cache_key, CacheParams, TranscriptionCache};
 use crate::settings::{get_settings, ModelUnloadTimeout};
 use anyhow::Result;
 use log::{debug, error, info, warn};
@@ -9,7 +10,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex, MutexGuard};
 use std::thread;
 use std::time::{Duration, SystemTime};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 use transcribe_rs::{
     engines::{
         moonshine::{
@@ -28,6 +29,38 @@ use transcribe_rs::{
     TranscriptionEngine,
 };
 
+/// Why `transcribe_with_timeout` produced no text.
+#[derive(Debug)]
+pub enum TranscriptionError {
+    /// The engine didn't finish within `transcription_timeout_secs`.
+    Timeout {
+        secs: u32,
+    },
+    Failed(anyhow::Error),
+}
+
+impl std::fmt::Display for TranscriptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranscriptionError::Timeout { secs } => {
+                write!(f, "Transcription timed out after {}s", secs)
+            }
+            TranscriptionError::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for TranscriptionError {}
+
+/// Tell the frontend a transcription was abandoned, so it can offer a retry
+/// with another model. `entry_id` is `None` when no entry exists yet.
+pub fn emit_transcription_timeout(app: &AppHandle, entry_id: Option<i64>) {
+    let _ = app.emit(
+        "transcription-timeout",
+        serde_json::json!({ "entry_id": entry_id }),
+    );
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelStateEvent {
     pub event_type: String,
@@ -44,6 +77,59 @@ enum LoadedEngine {
     SenseVoice(SenseVoiceEngine),
 }
 
+/// Create and load the engine for a downloaded model.
+fn create_engine(
+    model_id: &str,
+    engine_type: &EngineType,
+    model_path: &std::path::Path,
+) -> Result<LoadedEngine, String> {
+    match engine_type {
+        EngineType::Whisper => {
+            let mut engine = WhisperEngine::new();
+            engine
+                .load_model(model_path)
+                .map_err(|e| format!("Failed to load whisper model {}: {}", model_id, e))?;
+            Ok(LoadedEngine::Whisper(engine))
+        }
+        EngineType::Parakeet => {
+            let mut engine = ParakeetEngine::new();
+            engine
+                .load_model_with_params(model_path, ParakeetModelParams::int8())
+                .map_err(|e| format!("Failed to load parakeet model {}: {}", model_id, e))?;
+            Ok(LoadedEngine::Parakeet(engine))
+        }
+        EngineType::Moonshine => {
+            let mut engine = MoonshineEngine::new();
+            engine
+                .load_model_with_params(
+                    model_path,
+                    MoonshineModelParams::variant(ModelVariant::Base),
+                )
+                .map_err(|e| format!("Failed to load moonshine model {}: {}", model_id, e))?;
+            Ok(LoadedEngine::Moonshine(engine))
+        }
+        EngineType::MoonshineStreaming => {
+            let mut engine = MoonshineStreamingEngine::new();
+            engine
+                .load_model_with_params(model_path, StreamingModelParams::default())
+                .map_err(|e| {
+                    format!(
+                        "Failed to load moonshine streaming model {}: {}",
+                        model_id, e
+                    )
+                })?;
+            Ok(LoadedEngine::MoonshineStreaming(engine))
+        }
+        EngineType::SenseVoice => {
+            let mut engine = SenseVoiceEngine::new();
+            engine
+                .load_model_with_params(model_path, SenseVoiceModelParams::int8())
+                .map_err(|e| format!("Failed to load SenseVoice model {}: {}", model_id, e))?;
+            Ok(LoadedEngine::SenseVoice(engine))
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TranscriptionManager {
     engine: Arc<Mutex<Option<LoadedEngine>>>,
@@ -55,6 +141,38 @@ pub struct TranscriptionManager {
     watcher_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
     is_loading: Arc<Mutex<bool>>,
     loading_condvar: Arc<Condvar>,
+    cache: Arc<TranscriptionCache>,
+}
+
+/// Longest initial prompt passed to Whisper. It only conditions on roughly the
+/// last 224 tokens of a prompt, so anything longer is wasted.
+const MAX_INITIAL_PROMPT_CHARS: usize = 800;
+
+/// The initial prompt for a transcription: `base`, followed by as many of
+/// `custom_words` as fit when `include_custom_words` is on. `None` if empty.
+pub(crate) fn build_initial_prompt(
+    base: &str,
+    custom_words: &[String],
+    include_custom_words: bool,
+) -> Option<String> {
+    let mut prompt: String = base.trim().chars().take(MAX_INITIAL_PROMPT_CHARS).collect();
+    if include_custom_words {
+        let mut separator = if prompt.is_empty() { "" } else { " " };
+        for word in custom_words
+            .iter()
+            .map(|w| w.trim())
+            .filter(|w| !w.is_empty())
+        {
+            let len = prompt.chars().count() + separator.len() + word.chars().count();
+            if len > MAX_INITIAL_PROMPT_CHARS {
+                break;
+            }
+            prompt.push_str(separator);
+            prompt.push_str(word);
+            separator = ", ";
+        }
+    }
+    Some(prompt).filter(|p| !p.is_empty())
 }
 
 impl TranscriptionManager {
@@ -74,6 +192,12 @@ impl TranscriptionManager {
             watcher_handle: Arc::new(Mutex::new(None)),
             is_loading: Arc::new(Mutex::new(false)),
             loading_condvar: Arc::new(Condvar::new()),
+            cache: Arc::new(TranscriptionCache::load(
+                app_handle
+                    .path()
+                    .app_data_dir()?
+                    .join("transcription_cache.json"),
+            )),
         };
 
         // Start the idle watcher
@@ -242,111 +366,22 @@ impl TranscriptionManager {
 
         let model_path = self.model_manager.get_model_path(model_id)?;
 
-        // Create appropriate engine based on model type
-        let loaded_engine = match model_info.engine_type {
-            EngineType::Whisper => {
-                let mut engine = WhisperEngine::new();
-                engine.load_model(&model_path).map_err(|e| {
-                    let error_msg = format!("Failed to load whisper model {}: {}", model_id, e);
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "loading_failed".to_string(),
-                            model_id: Some(model_id.to_string()),
-                            model_name: Some(model_info.name.clone()),
-                            error: Some(error_msg.clone()),
-                        },
-                    );
-                    anyhow::anyhow!(error_msg)
-                })?;
-                LoadedEngine::Whisper(engine)
-            }
-            EngineType::Parakeet => {
-                let mut engine = ParakeetEngine::new();
-                engine
-                    .load_model_with_params(&model_path, ParakeetModelParams::int8())
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load parakeet model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Parakeet(engine)
-            }
-            EngineType::Moonshine => {
-                let mut engine = MoonshineEngine::new();
-                engine
-                    .load_model_with_params(
-                        &model_path,
-                        MoonshineModelParams::variant(ModelVariant::Base),
-                    )
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load moonshine model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Moonshine(engine)
-            }
-            EngineType::MoonshineStreaming => {
-                let mut engine = MoonshineStreamingEngine::new();
-                engine
-                    .load_model_with_params(&model_path, StreamingModelParams::default())
-                    .map_err(|e| {
-                        let error_msg = format!(
-                            "Failed to load moonshine streaming model {}: {}",
-                            model_id, e
-                        );
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::MoonshineStreaming(engine)
-            }
-            EngineType::SenseVoice => {
-                let mut engine = SenseVoiceEngine::new();
-                engine
-                    .load_model_with_params(&model_path, SenseVoiceModelParams::int8())
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load SenseVoice model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::SenseVoice(engine)
-            }
-        };
+        // ONNX engines pick up the execution provider when their sessions
+        // are created
+        crate::compute_backend::use_backend(&self.app_handle);
+        let loaded_engine =
+            create_engine(model_id, &model_info.engine_type, &model_path).map_err(|error_msg| {
+                let _ = self.app_handle.emit(
+                    "model-state-changed",
+                    ModelStateEvent {
+                        event_type: "loading_failed".to_string(),
+                        model_id: Some(model_id.to_string()),
+                        model_name: Some(model_info.name.clone()),
+                        error: Some(error_msg.clone()),
+                    },
+                );
+                anyhow::anyhow!(error_msg)
+            })?;
 
         // Update the current engine and model ID
         {
@@ -378,6 +413,11 @@ impl TranscriptionManager {
         Ok(())
     }
 
+    /// Whether a model load started by `initiate_model_load` is still running.
+    pub fn is_loading(&self) -> bool {
+        *self.is_loading.lock().unwrap()
+    }
+
     /// Kicks off the model loading in a background thread if it's not already loaded
     pub fn initiate_model_load(&self) {
         let mut is_loading = self.is_loading.lock().unwrap();
@@ -389,8 +429,12 @@ impl TranscriptionManager {
         let self_clone = self.clone();
         thread::spawn(move || {
             let settings = get_settings(&self_clone.app_handle);
-            if let Err(e) = self_clone.load_model(&settings.selected_model) {
-                error!("Failed to load model: {}", e);
+            match self_clone.load_model(&settings.selected_model) {
+                Ok(()) => crate::notifications::notify_model_loaded(
+                    &self_clone.app_handle,
+                    &settings.selected_model,
+                ),
+                Err(e) => error!("Failed to load model: {}", e),
             }
             let mut is_loading = self_clone.is_loading.lock().unwrap();
             *is_loading = false;
@@ -403,7 +447,64 @@ impl TranscriptionManager {
         current_model.clone()
     }
 
-    pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
+    /// Transcribe 16kHz mono audio. `language` (a code such as "de", or
+    /// "auto") overrides the selected language in settings for this call.
+    pub fn transcribe(&self, audio: Vec<f32>, language: Option<&str>) -> Result<String> {
+        self.run_transcription(audio, language, None, None, false)
+    }
+
+    /// Like `transcribe`, with `initial_prompt` (when given) replacing the
+    /// initial prompt from settings. Custom words are still appended to it
+    /// if that is turned on. Used for recorded files, so results are cached.
+    pub fn transcribe_with_prompt(
+        &self,
+        audio: Vec<f32>,
+        language: Option<&str>,
+        initial_prompt: Option<&str>,
+    ) -> Result<String> {
+        self.run_transcription(audio, language, initial_prompt, None, true)
+    }
+
+    /// Like `transcribe_with_prompt`, but with the model's translate task
+    /// enabled regardless of the "translate to English" setting. Only Whisper
+    /// models translate; see `current_model_supports_translation`.
+    pub fn translate_to_english(
+        &self,
+        audio: Vec<f32>,
+        language: Option<&str>,
+        initial_prompt: Option<&str>,
+    ) -> Result<String> {
+        self.run_transcription(audio, language, initial_prompt, Some(true), true)
+    }
+
+    /// Forget all cached transcripts; see `transcription_cache`.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+        info!("Transcription cache cleared");
+    }
+
+    /// Whether the loaded (or, if none is loaded yet, the selected) model can
+    /// translate speech to English.
+    pub fn current_model_supports_translation(&self) -> bool {
+        let model_id = self
+            .get_current_model()
+            .unwrap_or_else(|| get_settings(&self.app_handle).selected_model);
+        self.model_manager
+            .get_model_info(&model_id)
+            .is_some_and(|info| info.supports_translation)
+    }
+
+    /// `initial_prompt` and `translate` override their settings when given.
+    /// With `cached`, a transcript of identical audio with the same model and
+    /// options is reused from `transcription_cache` instead of running the engine.
+    fn run_transcription(
+        &self,
+        audio: Vec<f32>,
+        language: Option<&str>,
+        initial_prompt: Option<&str>,
+        translate: Option<bool>,
+        cached: bool,
+    ) -> Result<String> {
         // Update last activity timestamp
         self.last_activity.store(
             SystemTime::now()
@@ -439,166 +540,208 @@ impl TranscriptionManager {
 
         // Get current settings for configuration
         let settings = get_settings(&self.app_handle);
+        let language = language.unwrap_or(&settings.selected_language);
+        let initial_prompt = build_initial_prompt(
+            initial_prompt.unwrap_or(&settings.transcription_initial_prompt),
+            &settings.custom_words,
+            settings.initial_prompt_include_custom_words,
+        );
 
-        // Perform transcription with the appropriate engine.
-        // We use catch_unwind to prevent engine panics from poisoning the mutex,
-        // which would make the app hang indefinitely on subsequent operations.
-        let result = {
-            let mut engine_guard = self.lock_engine();
-
-            // Take the engine out so we own it during transcription.
-            // If the engine panics, we simply don't put it back (effectively unloading it)
-            // instead of poisoning the mutex.
-            let mut engine = match engine_guard.take() {
-                Some(e) => e,
-                None => {
-                    return Err(anyhow::anyhow!(
-                        "Model failed to load after auto-load attempt. Please check your model settings."
-                    ));
-                }
-            };
+        let translate = translate.unwrap_or(settings.translate_to_english);
+        // Only file-based transcription is cached; live audio never repeats
+        let key = cached.then(|| {
+            cache_key(
+                &audio,
+                &CacheParams {
+                    model_id: &self.get_current_model().unwrap_or_default(),
+                    language,
+                    translate,
+                    initial_prompt: initial_prompt.as_deref(),
+                },
+            )
+        });
+        let raw_text = if let Some(text) = key.as_ref().and_then(|key| self.cache.get(key)) {
+            info!("transcription-cache-hit: reusing transcript for identical audio");
+            text
+        } else {
+            // Perform transcription with the appropriate engine.
+            // We use catch_unwind to prevent engine panics from poisoning the mutex,
+            // which would make the app hang indefinitely on subsequent operations.
+            let result = {
+                let mut engine_guard = self.lock_engine();
+
+                // Take the engine out so we own it during transcription.
+                // If the engine panics, we simply don't put it back (effectively unloading it)
+                // instead of poisoning the mutex.
+                let mut engine = match engine_guard.take() {
+                    Some(e) => e,
+                    None => {
+                        return Err(anyhow::anyhow!(
+                            "Model failed to load after auto-load attempt. Please check your model settings."
+                        ));
+                    }
+                };
 
-            // Release the lock before transcribing — no mutex held during the engine call
-            drop(engine_guard);
-
-            let transcribe_result = catch_unwind(AssertUnwindSafe(
-                || -> Result<transcribe_rs::TranscriptionResult> {
-                    match &mut engine {
-                        LoadedEngine::Whisper(whisper_engine) => {
-                            let whisper_language = if settings.selected_language == "auto" {
-                                None
-                            } else {
-                                let normalized = if settings.selected_language == "zh-Hans"
-                                    || settings.selected_language == "zh-Hant"
-                                {
-                                    "zh".to_string()
+                // Release the lock before transcribing — no mutex held during the engine call
+                drop(engine_guard);
+
+                let transcribe_result = catch_unwind(AssertUnwindSafe(
+                    || -> Result<transcribe_rs::TranscriptionResult> {
+                        match &mut engine {
+                            LoadedEngine::Whisper(whisper_engine) => {
+                                let whisper_language = if language == "auto" {
+                                    None
                                 } else {
-                                    settings.selected_language.clone()
+                                    let normalized =
+                                        if language == "zh-Hans" || language == "zh-Hant" {
+                                            "zh".to_string()
+                                        } else {
+                                            language.to_string()
+                                        };
+                                    Some(normalized)
                                 };
-                                Some(normalized)
-                            };
-
-                            let params = WhisperInferenceParams {
-                                language: whisper_language,
-                                translate: settings.translate_to_english,
-                                ..Default::default()
-                            };
-
-                            whisper_engine
-                                .transcribe_samples(audio, Some(params))
-                                .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))
-                        }
-                        LoadedEngine::Parakeet(parakeet_engine) => {
-                            let params = ParakeetInferenceParams {
-                                timestamp_granularity: TimestampGranularity::Segment,
-                                ..Default::default()
-                            };
-                            parakeet_engine
-                                .transcribe_samples(audio, Some(params))
+
+                                let params = WhisperInferenceParams {
+                                    language: whisper_language,
+                                    translate,
+                                    initial_prompt,
+                                    ..Default::default()
+                                };
+
+                                whisper_engine
+                                    .transcribe_samples(audio, Some(params))
+                                    .map_err(|e| {
+                                        anyhow::anyhow!("Whisper transcription failed: {}", e)
+                                    })
+                            }
+                            LoadedEngine::Parakeet(parakeet_engine) => {
+                                let params = ParakeetInferenceParams {
+                                    timestamp_granularity: TimestampGranularity::Segment,
+                                    ..Default::default()
+                                };
+                                parakeet_engine
+                                    .transcribe_samples(audio, Some(params))
+                                    .map_err(|e| {
+                                        anyhow::anyhow!("Parakeet transcription failed: {}", e)
+                                    })
+                            }
+                            LoadedEngine::Moonshine(moonshine_engine) => moonshine_engine
+                                .transcribe_samples(audio, None)
                                 .map_err(|e| {
-                                    anyhow::anyhow!("Parakeet transcription failed: {}", e)
-                                })
-                        }
-                        LoadedEngine::Moonshine(moonshine_engine) => moonshine_engine
-                            .transcribe_samples(audio, None)
-                            .map_err(|e| anyhow::anyhow!("Moonshine transcription failed: {}", e)),
-                        LoadedEngine::MoonshineStreaming(streaming_engine) => streaming_engine
-                            .transcribe_samples(audio, None)
-                            .map_err(|e| {
-                                anyhow::anyhow!("Moonshine streaming transcription failed: {}", e)
-                            }),
-                        LoadedEngine::SenseVoice(sense_voice_engine) => {
-                            let language = match settings.selected_language.as_str() {
-                                "zh" | "zh-Hans" | "zh-Hant" => SenseVoiceLanguage::Chinese,
-                                "en" => SenseVoiceLanguage::English,
-                                "ja" => SenseVoiceLanguage::Japanese,
-                                "ko" => SenseVoiceLanguage::Korean,
-                                "yue" => SenseVoiceLanguage::Cantonese,
-                                _ => SenseVoiceLanguage::Auto,
-                            };
-                            let params = SenseVoiceInferenceParams {
-                                language,
-                                use_itn: true,
-                            };
-                            sense_voice_engine
-                                .transcribe_samples(audio, Some(params))
+                                    anyhow::anyhow!("Moonshine transcription failed: {}", e)
+                                }),
+                            LoadedEngine::MoonshineStreaming(streaming_engine) => streaming_engine
+                                .transcribe_samples(audio, None)
                                 .map_err(|e| {
-                                    anyhow::anyhow!("SenseVoice transcription failed: {}", e)
-                                })
+                                    anyhow::anyhow!(
+                                        "Moonshine streaming transcription failed: {}",
+                                        e
+                                    )
+                                }),
+                            LoadedEngine::SenseVoice(sense_voice_engine) => {
+                                let language = match language {
+                                    "zh" | "zh-Hans" | "zh-Hant" => SenseVoiceLanguage::Chinese,
+                                    "en" => SenseVoiceLanguage::English,
+                                    "ja" => SenseVoiceLanguage::Japanese,
+                                    "ko" => SenseVoiceLanguage::Korean,
+                                    "yue" => SenseVoiceLanguage::Cantonese,
+                                    _ => SenseVoiceLanguage::Auto,
+                                };
+                                let params = SenseVoiceInferenceParams {
+                                    language,
+                                    use_itn: true,
+                                };
+                                sense_voice_engine
+                                    .transcribe_samples(audio, Some(params))
+                                    .map_err(|e| {
+                                        anyhow::anyhow!("SenseVoice transcription failed: {}", e)
+                                    })
+                            }
                         }
-                    }
-                },
-            ));
-
-            match transcribe_result {
-                Ok(inner_result) => {
-                    // Success or normal error — put the engine back
-                    let mut engine_guard = self.lock_engine();
-                    *engine_guard = Some(engine);
-                    inner_result?
-                }
-                Err(panic_payload) => {
-                    // Engine panicked — do NOT put it back (it's in an unknown state).
-                    // The engine is dropped here, effectively unloading it.
-                    let panic_msg = if let Some(s) = panic_payload.downcast_ref::<&str>() {
-                        s.to_string()
-                    } else if let Some(s) = panic_payload.downcast_ref::<String>() {
-                        s.clone()
-                    } else {
-                        "unknown panic".to_string()
-                    };
-                    error!(
-                        "Transcription engine panicked: {}. Model has been unloaded.",
-                        panic_msg
-                    );
-
-                    // Clear the model ID so it will be reloaded on next attempt
-                    {
-                        let mut current_model = self
+                    },
+                ));
+
+                match transcribe_result {
+                    Ok(inner_result) => {
+                        // Success or normal error — put the engine back, unless it was
+                        // abandoned after a timeout (which clears the model id) or
+                        // another model loaded meanwhile
+                        let mut engine_guard = self.lock_engine();
+                        let abandoned = self
                             .current_model_id
                             .lock()
-                            .unwrap_or_else(|e| e.into_inner());
-                        *current_model = None;
+                            .unwrap_or_else(|e| e.into_inner())
+                            .is_none();
+                        if abandoned {
+                            info!("Dropping an engine abandoned after a timeout");
+                        } else if engine_guard.is_none() {
+                            *engine_guard = Some(engine);
+                        }
+                        inner_result?
                     }
+                    Err(panic_payload) => {
+                        // Engine panicked — do NOT put it back (it's in an unknown state).
+                        // The engine is dropped here, effectively unloading it.
+                        let panic_msg = if let Some(s) = panic_payload.downcast_ref::<&str>() {
+                            s.to_string()
+                        } else if let Some(s) = panic_payload.downcast_ref::<String>() {
+                            s.clone()
+                        } else {
+                            "unknown panic".to_string()
+                        };
+                        error!(
+                            "Transcription engine panicked: {}. Model has been unloaded.",
+                            panic_msg
+                        );
+
+                        // Clear the model ID so it will be reloaded on next attempt
+                        {
+                            let mut current_model = self
+                                .current_model_id
+                                .lock()
+                                .unwrap_or_else(|e| e.into_inner());
+                            *current_model = None;
+                        }
+
+                        let _ = self.app_handle.emit(
+                            "model-state-changed",
+                            ModelStateEvent {
+                                event_type: "unloaded".to_string(),
+                                model_id: None,
+                                model_name: None,
+                                error: Some(format!("Engine panicked: {}", panic_msg)),
+                            },
+                        );
 
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "unloaded".to_string(),
-                            model_id: None,
-                            model_name: None,
-                            error: Some(format!("Engine panicked: {}", panic_msg)),
-                        },
-                    );
-
-                    return Err(anyhow::anyhow!(
-                        "Transcription engine panicked: {}. The model has been unloaded and will reload on next attempt.",
-                        panic_msg
-                    ));
+                        return Err(anyhow::anyhow!(
+                            "Transcription engine panicked: {}. The model has been unloaded and will reload on next attempt.",
+                            panic_msg
+                        ));
+                    }
                 }
+            };
+            if let Some(key) = key {
+                self.cache.insert(key, result.text.clone());
             }
+            result.text
         };
 
         // Apply word correction if custom words are configured
         let corrected_result = if !settings.custom_words.is_empty() {
             apply_custom_words(
-                &result.text,
+                &raw_text,
                 &settings.custom_words,
                 settings.word_correction_threshold,
             )
         } else {
-            result.text
+            raw_text
         };
 
         // Filter out filler words and hallucinations
         let filtered_result = filter_transcription_output(&corrected_result);
 
         let et = std::time::Instant::now();
-        let translation_note = if settings.translate_to_english {
-            " (translated)"
-        } else {
-            ""
-        };
+        let translation_note = if translate { " (translated)" } else { "" };
         info!(
             "Transcription completed in {}ms{}",
             (et - st).as_millis(),
@@ -617,6 +760,99 @@ impl TranscriptionManager {
 
         Ok(final_result)
     }
+
+    /// `transcribe` on a blocking thread, giving up after the
+    /// `transcription_timeout_secs` setting. A stuck engine can't be interrupted,
+    /// so on timeout it is abandoned: the model is marked unloaded and the next
+    /// load replaces it.
+    pub async fn transcribe_with_timeout(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> Result<String, TranscriptionError> {
+        self.run_with_timeout(move |manager| manager.transcribe(audio, language.as_deref()))
+            .await
+    }
+
+    /// `transcribe_with_prompt` with the timeout of `transcribe_with_timeout`.
+    pub async fn transcribe_with_prompt_timeout(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        initial_prompt: Option<String>,
+    ) -> Result<String, TranscriptionError> {
+        self.run_with_timeout(move |manager| {
+            manager.transcribe_with_prompt(audio, language.as_deref(), initial_prompt.as_deref())
+        })
+        .await
+    }
+
+    /// `translate_to_english` with the timeout of `transcribe_with_timeout`.
+    pub async fn translate_with_timeout(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        initial_prompt: Option<String>,
+    ) -> Result<String, TranscriptionError> {
+        self.run_with_timeout(move |manager| {
+            manager.translate_to_english(audio, language.as_deref(), initial_prompt.as_deref())
+        })
+        .await
+    }
+
+    async fn run_with_timeout<F>(&self, run: F) -> Result<String, TranscriptionError>
+    where
+        F: FnOnce(&TranscriptionManager) -> Result<String> + Send + 'static,
+    {
+        let secs = get_settings(&self.app_handle).transcription_timeout_secs;
+        let manager = self.clone();
+        let task = tauri::async_runtime::spawn_blocking(move || run(&manager));
+
+        match tokio::time::timeout(Duration::from_secs(secs as u64), task).await {
+            Ok(Ok(result)) => result.map_err(TranscriptionError::Failed),
+            Ok(Err(e)) => Err(TranscriptionError::Failed(anyhow::anyhow!(
+                "Transcription task failed: {}",
+                e
+            ))),
+            Err(_) => {
+                error!("Transcription timed out after {}s", secs);
+                *self
+                    .current_model_id
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner()) = None;
+                let _ = self.app_handle.emit(
+                    "model-state-changed",
+                    ModelStateEvent {
+                        event_type: "unloaded".to_string(),
+                        model_id: None,
+                        model_name: None,
+                        error: Some(format!("Transcription timed out after {}s", secs)),
+                    },
+                );
+                Err(TranscriptionError::Timeout { secs })
+            }
+        }
+    }
+
+    /// The language `text` was spoken in, if it was transcribed with the
+    /// language on "auto" (`language` being the override passed to
+    /// `transcribe`, if any). None when a language was chosen, when the
+    /// model translated to English (the text then says nothing about what was
+    /// spoken) or when it can't be told.
+    pub fn detected_language(&self, language: Option<&str>, text: &str) -> Option<String> {
+        let settings = get_settings(&self.app_handle);
+        if language.unwrap_or(&settings.selected_language) != "auto" {
+            return None;
+        }
+        if settings.translate_to_english && self.current_model_supports_translation() {
+            return None;
+        }
+        let detected = crate::language_detect::detect_language(text);
+        if detected.is_none() && !text.trim().is_empty() {
+            debug!("Could not detect the language of the transcription");
+        }
+        detected
+    }
 }
 
 impl Drop for TranscriptionManager {
@@ -636,3 +872,42 @@ impl Drop for TranscriptionManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initial_prompt_appends_custom_words_when_enabled() {
+        let words = vec![
+            "Kubernetes".to_string(),
+            " ".to_string(),
+            "Priya".to_string(),
+        ];
+        assert_eq!(
+            build_initial_prompt(" Standup notes. ", &words, true).as_deref(),
+            Some("Standup notes. Kubernetes, Priya")
+        );
+        assert_eq!(
+            build_initial_prompt("Standup notes.", &words, false).as_deref(),
+            Some("Standup notes.")
+        );
+        assert_eq!(
+            build_initial_prompt("", &words, true).as_deref(),
+            Some("Kubernetes, Priya")
+        );
+        assert_eq!(build_initial_prompt("  ", &words, false), None);
+    }
+
+    #[test]
+    fn test_initial_prompt_is_capped() {
+        let words: Vec<String> = (0..500).map(|i| format!("word{}", i)).collect();
+        let prompt = build_initial_prompt("Names:", &words, true).unwrap();
+        assert!(prompt.chars().count() <= MAX_INITIAL_PROMPT_CHARS);
+        assert!(prompt.ends_with(|c: char| c.is_ascii_digit()));
+
+        let long = "x".repeat(MAX_INITIAL_PROMPT_CHARS * 2);
+        let prompt = build_initial_prompt(&long, &words, true).unwrap();
+        assert_eq!(prompt.chars().count(), MAX_INITIAL_PROMPT_CHARS);
+    }
+}
@@ -0,0 +1,221 @@
+//! Remembers recent transcripts by the audio they came from, so importing the
+//! same file again or retranscribing unchanged audio skips inference.
+//!
+//! Keys are SHA-256 over the samples plus everything else that changes the
+//! engine's output (model, language, task, initial prompt). Values are the raw
+//! engine text, before custom-word correction and filtering. The cache keeps
+//! the most recently used `CAPACITY` entries and is saved to
+//! `transcription_cache.json` in the app data directory.
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const CAPACITY: usize = 50;
+
+pub type CacheKey = [u8; 32];
+
+/// What a transcript depends on besides the audio.
+pub struct CacheParams<'a> {
+    pub model_id: &'a str,
+    pub language: &'a str,
+    pub translate: bool,
+    pub initial_prompt: Option<&'a str>,
+}
+
+pub fn cache_key(samples: &[f32], params: &CacheParams) -> CacheKey {
+    let mut hasher = Sha256::new();
+    let mut bytes = Vec::with_capacity(4096 * 4);
+    for chunk in samples.chunks(4096) {
+        bytes.clear();
+        bytes.extend(chunk.iter().flat_map(|s| s.to_le_bytes()));
+        hasher.update(&bytes);
+    }
+    for field in [
+        params.model_id,
+        params.language,
+        if params.translate {
+            "translate"
+        } else {
+            "transcribe"
+        },
+        params.initial_prompt.unwrap_or_default(),
+    ] {
+        hasher.update([0]);
+        hasher.update(field.as_bytes());
+    }
+    hasher.finalize().into()
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredEntry {
+    key: String,
+    text: String,
+}
+
+#[derive(Default)]
+struct Entries {
+    texts: HashMap<CacheKey, String>,
+    /// Least recently used first.
+    order: VecDeque<CacheKey>,
+}
+
+impl Entries {
+    fn touch(&mut self, key: &CacheKey) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            self.order.remove(pos);
+        }
+        self.order.push_back(*key);
+    }
+}
+
+pub struct TranscriptionCache {
+    path: PathBuf,
+    entries: Mutex<Entries>,
+}
+
+impl TranscriptionCache {
+    /// Open the cache saved at `path`. A missing or unreadable file starts empty.
+    pub fn load(path: PathBuf) -> Self {
+        let mut entries = Entries::default();
+        if let Ok(json) = std::fs::read_to_string(&path) {
+            match serde_json::from_str::<Vec<StoredEntry>>(&json) {
+                Ok(stored) => {
+                    for entry in stored {
+                        let Some(key) = decode_key(&entry.key) else {
+                            continue;
+                        };
+                        entries.texts.insert(key, entry.text);
+                        entries.touch(&key);
+                    }
+                }
+                Err(e) => warn!("Ignoring unreadable transcription cache: {}", e),
+            }
+        }
+        while entries.order.len() > CAPACITY {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.texts.remove(&oldest);
+            }
+        }
+        Self {
+            path,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        let text = entries.texts.get(key)?.clone();
+        entries.touch(key);
+        Some(text)
+    }
+
+    pub fn insert(&self, key: CacheKey, text: String) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.texts.insert(key, text);
+        entries.touch(&key);
+        if entries.order.len() > CAPACITY {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.texts.remove(&oldest);
+            }
+        }
+        self.save(&entries);
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        *entries = Entries::default();
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove transcription cache: {}", e);
+            }
+        }
+    }
+
+    fn save(&self, entries: &Entries) {
+        let stored: Vec<StoredEntry> = entries
+            .order
+            .iter()
+            .filter_map(|key| {
+                Some(StoredEntry {
+                    key: encode_key(key),
+                    text: entries.texts.get(key)?.clone(),
+                })
+            })
+            .collect();
+        let result = serde_json::to_string(&stored)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(&self.path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!("Failed to save transcription cache: {}", e);
+        }
+    }
+}
+
+fn encode_key(key: &CacheKey) -> String {
+    key.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_key(hex: &str) -> Option<CacheKey> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut key = [0u8; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn params(model_id: &str) -> CacheParams<'_> {
+        CacheParams {
+            model_id,
+            language: "auto",
+            translate: false,
+            initial_prompt: None,
+        }
+    }
+
+    #[test]
+    fn test_key_depends_on_audio_and_params() {
+        let audio = [0.1, 0.2, 0.3];
+        let key = cache_key(&audio, &params("small"));
+        assert_eq!(key, cache_key(&audio, &params("small")));
+        assert_ne!(key, cache_key(&[0.1, 0.2], &params("small")));
+        assert_ne!(key, cache_key(&audio, &params("large")));
+        assert_eq!(decode_key(&encode_key(&key)), Some(key));
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_and_persists() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("transcription_cache.json");
+        let cache = TranscriptionCache::load(path.clone());
+        let key = |i: usize| cache_key(&[i as f32], &params("small"));
+
+        for i in 0..CAPACITY {
+            cache.insert(key(i), format!("text {}", i));
+        }
+        // Using the oldest entry keeps it when the next insert evicts one
+        assert_eq!(cache.get(&key(0)).as_deref(), Some("text 0"));
+        cache.insert(key(CAPACITY), "newest".to_string());
+        assert!(cache.get(&key(1)).is_none());
+
+        let reloaded = TranscriptionCache::load(path.clone());
+        assert_eq!(reloaded.get(&key(0)).as_deref(), Some("text 0"));
+        assert_eq!(reloaded.get(&key(CAPACITY)).as_deref(), Some("newest"));
+        assert!(reloaded.get(&key(1)).is_none());
+
+        reloaded.clear();
+        assert!(reloaded.get(&key(0)).is_none());
+        assert!(!path.exists());
+    }
+}
@@ -8,6 +8,27 @@ use serde::Serialize;
 use std::sync::Arc;
 use tauri::AppHandle;
 
+#[derive(Debug)]
+pub enum TranscriptionError {
+    Timeout { secs: u32 },
+    Failed(anyhow::Error),
+}
+
+impl std::fmt::Display for TranscriptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranscriptionError::Timeout { secs } => {
+                write!(f, "Transcription timed out after {}s", secs)
+            }
+            TranscriptionError::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for TranscriptionError {}
+
+pub fn emit_transcription_timeout(_app: &AppHandle, _entry_id: Option<i64>) {}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelStateEvent {
     pub event_type: String,
@@ -52,4 +73,11 @@ impl TranscriptionManager {
     pub fn transcribe(&self, _audio: Vec<f32>) -> Result<String> {
         Ok(String::new())
     }
+
+    pub async fn transcribe_with_timeout(
+        &self,
+        _audio: Vec<f32>,
+    ) -> std::result::Result<String, TranscriptionError> {
+        Ok(String::new())
+    }
 }
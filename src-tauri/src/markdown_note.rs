@@ -0,0 +1,183 @@
+use chrono::{DateTime, NaiveDate, TimeZone};
+
+/// A markdown file split into the parts a journal entry needs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkdownNote {
+    pub title: String,
+    pub body: String,
+    pub tags: Vec<String>,
+    /// From a `timestamp`, `date` or `created` frontmatter key (unix seconds).
+    pub timestamp: Option<i64>,
+}
+
+/// Split a leading `---` frontmatter block from the rest of the file.
+fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+/// Strip YAML quotes. Double-quoted values are decoded as JSON strings, which
+/// covers the escapes in frontmatter this app writes.
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    if let Ok(decoded) = serde_json::from_str::<String>(value) {
+        return decoded;
+    }
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+        .to_string()
+}
+
+fn clean_tag(tag: &str) -> Option<String> {
+    let tag = unquote(tag);
+    let tag = tag.trim_start_matches('#').trim();
+    (!tag.is_empty()).then(|| tag.to_string())
+}
+
+/// Parse a frontmatter date: RFC 3339, a bare `YYYY-MM-DD` (local midnight)
+/// or unix seconds.
+fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = unquote(value);
+    if let Ok(dt) = DateTime::parse_from_rfc3339(&value) {
+        return Some(dt.timestamp());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+        return chrono::Local
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+            .earliest()
+            .map(|dt| dt.timestamp());
+    }
+    value.parse().ok()
+}
+
+/// Parse a markdown file. The title comes from a frontmatter `title`, then
+/// the first `# heading`, then `file_stem`. Tags may be an inline list
+/// (`[a, b]`), a block list (`- a`) or a comma-separated string.
+pub fn parse_markdown_note(file_stem: &str, content: &str) -> MarkdownNote {
+    let (frontmatter, body) = split_frontmatter(content.trim_start_matches('\u{feff}'));
+
+    let mut title = None;
+    let mut tags = Vec::new();
+    let mut timestamp = None;
+    let mut in_tag_list = false;
+    for line in frontmatter.unwrap_or_default().lines() {
+        if in_tag_list {
+            if let Some(item) = line.trim_start().strip_prefix("- ") {
+                tags.extend(clean_tag(item));
+                continue;
+            }
+            in_tag_list = false;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "title" if !value.is_empty() => title = Some(unquote(value)),
+            "tags" => {
+                if let Ok(list) = serde_json::from_str::<Vec<String>>(value) {
+                    tags.extend(list.iter().filter_map(|t| clean_tag(t)));
+                } else if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']'))
+                {
+                    tags.extend(list.split(',').filter_map(clean_tag));
+                } else if value.is_empty() {
+                    in_tag_list = true;
+                } else {
+                    tags.extend(value.split(',').filter_map(clean_tag));
+                }
+            }
+            "timestamp" | "date" | "created" if timestamp.is_none() => {
+                timestamp = parse_timestamp(value)
+            }
+            _ => {}
+        }
+    }
+
+    let mut body = body.trim().to_string();
+    if title.is_none() {
+        if let Some(heading) = body
+            .lines()
+            .find_map(|l| l.strip_prefix("# ").map(|h| h.trim().to_string()))
+        {
+            // A heading on the first line is the title, not part of the body
+            if body.lines().next().is_some_and(|l| l.starts_with("# ")) {
+                body = body
+                    .split_once('\n')
+                    .map(|(_, rest)| rest.trim().to_string())
+                    .unwrap_or_default();
+            }
+            title = Some(heading);
+        }
+    }
+
+    MarkdownNote {
+        title: title
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| file_stem.to_string()),
+        body,
+        tags,
+        timestamp,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frontmatter_with_inline_tags() {
+        let content = "---\ntitle: \"Standup\"\ntimestamp: 2024-03-01T09:30:00+00:00\n\
+                       tags: [\"work\", \"#daily\"]\n---\n\nShipped the importer.\n";
+        let note = parse_markdown_note("2024-03-01", content);
+        assert_eq!(note.title, "Standup");
+        assert_eq!(note.body, "Shipped the importer.");
+        assert_eq!(note.tags, vec!["work", "daily"]);
+        assert_eq!(note.timestamp, Some(1_709_285_400));
+    }
+
+    #[test]
+    fn test_block_tag_list_and_heading_title() {
+        let content = "---\ntags:\n  - ideas\n  - garden\nsource: obsidian\n---\n\
+                       # Raised beds\n\nBuild two more.";
+        let note = parse_markdown_note("note", content);
+        assert_eq!(note.title, "Raised beds");
+        assert_eq!(note.body, "Build two more.");
+        assert_eq!(note.tags, vec!["ideas", "garden"]);
+        assert_eq!(note.timestamp, None);
+    }
+
+    #[test]
+    fn test_reads_transcript_frontmatter() {
+        // As written by the transcript .md writer with `markdown_frontmatter` on
+        let content = "---\ntitle: \"Say \\\"hi\\\"\"\ntimestamp: 2024-03-01T17:30:00+08:00\n\
+                       tags: [\"a, b\",\"c\"]\nsource: \"voice\"\nsource_url: null\n---\n\nBody text";
+        let note = parse_markdown_note("file", content);
+        assert_eq!(note.title, "Say \"hi\"");
+        assert_eq!(note.tags, vec!["a, b", "c"]);
+        assert_eq!(note.timestamp, Some(1_709_285_400));
+        assert_eq!(note.body, "Body text");
+    }
+
+    #[test]
+    fn test_plain_file_uses_file_stem() {
+        let note = parse_markdown_note("Grocery list", "eggs\nmilk\n");
+        assert_eq!(note.title, "Grocery list");
+        assert_eq!(note.body, "eggs\nmilk");
+        assert!(note.tags.is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+//! Rendering of user-defined templates for transcript `.md` files.
+//!
+//! Available placeholders:
+//!
+//! - `{{title}}` – the entry title
+//! - `{{date}}` – recording date as `YYYY-MM-DD` (local time)
+//! - `{{time}}` – recording time as `HH:MM` (local time)
+//! - `{{transcript}}` – the raw transcription text
+//! - `{{post_processed}}` – the post-processed text, or empty if there is none
+//! - `{{tags}}` – tags as `#tag` separated by spaces
+//! - `{{source}}` – where the entry came from (`voice`, `video`, `meeting`, ...)
+//! - `{{source_url}}` – the source URL, or empty if there is none
+//!
+//! Whitespace inside the braces is ignored. Unknown placeholders are left in
+//! the output as written.
+
+/// Substitute `{{name}}` placeholders in `template` with `values`.
+pub fn render_template(template: &str, values: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find("{{") {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 2..];
+        let Some(close) = after.find("}}") else {
+            out.push_str(&rest[open..]);
+            return out;
+        };
+        let name = after[..close].trim();
+        match values.iter().find(|(key, _)| *key == name) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[open..open + 2 + close + 2]),
+        }
+        rest = &after[close + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitutes_known_placeholders() {
+        let values = [
+            ("title", "Standup".to_string()),
+            ("transcript", "Shipped it.".to_string()),
+        ];
+        assert_eq!(
+            render_template("# {{title}}\n\n{{ transcript }}\n", &values),
+            "# Standup\n\nShipped it.\n"
+        );
+    }
+
+    #[test]
+    fn test_unknown_and_unclosed_placeholders_stay_literal() {
+        let values = [("title", "Notes".to_string())];
+        assert_eq!(
+            render_template("{{title}} {{author}} {{title", &values),
+            "Notes {{author}} {{title"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use crate::settings;
+use log::warn;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+const BODY_PREVIEW_CHARS: usize = 80;
+
+/// First `BODY_PREVIEW_CHARS` characters of the text, with an ellipsis when cut.
+fn preview(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= BODY_PREVIEW_CHARS {
+        return text;
+    }
+    let mut cut: String = text.chars().take(BODY_PREVIEW_CHARS).collect();
+    cut.push('…');
+    cut
+}
+
+/// macOS only shows notifications once the user has granted permission.
+#[cfg(target_os = "macos")]
+fn permission_granted(app: &AppHandle) -> bool {
+    match app.notification().permission_state() {
+        Ok(tauri_plugin_notification::PermissionState::Granted) => true,
+        Ok(state) => {
+            log::debug!("Notification permission not granted ({:?})", state);
+            false
+        }
+        Err(e) => {
+            warn!("Failed to check notification permission: {}", e);
+            false
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn permission_granted(_app: &AppHandle) -> bool {
+    true
+}
+
+fn show(app: &AppHandle, title: &str, body: &str) {
+    if !permission_granted(app) {
+        return;
+    }
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        warn!("Failed to show notification: {}", e);
+    }
+}
+
+/// Notify that a transcription finished, showing the start of the transcript.
+pub fn notify_transcription_complete(app: &AppHandle, title: &str, text: &str) {
+    if !settings::get_settings(app).notifications_enabled {
+        return;
+    }
+    show(app, title, &preview(text));
+}
+
+/// Notify that a model finished loading in the background.
+pub fn notify_model_loaded(app: &AppHandle, model_id: &str) {
+    let settings = settings::get_settings(app);
+    if !settings.notifications_enabled || !settings.notification_on_model_load {
+        return;
+    }
+    show(
+        app,
+        "Model loaded",
+        &format!("{} is ready to transcribe", model_id),
+    );
+}
@@ -30,7 +30,7 @@ tauri_panel! {
     })
 }
 
-const OVERLAY_WIDTH: f64 = 172.0;
+const OVERLAY_WIDTH: f64 = 220.0;
 const OVERLAY_HEIGHT: f64 = 36.0;
 
 #[cfg(target_os = "macos")]
@@ -44,19 +44,36 @@ const OVERLAY_BOTTOM_OFFSET: f64 = 15.0;
 #[cfg(any(target_os = "windows", target_os = "linux"))]
 const OVERLAY_BOTTOM_OFFSET: f64 = 40.0;
 
+/// Gap between the cursor and the top of the overlay, for `OverlayPosition::Cursor`.
+const OVERLAY_CURSOR_OFFSET: f64 = 24.0;
+
 #[cfg(target_os = "linux")]
 fn update_gtk_layer_shell_anchors(overlay_window: &tauri::webview::WebviewWindow) {
     let window_clone = overlay_window.clone();
+    // Layer shell surfaces can't be moved; near the cursor they are pinned to
+    // the top-left corner and pushed into place with margins
+    let cursor_margins = cursor_overlay_position(overlay_window.app_handle())
+        .map(|(x, y, work_area)| ((x - work_area.0) as i32, (y - work_area.1) as i32));
     let _ = overlay_window.run_on_main_thread(move || {
         // Try to get the GTK window from the Tauri webview
         if let Ok(gtk_window) = window_clone.gtk_window() {
             let settings = settings::get_settings(window_clone.app_handle());
-            match settings.overlay_position {
-                OverlayPosition::Top => {
+            gtk_window.set_anchor(Edge::Left, false);
+            gtk_window.set_layer_shell_margin(Edge::Left, 0);
+            gtk_window.set_layer_shell_margin(Edge::Top, 0);
+            match (settings.overlay_position, cursor_margins) {
+                (OverlayPosition::Top, _) => {
                     gtk_window.set_anchor(Edge::Top, true);
                     gtk_window.set_anchor(Edge::Bottom, false);
                 }
-                OverlayPosition::Bottom | OverlayPosition::None => {
+                (OverlayPosition::Cursor, Some((left, top))) => {
+                    gtk_window.set_anchor(Edge::Top, true);
+                    gtk_window.set_anchor(Edge::Left, true);
+                    gtk_window.set_anchor(Edge::Bottom, false);
+                    gtk_window.set_layer_shell_margin(Edge::Left, left);
+                    gtk_window.set_layer_shell_margin(Edge::Top, top);
+                }
+                _ => {
                     gtk_window.set_anchor(Edge::Bottom, true);
                     gtk_window.set_anchor(Edge::Top, false);
                 }
@@ -170,7 +187,35 @@ fn is_mouse_within_monitor(
         && mouse_y < (monitor_y + monitor_height as i32)
 }
 
+/// Overlay position centred just below the cursor and kept inside the work
+/// area, along with the work area's origin. All in logical pixels.
+fn cursor_overlay_position(app_handle: &AppHandle) -> Option<(f64, f64, (f64, f64))> {
+    let (mouse_x, mouse_y) = input::get_cursor_position(app_handle)?;
+    let monitor = get_monitor_with_cursor(app_handle)?;
+    let work_area = monitor.work_area();
+    let scale = monitor.scale_factor();
+    let left = work_area.position.x as f64 / scale;
+    let top = work_area.position.y as f64 / scale;
+    let right = left + work_area.size.width as f64 / scale - OVERLAY_WIDTH;
+    let bottom = top + work_area.size.height as f64 / scale - OVERLAY_HEIGHT;
+
+    let x = (mouse_x as f64 / scale - OVERLAY_WIDTH / 2.0).clamp(left, right.max(left));
+    let mut y = mouse_y as f64 / scale + OVERLAY_CURSOR_OFFSET;
+    if y > bottom {
+        // No room below the cursor, so sit above it
+        y = mouse_y as f64 / scale - OVERLAY_CURSOR_OFFSET - OVERLAY_HEIGHT;
+    }
+    Some((x, y.clamp(top, bottom.max(top)), (left, top)))
+}
+
 fn calculate_overlay_position(app_handle: &AppHandle) -> Option<(f64, f64)> {
+    let settings = settings::get_settings(app_handle);
+    if settings.overlay_position == OverlayPosition::Cursor {
+        if let Some((x, y, _)) = cursor_overlay_position(app_handle) {
+            return Some((x, y));
+        }
+    }
+
     if let Some(monitor) = get_monitor_with_cursor(app_handle) {
         let work_area = monitor.work_area();
         let scale = monitor.scale_factor();
@@ -179,12 +224,10 @@ fn calculate_overlay_position(app_handle: &AppHandle) -> Option<(f64, f64)> {
         let work_area_x = work_area.position.x as f64 / scale;
         let work_area_y = work_area.position.y as f64 / scale;
 
-        let settings = settings::get_settings(app_handle);
-
         let x = work_area_x + (work_area_width - OVERLAY_WIDTH) / 2.0;
         let y = match settings.overlay_position {
             OverlayPosition::Top => work_area_y + OVERLAY_TOP_OFFSET,
-            OverlayPosition::Bottom | OverlayPosition::None => {
+            OverlayPosition::Bottom | OverlayPosition::Cursor | OverlayPosition::None => {
                 work_area_y + work_area_height - OVERLAY_HEIGHT - OVERLAY_BOTTOM_OFFSET
             }
         };
@@ -354,6 +397,28 @@ pub fn hide_recording_overlay(app_handle: &AppHandle) {
     }
 }
 
+/// Payload of `overlay-update`, sent to the overlay about ten times a second
+/// while recording.
+#[derive(Clone, serde::Serialize)]
+struct OverlayUpdate {
+    duration_ms: u64,
+    /// RMS level in dBFS, floored at -100.
+    level_db: f32,
+}
+
+/// Update the overlay's recording timer and level bar.
+pub fn emit_overlay_update(app_handle: &AppHandle, duration_ms: u64, level_db: f32) {
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        let _ = overlay_window.emit(
+            "overlay-update",
+            OverlayUpdate {
+                duration_ms,
+                level_db,
+            },
+        );
+    }
+}
+
 pub fn emit_levels(app_handle: &AppHandle, levels: &Vec<f32>) {
     // emit levels to main app
     let _ = app_handle.emit("mic-level", levels);
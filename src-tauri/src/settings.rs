@@ -90,6 +90,33 @@ pub struct LLMPrompt {
     pub id: String,
     pub name: String,
     pub prompt: String,
+    /// Sampling temperature; `None` leaves it to the provider.
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// Reply length cap; `None` leaves it to the provider.
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
+/// A literal find-and-replace rule applied to every transcript, for names
+/// the model keeps getting wrong (e.g. "Tory" -> "Tauri").
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct WordReplacement {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+/// Request/response schema a provider speaks.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiStyle {
+    /// `/chat/completions`, as served by OpenAI and most compatible runners.
+    #[default]
+    OpenAI,
+    /// Ollama's native `/api/chat`.
+    Ollama,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
@@ -103,6 +130,20 @@ pub struct PostProcessProvider {
     pub models_endpoint: Option<String>,
     #[serde(default)]
     pub supports_structured_output: bool,
+    #[serde(default)]
+    pub api_style: ApiStyle,
+}
+
+/// What a recording captures.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordingSource {
+    #[default]
+    Microphone,
+    /// System audio only (loopback).
+    System,
+    /// Microphone and system audio mixed together, e.g. for calls.
+    Both,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
@@ -111,6 +152,32 @@ pub enum OverlayPosition {
     None,
     Top,
     Bottom,
+    /// Just below the mouse cursor, wherever it is when recording starts.
+    Cursor,
+}
+
+/// How carefully imported audio is resampled to the transcription rate.
+/// Higher quality uses a longer sinc filter: fewer artifacts, more CPU.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ResampleQuality {
+    Fast,
+    #[default]
+    Balanced,
+    High,
+}
+
+/// ONNX Runtime execution provider for the ONNX transcription engines and
+/// diarization. `Auto` tries the GPU providers and falls back to CPU.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ComputeBackend {
+    #[default]
+    Auto,
+    Cpu,
+    Cuda,
+    Directml,
+    Coreml,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
@@ -301,6 +368,23 @@ pub struct AppSettings {
     pub clamshell_microphone: Option<String>,
     #[serde(default)]
     pub selected_output_device: Option<String>,
+    #[serde(default)]
+    pub recording_source: RecordingSource,
+    /// System-audio device by name; `None` uses the default (or first) one.
+    #[serde(default)]
+    pub selected_loopback_device: Option<String>,
+    /// Input gain in dB per microphone name ("default" for the host default).
+    /// Applied to every recording, with clipping protection.
+    #[serde(default)]
+    pub microphone_gain: HashMap<String, f32>,
+    /// Sample rate of the archival WAV saved next to each recording. At the
+    /// default (16kHz, 16 bit) no archival copy is written; transcription
+    /// always uses a 16kHz mono copy.
+    #[serde(default = "default_recording_sample_rate")]
+    pub recording_sample_rate: u32,
+    /// Bits per sample of the archival WAV: 16, 24, or 32 (float).
+    #[serde(default = "default_recording_bit_depth")]
+    pub recording_bit_depth: u16,
     #[serde(default = "default_translate_to_english")]
     pub translate_to_english: bool,
     #[serde(default = "default_selected_language")]
@@ -313,6 +397,15 @@ pub struct AppSettings {
     pub log_level: LogLevel,
     #[serde(default)]
     pub custom_words: Vec<String>,
+    /// Initial prompt given to Whisper to bias it toward names and domain
+    /// terms. Other engines ignore it.
+    #[serde(default)]
+    pub transcription_initial_prompt: String,
+    /// Append `custom_words` to the initial prompt.
+    #[serde(default)]
+    pub initial_prompt_include_custom_words: bool,
+    #[serde(default)]
+    pub word_replacements: Vec<WordReplacement>,
     #[serde(default)]
     pub model_unload_timeout: ModelUnloadTimeout,
     #[serde(default = "default_word_correction_threshold")]
@@ -339,6 +432,10 @@ pub struct AppSettings {
     pub post_process_api_keys: HashMap<String, String>,
     #[serde(default = "default_post_process_models")]
     pub post_process_models: HashMap<String, String>,
+    /// Per-provider override of the context window, in tokens. Prompts that
+    /// would exceed it are rejected before they are sent.
+    #[serde(default)]
+    pub post_process_max_context_tokens: HashMap<String, u32>,
     #[serde(default = "default_post_process_prompts")]
     pub post_process_prompts: Vec<LLMPrompt>,
     #[serde(default)]
@@ -363,6 +460,99 @@ pub struct AppSettings {
     /// Custom storage path for Mutter journal files. If None, uses app_data_dir/journal_recordings/.
     #[serde(default)]
     pub journal_storage_path: Option<String>,
+    /// Netscape-format cookies file passed to yt-dlp via `--cookies`.
+    #[serde(default)]
+    pub ytdlp_cookies_file: Option<String>,
+    /// Browser to read cookies from via `--cookies-from-browser` (e.g. "firefox").
+    #[serde(default)]
+    pub ytdlp_cookies_from_browser: Option<String>,
+    /// Release tag of the installed yt-dlp binary, recorded at install time.
+    #[serde(default)]
+    pub ytdlp_installed_version: Option<String>,
+    /// Minimum run of identical consecutive words collapsed before sending text to the LLM.
+    #[serde(default = "default_dedup_threshold")]
+    pub dedup_threshold: usize,
+    /// Obsidian vault that journal entries are mirrored into (under `voice-journal/`).
+    #[serde(default)]
+    pub obsidian_vault_path: Option<String>,
+    /// Commit journal files to the git repository at `git_sync_repo_path` as they are saved.
+    #[serde(default)]
+    pub git_sync_enabled: bool,
+    /// Work tree journal files are committed to. Files outside it are not synced.
+    #[serde(default)]
+    pub git_sync_repo_path: String,
+    /// Title new journal entries saved without one from the first sentence of the transcript.
+    #[serde(default)]
+    pub auto_generate_title: bool,
+    /// With `auto_generate_title`, replace the title of longer entries with an LLM summary.
+    #[serde(default)]
+    pub auto_title_use_llm: bool,
+    /// Show a desktop notification when a transcription finishes.
+    #[serde(default)]
+    pub notifications_enabled: bool,
+    /// With `notifications_enabled`, also notify when a model finishes loading.
+    #[serde(default)]
+    pub notification_on_model_load: bool,
+    /// ffmpeg binary used for formats symphonia can't decode. If None, PATH is searched.
+    #[serde(default)]
+    pub ffmpeg_path: Option<String>,
+    /// Days a deleted journal entry stays in the trash before it is purged at launch.
+    /// 0 keeps trashed entries until they are purged by hand.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Back up the journal database at launch and every 24 hours.
+    #[serde(default = "default_backup_enabled")]
+    pub backup_enabled: bool,
+    /// Number of rolling journal database backups to keep.
+    #[serde(default = "default_backup_count")]
+    pub backup_count: u32,
+    /// Start transcript `.md` files with a YAML frontmatter block.
+    #[serde(default)]
+    pub markdown_frontmatter: bool,
+    /// Template for transcript `.md` files; see `markdown_template` for the
+    /// placeholders. `None` writes the raw transcription text.
+    #[serde(default)]
+    pub markdown_template: Option<String>,
+    /// Minimum cosine similarity between a meeting speaker and an enrolled
+    /// speaker profile for the profile's name to be filled in.
+    #[serde(default = "default_speaker_match_threshold")]
+    pub speaker_match_threshold: f32,
+    /// Seconds before a stuck transcription is abandoned.
+    #[serde(default = "default_transcription_timeout_secs")]
+    pub transcription_timeout_secs: u32,
+    /// App group shared with the iOS share extension, e.g. "group.com.example.mutter".
+    /// Audio shared from other apps is dropped into this group's container.
+    #[serde(default)]
+    pub ios_app_group_id: Option<String>,
+    /// Proxy for all outbound requests and yt-dlp, e.g. "http://host:8080" or
+    /// "socks5://host:1080". When unset the standard proxy environment
+    /// variables apply.
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    /// Resampler used when imported audio isn't already at 16kHz.
+    #[serde(default)]
+    pub audio_resample_quality: ResampleQuality,
+    /// Folder watched for new `.wav`/`.mp3`/`.m4a` files, which are imported
+    /// as journal entries. None disables the watcher.
+    #[serde(default)]
+    pub watch_folder_path: Option<String>,
+    /// Journal folder watch-folder imports are filed into; None leaves them unfiled.
+    #[serde(default)]
+    pub watch_folder_import_folder_id: Option<i64>,
+    /// Execution provider for ONNX models; see `compute_backend`.
+    #[serde(default)]
+    pub compute_backend: ComputeBackend,
+}
+
+pub const TRANSCRIPTION_SAMPLE_RATE: u32 = 16000;
+pub const TRANSCRIPTION_BIT_DEPTH: u16 = 16;
+
+fn default_recording_sample_rate() -> u32 {
+    TRANSCRIPTION_SAMPLE_RATE
+}
+
+fn default_recording_bit_depth() -> u16 {
+    TRANSCRIPTION_BIT_DEPTH
 }
 
 fn default_model() -> String {
@@ -420,10 +610,34 @@ fn default_auto_submit() -> bool {
     false
 }
 
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_backup_enabled() -> bool {
+    true
+}
+
+fn default_backup_count() -> u32 {
+    7
+}
+
+fn default_speaker_match_threshold() -> f32 {
+    0.75
+}
+
+fn default_transcription_timeout_secs() -> u32 {
+    300
+}
+
 fn default_history_limit() -> usize {
     5
 }
 
+fn default_dedup_threshold() -> usize {
+    3
+}
+
 fn default_recording_retention_period() -> RecordingRetentionPeriod {
     RecordingRetentionPeriod::PreserveLimit
 }
@@ -463,6 +677,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: true,
+            api_style: ApiStyle::OpenAI,
         },
         PostProcessProvider {
             id: "zai".to_string(),
@@ -471,6 +686,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: true,
+            api_style: ApiStyle::OpenAI,
         },
         PostProcessProvider {
             id: "openrouter".to_string(),
@@ -479,6 +695,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: true,
+            api_style: ApiStyle::OpenAI,
         },
         PostProcessProvider {
             id: "anthropic".to_string(),
@@ -487,6 +704,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: false,
+            api_style: ApiStyle::OpenAI,
         },
         PostProcessProvider {
             id: "groq".to_string(),
@@ -495,6 +713,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: false,
+            api_style: ApiStyle::OpenAI,
         },
         PostProcessProvider {
             id: "cerebras".to_string(),
@@ -503,6 +722,16 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
             supports_structured_output: true,
+            api_style: ApiStyle::OpenAI,
+        },
+        PostProcessProvider {
+            id: "gemini".to_string(),
+            label: "Google Gemini".to_string(),
+            base_url: "https://generativelanguage.googleapis.com/v1beta".to_string(),
+            allow_base_url_edit: false,
+            models_endpoint: Some("/models".to_string()),
+            supports_structured_output: false,
+            api_style: ApiStyle::OpenAI,
         },
     ];
 
@@ -519,6 +748,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             allow_base_url_edit: false,
             models_endpoint: None,
             supports_structured_output: true,
+            api_style: ApiStyle::OpenAI,
         });
     }
 
@@ -530,6 +760,7 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
         allow_base_url_edit: true,
         models_endpoint: Some("/models".to_string()),
         supports_structured_output: false,
+        api_style: ApiStyle::OpenAI,
     });
 
     providers
@@ -566,6 +797,8 @@ fn default_post_process_prompts() -> Vec<LLMPrompt> {
         id: "default_improve_transcriptions".to_string(),
         name: "Improve Transcriptions".to_string(),
         prompt: "Clean this transcript:\n1. Fix spelling, capitalization, and punctuation errors\n2. Convert number words to digits (twenty-five → 25, ten percent → 10%, five dollars → $5)\n3. Replace spoken punctuation with symbols (period → ., comma → ,, question mark → ?)\n4. Remove filler words (um, uh, like as filler)\n5. Keep the language in the original version (if it was french, keep it in french for example)\n\nPreserve exact meaning and word order. Do not paraphrase or reorder content.\n\nReturn only the cleaned transcript.\n\nTranscript:\n${output}".to_string(),
+        temperature: None,
+        max_tokens: None,
     }]
 }
 
@@ -697,12 +930,20 @@ pub fn get_default_settings() -> AppSettings {
         selected_microphone: None,
         clamshell_microphone: None,
         selected_output_device: None,
+        recording_source: RecordingSource::default(),
+        selected_loopback_device: None,
+        microphone_gain: HashMap::new(),
+        recording_sample_rate: default_recording_sample_rate(),
+        recording_bit_depth: default_recording_bit_depth(),
         translate_to_english: false,
         selected_language: "auto".to_string(),
         overlay_position: default_overlay_position(),
         debug_mode: false,
         log_level: default_log_level(),
         custom_words: Vec::new(),
+        transcription_initial_prompt: String::new(),
+        initial_prompt_include_custom_words: false,
+        word_replacements: Vec::new(),
         model_unload_timeout: ModelUnloadTimeout::Never,
         word_correction_threshold: default_word_correction_threshold(),
         history_limit: default_history_limit(),
@@ -716,6 +957,7 @@ pub fn get_default_settings() -> AppSettings {
         post_process_providers: default_post_process_providers(),
         post_process_api_keys: default_post_process_api_keys(),
         post_process_models: default_post_process_models(),
+        post_process_max_context_tokens: HashMap::new(),
         post_process_prompts: default_post_process_prompts(),
         post_process_selected_prompt_id: None,
         mute_while_recording: false,
@@ -728,10 +970,46 @@ pub fn get_default_settings() -> AppSettings {
         typing_tool: default_typing_tool(),
         external_script_path: None,
         journal_storage_path: None,
+        ytdlp_cookies_file: None,
+        ytdlp_cookies_from_browser: None,
+        ytdlp_installed_version: None,
+        dedup_threshold: default_dedup_threshold(),
+        obsidian_vault_path: None,
+        git_sync_enabled: false,
+        git_sync_repo_path: String::new(),
+        auto_generate_title: false,
+        auto_title_use_llm: false,
+        notifications_enabled: false,
+        notification_on_model_load: false,
+        ffmpeg_path: None,
+        trash_retention_days: default_trash_retention_days(),
+        backup_enabled: default_backup_enabled(),
+        backup_count: default_backup_count(),
+        markdown_frontmatter: false,
+        markdown_template: None,
+        speaker_match_threshold: default_speaker_match_threshold(),
+        transcription_timeout_secs: default_transcription_timeout_secs(),
+        ios_app_group_id: None,
+        http_proxy: None,
+        audio_resample_quality: ResampleQuality::default(),
+        watch_folder_path: None,
+        watch_folder_import_folder_id: None,
+        compute_backend: ComputeBackend::default(),
     }
 }
 
 impl AppSettings {
+    pub fn microphone_gain_db(&self, device: &str) -> f32 {
+        self.microphone_gain.get(device).copied().unwrap_or(0.0)
+    }
+
+    /// Whether recordings get an archival copy in a format other than the
+    /// 16kHz/16 bit transcription WAV.
+    pub fn wants_archival_copy(&self) -> bool {
+        self.recording_sample_rate != TRANSCRIPTION_SAMPLE_RATE
+            || self.recording_bit_depth != TRANSCRIPTION_BIT_DEPTH
+    }
+
     pub fn active_post_process_provider(&self) -> Option<&PostProcessProvider> {
         self.post_process_providers
             .iter()
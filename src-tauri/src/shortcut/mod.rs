@@ -21,7 +21,7 @@ use tauri_plugin_autostart::ManagerExt;
 
 use crate::settings::{
     self, get_settings, AutoSubmitKey, ClipboardHandling, KeyboardImplementation, LLMPrompt,
-    OverlayPosition, PasteMethod, ShortcutBinding, SoundTheme, TypingTool,
+    OverlayPosition, PasteMethod, ShortcutBinding, SoundTheme, TypingTool, WordReplacement,
     APPLE_INTELLIGENCE_DEFAULT_MODEL_ID, APPLE_INTELLIGENCE_PROVIDER_ID,
 };
 use crate::tray;
@@ -540,6 +540,7 @@ pub fn change_overlay_position_setting(app: AppHandle, position: String) -> Resu
         "none" => OverlayPosition::None,
         "top" => OverlayPosition::Top,
         "bottom" => OverlayPosition::Bottom,
+        "cursor" => OverlayPosition::Cursor,
         other => {
             warn!("Invalid overlay position '{}', defaulting to bottom", other);
             OverlayPosition::Bottom
@@ -646,6 +647,49 @@ pub fn update_custom_words(app: AppHandle, words: Vec<String>) -> Result<(), Str
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_transcription_initial_prompt_setting(
+    app: AppHandle,
+    prompt: String,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.transcription_initial_prompt = prompt;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_initial_prompt_include_custom_words_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.initial_prompt_include_custom_words = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Replace the full list of word replacement rules.
+#[tauri::command]
+#[specta::specta]
+pub fn update_word_replacements(
+    app: AppHandle,
+    replacements: Vec<WordReplacement>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.word_replacements = replacements;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_word_replacements(app: AppHandle) -> Result<Vec<WordReplacement>, String> {
+    Ok(settings::get_settings(&app).word_replacements)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_word_correction_threshold_setting(
@@ -658,6 +702,142 @@ pub fn change_word_correction_threshold_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_transcription_timeout_setting(app: AppHandle, secs: u32) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.transcription_timeout_secs = secs.max(1);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_dedup_threshold_setting(app: AppHandle, threshold: usize) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.dedup_threshold = threshold.max(2);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_ffmpeg_path_setting(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    let path = path.filter(|p| !p.trim().is_empty());
+    if let Some(ref p) = path {
+        if !std::path::Path::new(p).is_file() {
+            return Err(format!("ffmpeg binary does not exist: {}", p));
+        }
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.ffmpeg_path = path;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Takes effect after a restart: ORT's execution providers can only be set
+/// once per process (see `compute_backend`).
+#[tauri::command]
+#[specta::specta]
+pub fn change_compute_backend_setting(
+    app: AppHandle,
+    backend: settings::ComputeBackend,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.compute_backend = backend;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Set (or with None, clear) the folder watched for audio files to import,
+/// and restart the watcher on it.
+#[tauri::command]
+#[specta::specta]
+pub fn change_watch_folder_setting(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    let path = path.filter(|p| !p.trim().is_empty());
+    if let Some(ref p) = path {
+        if !std::path::Path::new(p).is_dir() {
+            return Err(format!("Folder does not exist: {}", p));
+        }
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.watch_folder_path = path;
+    settings::write_settings(&app, settings);
+    crate::watch_folder::restart(&app);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_watch_folder_import_folder_setting(
+    app: AppHandle,
+    folder_id: Option<i64>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.watch_folder_import_folder_id = folder_id;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_notifications_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.notifications_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_notification_on_model_load_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.notification_on_model_load = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Set (or with an empty path, clear) the git repository journal changes are
+/// committed to.
+#[tauri::command]
+#[specta::specta]
+pub fn change_git_sync_repo_path_setting(app: AppHandle, path: String) -> Result<(), String> {
+    let path = path.trim().to_string();
+    if !path.is_empty() && !std::path::Path::new(&path).is_dir() {
+        return Err(format!("Git sync folder does not exist: {}", path));
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.git_sync_repo_path = path;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_git_sync_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.git_sync_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Minimum similarity for a meeting speaker to be named after an enrolled
+/// speaker profile, clamped to 0–1.
+#[tauri::command]
+#[specta::specta]
+pub fn change_speaker_match_threshold_setting(
+    app: AppHandle,
+    threshold: f32,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.speaker_match_threshold = threshold.clamp(0.0, 1.0);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(), String> {
@@ -725,6 +905,39 @@ pub fn change_external_script_path_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_ytdlp_cookies_file_setting(
+    app: AppHandle,
+    path: Option<String>,
+) -> Result<(), String> {
+    if let Some(ref p) = path {
+        if !p.trim().is_empty() && !std::path::Path::new(p.trim()).is_file() {
+            return Err("Cookies file does not exist".to_string());
+        }
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.ytdlp_cookies_file = path.filter(|p| !p.trim().is_empty());
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_ytdlp_cookies_from_browser_setting(
+    app: AppHandle,
+    browser: Option<String>,
+) -> Result<(), String> {
+    let browser = browser.filter(|b| !b.trim().is_empty());
+    if let Some(browser) = &browser {
+        crate::ytdlp::validate_cookie_browser(browser)?;
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.ytdlp_cookies_from_browser = browser;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_clipboard_handling_setting(app: AppHandle, handling: String) -> Result<(), String> {
@@ -833,6 +1046,30 @@ pub fn change_post_process_base_url_setting(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_api_style_setting(
+    app: AppHandle,
+    provider_id: String,
+    api_style: settings::ApiStyle,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let provider = settings
+        .post_process_provider_mut(&provider_id)
+        .ok_or_else(|| format!("Provider '{}' not found", provider_id))?;
+
+    if provider.id != "custom" {
+        return Err(format!(
+            "Provider '{}' does not allow changing the API style",
+            provider.label
+        ));
+    }
+
+    provider.api_style = api_style;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 /// Generic helper to validate provider exists
 fn validate_provider_exists(
     settings: &settings::AppSettings,
@@ -876,6 +1113,32 @@ pub fn change_post_process_model_setting(
     Ok(())
 }
 
+/// Set (or with `None`, clear) a provider's context limit in tokens.
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_max_context_tokens_setting(
+    app: AppHandle,
+    provider_id: String,
+    max_tokens: Option<u32>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    validate_provider_exists(&settings, &provider_id)?;
+    match max_tokens {
+        Some(max_tokens) => {
+            settings
+                .post_process_max_context_tokens
+                .insert(provider_id, max_tokens);
+        }
+        None => {
+            settings
+                .post_process_max_context_tokens
+                .remove(&provider_id);
+        }
+    }
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn set_post_process_provider(app: AppHandle, provider_id: String) -> Result<(), String> {
@@ -902,6 +1165,8 @@ pub fn add_post_process_prompt(
         id: id.clone(),
         name,
         prompt,
+        temperature: None,
+        max_tokens: None,
     };
 
     settings.post_process_prompts.push(new_prompt.clone());
@@ -934,6 +1199,28 @@ pub fn update_post_process_prompt(
     }
 }
 
+/// Set a prompt's sampling settings; `None` leaves them to the provider.
+#[tauri::command]
+#[specta::specta]
+pub fn update_post_process_prompt_sampling(
+    app: AppHandle,
+    id: String,
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    let prompt = settings
+        .post_process_prompts
+        .iter_mut()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Prompt with id '{}' not found", id))?;
+    prompt.temperature = temperature.map(|t| t.clamp(0.0, 2.0));
+    prompt.max_tokens = max_tokens.filter(|&n| n > 0);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn delete_post_process_prompt(app: AppHandle, id: String) -> Result<(), String> {
@@ -1007,6 +1294,43 @@ pub async fn fetch_post_process_models(
     crate::llm_client::fetch_models(provider, api_key).await
 }
 
+/// Check that a provider's base URL, API key and selected model work.
+#[tauri::command]
+#[specta::specta]
+pub async fn test_llm_connection(
+    app: AppHandle,
+    provider_id: String,
+) -> Result<crate::llm_client::ConnectionTestResult, String> {
+    let settings = settings::get_settings(&app);
+
+    let provider = settings
+        .post_process_providers
+        .iter()
+        .find(|p| p.id == provider_id)
+        .ok_or_else(|| format!("Provider '{}' not found", provider_id))?;
+
+    if provider.id == APPLE_INTELLIGENCE_PROVIDER_ID {
+        return Err("Apple Intelligence runs on-device and has no connection to test.".to_string());
+    }
+
+    let api_key = settings
+        .post_process_api_keys
+        .get(&provider_id)
+        .cloned()
+        .unwrap_or_default();
+    let model = settings
+        .post_process_models
+        .get(&provider_id)
+        .cloned()
+        .unwrap_or_default();
+
+    if model.trim().is_empty() {
+        return Err(format!("No model configured for {}.", provider.label));
+    }
+
+    crate::llm_client::test_connection(provider, api_key, &model).await
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn set_post_process_selected_prompt(app: AppHandle, id: String) -> Result<(), String> {
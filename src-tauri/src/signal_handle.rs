@@ -1,5 +1,7 @@
+use crate::managers::journal::JournalManager;
 use crate::TranscriptionCoordinator;
 use log::{debug, warn};
+use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
 #[cfg(unix)]
@@ -19,6 +21,13 @@ pub fn send_transcription_input(app: &AppHandle, binding_id: &str, source: &str)
     }
 }
 
+/// Last chance to save state before the app exits.
+pub fn flush_before_exit(app: &AppHandle) {
+    if let Some(journal_manager) = app.try_state::<Arc<JournalManager>>() {
+        tauri::async_runtime::block_on(journal_manager.flush_pending_writes());
+    }
+}
+
 #[cfg(unix)]
 pub fn setup_signal_handler(app_handle: AppHandle, mut signals: Signals) {
     debug!("Signal handlers registered (SIGUSR1, SIGUSR2)");
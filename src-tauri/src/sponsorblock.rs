@@ -0,0 +1,166 @@
+use log::debug;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+const API_URL: &str = "https://sponsor.ajay.app/api/skipSegments";
+
+/// A time range removed from a video's audio before transcription.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct SkippedSegment {
+    pub category: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+#[derive(Deserialize)]
+struct ApiSegment {
+    category: String,
+    segment: [f64; 2],
+}
+
+/// Extract the 11-character video ID from the common YouTube URL shapes
+/// (`watch?v=`, `youtu.be/`, `shorts/`, `embed/`, `live/`).
+pub fn extract_video_id(url: &str) -> Option<String> {
+    let is_id_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    let take_id = |s: &str| -> Option<String> {
+        let id: String = s.chars().take_while(|c| is_id_char(*c)).collect();
+        (id.len() == 11).then_some(id)
+    };
+
+    if let Some((_, query)) = url.split_once('?') {
+        for pair in query.split('&') {
+            if let Some(v) = pair.strip_prefix("v=") {
+                return take_id(v);
+            }
+        }
+    }
+    for marker in ["youtu.be/", "/shorts/", "/embed/", "/live/"] {
+        if let Some((_, rest)) = url.split_once(marker) {
+            return take_id(rest);
+        }
+    }
+    None
+}
+
+/// Query SponsorBlock for the sponsor and self-promotion segments of a video.
+/// A video without submissions returns an empty list.
+pub async fn fetch_skip_segments(video_id: &str) -> Result<Vec<SkippedSegment>, String> {
+    let client = crate::http::http_client_builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let resp = client
+        .get(API_URL)
+        .query(&[
+            ("videoID", video_id),
+            ("categories", r#"["sponsor","selfpromo"]"#),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query SponsorBlock: {}", e))?;
+
+    // 404 means nobody submitted segments for this video
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(vec![]);
+    }
+
+    let segments: Vec<ApiSegment> = resp
+        .error_for_status()
+        .map_err(|e| format!("SponsorBlock API error: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse SponsorBlock response: {}", e))?;
+
+    debug!(
+        "SponsorBlock returned {} segments for {}",
+        segments.len(),
+        video_id
+    );
+
+    Ok(segments
+        .into_iter()
+        .map(|s| SkippedSegment {
+            category: s.category,
+            start_ms: (s.segment[0] * 1000.0).round() as i64,
+            end_ms: (s.segment[1] * 1000.0).round() as i64,
+        })
+        .filter(|s| s.end_ms > s.start_ms)
+        .collect())
+}
+
+/// Remove the given time ranges from mono samples. Overlapping ranges are
+/// handled, and ranges past the end of the audio are clamped.
+pub fn excise_segments(
+    samples: Vec<f32>,
+    sample_rate: u32,
+    segments: &[SkippedSegment],
+) -> Vec<f32> {
+    if segments.is_empty() {
+        return samples;
+    }
+
+    let to_index = |ms: i64| -> usize {
+        ((ms.max(0) as u64 * sample_rate as u64) / 1000).min(samples.len() as u64) as usize
+    };
+    let mut ranges: Vec<(usize, usize)> = segments
+        .iter()
+        .map(|s| (to_index(s.start_ms), to_index(s.end_ms)))
+        .filter(|(start, end)| end > start)
+        .collect();
+    ranges.sort_unstable();
+
+    let mut kept = Vec::with_capacity(samples.len());
+    let mut cursor = 0;
+    for (start, end) in ranges {
+        if start > cursor {
+            kept.extend_from_slice(&samples[cursor..start]);
+        }
+        cursor = cursor.max(end);
+    }
+    kept.extend_from_slice(&samples[cursor..]);
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: i64, end_ms: i64) -> SkippedSegment {
+        SkippedSegment {
+            category: "sponsor".to_string(),
+            start_ms,
+            end_ms,
+        }
+    }
+
+    #[test]
+    fn test_extract_video_id() {
+        let id = Some("dQw4w9WgXcQ".to_string());
+        assert_eq!(
+            extract_video_id("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=10"),
+            id
+        );
+        assert_eq!(extract_video_id("https://youtu.be/dQw4w9WgXcQ?si=x"), id);
+        assert_eq!(
+            extract_video_id("https://www.youtube.com/shorts/dQw4w9WgXcQ"),
+            id
+        );
+        assert_eq!(extract_video_id("https://example.com/video"), None);
+    }
+
+    #[test]
+    fn test_excise_segments_merges_overlaps() {
+        // 10 samples per second, so 1 sample per 100ms
+        let samples: Vec<f32> = (0..10).map(|i| i as f32).collect();
+        let kept = excise_segments(samples, 10, &[segment(200, 400), segment(300, 500)]);
+        assert_eq!(kept, vec![0.0, 1.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+    }
+
+    #[test]
+    fn test_excise_segments_clamps_past_end() {
+        let samples: Vec<f32> = (0..10).map(|i| i as f32).collect();
+        let kept = excise_segments(samples, 10, &[segment(800, 5000)]);
+        assert_eq!(kept, (0..8).map(|i| i as f32).collect::<Vec<_>>());
+    }
+}
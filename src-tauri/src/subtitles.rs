@@ -0,0 +1,258 @@
+/// A single timed caption cue with markup removed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptionCue {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    /// Voice from a WebVTT `<v Name>` tag, if any.
+    pub speaker: Option<String>,
+}
+
+/// Cues parsed from a subtitle file, plus how many were dropped because
+/// their timing line could not be parsed.
+#[derive(Debug, Default)]
+pub struct ParsedSubtitles {
+    pub cues: Vec<CaptionCue>,
+    pub malformed: usize,
+}
+
+/// A timing line and the payload lines that follow it.
+struct CueBlock<'a> {
+    start_ms: i64,
+    end_ms: i64,
+    lines: Vec<&'a str>,
+}
+
+/// Parse a `HH:MM:SS.mmm` or `MM:SS.mmm` timestamp (`,` is accepted as the
+/// millisecond separator too) into milliseconds.
+fn parse_timestamp_ms(s: &str) -> Option<i64> {
+    let s = s.trim().replace(',', ".");
+    let (hms, millis) = match s.split_once('.') {
+        Some((hms, ms)) => (hms.to_string(), ms),
+        None => (s.clone(), "0"),
+    };
+    let millis: i64 = millis.get(..3.min(millis.len()))?.parse().ok()?;
+
+    let parts: Vec<i64> = hms
+        .split(':')
+        .map(|p| p.parse::<i64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let (h, m, sec) = match parts.as_slice() {
+        [h, m, s] => (*h, *m, *s),
+        [m, s] => (0, *m, *s),
+        _ => return None,
+    };
+    Some(((h * 60 + m) * 60 + sec) * 1000 + millis)
+}
+
+/// Strip inline markup such as `<c>`, `<00:00:01.000>` and `<i>` tags.
+fn strip_tags(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_tag = false;
+    for c in line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Speaker name from a leading WebVTT voice tag (`<v Alice>` or `<v.loud Alice>`).
+fn voice_name(line: &str) -> Option<String> {
+    let rest = line.trim_start().strip_prefix("<v")?;
+    let (tag, _) = rest.split_once('>')?;
+    // Classes are dot-separated and end at the first space
+    let name = match tag.strip_prefix('.') {
+        Some(classed) => classed.split_once(' ')?.1,
+        None if tag.starts_with(' ') => tag,
+        None => return None,
+    };
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Split SRT or WebVTT content into timed blocks. Index lines, cue identifiers,
+/// headers and NOTE/STYLE blocks carry no `-->` and are skipped.
+fn parse_blocks(content: &str) -> (Vec<CueBlock<'_>>, usize) {
+    let mut blocks = Vec::new();
+    let mut malformed = 0;
+    let mut lines = content.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let Some((start, rest)) = line.split_once("-->") else {
+            continue;
+        };
+        // Cue settings (e.g. "align:start position:0%") follow the end timestamp
+        let end = rest.split_whitespace().next().unwrap_or_default();
+        let timing = parse_timestamp_ms(start).zip(parse_timestamp_ms(end));
+
+        // The payload ends at the first truly empty line; YouTube emits
+        // whitespace-only lines inside cues, so those don't terminate it.
+        let mut payload = Vec::new();
+        while let Some(text_line) = lines.peek() {
+            let text_line = text_line.trim_end_matches('\r');
+            if text_line.is_empty() {
+                break;
+            }
+            payload.push(text_line);
+            lines.next();
+        }
+
+        match timing {
+            Some((start_ms, end_ms)) => blocks.push(CueBlock {
+                start_ms,
+                end_ms,
+                lines: payload,
+            }),
+            None => malformed += 1,
+        }
+    }
+
+    (blocks, malformed)
+}
+
+/// Parse WebVTT content into cues.
+///
+/// YouTube auto-generated captions repeat the previous line at the top of each
+/// cue ("rolling" captions), so a line identical to the last emitted one is skipped.
+pub fn parse_vtt(content: &str) -> Vec<CaptionCue> {
+    let mut cues: Vec<CaptionCue> = Vec::new();
+
+    for block in parse_blocks(content).0 {
+        for line in block.lines {
+            let text = strip_tags(line);
+            if text.is_empty() || cues.last().is_some_and(|last| last.text == text) {
+                continue;
+            }
+            cues.push(CaptionCue {
+                start_ms: block.start_ms,
+                end_ms: block.end_ms,
+                text,
+                speaker: None,
+            });
+        }
+    }
+
+    cues
+}
+
+/// Parse an .srt or .vtt subtitle file. Multi-line payloads become one cue and
+/// WebVTT voice tags are kept as the cue's speaker.
+pub fn parse_subtitles(content: &str) -> ParsedSubtitles {
+    let (blocks, malformed) = parse_blocks(content.trim_start_matches('\u{feff}'));
+
+    let cues = blocks
+        .into_iter()
+        .filter_map(|block| {
+            let speaker = block.lines.first().and_then(|l| voice_name(l));
+            let text = block
+                .lines
+                .iter()
+                .map(|l| strip_tags(l))
+                .filter(|t| !t.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (!text.is_empty()).then_some(CaptionCue {
+                start_ms: block.start_ms,
+                end_ms: block.end_ms,
+                text,
+                speaker,
+            })
+        })
+        .collect();
+
+    ParsedSubtitles { cues, malformed }
+}
+
+/// Join cues into a single plain-text transcript, labelling speaker changes
+/// as `[Name]` like diarized transcripts.
+pub fn cues_to_text(cues: &[CaptionCue]) -> String {
+    let mut parts: Vec<String> = Vec::with_capacity(cues.len());
+    let mut last_speaker: Option<&str> = None;
+    for cue in cues {
+        match cue.speaker.as_deref() {
+            Some(speaker) if last_speaker != Some(speaker) => {
+                parts.push(format!("[{}] {}", speaker, cue.text));
+            }
+            _ => parts.push(cue.text.clone()),
+        }
+        last_speaker = cue.speaker.as_deref();
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_srt() {
+        let srt = "1\r\n00:00:01,000 --> 00:00:02,500\r\nHello there\r\nsecond line\r\n\r\n\
+                   2\r\n00:00:03,000 --> 00:00:04,000\r\n<i>General Kenobi</i>\r\n";
+        let parsed = parse_subtitles(srt);
+        assert_eq!(parsed.malformed, 0);
+        assert_eq!(
+            parsed.cues,
+            vec![
+                CaptionCue {
+                    start_ms: 1000,
+                    end_ms: 2500,
+                    text: "Hello there second line".to_string(),
+                    speaker: None,
+                },
+                CaptionCue {
+                    start_ms: 3000,
+                    end_ms: 4000,
+                    text: "General Kenobi".to_string(),
+                    speaker: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_vtt_file_with_voice_tags() {
+        let vtt = "\u{feff}WEBVTT\n\nNOTE exported from a meeting\n\n\
+                   intro\n00:01.000 --> 00:02.000 align:start\n<v Alice>Morning all</v>\n\n\
+                   00:00:02.000 --> 00:00:03.000\n<v.loud Bob>Hi &amp; welcome\n";
+        let parsed = parse_subtitles(vtt);
+        assert_eq!(parsed.malformed, 0);
+        assert_eq!(parsed.cues.len(), 2);
+        assert_eq!(parsed.cues[0].start_ms, 1000);
+        assert_eq!(parsed.cues[0].speaker.as_deref(), Some("Alice"));
+        assert_eq!(parsed.cues[0].text, "Morning all");
+        assert_eq!(parsed.cues[1].speaker.as_deref(), Some("Bob"));
+        assert_eq!(parsed.cues[1].text, "Hi & welcome");
+        assert_eq!(
+            cues_to_text(&parsed.cues),
+            "[Alice] Morning all [Bob] Hi & welcome"
+        );
+    }
+
+    #[test]
+    fn test_malformed_timestamps_are_counted() {
+        let srt = "1\n00:00:xx,000 --> 00:00:02,000\nbroken\n\n\
+                   2\n00:00:03,000 --> 00:00:04,000\nfine\n";
+        let parsed = parse_subtitles(srt);
+        assert_eq!(parsed.malformed, 1);
+        assert_eq!(parsed.cues.len(), 1);
+        assert_eq!(parsed.cues[0].text, "fine");
+    }
+
+    #[test]
+    fn test_parse_vtt_skips_rolling_repeats() {
+        let vtt = "WEBVTT\n\n00:00:00.000 --> 00:00:01.000\nfirst line\n\n\
+                   00:00:01.000 --> 00:00:02.000\nfirst line\nsecond line\n";
+        let texts: Vec<String> = parse_vtt(vtt).into_iter().map(|c| c.text).collect();
+        assert_eq!(texts, vec!["first line", "second line"]);
+    }
+}
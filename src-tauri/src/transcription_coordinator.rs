@@ -21,6 +21,9 @@ enum Command {
         recording_was_active: bool,
     },
     ProcessingFinished,
+    TranscriptionComplete {
+        text: String,
+    },
 }
 
 /// Pipeline lifecycle, owned exclusively by the coordinator thread.
@@ -104,6 +107,13 @@ impl TranscriptionCoordinator {
                         Command::ProcessingFinished => {
                             stage = Stage::Idle;
                         }
+                        Command::TranscriptionComplete { text } => {
+                            crate::notifications::notify_transcription_complete(
+                                &app,
+                                "Transcription complete",
+                                &text,
+                            );
+                        }
                     }
                 }
                 debug!("Transcription coordinator exited");
@@ -156,6 +166,19 @@ impl TranscriptionCoordinator {
             warn!("Transcription coordinator channel closed");
         }
     }
+
+    /// Report a successful transcription so a desktop notification can be shown.
+    pub fn notify_transcription_complete(&self, text: &str) {
+        if self
+            .tx
+            .send(Command::TranscriptionComplete {
+                text: text.to_string(),
+            })
+            .is_err()
+        {
+            warn!("Transcription coordinator channel closed");
+        }
+    }
 }
 
 fn start(app: &AppHandle, stage: &mut Stage, binding_id: &str, hotkey_string: &str) {
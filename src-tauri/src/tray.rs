@@ -1,11 +1,16 @@
+use crate::managers::audio::AudioRecordingManager;
 use crate::managers::history::{HistoryEntry, HistoryManager};
+use crate::managers::journal::JournalManager;
+use crate::managers::model::ModelManager;
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings;
 use crate::tray_i18n::get_tray_translations;
 use log::{error, info, warn};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::image::Image;
-use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
 use tauri::tray::TrayIcon;
 use tauri::{AppHandle, Manager, Theme};
 use tauri_plugin_clipboard_manager::ClipboardExt;
@@ -80,8 +85,109 @@ pub fn change_tray_icon(app: &AppHandle, icon: TrayIconState) {
     update_tray_menu(app, &icon, None);
 }
 
+/// Entries listed in the Recent submenu.
+const RECENT_ENTRIES: i64 = 5;
+
+/// Longest entry title shown in the Recent submenu, in characters.
+const RECENT_TITLE_CHARS: usize = 40;
+
+/// Menu item id prefix for the Recent submenu; the entry id follows it.
+pub const OPEN_ENTRY_PREFIX: &str = "open_entry:";
+
+fn recent_entry_label(title: &str) -> String {
+    let title = title.trim();
+    if title.chars().count() > RECENT_TITLE_CHARS {
+        let truncated: String = title.chars().take(RECENT_TITLE_CHARS - 1).collect();
+        format!("{}…", truncated.trim_end())
+    } else {
+        title.to_string()
+    }
+}
+
+/// Submenu of the newest journal entries; disabled when there are none.
+fn recent_entries_submenu(app: &AppHandle, label: &str) -> Submenu<tauri::Wry> {
+    let entries = app
+        .try_state::<Arc<JournalManager>>()
+        .map(|journal| journal.get_recent_titles(RECENT_ENTRIES))
+        .transpose()
+        .unwrap_or_else(|e| {
+            warn!("Failed to load recent entries for the tray: {}", e);
+            None
+        })
+        .unwrap_or_default();
+    let submenu = Submenu::with_id(app, "recent", label, !entries.is_empty())
+        .expect("failed to create recent submenu");
+    for (id, title) in entries {
+        let item = MenuItem::with_id(
+            app,
+            format!("{}{}", OPEN_ENTRY_PREFIX, id),
+            recent_entry_label(&title),
+            true,
+            None::<&str>,
+        )
+        .expect("failed to create recent entry item");
+        submenu
+            .append(&item)
+            .expect("failed to add recent entry item");
+    }
+    submenu
+}
+
+/// Bumped whenever the tray menu is rebuilt, which stops the timer ticking in
+/// the previous menu.
+static MENU_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// State the tray menu was last built for, so `refresh_tray_menu` can rebuild
+/// it without dropping the Cancel item or the recording timer.
+static MENU_STATE: Mutex<TrayIconState> = Mutex::new(TrayIconState::Idle);
+
+/// Elapsed recording time as mm:ss, or h:mm:ss from an hour on.
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Update the recording time in `item` once a second until the recording
+/// stops or the menu is rebuilt.
+fn spawn_recording_timer(
+    app: &AppHandle,
+    item: MenuItem<tauri::Wry>,
+    label: String,
+    generation: u64,
+) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            if MENU_GENERATION.load(Ordering::SeqCst) != generation {
+                break;
+            }
+            let Some(elapsed) = app
+                .state::<Arc<AudioRecordingManager>>()
+                .recording_elapsed()
+            else {
+                break;
+            };
+            let _ = item.set_text(label.replace("{{time}}", &format_elapsed(elapsed)));
+        }
+    });
+}
+
+/// Rebuild the tray menu for the state it is already in, e.g. when the
+/// entries listed under Recent changed.
+pub fn refresh_tray_menu(app: &AppHandle) {
+    let state = MENU_STATE.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    update_tray_menu(app, &state, None);
+}
+
 pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&str>) {
+    *MENU_STATE.lock().unwrap_or_else(|e| e.into_inner()) = state.clone();
     let settings = settings::get_settings(app);
+    let generation = MENU_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
 
     let locale = locale.unwrap_or(&settings.app_language);
     let strings = get_tray_translations(Some(locale.to_string()));
@@ -133,6 +239,35 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
         None::<&str>,
     )
     .expect("failed to create unload model item");
+    // A journal recording leaves the tray idle, so offer to stop it from there
+    let journal_recording = app
+        .state::<Arc<AudioRecordingManager>>()
+        .active_binding()
+        .as_deref()
+        == Some("journal");
+    let journal_i = if journal_recording {
+        MenuItem::with_id(
+            app,
+            "stop_journal",
+            &strings.stop_journal,
+            true,
+            None::<&str>,
+        )
+    } else {
+        let has_model = app
+            .state::<Arc<ModelManager>>()
+            .get_available_models()
+            .iter()
+            .any(|m| m.is_downloaded);
+        MenuItem::with_id(
+            app,
+            "start_journal",
+            &strings.start_journal,
+            has_model,
+            None::<&str>,
+        )
+    }
+    .expect("failed to create journal recording item");
     let quit_i = MenuItem::with_id(app, "quit", &strings.quit, true, quit_accelerator)
         .expect("failed to create quit item");
     let separator = || PredefinedMenuItem::separator(app).expect("failed to create separator");
@@ -141,28 +276,55 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
         TrayIconState::Recording | TrayIconState::Transcribing => {
             let cancel_i = MenuItem::with_id(app, "cancel", &strings.cancel, true, None::<&str>)
                 .expect("failed to create cancel item");
-            Menu::with_items(
-                app,
-                &[
-                    &version_i,
-                    &separator(),
-                    &cancel_i,
-                    &separator(),
-                    &copy_last_transcript_i,
-                    &separator(),
-                    &settings_i,
-                    &check_updates_i,
-                    &separator(),
-                    &quit_i,
-                ],
-            )
-            .expect("failed to create menu")
+            // While recording, a disabled item shows how long it has been going
+            let elapsed_i = (*state == TrayIconState::Recording)
+                .then(|| {
+                    app.state::<Arc<AudioRecordingManager>>()
+                        .recording_elapsed()
+                })
+                .flatten()
+                .map(|elapsed| {
+                    let label = strings
+                        .recording_elapsed
+                        .replace("{{time}}", &format_elapsed(elapsed));
+                    let item =
+                        MenuItem::with_id(app, "recording_elapsed", label, false, None::<&str>)
+                            .expect("failed to create recording time item");
+                    spawn_recording_timer(
+                        app,
+                        item.clone(),
+                        strings.recording_elapsed.clone(),
+                        generation,
+                    );
+                    item
+                });
+            let menu =
+                Menu::with_items(app, &[&version_i, &separator()]).expect("failed to create menu");
+            if let Some(elapsed_i) = &elapsed_i {
+                menu.append(elapsed_i)
+                    .expect("failed to add recording time item");
+            }
+            menu.append_items(&[
+                &cancel_i,
+                &separator(),
+                &copy_last_transcript_i,
+                &separator(),
+                &settings_i,
+                &check_updates_i,
+                &separator(),
+                &quit_i,
+            ])
+            .expect("failed to create menu");
+            menu
         }
         TrayIconState::Idle => Menu::with_items(
             app,
             &[
                 &version_i,
                 &separator(),
+                &journal_i,
+                &recent_entries_submenu(app, &strings.recent),
+                &separator(),
                 &copy_last_transcript_i,
                 &unload_model_i,
                 &separator(),
@@ -220,8 +382,9 @@ pub fn copy_last_transcript(app: &AppHandle) {
 
 #[cfg(test)]
 mod tests {
-    use super::last_transcript_text;
+    use super::{format_elapsed, last_transcript_text, recent_entry_label};
     use crate::managers::history::HistoryEntry;
+    use std::time::Duration;
 
     fn build_entry(transcription: &str, post_processed: Option<&str>) -> HistoryEntry {
         HistoryEntry {
@@ -233,6 +396,7 @@ mod tests {
             transcription_text: transcription.to_string(),
             post_processed_text: post_processed.map(|text| text.to_string()),
             post_process_prompt: None,
+            detected_language: None,
         }
     }
 
@@ -242,6 +406,21 @@ mod tests {
         assert_eq!(last_transcript_text(&entry), "processed");
     }
 
+    #[test]
+    fn formats_elapsed_recording_time() {
+        assert_eq!(format_elapsed(Duration::from_millis(999)), "00:00");
+        assert_eq!(format_elapsed(Duration::from_secs(83)), "01:23");
+        assert_eq!(format_elapsed(Duration::from_secs(3723)), "1:02:03");
+    }
+
+    #[test]
+    fn truncates_long_recent_entry_titles() {
+        assert_eq!(recent_entry_label("  Standup notes "), "Standup notes");
+        let label = recent_entry_label(&"é".repeat(60));
+        assert_eq!(label.chars().count(), 40);
+        assert!(label.ends_with('…'));
+    }
+
     #[test]
     fn falls_back_to_raw_transcription() {
         let entry = build_entry("raw", None);
@@ -0,0 +1,82 @@
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+use specta::Type;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Type)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Equal,
+}
+
+/// A run of text that was added, removed or kept between two versions.
+#[derive(Clone, Debug, PartialEq, Serialize, Type)]
+pub struct DiffChunk {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// Word-level diff of two texts. Consecutive changes of the same kind are
+/// merged, so the chunks read as phrases rather than single tokens.
+pub fn compute_text_diff(original: &str, modified: &str) -> Vec<DiffChunk> {
+    let diff = TextDiff::from_words(original, modified);
+    let mut chunks: Vec<DiffChunk> = Vec::new();
+
+    for change in diff.iter_all_changes() {
+        let kind = match change.tag() {
+            ChangeTag::Insert => DiffKind::Added,
+            ChangeTag::Delete => DiffKind::Removed,
+            ChangeTag::Equal => DiffKind::Equal,
+        };
+        match chunks.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(change.value()),
+            _ => chunks.push(DiffChunk {
+                kind,
+                text: change.value().to_string(),
+            }),
+        }
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn side(chunks: &[DiffChunk], skip: DiffKind) -> String {
+        chunks
+            .iter()
+            .filter(|c| c.kind != skip)
+            .map(|c| c.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_word_diff_reconstructs_both_sides() {
+        let original = "the quick brown fox";
+        let modified = "the slow brown fox jumps";
+        let chunks = compute_text_diff(original, modified);
+
+        assert_eq!(side(&chunks, DiffKind::Added), original);
+        assert_eq!(side(&chunks, DiffKind::Removed), modified);
+        assert!(chunks
+            .iter()
+            .any(|c| c.kind == DiffKind::Removed && c.text == "quick"));
+        assert!(chunks
+            .iter()
+            .any(|c| c.kind == DiffKind::Added && c.text == "slow"));
+    }
+
+    #[test]
+    fn test_identical_texts_are_one_equal_chunk() {
+        let chunks = compute_text_diff("no changes here", "no changes here");
+        assert_eq!(
+            chunks,
+            vec![DiffChunk {
+                kind: DiffKind::Equal,
+                text: "no changes here".to_string(),
+            }]
+        );
+    }
+}
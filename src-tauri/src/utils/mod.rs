@@ -1,19 +1,48 @@
+pub mod diff;
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 use crate::managers::audio::AudioRecordingManager;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 use crate::managers::transcription::TranscriptionManager;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 use crate::shortcut;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 use crate::TranscriptionCoordinator;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 use log::info;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 use std::sync::Arc;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 use tauri::{AppHandle, Manager};
 
 // Re-export all utility modules for easy access
 // pub use crate::audio_feedback::*;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub use crate::clipboard::*;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub use crate::overlay::*;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub use crate::tray::*;
 
+/// Managed cancel flag for `batch_apply_prompt`. Reset when a batch starts, so
+/// a cancel issued while nothing was running doesn't stop the next batch.
+#[derive(Default)]
+pub struct BatchProcessCancellation(std::sync::Arc<std::sync::atomic::AtomicBool>);
+
+impl BatchProcessCancellation {
+    pub fn begin(&self) -> std::sync::Arc<std::sync::atomic::AtomicBool> {
+        self.0.store(false, std::sync::atomic::Ordering::Relaxed);
+        self.0.clone()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
 /// Centralized cancellation function that can be called from anywhere in the app.
 /// Handles cancelling both recording and transcription operations and updates UI state.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 pub fn cancel_current_operation(app: &AppHandle) {
     info!("Initiating operation cancellation...");
 
@@ -33,6 +62,16 @@ pub fn cancel_current_operation(app: &AppHandle) {
     let tm = app.state::<Arc<TranscriptionManager>>();
     tm.maybe_unload_immediately("cancellation");
 
+    // Stop a diarization run at its next segment
+    if let Some(cancellation) = app.try_state::<crate::diarize::DiarizationCancellation>() {
+        cancellation.cancel();
+    }
+
+    // Stop a batch post-process run before its next entry
+    if let Some(cancellation) = app.try_state::<BatchProcessCancellation>() {
+        cancellation.cancel();
+    }
+
     // Notify coordinator so it can keep lifecycle state coherent.
     if let Some(coordinator) = app.try_state::<TranscriptionCoordinator>() {
         coordinator.notify_cancel(recording_was_active);
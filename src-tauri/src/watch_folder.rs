@@ -0,0 +1,263 @@
+//! Import audio files dropped into a folder, for people who record on a
+//! dedicated hardware recorder and copy the files over.
+//!
+//! The folder is `watch_folder_path` in the settings; new files become journal
+//! entries in `watch_folder_import_folder_id`. Files are imported one at a
+//! time on a worker thread once they have finished being written. Only files
+//! that appear while the app is running are picked up.
+
+use notify::event::{CreateKind, ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::managers::journal::JournalManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings;
+
+const WATCHED_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a"];
+
+/// How often a new file's size is checked while it is still being copied in.
+const SETTLE_POLL: Duration = Duration::from_secs(1);
+
+/// Polls a file may stay empty before it is skipped (e.g. a failed copy).
+const MAX_EMPTY_POLLS: u32 = 30;
+
+/// The running watcher, if any. Dropping it ends the worker thread once the
+/// file it is importing (if any) is done.
+#[derive(Default)]
+pub struct WatchFolder(Mutex<Option<RecommendedWatcher>>);
+
+/// Start watching the configured folder, replacing any previous watcher.
+/// Does nothing beyond stopping the old watcher when no folder is set.
+pub fn restart(app: &AppHandle) {
+    stop(app);
+
+    let Some(path) = settings::get_settings(app)
+        .watch_folder_path
+        .filter(|p| !p.trim().is_empty())
+    else {
+        return;
+    };
+
+    match start(app, Path::new(&path)) {
+        Ok(watcher) => {
+            log::info!("Watching {} for new audio files", path);
+            if let Some(state) = app.try_state::<WatchFolder>() {
+                *state.0.lock().unwrap() = Some(watcher);
+            }
+        }
+        Err(e) => log::warn!("Failed to watch {}: {}", path, e),
+    }
+}
+
+pub fn stop(app: &AppHandle) {
+    if let Some(state) = app.try_state::<WatchFolder>() {
+        if state.0.lock().unwrap().take().is_some() {
+            log::info!("Stopped watching the import folder");
+        }
+    }
+}
+
+fn start(app: &AppHandle, path: &Path) -> notify::Result<RecommendedWatcher> {
+    let (tx, rx) = mpsc::channel::<PathBuf>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) => {
+            for path in new_audio_files(&event) {
+                let _ = tx.send(path);
+            }
+        }
+        Err(e) => log::warn!("Watch folder error: {}", e),
+    })?;
+    watcher.watch(path, RecursiveMode::NonRecursive)?;
+
+    let app = app.clone();
+    std::thread::spawn(move || run_imports(app, rx));
+    Ok(watcher)
+}
+
+/// Audio files an event says have appeared, by creation or by being renamed
+/// into place (recorders and copy tools often write to a temporary name first).
+fn new_audio_files(event: &Event) -> Vec<PathBuf> {
+    let paths: &[PathBuf] = match event.kind {
+        EventKind::Create(CreateKind::File | CreateKind::Any) => &event.paths,
+        // Both carries [from, to]
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            event.paths.last().map(std::slice::from_ref).unwrap_or(&[])
+        }
+        // Any doesn't say which side this is; the old name no longer exists
+        EventKind::Modify(ModifyKind::Name(RenameMode::To | RenameMode::Any)) => &event.paths,
+        _ => &[],
+    };
+    paths
+        .iter()
+        .filter(|p| is_watched_audio(p) && p.is_file())
+        .cloned()
+        .collect()
+}
+
+fn is_watched_audio(path: &Path) -> bool {
+    let hidden = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_none_or(|n| n.starts_with('.'));
+    !hidden
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| WATCHED_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Wait until `path` stops growing, checking every `poll`. False if it
+/// disappears in the meantime or is still empty after `MAX_EMPTY_POLLS`.
+fn wait_until_written(path: &Path, poll: Duration) -> bool {
+    let mut last_len = None;
+    let mut empty_polls = 0;
+    loop {
+        let Ok(len) = path.metadata().map(|m| m.len()) else {
+            return false;
+        };
+        if len == 0 {
+            empty_polls += 1;
+            if empty_polls > MAX_EMPTY_POLLS {
+                log::warn!("Skipping empty file in watch folder: {}", path.display());
+                return false;
+            }
+        } else if last_len == Some(len) {
+            return true;
+        }
+        last_len = Some(len);
+        std::thread::sleep(poll);
+    }
+}
+
+/// What identifies one version of a file: recorders reuse names like
+/// `REC001.WAV` once their card is cleared, so the path alone isn't enough.
+fn file_version(path: &Path) -> Option<(PathBuf, u64, SystemTime)> {
+    let metadata = path.metadata().ok()?;
+    Some((
+        path.to_path_buf(),
+        metadata.len(),
+        metadata.modified().ok()?,
+    ))
+}
+
+fn run_imports(app: AppHandle, rx: mpsc::Receiver<PathBuf>) {
+    // A file can be reported more than once (created, then renamed into place)
+    let mut seen = HashSet::new();
+    while let Ok(path) = rx.recv() {
+        if !wait_until_written(&path, SETTLE_POLL) {
+            continue;
+        }
+        let Some(version) = file_version(&path) else {
+            continue;
+        };
+        if !seen.insert(version) {
+            continue;
+        }
+        if let Err(e) = import(&app, &path) {
+            log::warn!(
+                "Failed to import {} from watch folder: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+fn import(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let journal_manager = app.state::<Arc<JournalManager>>().inner().clone();
+    let transcription_manager = app.state::<Arc<TranscriptionManager>>().inner().clone();
+    let settings = settings::get_settings(app);
+
+    transcription_manager.initiate_model_load();
+    let entry_id =
+        tauri::async_runtime::block_on(crate::commands::journal::import_audio_file_as_entry(
+            &journal_manager,
+            &transcription_manager,
+            path,
+            format!(
+                "mutter-import-{}.wav",
+                chrono::Utc::now().timestamp_millis()
+            ),
+            settings.watch_folder_import_folder_id,
+            settings.audio_resample_quality,
+        ))?;
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    log::info!(
+        "Imported {} from watch folder as entry {}",
+        file_name,
+        entry_id
+    );
+    let _ = app.emit(
+        "watch-folder-imported",
+        serde_json::json!({
+            "file_name": file_name,
+            "entry_id": entry_id,
+        }),
+    );
+    let _ = app.emit("journal-updated", ());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_new_audio_files_from_events() {
+        let dir = TempDir::new().unwrap();
+        let touch = |name: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, b"data").unwrap();
+            path
+        };
+        let memo = touch("memo.WAV");
+        let notes = touch("notes.txt");
+        let hidden = touch(".memo.wav");
+        let gone = dir.path().join("gone.mp3");
+
+        let created = Event::new(EventKind::Create(CreateKind::File))
+            .add_path(memo.clone())
+            .add_path(notes)
+            .add_path(hidden.clone())
+            .add_path(gone.clone());
+        assert_eq!(new_audio_files(&created), vec![memo.clone()]);
+
+        let renamed = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(hidden)
+            .add_path(memo.clone());
+        assert_eq!(new_audio_files(&renamed), vec![memo.clone()]);
+
+        let moved_away =
+            Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::From))).add_path(gone);
+        assert!(new_audio_files(&moved_away).is_empty());
+
+        let removed = Event::new(EventKind::Remove(notify::event::RemoveKind::File)).add_path(memo);
+        assert!(new_audio_files(&removed).is_empty());
+    }
+
+    #[test]
+    fn test_wait_until_written_gives_up_on_empty_files() {
+        let dir = TempDir::new().unwrap();
+        let empty = dir.path().join("REC001.WAV");
+        std::fs::write(&empty, b"").unwrap();
+        assert!(!wait_until_written(&empty, Duration::from_millis(1)));
+
+        let written = dir.path().join("REC002.WAV");
+        std::fs::write(&written, b"data").unwrap();
+        assert!(wait_until_written(&written, Duration::from_millis(1)));
+        assert!(!wait_until_written(
+            &dir.path().join("gone.wav"),
+            Duration::from_millis(1)
+        ));
+    }
+}
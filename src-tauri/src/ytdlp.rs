@@ -1,5 +1,6 @@
 use futures_util::StreamExt;
 use log::{info, warn};
+use sha2::{Digest, Sha256};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Listener, Manager};
@@ -45,10 +46,7 @@ pub fn ytdlp_exists(app: &AppHandle) -> Result<bool, String> {
 }
 
 pub async fn get_latest_version() -> Result<String, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("handyxmutter")
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let client = crate::http::build_http_client()?;
 
     let resp = client
         .get("https://api.github.com/repos/yt-dlp/yt-dlp/releases/latest")
@@ -69,6 +67,39 @@ pub async fn get_latest_version() -> Result<String, String> {
         .ok_or_else(|| "Missing tag_name in release response".to_string())
 }
 
+/// Checksum for `file_name` in a `SHA2-256SUMS` file, whose lines look like
+/// "<hex digest>  <file name>".
+fn expected_sha256(sums: &str, file_name: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (digest, name) = line.trim().split_once(char::is_whitespace)?;
+        let name = name.trim_start().trim_start_matches('*');
+        (name == file_name).then(|| digest.to_lowercase())
+    })
+}
+
+async fn fetch_expected_sha256(
+    client: &reqwest::Client,
+    version: &str,
+    binary_name: &str,
+) -> Result<String, String> {
+    let sums_url = format!(
+        "https://github.com/yt-dlp/yt-dlp/releases/download/{}/SHA2-256SUMS",
+        version
+    );
+    let sums = client
+        .get(&sums_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download yt-dlp checksums: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("Checksum download failed: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read yt-dlp checksums: {}", e))?;
+    expected_sha256(&sums, binary_name)
+        .ok_or_else(|| format!("No checksum for {} in release {}", binary_name, version))
+}
+
 pub async fn download_ytdlp_binary(app: &AppHandle, version: &str) -> Result<(), String> {
     let binary_name = get_binary_name();
     let download_url = format!(
@@ -80,10 +111,9 @@ pub async fn download_ytdlp_binary(app: &AppHandle, version: &str) -> Result<(),
     app.emit("ytdlp-download-progress", "downloading")
         .map_err(|e| e.to_string())?;
 
-    let client = reqwest::Client::builder()
-        .user_agent("handyxmutter")
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let client = crate::http::build_http_client()?;
+
+    let expected = fetch_expected_sha256(&client, version, binary_name).await?;
 
     let response = client
         .get(&download_url)
@@ -117,21 +147,39 @@ pub async fn download_ytdlp_binary(app: &AppHandle, version: &str) -> Result<(),
         }
     }
 
-    std::fs::write(&dest_path, &file_bytes)
+    // Check before touching the disk so a bad download never replaces a working binary
+    let actual = format!("{:x}", Sha256::digest(&file_bytes));
+    if actual != expected {
+        return Err(format!(
+            "yt-dlp download failed verification (expected SHA-256 {}, got {}). \
+             The download may be truncated or tampered with; the existing binary was kept.",
+            expected, actual
+        ));
+    }
+
+    // Write next to the final path and rename, so a failed write can't leave a
+    // half-written binary behind either
+    let temp_path = dest_path.with_file_name(format!("{}.part", binary_name));
+    std::fs::write(&temp_path, &file_bytes)
         .map_err(|e| format!("Failed to write yt-dlp binary: {}", e))?;
 
     // Set executable permissions on Unix
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        let mut perms = std::fs::metadata(&dest_path)
+        let mut perms = std::fs::metadata(&temp_path)
             .map_err(|e| format!("Failed to read file metadata: {}", e))?
             .permissions();
         perms.set_mode(0o755);
-        std::fs::set_permissions(&dest_path, perms)
+        std::fs::set_permissions(&temp_path, perms)
             .map_err(|e| format!("Failed to set executable permission: {}", e))?;
     }
 
+    if let Err(e) = std::fs::rename(&temp_path, &dest_path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(format!("Failed to install yt-dlp binary: {}", e));
+    }
+
     // Remove macOS quarantine/provenance attributes and ad-hoc sign the binary
     #[cfg(target_os = "macos")]
     {
@@ -155,22 +203,155 @@ pub async fn download_ytdlp_binary(app: &AppHandle, version: &str) -> Result<(),
     }
 
     info!("yt-dlp downloaded to {}", dest_path.display());
+    let mut settings = crate::settings::get_settings(app);
+    settings.ytdlp_installed_version = Some(version.to_string());
+    crate::settings::write_settings(app, settings);
     let _ = app.emit("ytdlp-download-progress", "done");
 
     Ok(())
 }
 
-/// Download audio from a YouTube URL using yt-dlp.
-/// Uses `-f bestaudio[ext=m4a]` so we get native m4a without needing ffmpeg.
+/// The latest release tag if it differs from the installed one. A binary
+/// installed before versions were recorded counts as stale.
+pub async fn check_for_update(app: &AppHandle) -> Result<Option<String>, String> {
+    if !ytdlp_exists(app)? {
+        return Ok(None);
+    }
+    let latest = get_latest_version().await?;
+    let installed = crate::settings::get_settings(app).ytdlp_installed_version;
+    Ok((installed.as_deref() != Some(latest.as_str())).then_some(latest))
+}
+
+/// Whether yt-dlp failed in a way that usually means the site changed and a
+/// newer yt-dlp is needed.
+fn is_outdated_ytdlp_error(stderr: &str) -> bool {
+    const MARKERS: [&str; 5] = [
+        "signature extraction failed",
+        "nsig extraction failed",
+        "unable to extract",
+        "please report this issue",
+        "confirm you are on the latest version",
+    ];
+    let stderr = stderr.to_lowercase();
+    MARKERS.iter().any(|marker| stderr.contains(marker))
+}
+
+/// Browsers `--cookies-from-browser` can read from.
+pub const COOKIE_BROWSERS: [&str; 9] = [
+    "brave", "chrome", "chromium", "edge", "firefox", "opera", "safari", "vivaldi", "whale",
+];
+
+/// Check a `--cookies-from-browser` value, which may carry yt-dlp's
+/// `+KEYRING`, `:PROFILE` and `::CONTAINER` suffixes after the browser name.
+pub fn validate_cookie_browser(value: &str) -> Result<(), String> {
+    let browser = value
+        .split(['+', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    if COOKIE_BROWSERS.contains(&browser.as_str()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unsupported browser '{}' for cookies. Use one of: {}",
+            browser,
+            COOKIE_BROWSERS.join(", ")
+        ))
+    }
+}
+
+/// Build the yt-dlp cookie arguments. A browser passed for this call wins;
+/// otherwise a configured cookies file takes precedence over browser cookies.
+fn cookie_args(app: &AppHandle, browser: Option<&str>) -> Result<Vec<String>, String> {
+    if let Some(browser) = browser.map(str::trim).filter(|s| !s.is_empty()) {
+        validate_cookie_browser(browser)?;
+        return Ok(vec![
+            "--cookies-from-browser".to_string(),
+            browser.to_string(),
+        ]);
+    }
+
+    let settings = crate::settings::get_settings(app);
+
+    if let Some(file) = settings
+        .ytdlp_cookies_file
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        if !std::path::Path::new(file).is_file() {
+            return Err("The configured yt-dlp cookies file does not exist".to_string());
+        }
+        return Ok(vec!["--cookies".to_string(), file.to_string()]);
+    }
+
+    if let Some(browser) = settings
+        .ytdlp_cookies_from_browser
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        return Ok(vec![
+            "--cookies-from-browser".to_string(),
+            browser.to_string(),
+        ]);
+    }
+
+    Ok(Vec::new())
+}
+
+/// Strip the cookies file path from messages that are surfaced to the frontend.
+fn redact_cookies(app: &AppHandle, message: &str) -> String {
+    let settings = crate::settings::get_settings(app);
+    match settings
+        .ytdlp_cookies_file
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        Some(file) => message.replace(file, "<cookies file>"),
+        None => message.to_string(),
+    }
+}
+
+/// Reduce yt-dlp stderr to its `ERROR:` lines (e.g. "ERROR: Unsupported URL: ...")
+/// so the reason for a failure reaches the user as yt-dlp reported it.
+fn ytdlp_error(app: &AppHandle, stderr: &str) -> String {
+    let errors: Vec<&str> = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.starts_with("ERROR:"))
+        .collect();
+    let mut message = if errors.is_empty() {
+        format!("yt-dlp failed: {}", stderr.trim())
+    } else {
+        errors.join("\n")
+    };
+    if errors.iter().any(|l| l.to_lowercase().contains("cookie")) {
+        message = format!(
+            "yt-dlp could not read the browser's cookies. Make sure the browser is \
+             installed and signed in (closing it can help for Chromium-based browsers).\n{}",
+            message
+        );
+    }
+    redact_cookies(app, &message)
+}
+
+/// Download audio from any yt-dlp supported URL.
+/// Prefers `-f bestaudio[ext=m4a]` so we usually get native m4a without needing ffmpeg,
+/// but other containers are accepted; `out_path` may use yt-dlp templates such as `%(ext)s`.
 pub async fn download_audio(
     app: &AppHandle,
     url: &str,
     out_path: &std::path::Path,
+    cookies_from_browser: Option<&str>,
 ) -> Result<(), String> {
     let ytdlp_path = get_ytdlp_path(app)?;
     if !ytdlp_path.exists() {
         return Err("yt-dlp is not installed".to_string());
     }
+    let cookies = cookie_args(app, cookies_from_browser)?;
 
     info!(
         "download_audio: binary={}, url={}, out={}",
@@ -203,6 +384,8 @@ pub async fn download_audio(
         "-o",
     ])
     .arg(out_path.as_os_str())
+    .args(&cookies)
+    .args(crate::http::ytdlp_proxy_args())
     .stdout(std::process::Stdio::piped())
     .stderr(std::process::Stdio::piped());
 
@@ -258,7 +441,12 @@ pub async fn download_audio(
             let _ = tokio::io::AsyncReadExt::read_to_string(&mut stderr, &mut buf).await;
             stderr_output = buf;
         }
-        return Err(format!("yt-dlp failed: {}", stderr_output));
+        if is_outdated_ytdlp_error(&stderr_output) {
+            warn!(
+                "yt-dlp could not extract the media; it may be out of date. Try updating yt-dlp."
+            );
+        }
+        return Err(ytdlp_error(app, &stderr_output));
     }
 
     info!("yt-dlp download completed successfully");
@@ -277,6 +465,7 @@ pub async fn get_video_title(app: &AppHandle, url: &str) -> Result<String, Strin
     if !ytdlp_path.exists() {
         return Err("yt-dlp is not installed".to_string());
     }
+    let cookies = cookie_args(app, None)?;
 
     // Ensure the binary is properly signed and quarantine-free
     #[cfg(target_os = "macos")]
@@ -292,6 +481,8 @@ pub async fn get_video_title(app: &AppHandle, url: &str) -> Result<String, Strin
 
     info!("Spawning yt-dlp --get-title for: {}", url);
     let output = Command::new(&ytdlp_path)
+        .args(&cookies)
+        .args(crate::http::ytdlp_proxy_args())
         .args(["--get-title", "--no-playlist", url])
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
@@ -308,7 +499,10 @@ pub async fn get_video_title(app: &AppHandle, url: &str) -> Result<String, Strin
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to get video title: {}", stderr));
+        return Err(redact_cookies(
+            app,
+            &format!("Failed to get video title: {}", stderr),
+        ));
     }
 
     let title = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -320,3 +514,330 @@ pub async fn get_video_title(app: &AppHandle, url: &str) -> Result<String, Strin
         Ok(title)
     }
 }
+
+/// Title and uploader of a media URL.
+pub struct MediaInfo {
+    pub title: String,
+    pub uploader: Option<String>,
+}
+
+/// Fetch title and uploader with `yt-dlp --print`. Works for any supported site.
+pub async fn get_media_info(
+    app: &AppHandle,
+    url: &str,
+    cookies_from_browser: Option<&str>,
+) -> Result<MediaInfo, String> {
+    let ytdlp_path = get_ytdlp_path(app)?;
+    if !ytdlp_path.exists() {
+        return Err("yt-dlp is not installed".to_string());
+    }
+    let cookies = cookie_args(app, cookies_from_browser)?;
+
+    // Ensure the binary is properly signed and quarantine-free
+    #[cfg(target_os = "macos")]
+    {
+        let path_str = ytdlp_path.to_string_lossy().to_string();
+        let _ = std::process::Command::new("xattr")
+            .args(["-cr", &path_str])
+            .output();
+        let _ = std::process::Command::new("codesign")
+            .args(["--force", "--sign", "-", &path_str])
+            .output();
+    }
+
+    info!("Spawning yt-dlp --print for: {}", url);
+    let mut cmd = Command::new(&ytdlp_path);
+    cmd.args(&cookies)
+        .args(crate::http::ytdlp_proxy_args())
+        .args([
+            "--print",
+            "%(title)s",
+            "--print",
+            "%(uploader)s",
+            "--no-playlist",
+            url,
+        ])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    #[cfg(windows)]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run yt-dlp: {} (kind={:?})", e, e.kind()))?;
+
+    if !output.status.success() {
+        return Err(ytdlp_error(app, &String::from_utf8_lossy(&output.stderr)));
+    }
+
+    // yt-dlp prints "NA" for fields the extractor doesn't provide
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout
+        .lines()
+        .map(str::trim)
+        .map(|l| (!l.is_empty() && l != "NA").then(|| l.to_string()));
+    let title = lines.next().flatten();
+    let uploader = lines.next().flatten();
+
+    info!("Got media info: title={:?}, uploader={:?}", title, uploader);
+    Ok(MediaInfo {
+        title: title.unwrap_or_else(|| "Downloaded Audio".to_string()),
+        uploader,
+    })
+}
+
+/// A single video listed in a playlist.
+pub struct PlaylistItem {
+    pub url: String,
+    pub title: String,
+}
+
+/// Enumerate a playlist via `yt-dlp --flat-playlist -J` without downloading anything.
+/// Returns the playlist title and its items in playlist order.
+pub async fn get_playlist_items(
+    app: &AppHandle,
+    url: &str,
+) -> Result<(String, Vec<PlaylistItem>), String> {
+    let ytdlp_path = get_ytdlp_path(app)?;
+    if !ytdlp_path.exists() {
+        return Err("yt-dlp is not installed".to_string());
+    }
+    let cookies = cookie_args(app, None)?;
+
+    // Ensure the binary is properly signed and quarantine-free
+    #[cfg(target_os = "macos")]
+    {
+        let path_str = ytdlp_path.to_string_lossy().to_string();
+        let _ = std::process::Command::new("xattr")
+            .args(["-cr", &path_str])
+            .output();
+        let _ = std::process::Command::new("codesign")
+            .args(["--force", "--sign", "-", &path_str])
+            .output();
+    }
+
+    info!("Spawning yt-dlp --flat-playlist for: {}", url);
+    let mut cmd = Command::new(&ytdlp_path);
+    cmd.args(&cookies)
+        .args(crate::http::ytdlp_proxy_args())
+        .args(["--flat-playlist", "-J", url])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    #[cfg(windows)]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run yt-dlp: {} (kind={:?})", e, e.kind()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(redact_cookies(
+            app,
+            &format!("Failed to read playlist: {}", stderr),
+        ));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse playlist JSON: {}", e))?;
+
+    let playlist_title = json["title"]
+        .as_str()
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or("YouTube Playlist")
+        .to_string();
+
+    let items = json["entries"]
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    // Flat entries usually carry a full URL, but fall back to the video id
+                    let url = entry["url"]
+                        .as_str()
+                        .filter(|u| u.starts_with("http"))
+                        .map(|u| u.to_string())
+                        .or_else(|| {
+                            entry["id"]
+                                .as_str()
+                                .map(|id| format!("https://www.youtube.com/watch?v={}", id))
+                        })?;
+                    let title = entry["title"]
+                        .as_str()
+                        .filter(|t| !t.trim().is_empty())
+                        .unwrap_or("YouTube Video")
+                        .to_string();
+                    Some(PlaylistItem { url, title })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    info!(
+        "Playlist '{}' contains {} items",
+        playlist_title,
+        items.len()
+    );
+    Ok((playlist_title, items))
+}
+
+/// `--sub-langs` for captions in `language` (e.g. "de", matching regional
+/// variants such as "de-DE"), or with None in the video's original language,
+/// falling back to English.
+fn caption_sub_langs(language: Option<&str>) -> String {
+    match language {
+        Some(lang) => format!("{0}.*,{0}", lang),
+        None => ".*-orig,en.*,en".to_string(),
+    }
+}
+
+/// The caption file to use among those downloaded: without a language, the
+/// original-language track (`<base>.<lang>-orig.vtt`) when there is one.
+fn pick_caption_file(files: &[std::path::PathBuf], language: Option<&str>) -> Option<usize> {
+    let original = match language {
+        Some(_) => None,
+        None => files
+            .iter()
+            .position(|path| path.to_string_lossy().ends_with("-orig.vtt")),
+    };
+    original.or_else(|| (!files.is_empty()).then_some(0))
+}
+
+/// VTT files yt-dlp wrote for `out_base` (`<out_base>.<lang>.vtt`), sorted.
+fn caption_files(out_base: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let parent = out_base.parent().unwrap_or(std::path::Path::new("."));
+    let prefix = format!(
+        "{}.",
+        out_base
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    );
+    let mut files: Vec<_> = std::fs::read_dir(parent)
+        .map(|dir| {
+            dir.flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    name.starts_with(&prefix) && name.ends_with(".vtt")
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Download existing subtitles (manual or auto-generated) in `language` (see
+/// `caption_sub_langs`) as VTT without downloading media. Files are written
+/// next to `out_base` (e.g. `<out_base>.en.vtt`); the best match is returned
+/// for the caller to read and delete, and any others are removed. Returns
+/// `None` if the video has no captions in that language.
+pub async fn download_captions(
+    app: &AppHandle,
+    url: &str,
+    out_base: &std::path::Path,
+    language: Option<&str>,
+    cookies_from_browser: Option<&str>,
+) -> Result<Option<std::path::PathBuf>, String> {
+    let ytdlp_path = get_ytdlp_path(app)?;
+    if !ytdlp_path.exists() {
+        return Err("yt-dlp is not installed".to_string());
+    }
+    let cookies = cookie_args(app, cookies_from_browser)?;
+
+    info!("Spawning yt-dlp caption download for: {}", url);
+    let mut cmd = Command::new(&ytdlp_path);
+    cmd.args(&cookies)
+        .args(crate::http::ytdlp_proxy_args())
+        .args([
+            "--write-subs",
+            "--write-auto-subs",
+            "--sub-format",
+            "vtt",
+            "--sub-langs",
+            caption_sub_langs(language).as_str(),
+            "--skip-download",
+            "--no-playlist",
+            url,
+            "-o",
+        ])
+        .arg(out_base.as_os_str())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    #[cfg(windows)]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run yt-dlp: {} (kind={:?})", e, e.kind()))?;
+
+    let mut files = caption_files(out_base);
+    if !output.status.success() {
+        for file in files {
+            let _ = std::fs::remove_file(file);
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(redact_cookies(
+            app,
+            &format!("Failed to download captions: {}", stderr),
+        ));
+    }
+
+    let found = pick_caption_file(&files, language).map(|index| files.remove(index));
+    for file in files {
+        let _ = std::fs::remove_file(file);
+    }
+
+    if found.is_none() {
+        info!("No captions available for {}", url);
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captions_follow_the_language() {
+        assert_eq!(caption_sub_langs(Some("de")), "de.*,de");
+        assert_eq!(caption_sub_langs(None), ".*-orig,en.*,en");
+
+        let files: Vec<std::path::PathBuf> = ["v.en.vtt", "v.fr-orig.vtt"]
+            .iter()
+            .map(Into::into)
+            .collect();
+        assert_eq!(pick_caption_file(&files, None), Some(1));
+        assert_eq!(pick_caption_file(&files[..1], None), Some(0));
+        assert_eq!(pick_caption_file(&files, Some("en")), Some(0));
+        assert_eq!(pick_caption_file(&[], None), None);
+    }
+
+    #[test]
+    fn test_expected_sha256_picks_the_binary() {
+        let sums = "AAAA1111  yt-dlp\n\
+                    bbbb2222  yt-dlp_linux\n\
+                    cccc3333 *yt-dlp.exe\n";
+        assert_eq!(
+            expected_sha256(sums, "yt-dlp_linux").as_deref(),
+            Some("bbbb2222")
+        );
+        assert_eq!(expected_sha256(sums, "yt-dlp").as_deref(), Some("aaaa1111"));
+        assert_eq!(
+            expected_sha256(sums, "yt-dlp.exe").as_deref(),
+            Some("cccc3333")
+        );
+        assert_eq!(expected_sha256(sums, "yt-dlp_macos"), None);
+    }
+}
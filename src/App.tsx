@@ -9,7 +9,10 @@ import { Sidebar, SidebarSection, SECTIONS_CONFIG } from "./components/Sidebar";
 import { MutterPanel } from "./components/mutter/MutterPanel";
 import { useSettings } from "./hooks/useSettings";
 import { useSettingsStore } from "./stores/settingsStore";
+import { useMutterStore } from "./stores/mutterStore";
 import { commands } from "@/bindings";
+import { journalCommands } from "@/lib/journal";
+import { listen } from "@tauri-apps/api/event";
 import { getLanguageDirection, initializeRTL } from "@/lib/utils/rtl";
 import { isMacOS, isDesktop, isMobile } from "@/lib/platform";
 
@@ -83,6 +86,45 @@ function App() {
     }
   }, [onboardingStep, refreshAudioDevices, refreshOutputDevices]);
 
+  // Tray journal recording items: switch to the journal, which starts or stops the recording
+  useEffect(() => {
+    if (!isDesktop) return;
+    const unlistenPromises = (["start", "stop"] as const).map((request) =>
+      listen(`tray-${request}-journal`, () => {
+        setCurrentSection("mutter");
+        useMutterStore.getState().setActiveTab("journal");
+        useMutterStore.getState().setTrayRecordingRequest(request);
+      }),
+    );
+    return () => {
+      unlistenPromises.forEach((p) => p.then((unlisten) => unlisten()));
+    };
+  }, []);
+
+  // Tray Recent submenu: open the entry in the tab for its source
+  useEffect(() => {
+    if (!isDesktop) return;
+    const unlisten = listen<number>("open-entry", async (event) => {
+      const entry = await journalCommands.getEntry(event.payload).catch(() => null);
+      if (!entry) return;
+      const store = useMutterStore.getState();
+      setCurrentSection("mutter");
+      if (entry.source === "video") {
+        store.setActiveTab("video");
+        store.setSelectedVideoEntryId(entry.id);
+      } else if (entry.source === "meeting") {
+        store.setActiveTab("meeting");
+        store.setSelectedMeetingEntryId(entry.id);
+      } else {
+        store.setActiveTab("journal");
+        store.setSelectedEntryId(entry.id);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   // Handle keyboard shortcuts for debug mode toggle
   useEffect(() => {
     const handleKeyDown = (event: KeyboardEvent) => {
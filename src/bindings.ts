@@ -109,6 +109,98 @@ async changeWordCorrectionThresholdSetting(threshold: number) : Promise<Result<n
     else return { status: "error", error: e  as any };
 }
 },
+async changeDedupThresholdSetting(threshold: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_dedup_threshold_setting", { threshold }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeTranscriptionTimeoutSetting(secs: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_transcription_timeout_setting", { secs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeNotificationsEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_notifications_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeNotificationOnModelLoadSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_notification_on_model_load_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeFfmpegPathSetting(path: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_ffmpeg_path_setting", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Takes effect after a restart: ORT's execution providers can only be set
+ * once per process (see `compute_backend`).
+ */
+async changeComputeBackendSetting(backend: ComputeBackend) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_compute_backend_setting", { backend }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set (or with None, clear) the folder watched for audio files to import,
+ * and restart the watcher on it.
+ */
+async changeWatchFolderSetting(path: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_watch_folder_setting", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeWatchFolderImportFolderSetting(folderId: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_watch_folder_import_folder_setting", { folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set (or with an empty path, clear) the git repository journal changes are
+ * committed to.
+ */
+async changeGitSyncRepoPathSetting(path: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_git_sync_repo_path_setting", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeGitSyncEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_git_sync_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePasteMethodSetting(method: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_paste_method_setting", { method }) };
@@ -136,6 +228,22 @@ async changeExternalScriptPathSetting(path: string | null) : Promise<Result<null
     else return { status: "error", error: e  as any };
 }
 },
+async changeYtdlpCookiesFileSetting(path: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_ytdlp_cookies_file_setting", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeYtdlpCookiesFromBrowserSetting(browser: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_ytdlp_cookies_from_browser_setting", { browser }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changeClipboardHandlingSetting(handling: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_clipboard_handling_setting", { handling }) };
@@ -184,6 +292,14 @@ async changePostProcessBaseUrlSetting(providerId: string, baseUrl: string) : Pro
     else return { status: "error", error: e  as any };
 }
 },
+async changePostProcessApiStyleSetting(providerId: string, apiStyle: ApiStyle) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_api_style_setting", { providerId, apiStyle }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePostProcessApiKeySetting(providerId: string, apiKey: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_post_process_api_key_setting", { providerId, apiKey }) };
@@ -200,6 +316,17 @@ async changePostProcessModelSetting(providerId: string, model: string) : Promise
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Set (or with `None`, clear) a provider's context limit in tokens.
+ */
+async changePostProcessMaxContextTokensSetting(providerId: string, maxTokens: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_max_context_tokens_setting", { providerId, maxTokens }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async setPostProcessProvider(providerId: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("set_post_process_provider", { providerId }) };
@@ -216,6 +343,17 @@ async fetchPostProcessModels(providerId: string) : Promise<Result<string[], stri
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Check that a provider's base URL, API key and selected model work.
+ */
+async testLlmConnection(providerId: string) : Promise<Result<ConnectionTestResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_llm_connection", { providerId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async addPostProcessPrompt(name: string, prompt: string) : Promise<Result<LLMPrompt, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("add_post_process_prompt", { name, prompt }) };
@@ -232,6 +370,17 @@ async updatePostProcessPrompt(id: string, name: string, prompt: string) : Promis
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Set a prompt's sampling settings; `None` leaves them to the provider.
+ */
+async updatePostProcessPromptSampling(id: string, temperature: number | null, maxTokens: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_post_process_prompt_sampling", { id, temperature, maxTokens }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async deletePostProcessPrompt(id: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("delete_post_process_prompt", { id }) };
@@ -256,6 +405,41 @@ async updateCustomWords(words: string[]) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async changeTranscriptionInitialPromptSetting(prompt: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_transcription_initial_prompt_setting", { prompt }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeInitialPromptIncludeCustomWordsSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_initial_prompt_include_custom_words_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replace the full list of word replacement rules.
+ */
+async updateWordReplacements(replacements: WordReplacement[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_word_replacements", { replacements }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getWordReplacements() : Promise<Result<WordReplacement[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_word_replacements") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Temporarily unregister a binding while the user is editing it in the UI.
  * This avoids firing the action while keys are being recorded.
@@ -372,6 +556,16 @@ async triggerUpdateCheck() : Promise<Result<null, string>> {
 async cancelOperation() : Promise<void> {
     await TAURI_INVOKE("cancel_operation");
 },
+/**
+ * Show the recording overlay, positioned per the overlay setting (near the
+ * cursor, if that is chosen). Does nothing when the overlay is turned off.
+ */
+async showRecordingOverlay() : Promise<void> {
+    await TAURI_INVOKE("show_recording_overlay");
+},
+async hideRecordingOverlay() : Promise<void> {
+    await TAURI_INVOKE("hide_recording_overlay");
+},
 async getAppDirPath() : Promise<Result<string, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_app_dir_path") };
@@ -412,6 +606,18 @@ async setLogLevel(level: LogLevel) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Set the proxy used for downloads, cloud transcription, LLM requests and
+ * yt-dlp. An empty value clears it.
+ */
+async changeHttpProxySetting(proxy: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_http_proxy_setting", { proxy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async openRecordingsFolder() : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("open_recordings_folder") };
@@ -484,6 +690,40 @@ async getModelInfo(modelId: string) : Promise<Result<ModelInfo | null, string>>
     else return { status: "error", error: e  as any };
 }
 },
+async getSystemMemoryInfo() : Promise<Result<MemoryInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_system_memory_info") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compute backends whose execution provider initializes on this machine.
+ */
+async getAvailableComputeBackends() : Promise<ComputeBackend[]> {
+    return await TAURI_INVOKE("get_available_compute_backends");
+},
+/**
+ * Backend ONNX models run on in this session. It differs from the setting
+ * until the app is restarted after a change, or when the chosen provider
+ * isn't available and CPU is used instead.
+ */
+async getActiveComputeBackend() : Promise<ComputeBackend> {
+    return await TAURI_INVOKE("get_active_compute_backend");
+},
+/**
+ * Suggest the most accurate model that fits in 70% of the available memory,
+ * favouring ones that are already downloaded.
+ */
+async recommendModel() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("recommend_model") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async downloadModel(modelId: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("download_model", { modelId }) };
@@ -492,6 +732,30 @@ async downloadModel(modelId: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Copy a Whisper model file from `path` into the models directory and list it
+ * as downloaded, for machines that can't reach the model CDN. `name` is an
+ * optional display name.
+ */
+async importLocalModel(path: string, name: string | null) : Promise<Result<ModelInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_local_model", { path, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-check an installed model against its catalog checksum. Emits `model-verified`.
+ */
+async verifyModelIntegrity(modelId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_model_integrity", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async deleteModel(modelId: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("delete_model", { modelId }) };
@@ -596,6 +860,84 @@ async getSelectedMicrophone() : Promise<Result<string, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * System-audio sources that can be recorded. Errs on platforms without
+ * loopback capture.
+ */
+async getAvailableLoopbackDevices() : Promise<Result<AudioDevice[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_available_loopback_devices") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Record about two seconds from the selected microphone, outside of any real
+ * recording, and report how loud it was. Backs the meter next to the mic picker.
+ */
+async testMicrophoneLevel() : Promise<Result<MicrophoneLevel, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_microphone_level") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setSelectedLoopbackDevice(deviceName: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_selected_loopback_device", { deviceName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSelectedLoopbackDevice() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_selected_loopback_device") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeRecordingSourceSetting(source: RecordingSource) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_recording_source_setting", { source }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set how imported audio is resampled to 16kHz before transcription.
+ */
+async changeAudioResampleQualitySetting(quality: ResampleQuality) : Promise<void> {
+    await TAURI_INVOKE("change_audio_resample_quality_setting", { quality });
+},
+/**
+ * Set the input gain for `device_name` ("default" for the host default).
+ * Applies to the recording in progress as well.
+ */
+async setMicrophoneGain(deviceName: string, gainDb: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_microphone_gain", { deviceName, gainDb }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Format of the archival WAV saved alongside each recording. 16000 Hz at
+ * 16 bit turns archival copies off.
+ */
+async changeRecordingFormatSetting(sampleRate: number, bitDepth: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_recording_format_setting", { sampleRate, bitDepth }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getAvailableOutputDevices() : Promise<Result<AudioDevice[], string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_available_output_devices") };
@@ -664,6 +1006,17 @@ async unloadModelManually() : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Drop every cached transcript, so identical audio is transcribed afresh.
+ */
+async clearTranscriptionCache() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_transcription_cache") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getHistoryEntries() : Promise<Result<HistoryEntry[], string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_history_entries") };
@@ -696,33 +1049,67 @@ async deleteHistoryEntry(id: number) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async updateHistoryLimit(limit: number) : Promise<Result<null, string>> {
+/**
+ * Write the whole history to `dest_path` as CSV.
+ */
+async exportHistoryToCsv(destPath: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_history_limit", { limit }) };
+    return { status: "ok", data: await TAURI_INVOKE("export_history_to_csv", { destPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateRecordingRetentionPeriod(period: string) : Promise<Result<null, string>> {
+/**
+ * Write the whole history to `dest_path` as a JSON array of entries.
+ */
+async exportHistoryToJson(destPath: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_recording_retention_period", { period }) };
+    return { status: "ok", data: await TAURI_INVOKE("export_history_to_json", { destPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async startJournalRecording() : Promise<Result<null, string>> {
+/**
+ * Ask where to save a history export, suggesting `history-export-<timestamp>.csv`.
+ * Returns `None` if the user cancelled.
+ */
+async openHistoryExportDialog() : Promise<Result<string | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_journal_recording") };
+    return { status: "ok", data: await TAURI_INVOKE("open_history_export_dialog") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async stopJournalRecording() : Promise<Result<JournalRecordingResult, string>> {
+async updateHistoryLimit(limit: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_journal_recording") };
+    return { status: "ok", data: await TAURI_INVOKE("update_history_limit", { limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateRecordingRetentionPeriod(period: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_recording_retention_period", { period }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async startJournalRecording() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_journal_recording") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async stopJournalRecording(language: string | null) : Promise<Result<JournalRecordingResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_journal_recording", { language }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -731,6 +1118,10 @@ async stopJournalRecording() : Promise<Result<JournalRecordingResult, string>> {
 /**
  * Get a partial transcription of the audio recorded so far (live transcription).
  * Returns the transcription text, or an empty string if no audio is available yet.
+ * 
+ * Only audio recorded since the last commit is transcribed; older text comes
+ * from a cache that lives until the recording stops. A call made while another
+ * is still transcribing returns an empty string instead of waiting.
  */
 async getPartialJournalTranscription() : Promise<Result<string, string>> {
     try {
@@ -740,6 +1131,64 @@ async getPartialJournalTranscription() : Promise<Result<string, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Push the journal recording's transcript to the frontend as it is spoken,
+ * instead of having it poll `get_partial_journal_transcription`.
+ * 
+ * Every `interval_secs` the newly captured audio is transcribed and a
+ * `live-transcription` event carrying the cumulative text is emitted. The
+ * task ends by itself when the journal recording stops, skips its turn while
+ * the model is loading, and shares the preview cache's lock so it never
+ * transcribes at the same time as a poll.
+ */
+async startLiveTranscription(intervalSecs: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_live_transcription", { intervalSecs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async stopLiveTranscription() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_live_transcription") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Recordings left behind by a crash, for the frontend to offer on startup.
+ */
+async getRecoverableRecordings() : Promise<Result<RecoverableRecording[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recoverable_recordings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Move an interrupted recording into the journal's recordings folder, as if
+ * it had just been stopped. It is not transcribed here (it may be hours
+ * long); save it as an entry and retranscribe it from there.
+ */
+async recoverRecording(fileName: string) : Promise<Result<JournalRecordingResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("recover_recording", { fileName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async discardRecoverableRecording(fileName: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("discard_recoverable_recording", { fileName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async discardJournalRecording(fileName: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("discard_journal_recording", { fileName }) };
@@ -748,9 +1197,21 @@ async discardJournalRecording(fileName: string) : Promise<Result<null, string>>
     else return { status: "error", error: e  as any };
 }
 },
-async saveJournalEntry(fileName: string, title: string, transcriptionText: string, postProcessedText: string | null, postProcessPromptId: string | null, tags: string[], linkedEntryIds: number[], folderId: number | null) : Promise<Result<JournalEntry, string>> {
+async saveJournalEntry(fileName: string, title: string, transcriptionText: string, postProcessedText: string | null, postProcessPromptId: string | null, tags: string[], linkedEntryIds: number[], folderId: number | null, language: string | null, detectedLanguage: string | null) : Promise<Result<JournalEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_journal_entry", { fileName, title, transcriptionText, postProcessedText, postProcessPromptId, tags, linkedEntryIds, folderId, language, detectedLanguage }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set the initial prompt used when re-transcribing an entry. Empty or `None`
+ * clears it, so the one in settings is used.
+ */
+async setJournalEntryInitialPrompt(id: number, initialPrompt: string | null) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_journal_entry", { fileName, title, transcriptionText, postProcessedText, postProcessPromptId, tags, linkedEntryIds, folderId }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_journal_entry_initial_prompt", { id, initialPrompt }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -764,450 +1225,1503 @@ async getJournalEntries() : Promise<Result<JournalEntry[], string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async getJournalEntry(id: number) : Promise<Result<JournalEntry | null, string>> {
+/**
+ * Entries whose recording is between `min_ms` and `max_ms` long, newest first.
+ */
+async getJournalEntriesInDurationRange(minMs: number, maxMs: number) : Promise<Result<JournalEntry[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_journal_entry", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_journal_entries_in_duration_range", { minMs, maxMs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateJournalEntry(id: number, title: string, tags: string[], linkedEntryIds: number[], folderId: number | null, userSource: string | null) : Promise<Result<null, string>> {
+/**
+ * All tags in use with their entry counts, most frequent first.
+ * `source` limits the scan to one tab (e.g. "voice", "video", "meeting").
+ */
+async getJournalTags(source: string | null) : Promise<Result<([string, number])[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_journal_entry", { id, title, tags, linkedEntryIds, folderId, userSource }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_journal_tags", { source }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteJournalEntry(id: number) : Promise<Result<null, string>> {
+/**
+ * One page of entries, newest first. `source` of `None` pages across all tabs.
+ */
+async getJournalEntriesPage(source: string | null, limit: number, offset: number) : Promise<Result<JournalEntry[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_journal_entry", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_journal_entries_page", { source, limit, offset }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async applyJournalPostProcess(text: string, promptId: string) : Promise<Result<string, string>> {
+async countJournalEntries(source: string | null) : Promise<Result<number, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("apply_journal_post_process", { text, promptId }) };
+    return { status: "ok", data: await TAURI_INVOKE("count_journal_entries", { source }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Run a prompt template against text using the configured LLM, without looking up a prompt by ID.
- * The prompt_text should contain ${output} as a placeholder for the text.
+ * Entries carrying every tag in `tags`, optionally limited to a source and a
+ * timestamp range (unix seconds, inclusive).
  */
-async applyPromptTextToText(text: string, promptText: string) : Promise<Result<string, string>> {
+async filterJournalEntries(source: string | null, tags: string[], fromTs: number | null, toTs: number | null) : Promise<Result<JournalEntry[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("apply_prompt_text_to_text", { text, promptText }) };
+    return { status: "ok", data: await TAURI_INVOKE("filter_journal_entries", { source, tags, fromTs, toTs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateJournalPostProcessedText(id: number, text: string, promptId: string) : Promise<Result<null, string>> {
+async getJournalEntry(id: number) : Promise<Result<JournalEntry | null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_journal_post_processed_text", { id, text, promptId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_journal_entry", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getJournalAudioFilePath(fileName: string, folderId: number | null) : Promise<Result<string, string>> {
+/**
+ * Entries that link to `id`.
+ */
+async getEntryBacklinks(id: number) : Promise<Result<JournalEntry[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_journal_audio_file_path", { fileName, folderId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_entry_backlinks", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async retranscribeJournalEntry(id: number) : Promise<Result<string, string>> {
+async updateJournalEntry(id: number, title: string, tags: string[], linkedEntryIds: number[], folderId: number | null, userSource: string | null) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("retranscribe_journal_entry", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_journal_entry", { id, title, tags, linkedEntryIds, folderId, userSource }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async applyPromptToJournalEntry(id: number, promptId: string) : Promise<Result<string, string>> {
+async deleteJournalEntry(id: number) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("apply_prompt_to_journal_entry", { id, promptId }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_journal_entry", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Apply a prompt to a journal entry using the prompt text directly (not by ID lookup).
- * Used by Mutter which stores its own prompts independently from Handy's settings.
+ * Trash several entries at once. Returns the ids that could not be trashed.
  */
-async applyPromptTextToJournalEntry(id: number, promptText: string, promptLabel: string) : Promise<Result<string, string>> {
+async deleteEntries(ids: number[]) : Promise<Result<number[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("apply_prompt_text_to_journal_entry", { id, promptText, promptLabel }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_entries", { ids }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async undoJournalPrompt(id: number, previousPromptId: string | null) : Promise<Result<string, string>> {
+/**
+ * Move several entries into a folder. Returns the ids that could not be moved.
+ */
+async moveEntriesToFolder(ids: number[], folderId: number | null) : Promise<Result<number[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("undo_journal_prompt", { id, previousPromptId }) };
+    return { status: "ok", data: await TAURI_INVOKE("move_entries_to_folder", { ids, folderId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateJournalTranscriptionText(id: number, text: string) : Promise<Result<null, string>> {
+/**
+ * Clone an entry so prompts can be tried on the copy. Returns the new entry.
+ */
+async duplicateJournalEntry(id: number) : Promise<Result<JournalEntry, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_journal_transcription_text", { id, text }) };
+    return { status: "ok", data: await TAURI_INVOKE("duplicate_journal_entry", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateEntryAfterProcessing(id: number, fileName: string, title: string, transcriptionText: string) : Promise<Result<null, string>> {
+async restoreJournalEntry(id: number) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_entry_after_processing", { id, fileName, title, transcriptionText }) };
+    return { status: "ok", data: await TAURI_INVOKE("restore_journal_entry", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async importAudioForJournal(filePath: string) : Promise<Result<JournalRecordingResult, string>> {
+/**
+ * Permanently delete an entry and its files.
+ */
+async purgeJournalEntry(id: number) : Promise<Result<null, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("import_audio_for_journal", { filePath }) };
+    return { status: "ok", data: await TAURI_INVOKE("purge_journal_entry", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async journalChat(messages: ([string, string])[]) : Promise<Result<string, string>> {
+async getTrashedEntries() : Promise<Result<TrashedEntry[], AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("journal_chat", { messages }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_trashed_entries") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createChatSession(entryId: number, mode: string) : Promise<Result<ChatSession, string>> {
+async applyJournalPostProcess(text: string, promptId: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_chat_session", { entryId, mode }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_journal_post_process", { text, promptId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getChatSessions(entryId: number) : Promise<Result<ChatSession[], string>> {
+/**
+ * Run a prompt template against text using the configured LLM, without looking up a prompt by ID.
+ * The prompt_text should contain ${output} as a placeholder for the text.
+ */
+async applyPromptTextToText(text: string, promptText: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_chat_sessions", { entryId }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_prompt_text_to_text", { text, promptText }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveChatMessage(sessionId: number, role: string, content: string) : Promise<Result<ChatMessage, string>> {
+async updateJournalPostProcessedText(id: number, text: string, promptId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_chat_message", { sessionId, role, content }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_journal_post_processed_text", { id, text, promptId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getChatMessages(sessionId: number) : Promise<Result<ChatMessage[], string>> {
+async getJournalAudioFilePath(fileName: string, folderId: number | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_chat_messages", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_journal_audio_file_path", { fileName, folderId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateChatSessionTitle(sessionId: number, title: string) : Promise<Result<null, string>> {
+async retranscribeJournalEntry(id: number, language: string | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_chat_session_title", { sessionId, title }) };
+    return { status: "ok", data: await TAURI_INVOKE("retranscribe_journal_entry", { id, language }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteChatSession(sessionId: number) : Promise<Result<null, string>> {
+/**
+ * Translate an entry's transcript to English and store it with an undo
+ * snapshot. Recordings are run back through the model with its translate
+ * task enabled; entries without audio, or with a model that can't translate,
+ * are translated by the post-processing LLM instead.
+ */
+async translateJournalEntry(id: number) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_chat_session", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("translate_journal_entry", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createJournalFolder(name: string) : Promise<Result<JournalFolder, string>> {
+/**
+ * RMS waveform of an entry's recording for the playback UI. Empty if the
+ * audio file is missing.
+ */
+async getJournalEntryWaveform(fileName: string, folderId: number | null, resolution: number | null) : Promise<Result<number[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_journal_folder", { name }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_journal_entry_waveform", { fileName, folderId, resolution }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async renameJournalFolder(id: number, name: string) : Promise<Result<null, string>> {
+async applyPromptToJournalEntry(id: number, promptId: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("rename_journal_folder", { id, name }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_prompt_to_journal_entry", { id, promptId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteJournalFolder(id: number) : Promise<Result<null, string>> {
+/**
+ * Run several prompts over an entry in order, each on the previous one's
+ * output. Nothing is saved until every step has succeeded; then the final text
+ * is stored with one snapshot per step. Emits `prompt-chain-progress` as steps
+ * finish. Returns the final text.
+ */
+async applyPromptChainToEntry(entryId: number, promptIds: string[]) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_journal_folder", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_prompt_chain_to_entry", { entryId, promptIds }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getJournalFolders() : Promise<Result<JournalFolder[], string>> {
+/**
+ * Apply a prompt to a journal entry using the prompt text directly (not by ID lookup).
+ * Used by Mutter which stores its own prompts independently from Handy's settings.
+ */
+async applyPromptTextToJournalEntry(id: number, promptText: string, promptLabel: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_journal_folders") };
+    return { status: "ok", data: await TAURI_INVOKE("apply_prompt_text_to_journal_entry", { id, promptText, promptLabel }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async moveJournalEntryToFolder(entryId: number, folderId: number | null) : Promise<Result<null, string>> {
+/**
+ * Apply the same prompt text to several entries in turn. A failing entry is
+ * recorded in the result and the batch moves on to the next one.
+ */
+async applyPromptTextToEntries(ids: number[], promptText: string, promptLabel: string) : Promise<Result<BatchPromptResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("move_journal_entry_to_folder", { entryId, folderId }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_prompt_text_to_entries", { ids, promptText, promptLabel }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getJournalStoragePath() : Promise<Result<string, string>> {
+/**
+ * Run a saved prompt over every entry (or every entry in `folder_id`),
+ * applying it like `apply_prompt_to_journal_entry` so each result can be
+ * undone. Entries a prompt was already applied to are skipped unless
+ * `overwrite_existing` is set, as are entries with no transcript. Entries are
+ * processed one at a time, emitting `batch-process-progress`;
+ * `cancel_batch_process` (or `cancel_current_operation` on desktop) stops the
+ * batch before the next entry and the partial result is returned.
+ */
+async batchApplyPrompt(promptId: string, overwriteExisting: boolean, folderId: number | null) : Promise<Result<BatchProcessResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_journal_storage_path") };
+    return { status: "ok", data: await TAURI_INVOKE("batch_apply_prompt", { promptId, overwriteExisting, folderId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setJournalStoragePath(path: string) : Promise<Result<null, string>> {
+/**
+ * Stop a running `batch_apply_prompt` before its next entry.
+ */
+async cancelBatchProcess() : Promise<void> {
+    await TAURI_INVOKE("cancel_batch_process");
+},
+async undoJournalPrompt(id: number, previousPromptId: string | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_journal_storage_path", { path }) };
+    return { status: "ok", data: await TAURI_INVOKE("undo_journal_prompt", { id, previousPromptId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkYtdlpInstalled() : Promise<Result<boolean, string>> {
+async redoJournalPrompt(id: number) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_ytdlp_installed") };
+    return { status: "ok", data: await TAURI_INVOKE("redo_journal_prompt", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async installYtdlp() : Promise<Result<null, string>> {
+/**
+ * Word-level diff between the text before the last applied prompt and the
+ * current transcription.
+ */
+async getPromptDiff(entryId: number) : Promise<Result<DiffChunk[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("install_ytdlp") };
+    return { status: "ok", data: await TAURI_INVOKE("get_prompt_diff", { entryId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async downloadYoutubeAudio(url: string) : Promise<Result<YouTubeDownloadResult, string>> {
+async updateJournalTranscriptionText(id: number, text: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("download_youtube_audio", { url }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_journal_transcription_text", { id, text }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async importVideoForJournal(filePath: string) : Promise<Result<JournalRecordingResult, string>> {
+async updateEntryAfterProcessing(id: number, fileName: string, title: string, transcriptionText: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("import_video_for_journal", { filePath }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_entry_after_processing", { id, fileName, title, transcriptionText }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getVideoEntries() : Promise<Result<JournalEntry[], string>> {
+async importAudioForJournal(filePath: string) : Promise<Result<JournalRecordingResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_video_entries") };
+    return { status: "ok", data: await TAURI_INVOKE("import_audio_for_journal", { filePath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getVideoFolders() : Promise<Result<JournalFolder[], string>> {
+/**
+ * Import a markdown file as a voice entry without audio.
+ */
+async importMarkdownFile(path: string, folderId: number | null) : Promise<Result<JournalEntry, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_video_folders") };
+    return { status: "ok", data: await TAURI_INVOKE("import_markdown_file", { path, folderId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createVideoFolder(name: string) : Promise<Result<JournalFolder, string>> {
+/**
+ * Import every `.md` file in a directory (not recursive). A file that fails
+ * is recorded in the result and the import moves on to the next one.
+ */
+async importMarkdownDirectory(dir: string, folderId: number | null) : Promise<Result<MarkdownImportResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_video_folder", { name }) };
+    return { status: "ok", data: await TAURI_INVOKE("import_markdown_directory", { dir, folderId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveVideoEntry(fileName: string, title: string, transcriptionText: string, source: string, sourceUrl: string | null, folderId: number | null) : Promise<Result<JournalEntry, string>> {
+/**
+ * Import a `.txt` or `.md` file as-is as an entry without audio. The file
+ * name (without extension) becomes the title.
+ */
+async importTextAsEntry(filePath: string, folderId: number | null) : Promise<Result<JournalEntry, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_video_entry", { fileName, title, transcriptionText, source, sourceUrl, folderId }) };
+    return { status: "ok", data: await TAURI_INVOKE("import_text_as_entry", { filePath, folderId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkDiarizeModelsInstalled() : Promise<Result<boolean, string>> {
+async getImportedEntries() : Promise<Result<JournalEntry[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_diarize_models_installed") };
+    return { status: "ok", data: await TAURI_INVOKE("get_imported_entries") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async installDiarizeModels() : Promise<Result<null, string>> {
+async getImportedFolders() : Promise<Result<JournalFolder[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("install_diarize_models") };
+    return { status: "ok", data: await TAURI_INVOKE("get_imported_folders") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getMeetingEntries() : Promise<Result<JournalEntry[], string>> {
+async createImportFolder(name: string) : Promise<Result<JournalFolder, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_meeting_entries") };
+    return { status: "ok", data: await TAURI_INVOKE("create_import_folder", { name }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getMeetingFolders() : Promise<Result<JournalFolder[], string>> {
+/**
+ * Import every supported audio file in a directory (not recursive) as its own
+ * journal entry. Emits `import-batch-progress` after each file; a failing file
+ * is recorded in the result and the import moves on to the next one.
+ */
+async importAudioDirectory(dirPath: string, folderId: number | null) : Promise<Result<AudioDirectoryImportResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_meeting_folders") };
+    return { status: "ok", data: await TAURI_INVOKE("import_audio_directory", { dirPath, folderId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createMeetingFolder(name: string) : Promise<Result<JournalFolder, string>> {
+async journalChat(messages: ([string, string])[], temperature: number | null, maxTokens: number | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_meeting_folder", { name }) };
+    return { status: "ok", data: await TAURI_INVOKE("journal_chat", { messages, temperature, maxTokens }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveMeetingEntry(fileName: string, title: string, transcriptionText: string, folderId: number | null) : Promise<Result<JournalEntry, string>> {
+/**
+ * Chat about several journal entries at once. Their transcripts go into a
+ * system prompt, trimmed to fit the provider's context window.
+ */
+async multiEntryJournalChat(entryIds: number[], messages: ([string, string])[], temperature: number | null, maxTokens: number | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_meeting_entry", { fileName, title, transcriptionText, folderId }) };
+    return { status: "ok", data: await TAURI_INVOKE("multi_entry_journal_chat", { entryIds, messages, temperature, maxTokens }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async transcribeMeeting(entryId: number, maxSpeakers: number | null, threshold: number | null) : Promise<Result<null, string>> {
+async getContextWindowEstimate(providerId: string) : Promise<Result<number | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("transcribe_meeting", { entryId, maxSpeakers, threshold }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_context_window_estimate", { providerId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getMeetingSegments(entryId: number) : Promise<Result<DiarizedSegment[], string>> {
+async createMultiChatSession(entryIds: number[]) : Promise<Result<MultiEntryChatSession, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_meeting_segments", { entryId }) };
+    return { status: "ok", data: await TAURI_INVOKE("create_multi_chat_session", { entryIds }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateMeetingSegmentText(segmentId: number, text: string) : Promise<Result<null, string>> {
+async getMultiChatSessions() : Promise<Result<MultiEntryChatSession[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_meeting_segment_text", { segmentId, text }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_multi_chat_sessions") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateMeetingSegmentSpeaker(segmentId: number, speaker: number | null) : Promise<Result<null, string>> {
+async saveMultiChatMessage(sessionId: number, role: string, content: string) : Promise<Result<ChatMessage, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_meeting_segment_speaker", { segmentId, speaker }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_multi_chat_message", { sessionId, role, content }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateMeetingSpeakerName(entryId: number, speakerId: number, name: string) : Promise<Result<null, string>> {
+async getMultiChatMessages(sessionId: number) : Promise<Result<ChatMessage[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_meeting_speaker_name", { entryId, speakerId, name }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_multi_chat_messages", { sessionId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getMeetingSpeakerNames(entryId: number) : Promise<Result<Partial<{ [key in string]: string }>, string>> {
+async deleteMultiChatSession(sessionId: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_meeting_speaker_names", { entryId }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_multi_chat_session", { sessionId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async diarizeEntry(entryId: number, maxSpeakers: number | null, threshold: number | null) : Promise<Result<null, string>> {
+async createChatSession(entryId: number, mode: string) : Promise<Result<ChatSession, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("diarize_entry", { entryId, maxSpeakers, threshold }) };
+    return { status: "ok", data: await TAURI_INVOKE("create_chat_session", { entryId, mode }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Checks if the Mac is a laptop by detecting battery presence
- * 
- * This uses pmset to check for battery information.
- * Returns true if a battery is detected (laptop), false otherwise (desktop)
- */
-async isLaptop() : Promise<Result<boolean, string>> {
+async getChatSessions(entryId: number) : Promise<Result<ChatSession[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("is_laptop") };
+    return { status: "ok", data: await TAURI_INVOKE("get_chat_sessions", { entryId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async saveChatMessage(sessionId: number, role: string, content: string) : Promise<Result<ChatMessage, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_chat_message", { sessionId, role, content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
+},
+async getChatMessages(sessionId: number) : Promise<Result<ChatMessage[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_chat_messages", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
+},
+async updateChatSessionTitle(sessionId: number, title: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_chat_session_title", { sessionId, title }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteChatSession(sessionId: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_chat_session", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createJournalFolder(name: string, parentId: number | null) : Promise<Result<JournalFolder, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_journal_folder", { name, parentId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async renameJournalFolder(id: number, name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rename_journal_folder", { id, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async moveJournalFolder(id: number, parentId: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("move_journal_folder", { id, parentId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteJournalFolder(id: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_journal_folder", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getJournalFolders() : Promise<Result<JournalFolder[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_journal_folders") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async moveJournalEntryToFolder(entryId: number, folderId: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("move_journal_entry_to_folder", { entryId, folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getJournalStoragePath() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_journal_storage_path") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setJournalStoragePath(path: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_journal_storage_path", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getObsidianVaultPath() : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_obsidian_vault_path") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set (or with None, clear) the Obsidian vault journal entries are mirrored
+ * into.
+ */
+async changeObsidianVaultPathSetting(path: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_obsidian_vault_path_setting", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Mirror all existing journal entries into the configured Obsidian vault.
+ */
+async syncEntriesToObsidian() : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sync_entries_to_obsidian") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Commit all pending changes in the journal folder to the git sync repository.
+ */
+async triggerGitSync() : Promise<Result<GitSyncStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("trigger_git_sync") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * RSS 2.0 feed of a folder's recordings, with episode audio served from
+ * `base_url`. Only entries recorded or imported as WAV audio are included;
+ * video and YouTube imports are skipped.
+ */
+async generateFolderPodcastFeed(folderId: number, title: string, description: string, baseUrl: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_folder_podcast_feed", { folderId, title, description, baseUrl }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Write a folder's podcast feed to `output_path`. Takes the same channel
+ * details as `generate_folder_podcast_feed`.
+ */
+async savePodcastFeed(folderId: number, title: string, description: string, baseUrl: string, outputPath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_podcast_feed", { folderId, title, description, baseUrl, outputPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Archive the journal (files plus a `journal.json` of its rows) into a zip at
+ * `dest_path`, optionally limited to one source. Returns the file count.
+ */
+async exportJournalZip(destPath: string, source: string | null) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_journal_zip", { destPath, source }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeAutoGenerateTitleSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_auto_generate_title_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeAutoTitleUseLlmSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_auto_title_use_llm_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeTrashRetentionDaysSetting(days: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_trash_retention_days_setting", { days }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeBackupEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_backup_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeBackupCountSetting(count: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_backup_count_setting", { count }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeMarkdownFrontmatterSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_markdown_frontmatter_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Set the transcript `.md` template. An empty template restores the default.
+ */
+async changeMarkdownTemplateSetting(template: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_markdown_template_setting", { template }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async backupJournalDatabase() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("backup_journal_database") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listJournalBackups() : Promise<Result<BackupInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_journal_backups") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreJournalFromBackup(path: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_journal_from_backup", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkYtdlpInstalled() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_ytdlp_installed") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async installYtdlp() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("install_ytdlp") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The newer yt-dlp release available, if any.
+ */
+async checkYtdlpUpdate() : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_ytdlp_update") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replace the installed yt-dlp with the latest release. Returns its version.
+ */
+async updateYtdlp() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_ytdlp") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run `yt-dlp --get-title` on a URL using the configured cookies so users can
+ * check that age-restricted or members-only videos are reachable.
+ */
+async testYtdlpAuth(url: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_ytdlp_auth", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Kept for existing callers; see `download_url_audio`.
+ */
+async downloadYoutubeAudio(url: string, preferCaptions: boolean | null, skipSponsors: boolean | null, entryId: number | null, cookiesFromBrowser: string | null, captionLanguage: string | null) : Promise<Result<UrlDownloadResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_youtube_audio", { url, preferCaptions, skipSponsors, entryId, cookiesFromBrowser, captionLanguage }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download and transcribe audio from any URL yt-dlp supports (YouTube, Vimeo,
+ * SoundCloud, podcast enclosures, ...). yt-dlp errors such as unsupported sites are
+ * returned as reported. When `entry_id` is given, the URL is stored as its `source_url`.
+ * `cookies_from_browser` (e.g. "firefox") overrides the cookie settings for
+ * videos that need a signed-in account. With `prefer_captions`, captions are
+ * fetched in `caption_language`, else in the selected transcription language
+ * (the video's own language when that is "auto").
+ */
+async downloadUrlAudio(url: string, preferCaptions: boolean | null, skipSponsors: boolean | null, entryId: number | null, cookiesFromBrowser: string | null, captionLanguage: string | null) : Promise<Result<UrlDownloadResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_url_audio", { url, preferCaptions, skipSponsors, entryId, cookiesFromBrowser, captionLanguage }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Import every video of a YouTube playlist as its own journal entry.
+ * Pending entries are created up front, then each video is downloaded and
+ * transcribed in order. Failed items are skipped; `ytdlp-cancel` stops the
+ * import between items.
+ */
+async downloadYoutubePlaylist(url: string, createFolder: boolean, folderId: number | null) : Promise<Result<PlaylistImportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_youtube_playlist", { url, createFolder, folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * SponsorBlock ranges that were cut from an imported video's audio.
+ */
+async getSkippedSegments(entryId: number) : Promise<Result<SkippedSegment[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_skipped_segments", { entryId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Extract, transcribe and save a video's audio. With `entry_id`, the transcript is
+ * also stored as timed segments (one per 30-second chunk) for playback sync.
+ */
+async importVideoForJournal(filePath: string, entryId: number | null) : Promise<Result<VideoImportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_video_for_journal", { filePath, entryId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Import an .srt or .vtt file as a journal entry without audio. The flat text becomes
+ * the transcript and each cue is stored as a meeting segment with its timing.
+ */
+async importSubtitlesForJournal(filePath: string, source: string, folderId: number | null) : Promise<Result<SubtitleImportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_subtitles_for_journal", { filePath, source, folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getVideoEntries() : Promise<Result<JournalEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_video_entries") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getVideoFolders() : Promise<Result<JournalFolder[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_video_folders") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createVideoFolder(name: string) : Promise<Result<JournalFolder, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_video_folder", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveVideoEntry(fileName: string, title: string, transcriptionText: string, source: string, sourceUrl: string | null, folderId: number | null) : Promise<Result<JournalEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_video_entry", { fileName, title, transcriptionText, source, sourceUrl, folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkDiarizeModelsInstalled() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_diarize_models_installed") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download missing diarize models and replace any that fail their checksum.
+ */
+async installDiarizeModels() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("install_diarize_models") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Hash the installed diarize models to catch truncated or corrupt files.
+ */
+async verifyDiarizeModels() : Promise<Result<DiarizeModelCheck[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_diarize_models") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getMeetingEntries() : Promise<Result<JournalEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_entries") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getMeetingFolders() : Promise<Result<JournalFolder[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_folders") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createMeetingFolder(name: string) : Promise<Result<JournalFolder, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_meeting_folder", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveMeetingEntry(fileName: string, title: string, transcriptionText: string, folderId: number | null) : Promise<Result<JournalEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_meeting_entry", { fileName, title, transcriptionText, folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async transcribeMeeting(entryId: number, maxSpeakers: number | null, threshold: number | null, minSegmentMs: number | null, mergeGapMs: number | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("transcribe_meeting", { entryId, maxSpeakers, threshold, minSegmentMs, mergeGapMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getMeetingSegments(entryId: number) : Promise<Result<DiarizedSegment[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_segments", { entryId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateMeetingSegmentText(segmentId: number, text: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_meeting_segment_text", { segmentId, text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-transcribe one segment from the entry's audio and store the new text.
+ * `start_ms`/`end_ms` override the segment's boundaries, which are saved too.
+ */
+async retranscribeMeetingSegment(entryId: number, segmentId: number, startMs: number | null, endMs: number | null) : Promise<Result<string, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("retranscribe_meeting_segment", { entryId, segmentId, startMs, endMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateMeetingSegmentSpeaker(segmentId: number, speaker: number | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_meeting_segment_speaker", { segmentId, speaker }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateMeetingSpeakerName(entryId: number, speakerId: number, name: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_meeting_speaker_name", { entryId, speakerId, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Collapse consecutive same-speaker segments separated by at most `max_gap_ms`
+ * (default 1000ms). Returns how many segments were merged away.
+ */
+async mergeMeetingSegments(entryId: number, maxGapMs: number | null) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("merge_meeting_segments", { entryId, maxGapMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Merge two segments into the earlier one. Returns the surviving segment id.
+ */
+async mergeTwoSegments(firstId: number, secondId: number) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("merge_two_segments", { firstId, secondId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Split a segment in two at `split_ms`. `text_index` is the character index
+ * where the text is cut; without it the text is divided proportionally.
+ * Returns the ids of the two halves.
+ */
+async splitMeetingSegment(segmentId: number, splitMs: number, textIndex: number | null) : Promise<Result<[number, number], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("split_meeting_segment", { segmentId, splitMs, textIndex }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteMeetingSegment(segmentId: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_meeting_segment", { segmentId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Add a segment by hand. Returns the new segment id.
+ */
+async insertMeetingSegment(entryId: number, startMs: number, endMs: number, speaker: number | null, text: string) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("insert_meeting_segment", { entryId, startMs, endMs, speaker, text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getMeetingSpeakerNames(entryId: number) : Promise<Result<Partial<{ [key in string]: string }>, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_speaker_names", { entryId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Enroll the voice in a segment under `name`, so later meetings can recognize
+ * it. The segment's speaker in this meeting is given the name too.
+ */
+async enrollSpeakerFromSegment(segmentId: number, name: string) : Promise<Result<SpeakerProfile, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("enroll_speaker_from_segment", { segmentId, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listSpeakerProfiles() : Promise<Result<SpeakerProfile[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_speaker_profiles") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteSpeakerProfile(id: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_speaker_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Minimum similarity for a meeting speaker to be named after an enrolled
+ * speaker profile, clamped to 0–1.
+ */
+async changeSpeakerMatchThresholdSetting(threshold: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_speaker_match_threshold_setting", { threshold }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Talk-time breakdown per speaker, for the meeting stats chart.
+ */
+async getMeetingSpeakerStats(entryId: number) : Promise<Result<SpeakerStats[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_speaker_stats", { entryId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportMeetingTranscript(entryId: number, format: ExportFormat) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_meeting_transcript", { entryId, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Export a meeting transcript and write it to a location chosen via a save dialog.
+ * Returns the saved path, or `None` if the user cancelled the dialog.
+ */
+async saveMeetingTranscriptToFile(entryId: number, format: ExportFormat) : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_meeting_transcript_to_file", { entryId, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Render a meeting as Markdown (`**Alice** [00:12:31]: text` per speaker turn).
+ * With `save_beside_audio`, also writes it next to the entry's audio file.
+ */
+async exportMeetingMarkdown(entryId: number, saveBesideAudio: boolean) : Promise<Result<MeetingMarkdownExport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_meeting_markdown", { entryId, saveBesideAudio }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Summarize a meeting into minutes with the post-processing LLM and store the
+ * result as the entry's post-processed text.
+ */
+async generateMeetingMinutes(entryId: number) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_meeting_minutes", { entryId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async diarizeEntry(entryId: number, maxSpeakers: number | null, threshold: number | null, minSegmentMs: number | null, mergeGapMs: number | null) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diarize_entry", { entryId, maxSpeakers, threshold, minSegmentMs, mergeGapMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop the running diarization at its next segment.
+ */
+async cancelDiarization() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_diarization") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-run diarization with new parameters without losing manual work. New
+ * speaker clusters are mapped onto the old ones by shared speaking time so
+ * speaker names still apply, and segments whose boundaries barely moved keep
+ * their edited text and speaker. Everything else is transcribed fresh.
+ */
+async rediarizeMeeting(entryId: number, maxSpeakers: number | null, threshold: number | null, minSegmentMs: number | null, mergeGapMs: number | null) : Promise<Result<RediarizeSummary, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rediarize_meeting", { entryId, maxSpeakers, threshold, minSegmentMs, mergeGapMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Checks if the Mac is a laptop by detecting battery presence
+ * 
+ * This uses pmset to check for battery information.
+ * Returns true if a battery is detected (laptop), false otherwise (desktop)
+ */
+async isLaptop() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("is_laptop") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
 
-/** user-defined events **/
-
-
-
-/** user-defined constants **/
-
-
-
-/** user-defined types **/
-
-export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; push_to_talk: boolean; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; always_on_microphone?: boolean; selected_microphone?: string | null; clamshell_microphone?: string | null; selected_output_device?: string | null; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; auto_submit?: boolean; auto_submit_key?: AutoSubmitKey; post_process_enabled?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; mute_while_recording?: boolean; append_trailing_space?: boolean; app_language?: string; experimental_enabled?: boolean; keyboard_implementation?: KeyboardImplementation; show_tray_icon?: boolean; paste_delay_ms?: number; typing_tool?: TypingTool; external_script_path: string | null; 
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * Request/response schema a provider speaks.
+ */
+export type ApiStyle = "openai" | "ollama"
+export type AppError = { kind: "NotFound" } | { kind: "InvalidInput"; message: string } | { kind: "Io"; message: string } | { kind: "Database"; message: string } | { kind: "Transcription"; message: string } | { kind: "LlmClient"; message: string } | { kind: "Cancelled" } | { kind: "Other"; message: string }
+export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; push_to_talk: boolean; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; always_on_microphone?: boolean; selected_microphone?: string | null; clamshell_microphone?: string | null; selected_output_device?: string | null; recording_source?: RecordingSource; 
+/**
+ * System-audio device by name; `None` uses the default (or first) one.
+ */
+selected_loopback_device?: string | null; 
+/**
+ * Input gain in dB per microphone name ("default" for the host default).
+ * Applied to every recording, with clipping protection.
+ */
+microphone_gain?: Partial<{ [key in string]: number }>; 
+/**
+ * Sample rate of the archival WAV saved next to each recording. At the
+ * default (16kHz, 16 bit) no archival copy is written; transcription
+ * always uses a 16kHz mono copy.
+ */
+recording_sample_rate?: number; 
+/**
+ * Bits per sample of the archival WAV: 16, 24, or 32 (float).
+ */
+recording_bit_depth?: number; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; 
+/**
+ * Initial prompt given to Whisper to bias it toward names and domain
+ * terms. Other engines ignore it.
+ */
+transcription_initial_prompt?: string; 
+/**
+ * Append `custom_words` to the initial prompt.
+ */
+initial_prompt_include_custom_words?: boolean; word_replacements?: WordReplacement[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; auto_submit?: boolean; auto_submit_key?: AutoSubmitKey; post_process_enabled?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; 
+/**
+ * Per-provider override of the context window, in tokens. Prompts that
+ * would exceed it are rejected before they are sent.
+ */
+post_process_max_context_tokens?: Partial<{ [key in string]: number }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; mute_while_recording?: boolean; append_trailing_space?: boolean; app_language?: string; experimental_enabled?: boolean; keyboard_implementation?: KeyboardImplementation; show_tray_icon?: boolean; paste_delay_ms?: number; typing_tool?: TypingTool; external_script_path: string | null; 
 /**
  * Custom storage path for Mutter journal files. If None, uses app_data_dir/journal_recordings/.
  */
-journal_storage_path?: string | null }
+journal_storage_path?: string | null; 
+/**
+ * Netscape-format cookies file passed to yt-dlp via `--cookies`.
+ */
+ytdlp_cookies_file?: string | null; 
+/**
+ * Browser to read cookies from via `--cookies-from-browser` (e.g. "firefox").
+ */
+ytdlp_cookies_from_browser?: string | null; 
+/**
+ * Release tag of the installed yt-dlp binary, recorded at install time.
+ */
+ytdlp_installed_version?: string | null; 
+/**
+ * Minimum run of identical consecutive words collapsed before sending text to the LLM.
+ */
+dedup_threshold?: number; 
+/**
+ * Obsidian vault that journal entries are mirrored into (under `voice-journal/`).
+ */
+obsidian_vault_path?: string | null; 
+/**
+ * Commit journal files to the git repository at `git_sync_repo_path` as they are saved.
+ */
+git_sync_enabled?: boolean; 
+/**
+ * Work tree journal files are committed to. Files outside it are not synced.
+ */
+git_sync_repo_path?: string; 
+/**
+ * Title new journal entries saved without one from the first sentence of the transcript.
+ */
+auto_generate_title?: boolean; 
+/**
+ * With `auto_generate_title`, replace the title of longer entries with an LLM summary.
+ */
+auto_title_use_llm?: boolean; 
+/**
+ * Show a desktop notification when a transcription finishes.
+ */
+notifications_enabled?: boolean; 
+/**
+ * With `notifications_enabled`, also notify when a model finishes loading.
+ */
+notification_on_model_load?: boolean; 
+/**
+ * ffmpeg binary used for formats symphonia can't decode. If None, PATH is searched.
+ */
+ffmpeg_path?: string | null; 
+/**
+ * Days a deleted journal entry stays in the trash before it is purged at launch.
+ * 0 keeps trashed entries until they are purged by hand.
+ */
+trash_retention_days?: number; 
+/**
+ * Back up the journal database at launch and every 24 hours.
+ */
+backup_enabled?: boolean; 
+/**
+ * Number of rolling journal database backups to keep.
+ */
+backup_count?: number; 
+/**
+ * Start transcript `.md` files with a YAML frontmatter block.
+ */
+markdown_frontmatter?: boolean; 
+/**
+ * Template for transcript `.md` files; see `markdown_template` for the
+ * placeholders. `None` writes the raw transcription text.
+ */
+markdown_template?: string | null; 
+/**
+ * Minimum cosine similarity between a meeting speaker and an enrolled
+ * speaker profile for the profile's name to be filled in.
+ */
+speaker_match_threshold?: number; 
+/**
+ * Seconds before a stuck transcription is abandoned.
+ */
+transcription_timeout_secs?: number; 
+/**
+ * App group shared with the iOS share extension, e.g. "group.com.example.mutter".
+ * Audio shared from other apps is dropped into this group's container.
+ */
+ios_app_group_id?: string | null; 
+/**
+ * Proxy for all outbound requests and yt-dlp, e.g. "http://host:8080" or
+ * "socks5://host:1080". When unset the standard proxy environment
+ * variables apply.
+ */
+http_proxy?: string | null; 
+/**
+ * Resampler used when imported audio isn't already at 16kHz.
+ */
+audio_resample_quality?: ResampleQuality; 
+/**
+ * Folder watched for new `.wav`/`.mp3`/`.m4a` files, which are imported
+ * as journal entries. None disables the watcher.
+ */
+watch_folder_path?: string | null; 
+/**
+ * Journal folder watch-folder imports are filed into; None leaves them unfiled.
+ */
+watch_folder_import_folder_id?: number | null; 
+/**
+ * Execution provider for ONNX models; see `compute_backend`.
+ */
+compute_backend?: ComputeBackend }
 export type AudioDevice = { index: string; name: string; is_default: boolean }
+export type AudioDirectoryImportResult = { imported: number[]; failed: FileImportError[] }
 export type AutoSubmitKey = "enter" | "ctrl_enter" | "cmd_enter"
+/**
+ * A rolling copy of the journal database in the `backups/` directory.
+ */
+export type BackupInfo = { path: string; file_name: string; 
+/**
+ * When the backup was taken (unix seconds).
+ */
+created_at: number; size_bytes: number }
+export type BatchProcessResult = { processed: number; skipped: number; 
+/**
+ * Entry id and error for each entry that failed.
+ */
+failed: ([number, string])[] }
+export type BatchPromptError = { id: number; error: string }
+export type BatchPromptResult = { succeeded: number[]; failed: BatchPromptError[] }
 export type BindingResponse = { success: boolean; binding: ShortcutBinding | null; error: string | null }
 export type ChatMessage = { id: number; session_id: number; role: string; content: string; created_at: number }
 export type ChatSession = { id: number; entry_id: number; mode: string; title: string; created_at: number; updated_at: number }
 export type ClipboardHandling = "dont_modify" | "copy_to_clipboard"
+/**
+ * ONNX Runtime execution provider for the ONNX transcription engines and
+ * diarization. `Auto` tries the GPU providers and falls back to CPU.
+ */
+export type ComputeBackend = "auto" | "cpu" | "cuda" | "directml" | "coreml"
+export type ConnectionTestResult = { 
+/**
+ * `/models` answered successfully.
+ */
+models_reachable: boolean; 
+/**
+ * The configured model appears in the `/models` list.
+ */
+model_exists: boolean; 
+/**
+ * A one-token completion succeeded.
+ */
+can_complete: boolean; 
+/**
+ * Round trip of the completion request.
+ */
+latency_ms: number; 
+/**
+ * Why the first failing step failed, if any.
+ */
+error: string | null }
 export type CustomSounds = { start: boolean; stop: boolean }
+/**
+ * Health of one diarize model file on disk.
+ */
+export type DiarizeModelCheck = { label: string; present: boolean; 
+/**
+ * False when the file doesn't match its expected hash. Files with no known
+ * hash (downloaded before hashes were recorded) are assumed valid.
+ */
+valid: boolean }
 /**
  * A single diarized speech segment with speaker assignment and audio samples.
  */
-export type DiarizedSegment = { id?: number | null; speaker: number | null; start_ms: number; end_ms: number; text: string }
+export type DiarizedSegment = { id?: number | null; speaker: number | null; start_ms: number; end_ms: number; text: string; 
+/**
+ * Cosine similarity between the segment embedding and its assigned speaker (0.0–1.0).
+ * `None` for segments saved before confidence was recorded.
+ */
+confidence?: number | null }
+/**
+ * A run of text that was added, removed or kept between two versions.
+ */
+export type DiffChunk = { kind: DiffKind; text: string }
+export type DiffKind = "Added" | "Removed" | "Equal"
+/**
+ * Payload of the `model-download-complete` event.
+ */
+export type DownloadComplete = { model_id: string }
+/**
+ * Payload of the `model-download-progress` event. `total_bytes` is 0 when the
+ * server doesn't report a size.
+ */
+export type DownloadProgress = { model_id: string; bytes_downloaded: number; total_bytes: number; percent: number }
+/**
+ * Payload of the `model-download-started` event, emitted once the size of the
+ * download is known.
+ */
+export type DownloadStarted = { model_id: string; total_bytes: number }
 export type EngineType = "Whisper" | "Parakeet" | "Moonshine" | "MoonshineStreaming" | "SenseVoice"
-export type HistoryEntry = { id: number; file_name: string; timestamp: number; saved: boolean; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null }
+export type ExportFormat = "Srt" | "Vtt" | "Txt" | "Json" | "Markdown"
+export type FileImportError = { file: string; error: string }
+export type GitSyncStatus = { 
+/**
+ * False when there was nothing new to commit.
+ */
+committed: boolean; files_changed: number; 
+/**
+ * Hash of the new commit, if one was made.
+ */
+commit: string | null }
+export type HistoryEntry = { id: number; file_name: string; timestamp: number; saved: boolean; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null; 
+/**
+ * Language detected when transcribing with the language on "auto".
+ */
+detected_language: string | null }
 /**
  * Result of changing keyboard implementation
  */
@@ -1216,23 +2730,191 @@ export type ImplementationChangeResult = { success: boolean;
  * List of binding IDs that were reset to defaults due to incompatibility
  */
 reset_bindings: string[] }
-export type JournalEntry = { id: number; file_name: string; timestamp: number; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt_id: string | null; tags: string[]; linked_entry_ids: number[]; folder_id: number | null; transcript_snapshots: string[]; source: string; source_url: string | null; speaker_names: string; user_source: string }
-export type JournalFolder = { id: number; name: string; created_at: number; source: string }
-export type JournalRecordingResult = { file_name: string; transcription_text: string }
+export type JournalEntry = { id: number; file_name: string; timestamp: number; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt_id: string | null; tags: string[]; linked_entry_ids: number[]; folder_id: number | null; transcript_snapshots: string[]; source: string; source_url: string | null; speaker_names: string; user_source: string; 
+/**
+ * States undone by `undo_last_prompt`, most recent last.
+ */
+redo_snapshots: PromptSnapshot[]; 
+/**
+ * Language the entry is transcribed in, or `None` to follow the
+ * selected language in settings.
+ */
+language: string | null; 
+/**
+ * Language detected when the entry was transcribed with the language on
+ * "auto".
+ */
+detected_language: string | null; 
+/**
+ * Replaces the initial prompt from settings when re-transcribing, or
+ * `None` to use the one in settings.
+ */
+initial_prompt: string | null; 
+/**
+ * Length of the entry's recording, or `None` for entries without audio.
+ */
+duration_ms: number | null }
+export type JournalFolder = { id: number; name: string; created_at: number; source: string; 
+/**
+ * Containing folder, or `None` for a top-level folder.
+ */
+parent_id: number | null }
+export type JournalRecordingResult = { file_name: string; transcription_text: string; 
+/**
+ * Language the recording was transcribed in, if one was requested; pass
+ * it to `save_journal_entry` to keep it on the entry.
+ */
+language: string | null; 
+/**
+ * Language detected when transcribing with the language on "auto".
+ */
+detected_language: string | null }
 export type KeyboardImplementation = "tauri" | "handy_keys"
-export type LLMPrompt = { id: string; name: string; prompt: string }
+export type LLMPrompt = { id: string; name: string; prompt: string; 
+/**
+ * Sampling temperature; `None` leaves it to the provider.
+ */
+temperature?: number | null; 
+/**
+ * Reply length cap; `None` leaves it to the provider.
+ */
+max_tokens?: number | null }
 export type LogLevel = "trace" | "debug" | "info" | "warn" | "error"
-export type ModelInfo = { id: string; name: string; description: string; filename: string; url: string | null; size_mb: number; is_downloaded: boolean; is_downloading: boolean; partial_size: number; is_directory: boolean; engine_type: EngineType; accuracy_score: number; speed_score: number; supports_translation: boolean; is_recommended: boolean; supported_languages: string[]; is_custom: boolean }
+export type MarkdownImportResult = { imported: JournalEntry[]; failed: FileImportError[] }
+export type MeetingMarkdownExport = { markdown: string; 
+/**
+ * Where the file was written, when `save_beside_audio` was set.
+ */
+path: string | null }
+/**
+ * Total and currently available system memory.
+ */
+export type MemoryInfo = { total_bytes: number; available_bytes: number }
+export type MicrophoneLevel = { rms: number; peak: number }
+export type ModelInfo = { id: string; name: string; description: string; filename: string; url: string | null; 
+/**
+ * Expected SHA-256 of the downloaded file (the archive for directory models).
+ * `None` when the catalog has no published hash.
+ */
+sha256: string | null; size_mb: number; is_downloaded: boolean; is_downloading: boolean; partial_size: number; is_directory: boolean; engine_type: EngineType; accuracy_score: number; speed_score: number; supports_translation: boolean; is_recommended: boolean; supported_languages: string[]; is_custom: boolean; 
+/**
+ * RAM the model needs to run comfortably; see `min_ram_mb`.
+ */
+recommended_min_ram_mb: number }
 export type ModelLoadStatus = { is_loaded: boolean; current_model: string | null }
 export type ModelUnloadTimeout = "never" | "immediately" | "min_2" | "min_5" | "min_10" | "min_15" | "hour_1" | "sec_5"
-export type OverlayPosition = "none" | "top" | "bottom"
+/**
+ * A chat that draws on several journal entries at once. Its messages are
+ * `ChatMessage`s whose `session_id` points at this session.
+ */
+export type MultiEntryChatSession = { id: number; chat_entry_ids: number[]; title: string; created_at: number; updated_at: number }
+export type OverlayPosition = "none" | "top" | "bottom" | "cursor"
 export type PasteMethod = "ctrl_v" | "direct" | "none" | "shift_insert" | "ctrl_shift_v" | "external_script"
-export type PostProcessProvider = { id: string; label: string; base_url: string; allow_base_url_edit?: boolean; models_endpoint?: string | null; supports_structured_output?: boolean }
+export type PlaylistImportResult = { playlist_title: string; folder_id: number | null; 
+/**
+ * Entries that were downloaded and transcribed successfully.
+ */
+completed_entry_ids: number[]; 
+/**
+ * Entries that failed and were left as pending placeholders.
+ */
+failed_entry_ids: number[]; cancelled: boolean }
+export type PostProcessProvider = { id: string; label: string; base_url: string; allow_base_url_edit?: boolean; models_endpoint?: string | null; supports_structured_output?: boolean; api_style?: ApiStyle }
+/**
+ * A transcript state together with the prompt that produced it (used for redo).
+ */
+export type PromptSnapshot = { text: string; prompt_id: string | null }
 export type RecordingRetentionPeriod = "never" | "preserve_limit" | "days_3" | "weeks_2" | "months_3"
+/**
+ * What a recording captures.
+ */
+export type RecordingSource = "microphone" | "system" | "both"
+/**
+ * A recording whose capture was interrupted (the app crashed or was killed)
+ * and whose audio survived on disk.
+ */
+export type RecoverableRecording = { file_name: string; 
+/**
+ * When the recording started, in milliseconds since the epoch.
+ */
+started_at: number; duration_secs: number }
+export type RediarizeSummary = { 
+/**
+ * Segments whose time range survived, keeping their edited text and speaker.
+ */
+preserved: number; 
+/**
+ * Segments that were transcribed fresh.
+ */
+replaced: number; 
+/**
+ * Previous segments with no counterpart in the new run.
+ */
+removed: number }
+/**
+ * How carefully imported audio is resampled to the transcription rate.
+ * Higher quality uses a longer sinc filter: fewer artifacts, more CPU.
+ */
+export type ResampleQuality = "fast" | "balanced" | "high"
 export type ShortcutBinding = { id: string; name: string; description: string; default_binding: string; current_binding: string }
+/**
+ * A time range removed from a video's audio before transcription.
+ */
+export type SkippedSegment = { category: string; start_ms: number; end_ms: number }
 export type SoundTheme = "marimba" | "pop" | "custom"
+/**
+ * A named voice enrolled from meeting segments. Its embedding centroid stays
+ * in the database and is only used for matching.
+ */
+export type SpeakerProfile = { id: number; name: string; 
+/**
+ * Number of segments averaged into the centroid.
+ */
+sample_count: number; created_at: number; updated_at: number }
+/**
+ * Talk-time totals for one speaker in a meeting.
+ */
+export type SpeakerStats = { 
+/**
+ * `None` for segments with no speaker assigned (reported as "Unknown").
+ */
+speaker: number | null; name: string; total_ms: number; segment_count: number; word_count: number; 
+/**
+ * Share of the meeting's total speaking time, 0.0–100.0.
+ */
+percentage: number }
+export type SubtitleImportResult = { entry: JournalEntry; cue_count: number; 
+/**
+ * Cues dropped because their timestamps could not be parsed.
+ */
+skipped_cues: number }
+export type TranscriptSource = "captions" | "transcription"
+/**
+ * An entry in the trash, with when it was deleted (unix seconds).
+ */
+export type TrashedEntry = { entry: JournalEntry; deleted_at: number }
 export type TypingTool = "auto" | "wtype" | "kwtype" | "dotool" | "ydotool" | "xdotool"
-export type YouTubeDownloadResult = { title: string; transcription: string; file_name: string }
+export type UrlDownloadResult = { title: string; uploader: string | null; transcription: string; 
+/**
+ * The downloaded recording; for captions, which have no audio, the
+ * entry's current file name (empty without an `entry_id`).
+ */
+file_name: string; transcript_source: TranscriptSource; 
+/**
+ * Sponsor/self-promo ranges cut from the audio before transcription.
+ */
+skipped_segments: SkippedSegment[] }
+export type VideoImportResult = { file_name: string; transcription_text: string; 
+/**
+ * Per-chunk timed segments were stored for the entry and can be read
+ * with `get_meeting_segments`.
+ */
+has_timed_segments: boolean }
+/**
+ * A literal find-and-replace rule applied to every transcript, for names
+ * the model keeps getting wrong (e.g. "Tory" -> "Tauri").
+ */
+export type WordReplacement = { from: string; to: string; case_sensitive?: boolean }
 
 /** tauri-specta globals **/
 
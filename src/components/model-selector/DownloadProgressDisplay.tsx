@@ -1,13 +1,7 @@
 import React from "react";
+import type { DownloadProgress } from "@/bindings";
 import { ProgressBar, ProgressData } from "../shared";
 
-interface DownloadProgress {
-  model_id: string;
-  downloaded: number;
-  total: number;
-  percentage: number;
-}
-
 interface DownloadStats {
   startTime: number;
   lastUpdate: number;
@@ -35,7 +29,7 @@ const DownloadProgressDisplay: React.FC<DownloadProgressDisplayProps> = ({
     const stats = downloadStats[progress.model_id];
     return {
       id: progress.model_id,
-      percentage: progress.percentage,
+      percentage: progress.percent,
       speed: stats?.speed,
     };
   });
@@ -101,10 +101,10 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
     );
 
     // Auto-select model when download completes (fires after extraction too)
-    const downloadCompleteUnlisten = listen<string>(
+    const downloadCompleteUnlisten = listen<{ model_id: string }>(
       "model-download-complete",
       (event) => {
-        const modelId = event.payload;
+        const modelId = event.payload.model_id;
         setTimeout(async () => {
           try {
             const isRecording = await commands.isRecording();
@@ -179,7 +179,7 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
         const progress = progressValues[0];
         const percentage = Math.max(
           0,
-          Math.min(100, Math.round(progress.percentage)),
+          Math.min(100, Math.round(progress.percent)),
         );
         return t("modelSelector.downloading", { percentage });
       } else {
@@ -71,7 +71,7 @@ const Onboarding: React.FC<OnboardingProps> = ({ onModelSelected }) => {
   };
 
   const getModelDownloadProgress = (modelId: string): number | undefined => {
-    return downloadProgress[modelId]?.percentage;
+    return downloadProgress[modelId]?.percent;
   };
 
   const getModelDownloadSpeed = (modelId: string): number | undefined => {
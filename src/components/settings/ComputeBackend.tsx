@@ -0,0 +1,83 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type ComputeBackend } from "@/bindings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface ComputeBackendProps {
+  descriptionMode?: "tooltip" | "inline";
+  grouped?: boolean;
+}
+
+const BACKEND_LABELS: Record<Exclude<ComputeBackend, "auto">, string> = {
+  cpu: "CPU",
+  cuda: "CUDA",
+  directml: "DirectML",
+  coreml: "CoreML",
+};
+
+export const ComputeBackendSetting: React.FC<ComputeBackendProps> = ({
+  descriptionMode = "inline",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+  const [available, setAvailable] = useState<ComputeBackend[]>(["auto"]);
+  const [active, setActive] = useState<ComputeBackend | null>(null);
+
+  useEffect(() => {
+    const loadBackends = async () => {
+      try {
+        setAvailable(await commands.getAvailableComputeBackends());
+        setActive(await commands.getActiveComputeBackend());
+      } catch (error) {
+        console.error("Failed to load compute backends:", error);
+      }
+    };
+
+    loadBackends();
+  }, []);
+
+  const options = available.map((backend) => ({
+    value: backend,
+    label:
+      backend === "auto"
+        ? t("settings.advanced.computeBackend.auto")
+        : BACKEND_LABELS[backend],
+  }));
+
+  const currentValue = getSetting("compute_backend") ?? "auto";
+  // ORT's execution providers are fixed for the life of the process, so a
+  // new choice only applies once the app restarts. A saved backend that isn't
+  // available here runs on CPU however often the app restarts.
+  const needsRestart =
+    active !== null &&
+    currentValue !== active &&
+    available.includes(currentValue);
+
+  return (
+    <SettingContainer
+      title={t("settings.advanced.computeBackend.title")}
+      description={t("settings.advanced.computeBackend.description")}
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+    >
+      <div className="flex flex-col items-end gap-1">
+        <Dropdown
+          options={options}
+          selectedValue={currentValue}
+          onSelect={(value) =>
+            updateSetting("compute_backend", value as ComputeBackend)
+          }
+          disabled={isUpdating("compute_backend")}
+        />
+        {needsRestart && (
+          <p className="text-xs text-mid-gray/70">
+            {t("settings.advanced.computeBackend.restartRequired")}
+          </p>
+        )}
+      </div>
+    </SettingContainer>
+  );
+};
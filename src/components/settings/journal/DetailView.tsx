@@ -1119,7 +1119,14 @@ ${linkedEntryDetails}` : ""}`;
             className="w-full text-lg font-medium bg-transparent rounded-md px-2 py-1 -mx-2 hover:bg-mutter-primary/10 focus:bg-mutter-primary/10 focus:outline-none transition-colors"
           />
 
-          <p className="text-xs text-text/50">{formattedDate}</p>
+          <p className="text-xs text-text/50">
+            {formattedDate}
+            {entry.detected_language && (
+              <span className="ml-2 px-1.5 py-0.5 rounded bg-mutter-primary/10 uppercase">
+                {entry.detected_language}
+              </span>
+            )}
+          </p>
 
           {/* Tags — click to show dropdown */}
           <div className="relative" ref={tagDropdownRef}>
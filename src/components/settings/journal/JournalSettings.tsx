@@ -36,6 +36,7 @@ import {
 } from "lucide-react";
 import { convertFileSrc } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import type { DownloadProgress } from "@/bindings";
 import { readFile } from "@tauri-apps/plugin-fs";
 import { ask, open as openFileDialog } from "@tauri-apps/plugin-dialog";
 import { useOsType } from "@/hooks/useOsType";
@@ -45,6 +46,7 @@ import {
   videoCommands,
   meetingCommands,
   MUTTER_DEFAULT_PROMPTS,
+  errorMessage,
   type JournalEntry,
   type JournalFolder,
 } from "@/lib/journal";
@@ -375,6 +377,8 @@ export const JournalSettings: React.FC<JournalSettingsProps> = ({
         tags: [],
         linkedEntryIds: [],
         folderId,
+        language: result.language,
+        detectedLanguage: result.detected_language,
       });
       await loadData();
       // Find the newly saved entry (most recent)
@@ -399,6 +403,26 @@ export const JournalSettings: React.FC<JournalSettingsProps> = ({
     setView({ mode: "folder", folderId });
   };
 
+  // Start/stop requested from the tray menu. Recordings go into the open
+  // folder, or the first folder when none is open.
+  const trayRecordingRequest = useMutterStore((s) => s.trayRecordingRequest);
+  const setTrayRecordingRequest = useMutterStore((s) => s.setTrayRecordingRequest);
+  useEffect(() => {
+    if (source !== "voice" || !trayRecordingRequest || view.mode === "loading") return;
+    setTrayRecordingRequest(null);
+    if (trayRecordingRequest === "stop") {
+      if (view.mode === "recording") handleStopRecording(view.folderId);
+      return;
+    }
+    if (view.mode === "recording") return;
+    const folderId = "folderId" in view ? view.folderId : folders[0]?.id;
+    if (folderId == null) {
+      toast.error(t("settings.journal.trayNoFolder"));
+      return;
+    }
+    handleStartRecording(folderId);
+  }, [trayRecordingRequest, view, folders, source]); // eslint-disable-line react-hooks/exhaustive-deps
+
   const handleDiscard = async (fileName: string, folderId: number) => {
     try {
       await journalCommands.discardRecording(fileName);
@@ -457,7 +481,7 @@ export const JournalSettings: React.FC<JournalSettingsProps> = ({
       }).catch((error) => {
         console.error("Failed to download YouTube audio:", error);
         clearProcessingEntry(entry.id);
-        toast.error(String(error));
+        toast.error(errorMessage(error));
       });
     } catch (error) {
       console.error("YouTube submit failed:", error);
@@ -497,7 +521,7 @@ export const JournalSettings: React.FC<JournalSettingsProps> = ({
       setProcessingEntry(entry.id, "importing", 0);
       setView({ mode: "detail", entryId: entry.id, folderId, trail: [] });
 
-      videoCommands.importVideo(path).then(async (result) => {
+      videoCommands.importVideo(path, entry.id).then(async (result) => {
         await videoCommands.updateEntryAfterProcessing(
           entry.id, result.file_name, title, result.transcription_text
         );
@@ -546,7 +570,7 @@ export const JournalSettings: React.FC<JournalSettingsProps> = ({
       }).catch((error) => {
         console.error("Meeting transcription failed:", error);
         clearProcessingEntry(entry.id);
-        toast.error(String(error));
+        toast.error(errorMessage(error));
       });
     } catch (error) {
       console.error("Failed to stop meeting recording:", error);
@@ -595,7 +619,7 @@ export const JournalSettings: React.FC<JournalSettingsProps> = ({
       }).catch((error) => {
         console.error("Failed to import meeting audio:", error);
         clearProcessingEntry(entry.id);
-        toast.error(String(error));
+        toast.error(errorMessage(error));
       });
     } catch (error) {
       console.error("Failed to create meeting entry:", error);
@@ -2393,12 +2417,10 @@ const MeetingNewEntryView: React.FC<{
   useEffect(() => {
     if (!installing) return;
     let unlisten: (() => void) | undefined;
-    listen<{ label: string; progress: number }>("diarize-download-progress", (event) => {
-      const { label, progress } = event.payload;
-      if (label !== "done") {
-        setDownloadLabel(label);
-        setDownloadProgress(progress);
-      }
+    listen<DownloadProgress>("diarize-download-progress", (event) => {
+      const { model_id, percent } = event.payload;
+      setDownloadLabel(model_id);
+      setDownloadProgress(Math.round(percent));
     }).then((fn) => { unlisten = fn; });
     return () => { unlisten?.(); };
   }, [installing]);
@@ -96,7 +96,7 @@ export const ModelsSettings: React.FC = () => {
 
   const getDownloadProgress = (modelId: string): number | undefined => {
     const progress = downloadProgress[modelId];
-    return progress?.percentage;
+    return progress?.percent;
   };
 
   const getDownloadSpeed = (modelId: string): number | undefined => {
@@ -1,5 +1,33 @@
 import { invoke } from "@tauri-apps/api/core";
 
+/** Structured error from commands that return `AppError` (see src-tauri/src/error.rs). */
+export type AppError =
+  | { kind: "NotFound" }
+  | { kind: "InvalidInput"; message: string }
+  | { kind: "Io"; message: string }
+  | { kind: "Database"; message: string }
+  | { kind: "Transcription"; message: string }
+  | { kind: "LlmClient"; message: string }
+  | { kind: "Cancelled" }
+  | { kind: "Other"; message: string };
+
+export function isAppError(error: unknown): error is AppError {
+  return typeof error === "object" && error !== null && "kind" in error;
+}
+
+/** Readable message for either an `AppError` or a legacy string error. */
+export function errorMessage(error: unknown): string {
+  if (!isAppError(error)) return String(error);
+  switch (error.kind) {
+    case "NotFound":
+      return "Not found";
+    case "Cancelled":
+      return "Cancelled";
+    default:
+      return error.message;
+  }
+}
+
 export interface JournalEntry {
   id: number;
   file_name: string;
@@ -16,6 +44,15 @@ export interface JournalEntry {
   source_url: string | null;
   speaker_names: string;
   user_source: string;
+  redo_snapshots: { text: string; prompt_id: string | null }[];
+  /** Transcription language, or null to follow the selected language. */
+  language: string | null;
+  /** Language detected when transcribed with the language on "auto". */
+  detected_language: string | null;
+  /** Initial prompt for re-transcribing, or null to use the one in settings. */
+  initial_prompt: string | null;
+  /** Length of the recording, or null for entries without audio. */
+  duration_ms: number | null;
 }
 
 export interface MeetingSegment {
@@ -31,17 +68,158 @@ export interface JournalFolder {
   name: string;
   created_at: number;
   source: string;
+  parent_id: number | null;
 }
 
-export interface YouTubeDownloadResult {
+export interface UrlDownloadResult {
   title: string;
+  uploader: string | null;
   transcription: string;
   file_name: string;
+  transcript_source: "captions" | "transcription";
+  skipped_segments: SkippedSegment[];
+}
+
+export type YouTubeDownloadResult = UrlDownloadResult;
+
+export interface SkippedSegment {
+  category: string;
+  start_ms: number;
+  end_ms: number;
+}
+
+export interface BatchPromptResult {
+  succeeded: number[];
+  failed: { id: number; error: string }[];
+}
+
+export interface BatchProcessResult {
+  processed: number;
+  skipped: number;
+  /** [entry id, error] pairs. */
+  failed: [number, string][];
+}
+
+export interface BatchProcessProgress {
+  current: number;
+  total: number;
+  entry_id: number;
+}
+
+export interface MarkdownImportResult {
+  imported: JournalEntry[];
+  failed: { file: string; error: string }[];
+}
+
+export interface RediarizeSummary {
+  preserved: number;
+  replaced: number;
+  removed: number;
+}
+
+export interface AudioDirectoryImportResult {
+  imported: number[];
+  failed: { file: string; error: string }[];
+}
+
+export interface VideoImportResult {
+  file_name: string;
+  transcription_text: string;
+  has_timed_segments: boolean;
+}
+
+export interface SubtitleImportResult {
+  entry: JournalEntry;
+  cue_count: number;
+  skipped_cues: number;
+}
+
+export interface TrashedEntry {
+  entry: JournalEntry;
+  deleted_at: number;
+}
+
+/** Payload of the `transcription-chunk-progress` event. */
+export interface TranscriptionChunkProgress {
+  entry_id: number | null;
+  current: number;
+  total: number;
+  partial_text: string;
+}
+
+/** Payload of `prompt-chain-progress`, emitted after each step of a prompt chain. */
+export interface PromptChainProgress {
+  entry_id: number;
+  step: number;
+  total: number;
+}
+
+/** Payload of `live-transcription`, pushed while live transcription is running. */
+export interface LiveTranscription {
+  text: string;
+  revision: number;
+}
+
+/** Payload of `cloud-transcription-progress`, emitted on mobile after each
+ * upload when a long recording is sent to the provider in pieces. */
+export interface CloudTranscriptionProgress {
+  current: number;
+  total: number;
+}
+
+/** A recording interrupted by a crash, from `getRecoverableRecordings`. */
+export interface RecoverableRecording {
+  file_name: string;
+  /** Milliseconds since the epoch. */
+  started_at: number;
+  duration_secs: number;
+}
+
+export interface BackupInfo {
+  path: string;
+  file_name: string;
+  created_at: number;
+  size_bytes: number;
+}
+
+export interface DiffChunk {
+  kind: "Added" | "Removed" | "Equal";
+  text: string;
+}
+
+export interface DiarizeModelCheck {
+  label: string;
+  present: boolean;
+  valid: boolean;
+}
+
+export interface SpeakerProfile {
+  id: number;
+  name: string;
+  sample_count: number;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface SpeakerStats {
+  speaker: number | null;
+  name: string;
+  total_ms: number;
+  segment_count: number;
+  word_count: number;
+  percentage: number;
+}
+
+export interface MeetingMarkdownExport {
+  markdown: string;
+  path: string | null;
 }
 
 export interface JournalRecordingResult {
   file_name: string;
   transcription_text: string;
+  language: string | null;
+  detected_language: string | null;
 }
 
 export interface ChatSession {
@@ -53,6 +231,14 @@ export interface ChatSession {
   updated_at: number;
 }
 
+export interface MultiEntryChatSession {
+  id: number;
+  chat_entry_ids: number[];
+  title: string;
+  created_at: number;
+  updated_at: number;
+}
+
 export interface ChatMessage {
   id: number;
   session_id: number;
@@ -355,16 +541,44 @@ export const journalCommands = {
   /** Stop recording.
    * On desktop: no args needed (audio is in Rust).
    * On mobile: pass `audioFilePath` pointing to raw f32 temp file from WebView recording. */
-  stopRecording: (audioFilePath?: string) =>
+  /** `language` (e.g. "de") overrides the selected language in settings;
+   * pass the returned `language` to `saveEntry` to keep it on the entry.
+   * On mobile, `prompt` and `temperature` are also sent to the cloud
+   * transcription provider. */
+  stopRecording: (
+    audioFilePath?: string,
+    options?: { language?: string; prompt?: string; temperature?: number },
+  ) =>
     audioFilePath
       ? invoke<JournalRecordingResult>("stop_journal_recording", {
           audioFilePath,
+          language: options?.language ?? null,
+          prompt: options?.prompt ?? null,
+          temperature: options?.temperature ?? null,
         })
-      : invoke<JournalRecordingResult>("stop_journal_recording"),
+      : invoke<JournalRecordingResult>("stop_journal_recording", {
+          language: options?.language ?? null,
+        }),
 
   getPartialTranscription: () =>
     invoke<string>("get_partial_journal_transcription"),
 
+  startLiveTranscription: (intervalSecs?: number) =>
+    invoke<void>("start_live_transcription", { intervalSecs: intervalSecs ?? null }),
+
+  stopLiveTranscription: () =>
+    invoke<void>("stop_live_transcription"),
+
+  /** Recordings cut short by a crash whose audio survived on disk. */
+  getRecoverableRecordings: () =>
+    invoke<RecoverableRecording[]>("get_recoverable_recordings"),
+
+  recoverRecording: (fileName: string) =>
+    invoke<JournalRecordingResult>("recover_recording", { fileName }),
+
+  discardRecoverableRecording: (fileName: string) =>
+    invoke<void>("discard_recoverable_recording", { fileName }),
+
   discardRecording: (fileName: string) =>
     invoke<void>("discard_journal_recording", { fileName }),
 
@@ -377,13 +591,43 @@ export const journalCommands = {
     tags: string[];
     linkedEntryIds: number[];
     folderId: number | null;
-  }) => invoke<JournalEntry>("save_journal_entry", params),
+    language?: string | null;
+    detectedLanguage?: string | null;
+  }) =>
+    invoke<JournalEntry>("save_journal_entry", {
+      ...params,
+      language: params.language ?? null,
+      detectedLanguage: params.detectedLanguage ?? null,
+    }),
 
   getEntries: () => invoke<JournalEntry[]>("get_journal_entries"),
 
+  getEntriesPage: (source: string | null, limit: number, offset: number) =>
+    invoke<JournalEntry[]>("get_journal_entries_page", { source, limit, offset }),
+
+  /** Entries with a recording between `minMs` and `maxMs` long (inclusive). */
+  getEntriesInDurationRange: (minMs: number, maxMs: number) =>
+    invoke<JournalEntry[]>("get_journal_entries_in_duration_range", { minMs, maxMs }),
+
+  countEntries: (source: string | null) =>
+    invoke<number>("count_journal_entries", { source }),
+
+  getTags: (source?: string) =>
+    invoke<[string, number][]>("get_journal_tags", { source: source ?? null }),
+
+  filterEntries: (params: {
+    source: string | null;
+    tags: string[];
+    fromTs: number | null;
+    toTs: number | null;
+  }) => invoke<JournalEntry[]>("filter_journal_entries", params),
+
   getEntry: (id: number) =>
     invoke<JournalEntry | null>("get_journal_entry", { id }),
 
+  getBacklinks: (id: number) =>
+    invoke<JournalEntry[]>("get_entry_backlinks", { id }),
+
   updateEntry: (params: {
     id: number;
     title: string;
@@ -396,6 +640,45 @@ export const journalCommands = {
   deleteEntry: (id: number) =>
     invoke<void>("delete_journal_entry", { id }),
 
+  duplicateEntry: (id: number) =>
+    invoke<JournalEntry>("duplicate_journal_entry", { id }),
+
+  deleteEntries: (ids: number[]) =>
+    invoke<number[]>("delete_entries", { ids }),
+
+  moveEntriesToFolder: (ids: number[], folderId: number | null) =>
+    invoke<number[]>("move_entries_to_folder", { ids, folderId }),
+
+  generatePodcastFeed: (folderId: number, title: string, description: string, baseUrl: string) =>
+    invoke<string>("generate_folder_podcast_feed", { folderId, title, description, baseUrl }),
+
+  savePodcastFeed: (
+    folderId: number,
+    title: string,
+    description: string,
+    baseUrl: string,
+    outputPath: string,
+  ) =>
+    invoke<void>("save_podcast_feed", { folderId, title, description, baseUrl, outputPath }),
+
+  exportZip: (destPath: string, source: string | null = null) =>
+    invoke<number>("export_journal_zip", { destPath, source }),
+
+  restoreEntry: (id: number) =>
+    invoke<void>("restore_journal_entry", { id }),
+
+  purgeEntry: (id: number) =>
+    invoke<void>("purge_journal_entry", { id }),
+
+  getTrashedEntries: () => invoke<TrashedEntry[]>("get_trashed_entries"),
+
+  backupDatabase: () => invoke<string>("backup_journal_database"),
+
+  listBackups: () => invoke<BackupInfo[]>("list_journal_backups"),
+
+  restoreFromBackup: (path: string) =>
+    invoke<void>("restore_journal_from_backup", { path }),
+
   applyPostProcess: (text: string, promptId: string) =>
     invoke<string>("apply_journal_post_process", { text, promptId }),
 
@@ -411,26 +694,103 @@ export const journalCommands = {
   updateTranscriptionText: (id: number, text: string) =>
     invoke<void>("update_journal_transcription_text", { id, text }),
 
-  retranscribe: (id: number) =>
-    invoke<string>("retranscribe_journal_entry", { id }),
+  /** Uses the entry's stored language unless `language` is given, in which
+   * case it is stored on the entry. */
+  retranscribe: (id: number, language?: string) =>
+    invoke<string>("retranscribe_journal_entry", {
+      id,
+      language: language ?? null,
+    }),
+
+  /** Initial prompt used when re-transcribing the entry; null clears it so
+   * the one in settings is used. */
+  setInitialPrompt: (id: number, initialPrompt: string | null) =>
+    invoke<void>("set_journal_entry_initial_prompt", { id, initialPrompt }),
+
+  /** Translates to English, through the model when the entry has audio and
+   * through the post-processing LLM otherwise. Undo with `undoPrompt`. */
+  translate: (id: number) =>
+    invoke<string>("translate_journal_entry", { id }),
+
+  getWaveform: (
+    fileName: string,
+    folderId: number | null,
+    resolution: number | null = null,
+  ) =>
+    invoke<number[]>("get_journal_entry_waveform", {
+      fileName,
+      folderId,
+      resolution,
+    }),
 
   applyPromptToEntry: (id: number, promptId: string) =>
     invoke<string>("apply_prompt_to_journal_entry", { id, promptId }),
 
+  applyPromptChain: (entryId: number, promptIds: string[]) =>
+    invoke<string>("apply_prompt_chain_to_entry", { entryId, promptIds }),
+
   applyPromptTextToEntry: (id: number, promptText: string, promptLabel: string) =>
     invoke<string>("apply_prompt_text_to_journal_entry", { id, promptText, promptLabel }),
 
+  applyPromptTextToEntries: (ids: number[], promptText: string, promptLabel: string) =>
+    invoke<BatchPromptResult>("apply_prompt_text_to_entries", { ids, promptText, promptLabel }),
+
+  batchApplyPrompt: (promptId: string, overwriteExisting: boolean, folderId?: number | null) =>
+    invoke<BatchProcessResult>("batch_apply_prompt", {
+      promptId,
+      overwriteExisting,
+      folderId: folderId ?? null,
+    }),
+
+  cancelBatchProcess: () => invoke<void>("cancel_batch_process"),
+
   undoPrompt: (id: number, previousPromptId: string | null) =>
     invoke<string>("undo_journal_prompt", { id, previousPromptId }),
 
+  redoPrompt: (id: number) =>
+    invoke<string>("redo_journal_prompt", { id }),
+
+  getPromptDiff: (entryId: number) =>
+    invoke<DiffChunk[]>("get_prompt_diff", { entryId }),
+
   updateEntryAfterProcessing: (id: number, fileName: string, title: string, transcriptionText: string) =>
     invoke<void>("update_entry_after_processing", { id, fileName, title, transcriptionText }),
 
   importAudio: (filePath: string) =>
     invoke<JournalRecordingResult>("import_audio_for_journal", { filePath }),
+  // Mobile only: audio handed over by the share sheet
+  processSharedAudioFile: (filePath: string) =>
+    invoke<JournalRecordingResult>("process_shared_audio_file", { filePath }),
+  /** Mobile only: save shared audio as an entry, transcribed in the cloud
+   * when configured. Untranscribed entries are tagged "needs-transcription". */
+  importSharedAudio: (filePath: string, folderId: number | null) =>
+    invoke<JournalEntry>("import_shared_audio", { filePath, folderId }),
+
+  importAudioDirectory: (dirPath: string, folderId: number | null) =>
+    invoke<AudioDirectoryImportResult>("import_audio_directory", { dirPath, folderId }),
+
+  importMarkdownFile: (path: string, folderId: number | null) =>
+    invoke<JournalEntry>("import_markdown_file", { path, folderId }),
+
+  importMarkdownDirectory: (dir: string, folderId: number | null) =>
+    invoke<MarkdownImportResult>("import_markdown_directory", { dir, folderId }),
+
+  importTextFile: (filePath: string, folderId: number | null) =>
+    invoke<JournalEntry>("import_text_as_entry", { filePath, folderId }),
+
+  getImportedEntries: () => invoke<JournalEntry[]>("get_imported_entries"),
+
+  getImportedFolders: () => invoke<JournalFolder[]>("get_imported_folders"),
 
-  chat: (messages: [string, string][]) =>
-    invoke<string>("journal_chat", { messages }),
+  createImportFolder: (name: string) =>
+    invoke<JournalFolder>("create_import_folder", { name }),
+
+  chat: (messages: [string, string][], temperature?: number, maxTokens?: number) =>
+    invoke<string>("journal_chat", {
+      messages,
+      temperature: temperature ?? null,
+      maxTokens: maxTokens ?? null,
+    }),
 
   // Chat session commands
   createChatSession: (entryId: number, mode: string) =>
@@ -451,13 +811,48 @@ export const journalCommands = {
   deleteChatSession: (sessionId: number) =>
     invoke<void>("delete_chat_session", { sessionId }),
 
+  // Multi-entry chat commands
+  multiEntryChat: (
+    entryIds: number[],
+    messages: [string, string][],
+    temperature?: number,
+    maxTokens?: number,
+  ) =>
+    invoke<string>("multi_entry_journal_chat", {
+      entryIds,
+      messages,
+      temperature: temperature ?? null,
+      maxTokens: maxTokens ?? null,
+    }),
+
+  getContextWindowEstimate: (providerId: string) =>
+    invoke<number | null>("get_context_window_estimate", { providerId }),
+
+  createMultiChatSession: (entryIds: number[]) =>
+    invoke<MultiEntryChatSession>("create_multi_chat_session", { entryIds }),
+
+  getMultiChatSessions: () =>
+    invoke<MultiEntryChatSession[]>("get_multi_chat_sessions"),
+
+  saveMultiChatMessage: (sessionId: number, role: string, content: string) =>
+    invoke<ChatMessage>("save_multi_chat_message", { sessionId, role, content }),
+
+  getMultiChatMessages: (sessionId: number) =>
+    invoke<ChatMessage[]>("get_multi_chat_messages", { sessionId }),
+
+  deleteMultiChatSession: (sessionId: number) =>
+    invoke<void>("delete_multi_chat_session", { sessionId }),
+
   // Folder commands
-  createFolder: (name: string) =>
-    invoke<JournalFolder>("create_journal_folder", { name }),
+  createFolder: (name: string, parentId: number | null = null) =>
+    invoke<JournalFolder>("create_journal_folder", { name, parentId }),
 
   renameFolder: (id: number, name: string) =>
     invoke<void>("rename_journal_folder", { id, name }),
 
+  moveFolder: (id: number, parentId: number | null) =>
+    invoke<void>("move_journal_folder", { id, parentId }),
+
   deleteFolder: (id: number) =>
     invoke<void>("delete_journal_folder", { id }),
 
@@ -480,11 +875,52 @@ export const videoCommands = {
 
   installYtDlp: () => invoke<void>("install_ytdlp"),
 
-  downloadYouTubeAudio: (url: string) =>
-    invoke<YouTubeDownloadResult>("download_youtube_audio", { url }),
-
-  importVideo: (filePath: string) =>
-    invoke<JournalRecordingResult>("import_video_for_journal", { filePath }),
+  checkYtDlpUpdate: () => invoke<string | null>("check_ytdlp_update"),
+
+  updateYtDlp: () => invoke<string>("update_ytdlp"),
+
+  downloadYouTubeAudio: (
+    url: string,
+    preferCaptions?: boolean,
+    entryId?: number,
+    skipSponsors?: boolean,
+    cookiesFromBrowser?: string,
+    captionLanguage?: string,
+  ) =>
+    invoke<YouTubeDownloadResult>("download_youtube_audio", {
+      url,
+      preferCaptions: preferCaptions ?? null,
+      skipSponsors: skipSponsors ?? null,
+      entryId: entryId ?? null,
+      cookiesFromBrowser: cookiesFromBrowser ?? null,
+      captionLanguage: captionLanguage ?? null,
+    }),
+
+  downloadUrlAudio: (
+    url: string,
+    preferCaptions?: boolean,
+    entryId?: number,
+    skipSponsors?: boolean,
+    cookiesFromBrowser?: string,
+    captionLanguage?: string,
+  ) =>
+    invoke<UrlDownloadResult>("download_url_audio", {
+      url,
+      preferCaptions: preferCaptions ?? null,
+      skipSponsors: skipSponsors ?? null,
+      entryId: entryId ?? null,
+      cookiesFromBrowser: cookiesFromBrowser ?? null,
+      captionLanguage: captionLanguage ?? null,
+    }),
+
+  getSkippedSegments: (entryId: number) =>
+    invoke<SkippedSegment[]>("get_skipped_segments", { entryId }),
+
+  importVideo: (filePath: string, entryId: number | null = null) =>
+    invoke<VideoImportResult>("import_video_for_journal", { filePath, entryId }),
+
+  importSubtitles: (filePath: string, source: "video" | "meeting", folderId: number | null) =>
+    invoke<SubtitleImportResult>("import_subtitles_for_journal", { filePath, source, folderId }),
 
   getEntries: () => invoke<JournalEntry[]>("get_video_entries"),
 
@@ -503,8 +939,20 @@ export const videoCommands = {
   }) => invoke<JournalEntry>("save_video_entry", params),
 
   // Diarization (shared — works on any entry with audio)
-  diarizeEntry: (entryId: number, maxSpeakers?: number, threshold?: number) =>
-    invoke<void>("diarize_entry", { entryId, maxSpeakers: maxSpeakers ?? null, threshold: threshold ?? null }),
+  diarizeEntry: (
+    entryId: number,
+    maxSpeakers?: number,
+    threshold?: number,
+    minSegmentMs?: number,
+    mergeGapMs?: number,
+  ) =>
+    invoke<void>("diarize_entry", {
+      entryId,
+      maxSpeakers: maxSpeakers ?? null,
+      threshold: threshold ?? null,
+      minSegmentMs: minSegmentMs ?? null,
+      mergeGapMs: mergeGapMs ?? null,
+    }),
 
   getSegments: (entryId: number) =>
     invoke<MeetingSegment[]>("get_meeting_segments", { entryId }),
@@ -529,11 +977,15 @@ export const videoCommands = {
   applyPromptTextToText: journalCommands.applyPromptTextToText,
   updatePostProcessedText: journalCommands.updatePostProcessedText,
   getAudioFilePath: journalCommands.getAudioFilePath,
+  getWaveform: journalCommands.getWaveform,
   updateTranscriptionText: journalCommands.updateTranscriptionText,
   updateEntryAfterProcessing: journalCommands.updateEntryAfterProcessing,
   applyPromptToEntry: journalCommands.applyPromptToEntry,
+  applyPromptChain: journalCommands.applyPromptChain,
   applyPromptTextToEntry: journalCommands.applyPromptTextToEntry,
   undoPrompt: journalCommands.undoPrompt,
+  redoPrompt: journalCommands.redoPrompt,
+  getPromptDiff: journalCommands.getPromptDiff,
   chat: journalCommands.chat,
   createChatSession: journalCommands.createChatSession,
   getChatSessions: journalCommands.getChatSessions,
@@ -542,6 +994,7 @@ export const videoCommands = {
   updateChatSessionTitle: journalCommands.updateChatSessionTitle,
   deleteChatSession: journalCommands.deleteChatSession,
   renameFolder: journalCommands.renameFolder,
+  moveFolder: journalCommands.moveFolder,
   deleteFolder: journalCommands.deleteFolder,
   moveEntryToFolder: journalCommands.moveEntryToFolder,
   getStoragePath: journalCommands.getStoragePath,
@@ -555,6 +1008,8 @@ export const meetingCommands = {
 
   installDiarizeModels: () => invoke<void>("install_diarize_models"),
 
+  verifyDiarizeModels: () => invoke<DiarizeModelCheck[]>("verify_diarize_models"),
+
   getEntries: () => invoke<JournalEntry[]>("get_meeting_entries"),
 
   getFolders: () => invoke<JournalFolder[]>("get_meeting_folders"),
@@ -569,8 +1024,37 @@ export const meetingCommands = {
     folderId: number | null;
   }) => invoke<JournalEntry>("save_meeting_entry", params),
 
-  transcribeMeeting: (entryId: number, maxSpeakers?: number, threshold?: number) =>
-    invoke<void>("transcribe_meeting", { entryId, maxSpeakers: maxSpeakers ?? null, threshold: threshold ?? null }),
+  transcribeMeeting: (
+    entryId: number,
+    maxSpeakers?: number,
+    threshold?: number,
+    minSegmentMs?: number,
+    mergeGapMs?: number,
+  ) =>
+    invoke<void>("transcribe_meeting", {
+      entryId,
+      maxSpeakers: maxSpeakers ?? null,
+      threshold: threshold ?? null,
+      minSegmentMs: minSegmentMs ?? null,
+      mergeGapMs: mergeGapMs ?? null,
+    }),
+
+  cancelDiarization: () => invoke<void>("cancel_diarization"),
+
+  rediarize: (
+    entryId: number,
+    maxSpeakers?: number,
+    threshold?: number,
+    minSegmentMs?: number,
+    mergeGapMs?: number,
+  ) =>
+    invoke<RediarizeSummary>("rediarize_meeting", {
+      entryId,
+      maxSpeakers: maxSpeakers ?? null,
+      threshold: threshold ?? null,
+      minSegmentMs: minSegmentMs ?? null,
+      mergeGapMs: mergeGapMs ?? null,
+    }),
 
   getSegments: (entryId: number) =>
     invoke<MeetingSegment[]>("get_meeting_segments", { entryId }),
@@ -578,6 +1062,14 @@ export const meetingCommands = {
   updateSegmentText: (segmentId: number, text: string) =>
     invoke<void>("update_meeting_segment_text", { segmentId, text }),
 
+  retranscribeSegment: (
+    entryId: number,
+    segmentId: number,
+    startMs: number | null = null,
+    endMs: number | null = null,
+  ) =>
+    invoke<string>("retranscribe_meeting_segment", { entryId, segmentId, startMs, endMs }),
+
   updateSegmentSpeaker: (segmentId: number, speaker: number | null) =>
     invoke<void>("update_meeting_segment_speaker", { segmentId, speaker }),
 
@@ -587,6 +1079,45 @@ export const meetingCommands = {
   getSpeakerNames: (entryId: number) =>
     invoke<Record<string, string>>("get_meeting_speaker_names", { entryId }),
 
+  enrollSpeaker: (segmentId: number, name: string) =>
+    invoke<SpeakerProfile>("enroll_speaker_from_segment", { segmentId, name }),
+
+  listSpeakerProfiles: () =>
+    invoke<SpeakerProfile[]>("list_speaker_profiles"),
+
+  deleteSpeakerProfile: (id: number) =>
+    invoke<void>("delete_speaker_profile", { id }),
+
+  mergeSegments: (entryId: number, maxGapMs?: number) =>
+    invoke<number>("merge_meeting_segments", { entryId, maxGapMs: maxGapMs ?? null }),
+
+  mergeTwoSegments: (firstId: number, secondId: number) =>
+    invoke<number>("merge_two_segments", { firstId, secondId }),
+
+  splitSegment: (segmentId: number, splitMs: number, textIndex: number | null = null) =>
+    invoke<[number, number]>("split_meeting_segment", { segmentId, splitMs, textIndex }),
+
+  deleteSegment: (segmentId: number) =>
+    invoke<void>("delete_meeting_segment", { segmentId }),
+
+  insertSegment: (
+    entryId: number,
+    startMs: number,
+    endMs: number,
+    speaker: number | null,
+    text: string,
+  ) =>
+    invoke<number>("insert_meeting_segment", { entryId, startMs, endMs, speaker, text }),
+
+  getSpeakerStats: (entryId: number) =>
+    invoke<SpeakerStats[]>("get_meeting_speaker_stats", { entryId }),
+
+  exportMarkdown: (entryId: number, saveBesideAudio: boolean) =>
+    invoke<MeetingMarkdownExport>("export_meeting_markdown", { entryId, saveBesideAudio }),
+
+  generateMinutes: (entryId: number) =>
+    invoke<string>("generate_meeting_minutes", { entryId }),
+
   // Reuse journal commands for shared operations
   getEntry: journalCommands.getEntry,
   updateEntry: journalCommands.updateEntry,
@@ -595,11 +1126,15 @@ export const meetingCommands = {
   applyPromptTextToText: journalCommands.applyPromptTextToText,
   updatePostProcessedText: journalCommands.updatePostProcessedText,
   getAudioFilePath: journalCommands.getAudioFilePath,
+  getWaveform: journalCommands.getWaveform,
   updateTranscriptionText: journalCommands.updateTranscriptionText,
   updateEntryAfterProcessing: journalCommands.updateEntryAfterProcessing,
   applyPromptToEntry: journalCommands.applyPromptToEntry,
+  applyPromptChain: journalCommands.applyPromptChain,
   applyPromptTextToEntry: journalCommands.applyPromptTextToEntry,
   undoPrompt: journalCommands.undoPrompt,
+  redoPrompt: journalCommands.redoPrompt,
+  getPromptDiff: journalCommands.getPromptDiff,
   chat: journalCommands.chat,
   createChatSession: journalCommands.createChatSession,
   getChatSessions: journalCommands.getChatSessions,
@@ -608,6 +1143,7 @@ export const meetingCommands = {
   updateChatSessionTitle: journalCommands.updateChatSessionTitle,
   deleteChatSession: journalCommands.deleteChatSession,
   renameFolder: journalCommands.renameFolder,
+  moveFolder: journalCommands.moveFolder,
   deleteFolder: journalCommands.deleteFolder,
   moveEntryToFolder: journalCommands.moveEntryToFolder,
   getStoragePath: journalCommands.getStoragePath,
@@ -13,12 +13,31 @@ import { getLanguageDirection } from "@/lib/utils/rtl";
 
 type OverlayState = "recording" | "transcribing" | "processing";
 
+/** Payload of `overlay-update`, sent about ten times a second while recording. */
+interface OverlayUpdate {
+  duration_ms: number;
+  level_db: number;
+}
+
+/** Quietest level the amplitude bar shows, in dBFS. */
+const LEVEL_FLOOR_DB = -60;
+
+const formatDuration = (ms: number) => {
+  const totalSeconds = Math.floor(ms / 1000);
+  const minutes = Math.floor(totalSeconds / 60);
+  const seconds = totalSeconds % 60;
+  return `${String(minutes).padStart(2, "0")}:${String(seconds).padStart(2, "0")}`;
+};
+
 const RecordingOverlay: React.FC = () => {
   const { t } = useTranslation();
   const [isVisible, setIsVisible] = useState(false);
   const [state, setState] = useState<OverlayState>("recording");
   const [levels, setLevels] = useState<number[]>(Array(16).fill(0));
   const smoothedLevelsRef = useRef<number[]>(Array(16).fill(0));
+  const [durationMs, setDurationMs] = useState(0);
+  const [levelDb, setLevelDb] = useState(LEVEL_FLOOR_DB);
+  const levelCanvasRef = useRef<HTMLCanvasElement>(null);
   const direction = getLanguageDirection(i18n.language);
 
   useEffect(() => {
@@ -29,6 +48,10 @@ const RecordingOverlay: React.FC = () => {
         await syncLanguageFromSettings();
         const overlayState = event.payload as OverlayState;
         setState(overlayState);
+        if (overlayState === "recording") {
+          setDurationMs(0);
+          setLevelDb(LEVEL_FLOOR_DB);
+        }
         setIsVisible(true);
       });
 
@@ -51,17 +74,43 @@ const RecordingOverlay: React.FC = () => {
         setLevels(smoothed.slice(0, 9));
       });
 
+      // Listen for the recording timer and input level
+      const unlistenUpdate = await listen<OverlayUpdate>(
+        "overlay-update",
+        (event) => {
+          setDurationMs(event.payload.duration_ms);
+          setLevelDb(event.payload.level_db);
+        },
+      );
+
       // Cleanup function
       return () => {
         unlistenShow();
         unlistenHide();
         unlistenLevel();
+        unlistenUpdate();
       };
     };
 
     setupEventListeners();
   }, []);
 
+  // Draw the amplitude bar
+  useEffect(() => {
+    const canvas = levelCanvasRef.current;
+    const ctx = canvas?.getContext("2d");
+    if (!canvas || !ctx) return;
+    const fraction = Math.min(
+      1,
+      Math.max(0, (levelDb - LEVEL_FLOOR_DB) / -LEVEL_FLOOR_DB),
+    );
+    ctx.clearRect(0, 0, canvas.width, canvas.height);
+    ctx.fillStyle = "#ffe5ee33";
+    ctx.fillRect(0, 0, canvas.width, canvas.height);
+    ctx.fillStyle = levelDb > -3 ? "#ff6b8a" : "#ffe5ee";
+    ctx.fillRect(0, 0, canvas.width * fraction, canvas.height);
+  }, [levelDb, state]);
+
   const getIcon = () => {
     if (state === "recording") {
       return <MicrophoneIcon />;
@@ -102,6 +151,19 @@ const RecordingOverlay: React.FC = () => {
       </div>
 
       <div className="overlay-right">
+        {state === "recording" && (
+          <div className="recording-meta">
+            <span className="recording-timer">
+              {formatDuration(durationMs)}
+            </span>
+            <canvas
+              ref={levelCanvasRef}
+              className="level-canvas"
+              width={36}
+              height={4}
+            />
+          </div>
+        )}
         {state === "recording" && (
           <div
             className="cancel-button"
@@ -2,14 +2,12 @@ import { create } from "zustand";
 import { subscribeWithSelector } from "zustand/middleware";
 import { produce } from "immer";
 import { listen } from "@tauri-apps/api/event";
-import { commands, type ModelInfo } from "@/bindings";
-
-interface DownloadProgress {
-  model_id: string;
-  downloaded: number;
-  total: number;
-  percentage: number;
-}
+import {
+  commands,
+  type DownloadComplete,
+  type DownloadProgress,
+  type ModelInfo,
+} from "@/bindings";
 
 interface DownloadStats {
   startTime: number;
@@ -168,9 +166,9 @@ export const useModelStore = create<ModelsStore>()(
             state.downloadingModels[modelId] = true;
             state.downloadProgress[modelId] = {
               model_id: modelId,
-              downloaded: 0,
-              total: 0,
-              percentage: 0,
+              bytes_downloaded: 0,
+              total_bytes: 0,
+              percent: 0,
             };
           }),
         );
@@ -284,12 +282,12 @@ export const useModelStore = create<ModelsStore>()(
               state.downloadStats[progress.model_id] = {
                 startTime: now,
                 lastUpdate: now,
-                totalDownloaded: progress.downloaded,
+                totalDownloaded: progress.bytes_downloaded,
                 speed: 0,
               };
             } else {
               const timeDiff = (now - current.lastUpdate) / 1000;
-              const bytesDiff = progress.downloaded - current.totalDownloaded;
+              const bytesDiff = progress.bytes_downloaded - current.totalDownloaded;
 
               if (timeDiff > 0.5) {
                 const currentSpeed = bytesDiff / (1024 * 1024) / timeDiff;
@@ -302,7 +300,7 @@ export const useModelStore = create<ModelsStore>()(
                 state.downloadStats[progress.model_id] = {
                   startTime: current.startTime,
                   lastUpdate: now,
-                  totalDownloaded: progress.downloaded,
+                  totalDownloaded: progress.bytes_downloaded,
                   speed: Math.max(0, smoothedSpeed),
                 };
               }
@@ -311,8 +309,8 @@ export const useModelStore = create<ModelsStore>()(
         );
       });
 
-      listen<string>("model-download-complete", (event) => {
-        const modelId = event.payload;
+      listen<DownloadComplete>("model-download-complete", (event) => {
+        const modelId = event.payload.model_id;
         set(
           produce((state) => {
             delete state.downloadingModels[modelId];
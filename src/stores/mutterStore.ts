@@ -20,6 +20,8 @@ export interface MutterPromptOverrides {
 
 export type MutterTab = "journal" | "video" | "meeting";
 
+export type TrayRecordingRequest = "start" | "stop";
+
 interface MutterStore {
   /** Active tab — shared between sidebar and main panel */
   activeTab: MutterTab;
@@ -52,6 +54,9 @@ interface MutterStore {
   processingEntries: Record<number, ProcessingEntry>;
   setProcessingEntry: (id: number, status: string, progress: number) => void;
   clearProcessingEntry: (id: number) => void;
+  /** Journal recording start/stop requested from the tray, handled by the journal view */
+  trayRecordingRequest: TrayRecordingRequest | null;
+  setTrayRecordingRequest: (request: TrayRecordingRequest | null) => void;
 }
 
 export const useMutterStore = create<MutterStore>()(
@@ -102,6 +107,8 @@ export const useMutterStore = create<MutterStore>()(
           delete next[id];
           return { processingEntries: next };
         }),
+      trayRecordingRequest: null,
+      setTrayRecordingRequest: (request) => set({ trayRecordingRequest: request }),
     }),
     {
       name: "mutter-store",